@@ -71,6 +71,7 @@ pub const PRODUCT_NAME_ENV: &str = "DIOXUS_PRODUCT_NAME";
 pub const OUT_DIR: &str = "DIOXUS_OUT_DIR";
 pub const SESSION_CACHE_DIR: &str = "DIOXUS_SESSION_CACHE_DIR";
 pub const BUILD_ID: &str = "DIOXUS_BUILD_ID";
+pub const SERVER_FN_MANIFEST_ENV: &str = "DIOXUS_SERVER_FN_MANIFEST";
 
 /// Reads an environment variable at runtime in debug mode or at compile time in
 /// release mode. When bundling in release mode, we will not be running under the
@@ -330,6 +331,16 @@ pub fn build_id() -> u64 {
     }
 }
 
+/// The path the server should write the manifest of its server functions to instead of serving.
+///
+/// This is set by `dx client` to generate clients for the server functions of an app. When set, the
+/// server writes the manifest and exits immediately.
+pub fn server_fn_manifest_path() -> Option<PathBuf> {
+    std::env::var(SERVER_FN_MANIFEST_ENV)
+        .ok()
+        .map(PathBuf::from)
+}
+
 /// The product name of the bundled application.
 pub fn product_name() -> Option<String> {
     read_env_config!("DIOXUS_PRODUCT_NAME")
@@ -0,0 +1,777 @@
+use super::*;
+use crate::{AppBuilder, BuildId, BuildMode, BundleFormat};
+use anyhow::{bail, Context};
+use convert_case::{Case, Casing};
+use serde_json::Map;
+use std::{collections::HashSet, fmt::Write as _, time::Duration};
+
+/// Generate a typed client for the server functions of a fullstack app.
+///
+/// This builds the server, asks it for the manifest of its server functions, and converts the
+/// manifest into a client that can call the server functions without Dioxus. The server must be built
+/// with the `openapi` feature of dioxus, which describes the names, arguments and return types of
+/// the server functions.
+#[derive(Clone, Debug, Parser)]
+pub(crate) struct GenerateClient {
+    /// The language of the generated client.
+    #[clap(long, value_enum, default_value_t = ClientLanguage::Typescript)]
+    pub(crate) lang: ClientLanguage,
+
+    /// The file to write the client to [default: `server_fns.ts` or `server_fns.json` in the crate directory]
+    #[clap(long, short)]
+    pub(crate) out: Option<PathBuf>,
+
+    /// The arguments for the server build
+    #[clap(flatten)]
+    pub(crate) args: CommandWithPlatformOverrides<BuildArgs>,
+}
+
+/// The languages `dx client` can generate clients for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum ClientLanguage {
+    /// A TypeScript module with one async function per server function, built on `fetch`.
+    Typescript,
+
+    /// The raw OpenAPI 3.1 document, for use with other client generators.
+    Openapi,
+}
+
+impl GenerateClient {
+    pub(crate) async fn generate(self) -> Result<StructuredOutput> {
+        let BuildTargets { client, server } = self.args.into_targets().await?;
+
+        // The server is either part of a fullstack build or the only build of a server app
+        let server = match server {
+            Some(server) => server,
+            None if client.bundle == BundleFormat::Server => client,
+            None => bail!("`dx client` requires a fullstack app. Make sure the `fullstack` feature of dioxus is enabled."),
+        };
+
+        tracing::info!("Building the server to collect its server functions...");
+        let mut builder =
+            AppBuilder::started(&server, BuildMode::Base { run: false }, BuildId::SECONDARY)?;
+        builder.finish_build().await?;
+
+        let manifest = read_manifest(&mut builder).await?;
+        let undescribed = undescribed_operations(&manifest);
+        if !undescribed.is_empty() {
+            bail!(
+                "The server functions {} are not described because the server was built without the `openapi` feature of dioxus. Enable it to generate a client.",
+                undescribed.join(", ")
+            );
+        }
+
+        let (contents, extension) = match self.lang {
+            ClientLanguage::Typescript => (typescript_client(&manifest), "ts"),
+            ClientLanguage::Openapi => (serde_json::to_string_pretty(&manifest)?, "json"),
+        };
+
+        let out = self
+            .out
+            .unwrap_or_else(|| server.crate_dir().join(format!("server_fns.{extension}")));
+        if let Some(parent) = out.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&out, contents)
+            .with_context(|| format!("Failed to write the client to {}", out.display()))?;
+
+        tracing::info!(path = ?out, "Generated the server function client 🚀");
+
+        Ok(StructuredOutput::Success)
+    }
+}
+
+/// Run the server executable with the manifest env var set and read the manifest it writes.
+async fn read_manifest(builder: &mut AppBuilder) -> Result<Value> {
+    let server_exe = builder.build.main_exe();
+    let manifest_dir = tempfile::tempdir()?;
+    let manifest_path = manifest_dir.path().join("server_fns.json");
+
+    let vars = builder.child_environment_variables(None, None, false, BuildId::SECONDARY);
+    let mut command = tokio::process::Command::new(&server_exe);
+    command
+        .envs(vars)
+        .env(dioxus_cli_config::SERVER_FN_MANIFEST_ENV, &manifest_path)
+        .current_dir(server_exe.parent().unwrap())
+        .stdout(std::process::Stdio::null())
+        .kill_on_drop(true);
+
+    // The server exits right after writing the manifest. If it doesn't, it was likely started
+    // without `dioxus::launch` or `dioxus::serve` and is serving the app instead.
+    let status = tokio::time::timeout(Duration::from_secs(60), command.status())
+        .await
+        .context("The server did not write its server function manifest. Make sure it registers its server functions with `dioxus::launch`, `dioxus::serve` or `register_server_functions`.")??;
+    if !status.success() {
+        bail!("The server exited with {status} while writing its server function manifest");
+    }
+
+    let manifest = std::fs::read_to_string(&manifest_path).context(
+        "The server did not write its server function manifest. Make sure it registers its server functions with `dioxus::launch`, `dioxus::serve` or `register_server_functions`.",
+    )?;
+    serde_json::from_str(&manifest).context("Failed to parse the server function manifest")
+}
+
+/// The routes of the operations without an operation id. Servers built without the `openapi`
+/// feature only list the paths and methods of their server functions.
+fn undescribed_operations(manifest: &Value) -> Vec<String> {
+    let paths = manifest.get("paths").and_then(Value::as_object);
+    paths
+        .into_iter()
+        .flatten()
+        .flat_map(|(path, item)| {
+            item.as_object()
+                .into_iter()
+                .flatten()
+                .filter(|(_, operation)| operation.get("operationId").is_none())
+                .map(move |(method, _)| format!("`{} {path}`", method.to_ascii_uppercase()))
+        })
+        .collect()
+}
+
+/// The runtime shared by all generated functions.
+const TYPESCRIPT_RUNTIME: &str = r#"export interface ClientOptions {
+  /** The origin of the server, like `https://example.com`. Defaults to the current origin. */
+  baseUrl?: string;
+  /** The `fetch` implementation to use. Defaults to the global `fetch`. */
+  fetch?: typeof fetch;
+  /** Headers added to every request, like an `Authorization` header. */
+  headers?: Record<string, string>;
+  /** A signal to abort the request. */
+  signal?: AbortSignal;
+}
+
+let defaults: ClientOptions = {};
+
+/** Set the options used by all calls. Options passed to a call take precedence. */
+export function configure(options: ClientOptions): void {
+  defaults = { ...defaults, ...options };
+}
+
+/** The error thrown when a server function responds with an error status. */
+export class ServerFnError extends Error {
+  constructor(
+    public readonly status: number,
+    public readonly body: unknown,
+  ) {
+    const message =
+      typeof body === "object" && body !== null && "message" in body
+        ? String((body as { message: unknown }).message)
+        : typeof body === "string" && body.length > 0
+          ? body
+          : `Server function failed with status ${status}`;
+    super(message);
+    this.name = "ServerFnError";
+  }
+}
+
+/** The body of a request: the arguments as a JSON object, or a payload that is sent as is. */
+type RequestBody = { json: Record<string, unknown> } | { raw: BodyInit; contentType?: string };
+
+async function call<T>(
+  method: string,
+  path: string,
+  query: Record<string, unknown> | undefined,
+  body: RequestBody | undefined,
+  options: ClientOptions = {},
+): Promise<T> {
+  const { baseUrl = "", fetch: fetchImpl = fetch, headers, signal } = { ...defaults, ...options };
+
+  let url = baseUrl.replace(/\/$/, "") + path;
+  if (query !== undefined) {
+    const params = new URLSearchParams();
+    for (const [key, value] of Object.entries(query)) {
+      if (value === undefined || value === null) continue;
+      params.append(key, typeof value === "object" ? JSON.stringify(value) : String(value));
+    }
+    const search = params.toString();
+    if (search.length > 0) url += `?${search}`;
+  }
+
+  // `fetch` sets the content type of form data and url encoded bodies itself
+  let contentType: string | undefined;
+  let payload: BodyInit | undefined;
+  if (body !== undefined && "json" in body) {
+    contentType = "application/json";
+    payload = JSON.stringify(body.json);
+  } else if (body !== undefined) {
+    contentType = body.contentType;
+    payload = body.raw;
+  }
+
+  const response = await fetchImpl(url, {
+    method,
+    headers: {
+      Accept: "application/json",
+      ...(contentType !== undefined ? { "Content-Type": contentType } : {}),
+      ...defaults.headers,
+      ...headers,
+    },
+    body: payload,
+    signal,
+  });
+
+  // JSON and text responses are decoded, other encodings are returned as bytes
+  const responseType = response.headers.get("Content-Type") ?? "";
+  let data: unknown;
+  if (responseType.includes("json") || responseType.startsWith("text/") || !response.ok) {
+    const text = await response.text();
+    if (text.length > 0) data = responseType.includes("json") ? JSON.parse(text) : text;
+  } else {
+    data = await response.arrayBuffer();
+  }
+  if (!response.ok) {
+    throw new ServerFnError(response.status, data);
+  }
+  return data as T;
+}
+"#;
+
+/// Convert the server function manifest into a TypeScript module.
+fn typescript_client(manifest: &Value) -> String {
+    let mut out = String::new();
+    out.push_str("// This file is generated by `dx client`. Do not edit it by hand.\n");
+    out.push_str("/* eslint-disable */\n\n");
+    out.push_str(TYPESCRIPT_RUNTIME);
+
+    // Shared types from the components of the document
+    if let Some(schemas) = manifest
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+    {
+        for (name, schema) in schemas {
+            out.push('\n');
+            write_doc_comment(&mut out, schema.get("description").and_then(Value::as_str));
+            _ = writeln!(
+                out,
+                "export type {} = {};",
+                type_name(name),
+                typescript_type(schema)
+            );
+        }
+    }
+
+    let mut names = HashSet::new();
+    let paths = manifest.get("paths").and_then(Value::as_object);
+    for (path, item) in paths.into_iter().flatten() {
+        let Some(item) = item.as_object() else {
+            continue;
+        };
+        for (method, operation) in item {
+            let Some(id) = operation.get("operationId").and_then(Value::as_str) else {
+                continue;
+            };
+            let name = unique_name(&mut names, function_name(id));
+            out.push('\n');
+            typescript_function(&mut out, &name, method, path, operation);
+        }
+    }
+
+    out
+}
+
+/// Write one async function that calls the server function at `method` `path`.
+fn typescript_function(out: &mut String, name: &str, method: &str, path: &str, operation: &Value) {
+    let mut fields = Vec::new();
+    let mut path_params = Vec::new();
+    let mut query_params = Vec::new();
+
+    let parameters = operation.get("parameters").and_then(Value::as_array);
+    for param in parameters.into_iter().flatten() {
+        let (Some(param_name), Some(location)) = (
+            param.get("name").and_then(Value::as_str),
+            param.get("in").and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+        let required = param
+            .get("required")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let ty = param.get("schema").map(typescript_type);
+        fields.push(field(param_name, required, ty.as_deref()));
+        match location {
+            "path" => path_params.push(param_name.to_string()),
+            "query" => query_params.push(param_name.to_string()),
+            _ => {}
+        }
+    }
+
+    // Arguments are sent as a JSON object, unless the server function takes a single payload
+    let content_type = operation
+        .pointer("/requestBody/content")
+        .and_then(Value::as_object)
+        .and_then(|content| content.keys().next())
+        .map(String::as_str);
+    let raw_body = content_type
+        .filter(|content_type| *content_type != "application/json")
+        .map(raw_request_body);
+    let body = operation.pointer("/requestBody/content/application~1json/schema");
+    let body_properties = body
+        .and_then(|body| body.get("properties"))
+        .and_then(Value::as_object);
+    let body_required = required_properties(body);
+    for (property, schema) in body_properties.into_iter().flatten() {
+        let ty = typescript_type(schema);
+        fields.push(field(property, body_required.contains(property), Some(&ty)));
+    }
+    if let Some((ty, _)) = &raw_body {
+        fields.push(field("body", true, Some(ty)));
+    }
+
+    let response = success_response(operation)
+        .and_then(|response| response.pointer("/content/application~1json/schema"))
+        .map(typescript_type)
+        .unwrap_or_else(|| "unknown".to_string());
+
+    // Document the function with the summary, description and route of the server function
+    let summary = operation.get("summary").and_then(Value::as_str);
+    let description = operation.get("description").and_then(Value::as_str);
+    let route = format!("`{} {path}`", method.to_ascii_uppercase());
+    let doc = [summary, description, Some(&route)]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    write_doc_comment(out, Some(&doc));
+
+    let args = match fields.is_empty() {
+        true => String::new(),
+        false => {
+            let optional = if fields.iter().all(|(_, required)| !required) {
+                " = {}"
+            } else {
+                ""
+            };
+            let fields = fields
+                .iter()
+                .map(|(field, _)| field.as_str())
+                .collect::<Vec<_>>()
+                .join("; ");
+            format!("args: {{ {fields} }}{optional}, ")
+        }
+    };
+    _ = writeln!(
+        out,
+        "export async function {name}({args}options?: ClientOptions): Promise<{response}> {{"
+    );
+
+    let url = path_params.iter().fold(path.to_string(), |url, param| {
+        url.replace(
+            &format!("{{{param}}}"),
+            &format!("${{encodeURIComponent(String(args[{param:?}]))}}"),
+        )
+    });
+    let query = match query_params.is_empty() {
+        true => "undefined".to_string(),
+        false => object_literal(&query_params),
+    };
+    let body = match (raw_body, body_properties) {
+        (Some((_, body)), _) => body,
+        (None, Some(properties)) => {
+            let properties = properties.keys().cloned().collect::<Vec<_>>();
+            format!("{{ json: {} }}", object_literal(&properties))
+        }
+        (None, None) => "undefined".to_string(),
+    };
+    _ = writeln!(
+        out,
+        "  return call<{response}>({:?}, `{url}`, {query}, {body}, options);",
+        method.to_ascii_uppercase()
+    );
+    out.push_str("}\n");
+}
+
+/// A field of the `args` object of a function, and whether it is required.
+fn field(name: &str, required: bool, ty: Option<&str>) -> (String, bool) {
+    let optional = if required { "" } else { "?" };
+    let ty = ty.unwrap_or("unknown");
+    (format!("{}{optional}: {ty}", property_name(name)), required)
+}
+
+/// The TypeScript type of a request body that is sent as is with `content_type`, and the expression
+/// that passes `args.body` to `call`. Binary encodings like CBOR are encoded by the caller.
+fn raw_request_body(content_type: &str) -> (String, String) {
+    let (ty, body) = match content_type {
+        "application/x-www-form-urlencoded" => (
+            "Record<string, string>",
+            "{ raw: new URLSearchParams(args.body) }".to_string(),
+        ),
+        "multipart/form-data" => ("FormData", "{ raw: args.body }".to_string()),
+        "text/plain" => (
+            "string",
+            format!("{{ raw: args.body, contentType: {content_type:?} }}"),
+        ),
+        _ => (
+            "Blob | BufferSource",
+            format!("{{ raw: args.body, contentType: {content_type:?} }}"),
+        ),
+    };
+    (ty.to_string(), body)
+}
+
+/// An object literal that picks `names` from `args`.
+fn object_literal(names: &[String]) -> String {
+    let fields = names
+        .iter()
+        .map(|name| format!("{}: args[{name:?}]", property_name(name)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{{ {fields} }}")
+}
+
+/// The response of the first successful status code of an operation.
+fn success_response(operation: &Value) -> Option<&Value> {
+    operation
+        .get("responses")?
+        .as_object()?
+        .iter()
+        .find(|(status, _)| status.starts_with('2'))
+        .map(|(_, response)| response)
+}
+
+/// The name of the function for an operation, from its operation id. The id is the name of the
+/// Rust function, so `get_user` becomes `getUser`.
+fn function_name(operation_id: &str) -> String {
+    let name = sanitize_identifier(operation_id).to_case(Case::Camel);
+    match is_reserved(&name) {
+        true => format!("{name}_"),
+        false => name,
+    }
+}
+
+/// Make `name` unique among the generated functions.
+fn unique_name(names: &mut HashSet<String>, name: String) -> String {
+    let mut unique = name.clone();
+    let mut index = 2;
+    while !names.insert(unique.clone()) {
+        unique = format!("{name}{index}");
+        index += 1;
+    }
+    unique
+}
+
+/// Convert a JSON schema into a TypeScript type.
+fn typescript_type(schema: &Value) -> String {
+    let schema = match schema {
+        Value::Bool(true) => return "unknown".to_string(),
+        Value::Bool(false) => return "never".to_string(),
+        Value::Object(schema) => schema,
+        _ => return "unknown".to_string(),
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return match reference.rsplit_once('/') {
+            Some((_, name)) => type_name(name),
+            None => "unknown".to_string(),
+        };
+    }
+    if let Some(constant) = schema.get("const") {
+        return constant.to_string();
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(Value::to_string));
+    }
+    if let Some(variants) = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(Value::as_array)
+    {
+        return union(variants.iter().map(typescript_type));
+    }
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        let parts = parts.iter().map(typescript_type).collect::<Vec<_>>();
+        return format!("({})", parts.join(" & "));
+    }
+
+    match schema.get("type") {
+        Some(Value::String(ty)) => primitive_type(ty, schema),
+        Some(Value::Array(types)) => union(
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|ty| primitive_type(ty, schema)),
+        ),
+        _ if schema.contains_key("properties") => object_type(schema),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn primitive_type(ty: &str, schema: &Map<String, Value>) -> String {
+    match ty {
+        "string" => "string".to_string(),
+        "integer" | "number" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => array_type(schema),
+        "object" => object_type(schema),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn array_type(schema: &Map<String, Value>) -> String {
+    // Tuples are described with `prefixItems`
+    if let Some(items) = schema.get("prefixItems").and_then(Value::as_array) {
+        let items = items.iter().map(typescript_type).collect::<Vec<_>>();
+        return format!("[{}]", items.join(", "));
+    }
+    let items = schema
+        .get("items")
+        .map(typescript_type)
+        .unwrap_or_else(|| "unknown".to_string());
+    format!("Array<{items}>")
+}
+
+fn object_type(schema: &Map<String, Value>) -> String {
+    let schema_value = Value::Object(schema.clone());
+    let required = required_properties(Some(&schema_value));
+    let mut fields = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, property)| {
+            let optional = if required.contains(name) { "" } else { "?" };
+            format!(
+                "{}{optional}: {}",
+                property_name(name),
+                typescript_type(property)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    match schema.get("additionalProperties") {
+        Some(Value::Bool(false)) | None => {}
+        Some(additional) => fields.push(format!("[key: string]: {}", typescript_type(additional))),
+    }
+
+    match fields.is_empty() {
+        true if schema.contains_key("properties") => "{}".to_string(),
+        true => "Record<string, unknown>".to_string(),
+        false => format!("{{ {} }}", fields.join("; ")),
+    }
+}
+
+fn required_properties(schema: Option<&Value>) -> HashSet<String> {
+    schema
+        .and_then(|schema| schema.get("required"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+fn union(types: impl Iterator<Item = String>) -> String {
+    let mut seen = HashSet::new();
+    let types = types
+        .filter(|ty| seen.insert(ty.clone()))
+        .collect::<Vec<_>>();
+    match types.len() {
+        0 => "never".to_string(),
+        1 => types.into_iter().next().unwrap(),
+        _ => format!("({})", types.join(" | ")),
+    }
+}
+
+/// The name of a shared type. Schema names of generic types contain characters like `<` that are
+/// not valid in TypeScript identifiers.
+fn type_name(name: &str) -> String {
+    let name = sanitize_identifier(name);
+    match is_reserved(&name) {
+        true => format!("{name}_"),
+        false => name,
+    }
+}
+
+/// A property name, quoted if it isn't a valid identifier.
+fn property_name(name: &str) -> String {
+    match sanitize_identifier(name) == name {
+        true => name.to_string(),
+        false => format!("{name:?}"),
+    }
+}
+
+fn sanitize_identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(
+            |c| match c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect();
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    identifier
+}
+
+fn is_reserved(name: &str) -> bool {
+    matches!(
+        name,
+        "break"
+            | "case"
+            | "catch"
+            | "class"
+            | "const"
+            | "continue"
+            | "debugger"
+            | "default"
+            | "delete"
+            | "do"
+            | "else"
+            | "enum"
+            | "export"
+            | "extends"
+            | "false"
+            | "finally"
+            | "for"
+            | "function"
+            | "if"
+            | "import"
+            | "in"
+            | "instanceof"
+            | "new"
+            | "null"
+            | "return"
+            | "super"
+            | "switch"
+            | "this"
+            | "throw"
+            | "true"
+            | "try"
+            | "typeof"
+            | "var"
+            | "void"
+            | "while"
+            | "with"
+            | "call"
+            | "configure"
+            | "ServerFnError"
+            | "ClientOptions"
+    )
+}
+
+fn write_doc_comment(out: &mut String, doc: Option<&str>) {
+    let Some(doc) = doc.filter(|doc| !doc.trim().is_empty()) else {
+        return;
+    };
+    out.push_str("/**\n");
+    for line in doc.lines() {
+        let line = line.trim_end().replace("*/", "*\\/");
+        if line.is_empty() {
+            out.push_str(" *\n");
+        } else {
+            _ = writeln!(out, " * {line}");
+        }
+    }
+    out.push_str(" */\n");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn function_names_come_from_operation_ids() {
+        assert_eq!(function_name("get_user"), "getUser");
+        assert_eq!(function_name("list_orders_v2"), "listOrdersV2");
+        assert_eq!(function_name("delete"), "delete_");
+        assert_eq!(function_name("configure"), "configure_");
+    }
+
+    #[test]
+    fn duplicate_names_are_numbered() {
+        let mut names = HashSet::new();
+        assert_eq!(unique_name(&mut names, "getUser".to_string()), "getUser");
+        assert_eq!(unique_name(&mut names, "getUser".to_string()), "getUser2");
+        assert_eq!(unique_name(&mut names, "getUser".to_string()), "getUser3");
+    }
+
+    #[test]
+    fn converts_schemas_to_typescript_types() {
+        assert_eq!(typescript_type(&json!(true)), "unknown");
+        assert_eq!(typescript_type(&json!(false)), "never");
+        assert_eq!(typescript_type(&json!({ "type": "integer" })), "number");
+        assert_eq!(
+            typescript_type(&json!({ "type": ["string", "null"] })),
+            "(string | null)"
+        );
+        assert_eq!(
+            typescript_type(&json!({ "$ref": "#/components/schemas/Page<User>" })),
+            "Page_User_"
+        );
+        assert_eq!(
+            typescript_type(&json!({ "enum": ["Admin", "Member"] })),
+            "(\"Admin\" | \"Member\")"
+        );
+        assert_eq!(
+            typescript_type(&json!({ "type": "array", "items": { "type": "string" } })),
+            "Array<string>"
+        );
+        assert_eq!(
+            typescript_type(&json!({
+                "type": "array",
+                "prefixItems": [{ "type": "string" }, { "type": "boolean" }]
+            })),
+            "[string, boolean]"
+        );
+        assert_eq!(
+            typescript_type(&json!({
+                "type": "object",
+                "additionalProperties": { "type": "number" }
+            })),
+            "{ [key: string]: number }"
+        );
+        assert_eq!(
+            typescript_type(&json!({
+                "type": "object",
+                "properties": {
+                    "display-name": { "type": "string" },
+                    "id": { "type": "integer" }
+                },
+                "required": ["id"]
+            })),
+            "{ \"display-name\"?: string; id: number }"
+        );
+    }
+
+    #[test]
+    fn finds_undescribed_operations() {
+        let manifest = json!({
+            "paths": {
+                "/api/users": {
+                    "get": { "operationId": "list_users" },
+                    "post": { "responses": {} },
+                }
+            }
+        });
+        assert_eq!(undescribed_operations(&manifest), ["`POST /api/users`"]);
+    }
+
+    #[test]
+    fn sends_json_and_raw_bodies() {
+        let mut out = String::new();
+        let operation = json!({
+            "requestBody": { "content": { "application/json": { "schema": {
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+                "required": ["name"]
+            } } } },
+        });
+        typescript_function(&mut out, "createUser", "post", "/api/users", &operation);
+        assert!(out.contains("args: { name: string }, options?: ClientOptions"));
+        assert!(out.contains(r#"call<unknown>("POST", `/api/users`, undefined, { json: { name: args["name"] } }, options)"#));
+
+        let mut out = String::new();
+        let operation = json!({
+            "requestBody": { "content": { "application/cbor": {} } },
+        });
+        typescript_function(&mut out, "upload", "post", "/api/upload", &operation);
+        assert!(out.contains("args: { body: Blob | BufferSource }"));
+        assert!(out.contains(r#"{ raw: args.body, contentType: "application/cbor" }"#));
+    }
+}
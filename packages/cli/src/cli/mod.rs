@@ -3,6 +3,7 @@ pub(crate) mod build;
 pub(crate) mod build_assets;
 pub(crate) mod bundle;
 pub(crate) mod check;
+pub(crate) mod client;
 pub(crate) mod component;
 pub(crate) mod config;
 pub(crate) mod create;
@@ -102,6 +103,10 @@ pub(crate) enum Commands {
     #[clap(name = "check")]
     Check(check::Check),
 
+    /// Generate a typed client for the server functions of a fullstack app.
+    #[clap(name = "client")]
+    Client(client::GenerateClient),
+
     /// Dioxus config file controls.
     #[clap(subcommand)]
     #[clap(name = "config")]
@@ -869,6 +869,14 @@ impl TraceController {
                     "build_args": cmd.build_args.anonymized(),
                 }),
             ),
+            Commands::Client(cmd) => (
+                "client".to_string(),
+                json!({
+                    "lang": format!("{:?}", cmd.lang),
+                    "out": cmd.out.is_some(),
+                    "build_args": cmd.args.anonymized(),
+                }),
+            ),
             Commands::Config(config) => match config {
                 Config::Init { force, .. } => (
                     "config init".to_string(),
@@ -67,6 +67,9 @@ pub struct Output {
     // ! needs to be wrapped in an &mut since `render stateful widget` requires &mut... but our
     // "render" method only borrows &self (for no particular reason at all...)
     throbber: RefCell<throbber_widgets_tui::ThrobberState>,
+
+    // The chrome trace file that render profiles streamed from the app are appended to
+    render_profile: Option<std::fs::File>,
 }
 
 #[derive(Clone, Copy)]
@@ -97,6 +100,7 @@ impl Output {
                 interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
                 interval
             },
+            render_profile: None,
         };
 
         output.startup()?;
@@ -397,8 +401,13 @@ impl Output {
             }
         };
 
-        let ClientMsg::Log { level, messages } = msg else {
-            return;
+        let (level, messages) = match msg {
+            ClientMsg::Log { level, messages } => (level, messages),
+            ClientMsg::RenderProfile { events } => {
+                self.push_render_profile(events);
+                return;
+            }
+            _ => return,
         };
 
         // FIXME(jon): why are we pulling only the first message here?
@@ -417,6 +426,38 @@ impl Output {
         self.push_log(TraceMsg::text(TraceSrc::App(bundle), level, content));
     }
 
+    /// Append trace events from the render profiler to a chrome trace file that can be opened in `chrome://tracing` or
+    /// https://ui.perfetto.dev. The file uses the JSON array format, which doesn't need a closing bracket, so it is
+    /// valid after every write.
+    fn push_render_profile(&mut self, events: Vec<String>) {
+        use std::io::Write;
+
+        if events.is_empty() {
+            return;
+        }
+
+        let first_write = self.render_profile.is_none();
+        if first_write {
+            let path = std::env::temp_dir().join("dioxus-render-profile.json");
+            match std::fs::File::create(&path) {
+                Ok(file) => self.render_profile = Some(file),
+                Err(err) => {
+                    tracing::warn!(dx_src = ?TraceSrc::Dev, "Failed to create render profile at {}: {err}", path.display());
+                    return;
+                }
+            }
+            tracing::info!(dx_src = ?TraceSrc::Dev, "Writing render profile to {}", path.display());
+        }
+
+        let Some(file) = self.render_profile.as_mut() else {
+            return;
+        };
+        let separator = if first_write { "[\n" } else { ",\n" };
+        if let Err(err) = write!(file, "{separator}{}", events.join(",\n")) {
+            tracing::warn!(dx_src = ?TraceSrc::Dev, "Failed to write render profile: {err}");
+        }
+    }
+
     /// Change internal state based on the build engine's update
     ///
     /// We want to keep internal state as limited as possible, so currently we're only setting our
@@ -38,6 +38,14 @@ impl ToTokens for ComponentBody {
         // Components can also use the struct pattern to "inline" their props.
         // Freya uses this a bunch (because it's clean),
         // e.g. `fn Navbar(NavbarProps { title }: NavbarProps)` was previously being incorrectly parsed
+        if let Some(hydrate) = &self.options.island {
+            match self.island(hydrate) {
+                Ok(island) => tokens.append_all(island),
+                Err(err) => tokens.append_all(err.to_compile_error()),
+            }
+            return;
+        }
+
         if self.is_explicit_props_ident() || self.has_struct_parameter_pattern() {
             let comp_fn = &self.item_fn;
             tokens.append_all(allow_camel_case_for_fn_ident(comp_fn).into_token_stream());
@@ -208,6 +216,110 @@ impl ComponentBody {
         }
     }
 
+    /// Generate an island component
+    ///
+    /// The body moves into a hidden component that the client hydrates on its own. The component itself serializes its
+    /// props and wraps the hidden component in an island boundary that the server renderer marks in the html.
+    fn island(&self, hydrate: &Ident) -> Result<TokenStream> {
+        let ItemFn {
+            attrs,
+            vis,
+            sig,
+            block,
+        } = &self.item_fn;
+        let Signature {
+            inputs,
+            ident: fn_ident,
+            generics,
+            output: fn_output,
+            ..
+        } = sig;
+
+        if !generics.params.is_empty() {
+            return Err(Error::new(
+                generics.span(),
+                "Generic islands are not supported",
+            ));
+        }
+        if self.is_explicit_props_ident() || self.has_struct_parameter_pattern() {
+            return Err(Error::new(
+                inputs.span(),
+                "Islands must declare their props as arguments",
+            ));
+        }
+        if let Some(arg) = inputs.iter().find(|f| {
+            let FnArg::Typed(pt) = f else { return true };
+            !matches!(pt.pat.as_ref(), Pat::Ident(_))
+        }) {
+            return Err(Error::new(
+                arg.span(),
+                "Island props must be plain identifiers",
+            ));
+        }
+
+        let struct_ident = Ident::new(&format!("{fn_ident}Props"), fn_ident.span());
+        let body_ident = format_ident!("__{fn_ident}_island");
+        let struct_field_names = inputs.iter().map(rebind_mutability);
+        let struct_field_idents = inputs
+            .iter()
+            .map(make_prop_struct_field_idents)
+            .collect::<Vec<_>>();
+        let props_docs = self.props_docs(inputs.iter().collect());
+
+        let doc = format!("Properties for the [`{fn_ident}`] island.");
+        let (props_struct, props_impls) = self.props_struct();
+        let completion_hints = self.completion_hints();
+
+        Ok(quote! {
+            #[doc = #doc]
+            #[allow(missing_docs)]
+            #props_struct
+
+            #(#props_impls)*
+
+            impl dioxus::fullstack::Island for #struct_ident {
+                const NAME: &'static str = concat!(module_path!(), "::", stringify!(#fn_ident));
+
+                fn serialize(&self) -> String {
+                    dioxus::fullstack::serialize_island_props(&( #(&self.#struct_field_idents,)* ))
+                }
+
+                fn deserialize(props: &str) -> Option<Self> {
+                    let ( #(#struct_field_idents,)* ) = dioxus::fullstack::deserialize_island_props(props)?;
+                    Some(Self { #(#struct_field_idents),* })
+                }
+
+                fn render(self) -> dioxus::prelude::Element {
+                    #body_ident(self)
+                }
+            }
+
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            fn #body_ident(#struct_ident { #(#struct_field_names),* }: #struct_ident) #fn_output {
+                {
+                    #block
+                }
+            }
+
+            #(#attrs)*
+            #(#props_docs)*
+            #[allow(non_snake_case)]
+            #vis fn #fn_ident(props: #struct_ident) #fn_output {
+                let serialized = <#struct_ident as dioxus::fullstack::Island>::serialize(&props);
+                dioxus::dioxus_core::render_island(
+                    <#struct_ident as dioxus::fullstack::Island>::NAME,
+                    dioxus::dioxus_core::IslandHydration::#hydrate,
+                    serialized,
+                    #body_ident,
+                    props,
+                )
+            }
+
+            #completion_hints
+        })
+    }
+
     /// Build an associated struct for the props of the component
     ///
     /// This will expand to the typed-builder implementation that we have vendored in this crate.
@@ -634,6 +746,8 @@ fn allow_camel_case_for_fn_ident(item_fn: &ItemFn) -> ItemFn {
 #[derive(Default)]
 pub struct ComponentMacroOptions {
     pub lazy: bool,
+    /// The hydration strategy of an island, if the component is one
+    pub island: Option<Ident>,
 }
 
 impl Parse for ComponentMacroOptions {
@@ -659,6 +773,49 @@ impl Parse for ComponentMacroOptions {
             }
         }
 
-        Ok(Self { lazy: lazy_load })
+        Ok(Self {
+            lazy: lazy_load,
+            island: None,
+        })
+    }
+}
+
+/// The options of the island macro: `#[island(hydrate = visible)]`
+pub struct IslandMacroOptions {
+    /// The variant of `IslandHydration` to use
+    pub hydrate: Ident,
+}
+
+impl Parse for IslandMacroOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut hydrate = Ident::new("Load", input.span());
+
+        while !input.is_empty() {
+            let ident = input.parse::<Ident>()?;
+            if ident != "hydrate" {
+                return Err(Error::new(ident.span(), "Unknown option for island macro"));
+            }
+            input.parse::<Token![=]>()?;
+            let strategy = input.parse::<Ident>()?;
+            let variant = match strategy.to_string().as_str() {
+                "load" => "Load",
+                "visible" => "Visible",
+                "idle" => "Idle",
+                "interaction" => "Interaction",
+                _ => {
+                    return Err(Error::new(
+                        strategy.span(),
+                        "Unknown hydration strategy. Expected one of `load`, `visible`, `idle` or `interaction`",
+                    ))
+                }
+            };
+            hydrate = Ident::new(variant, strategy.span());
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(Self { hydrate })
     }
 }
@@ -2,7 +2,7 @@
 #![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 
-use component::{ComponentBody, ComponentMacroOptions};
+use component::{ComponentBody, ComponentMacroOptions, IslandMacroOptions};
 use proc_macro::TokenStream;
 use quote::ToTokens;
 use syn::parse_macro_input;
@@ -40,3 +40,16 @@ pub fn component(_args: TokenStream, input: TokenStream) -> TokenStream {
         .into_token_stream()
         .into()
 }
+
+#[doc = include_str!("../docs/island.md")]
+#[proc_macro_attribute]
+pub fn island(args: TokenStream, input: TokenStream) -> TokenStream {
+    let options = parse_macro_input!(args as IslandMacroOptions);
+    parse_macro_input!(input as ComponentBody)
+        .with_options(ComponentMacroOptions {
+            lazy: false,
+            island: Some(options.hydrate),
+        })
+        .into_token_stream()
+        .into()
+}
@@ -38,6 +38,9 @@ impl VirtualDom {
         new_nodes: Element,
     ) {
         self.runtime.clone().with_scope_on_stack(scope, || {
+            #[cfg(feature = "profiler")]
+            let _diff = crate::profiler::DiffTimer::start(&self.runtime, scope);
+
             // We don't diff the nodes if the scope is suspended or has an error
             let Ok(new_real_nodes) = &new_nodes else {
                 return;
@@ -72,6 +75,9 @@ impl VirtualDom {
         parent: Option<ElementRef>,
     ) -> usize {
         self.runtime.clone().with_scope_on_stack(scope, || {
+            #[cfg(feature = "profiler")]
+            let _diff = crate::profiler::DiffTimer::start(&self.runtime, scope);
+
             // If there are suspended scopes, we need to check if the scope is suspended before we diff it
             // If it is suspended, we need to diff it but write the mutations nothing
             // Note: It is important that we still diff the scope even if it is suspended, because the scope may render other child components which may change between renders
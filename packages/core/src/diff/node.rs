@@ -1,4 +1,5 @@
 use crate::innerlude::MountId;
+use crate::portal::portal_target;
 use crate::{Attribute, AttributeValue, DynamicNode::*};
 use crate::{VNode, VirtualDom, WriteMutations};
 use core::iter::Peekable;
@@ -502,6 +503,8 @@ impl VNode {
             AttributeValue::Listener(_) => {
                 to.remove_event_listener(&attribute.name[2..], id);
             }
+            // The portal attribute is never written to the element, so there is nothing to remove
+            _ if portal_target(attribute).is_some() => {}
             _ => {
                 to.set_attribute(
                     attribute.name,
@@ -532,12 +535,34 @@ impl VNode {
                 elements[id.0] = Some(element_ref);
                 to.create_event_listener(&attribute.name[2..], id);
             }
-            _ => {
-                to.set_attribute(attribute.name, attribute.namespace, &attribute.value, id);
-            }
+            _ => match portal_target(attribute) {
+                Some(target) => self.mount_portal(path, target, id, mount, dom, to),
+                None => to.set_attribute(attribute.name, attribute.namespace, &attribute.value, id),
+            },
         }
     }
 
+    /// Move the element a portal renders its children into to the target of the portal.
+    ///
+    /// The element is registered like an element with listeners so events from the portal, which the
+    /// renderer finds through the element in its new place, bubble through the component tree.
+    fn mount_portal(
+        &self,
+        path: &'static [u8],
+        target: &str,
+        id: ElementId,
+        mount: MountId,
+        dom: &mut VirtualDom,
+        to: &mut impl WriteMutations,
+    ) {
+        let element_ref = ElementRef {
+            path: ElementPath { path },
+            mount,
+        };
+        dom.runtime.elements.borrow_mut()[id.0] = Some(element_ref);
+        to.mount_portal(id, target);
+    }
+
     /// Create this rsx block. This will create scopes from components that this rsx block contains, but it will not write anything to the DOM.
     pub(crate) fn create(
         &self,
@@ -616,8 +641,16 @@ impl VNode {
                             // !!VERY IMPORTANT!!
                             // Write out all attributes before we load the children. Loading the children will change paths we rely on
                             // to assign ids to elements with dynamic attributes
+                            let mut portals = Vec::new();
                             if let Some(to) = to.as_deref_mut() {
-                                self.write_attrs(mount, &mut attrs, root_idx as u8, dom, to);
+                                self.write_attrs(
+                                    mount,
+                                    &mut attrs,
+                                    root_idx as u8,
+                                    &mut portals,
+                                    dom,
+                                    to,
+                                );
                             }
                             // This operation relies on the fact that the root node is the top node on the stack so we need to do it here
                             self.load_placeholders(
@@ -627,6 +660,15 @@ impl VNode {
                                 dom,
                                 to.as_deref_mut(),
                             );
+                            // Portals move their element out of the template, so they are mounted once every path into
+                            // the template was used
+                            if let Some(to) = to.as_deref_mut() {
+                                for (path, attribute, id) in portals {
+                                    if let Some(target) = portal_target(attribute) {
+                                        self.mount_portal(path, target, id, mount, dom, to);
+                                    }
+                                }
+                            }
                         }
 
                         // This creates one node on the stack
@@ -792,11 +834,12 @@ impl VNode {
     /// ```
     ///
     /// IMPORTANT: This function assumes that root node is the top node on the stack
-    fn write_attrs(
-        &self,
+    fn write_attrs<'a>(
+        &'a self,
         mount: MountId,
         dynamic_attributes_iter: &mut Peekable<impl Iterator<Item = (usize, &'static [u8])>>,
         root_idx: u8,
+        portals: &mut Vec<(&'static [u8], &'a Attribute, ElementId)>,
         dom: &mut VirtualDom,
         to: &mut impl WriteMutations,
     ) {
@@ -819,9 +862,12 @@ impl VNode {
                 }
             };
 
-            // Write the value for each attribute in the group
+            // Write the value for each attribute in the group. Portals are mounted after the children are loaded
             for attr in &**attribute {
-                self.write_attribute(attribute_path, attr, id, mount, dom, to);
+                match portal_target(attr) {
+                    Some(_) => portals.push((attribute_path, attr, id)),
+                    None => self.write_attribute(attribute_path, attr, id, mount, dom, to),
+                }
             }
             // Set the mounted dynamic attribute once. This must be set even if no actual
             // attributes are present so it is present for renderers like fullstack to look
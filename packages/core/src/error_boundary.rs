@@ -1,12 +1,13 @@
 use crate::{
     innerlude::{provide_context, CapturedError},
-    try_consume_context, use_hook, Element, IntoDynNode, Properties, ReactiveContext, Subscribers,
-    Template, TemplateAttribute, TemplateNode, VNode,
+    try_consume_context, use_hook, DynamicNode, Element, IntoDynNode, Properties, ReactiveContext,
+    Runtime, ScopeId, Subscribers, Template, TemplateAttribute, TemplateNode, VNode,
 };
 use std::{
     any::Any,
-    cell::RefCell,
+    cell::{Cell, RefCell},
     fmt::{Debug, Display},
+    hash::{DefaultHasher, Hash, Hasher},
     rc::Rc,
 };
 
@@ -82,6 +83,9 @@ pub fn use_error_boundary_provider() -> ErrorContext {
 pub struct ErrorContext {
     error: Rc<RefCell<Option<CapturedError>>>,
     subscribers: Subscribers,
+    // Bumped on every retry to remount the children of the boundary
+    generation: Rc<Cell<usize>>,
+    reporter: Rc<RefCell<Option<ErrorReporter>>>,
 }
 
 impl Debug for ErrorContext {
@@ -104,6 +108,8 @@ impl ErrorContext {
         Self {
             error: Rc::new(RefCell::new(error)),
             subscribers: Subscribers::new(),
+            generation: Default::default(),
+            reporter: Default::default(),
         }
     }
 
@@ -131,6 +137,21 @@ impl ErrorContext {
         self.mark_dirty();
     }
 
+    /// Clear all errors and remount the children of the Error Boundary. Unlike [`Self::clear_errors`], this always
+    /// runs the failed components again from scratch, with fresh hooks.
+    pub fn retry(&self) {
+        self.generation.set(self.generation.get() + 1);
+        self.clear_errors();
+    }
+
+    /// Send an error thrown from a scope to the `on_error` handler of the boundary, if it has one
+    pub(crate) fn report(&self, runtime: &Runtime, scope: ScopeId, error: &CapturedError) {
+        let reporter = self.reporter.borrow().clone();
+        if let Some(reporter) = reporter {
+            (reporter.0)(ErrorReport::new(runtime, scope, error.clone()));
+        }
+    }
+
     /// Mark the error context as dirty and notify all subscribers
     fn mark_dirty(&self) {
         let mut this_subscribers_vec = Vec::new();
@@ -152,6 +173,81 @@ impl<F: Fn(ErrorContext) -> Element + 'static> From<F> for ErrorHandler {
     }
 }
 
+/// An error caught by an error boundary, along with where it was thrown. See [`ErrorBoundary`]'s `on_error` prop.
+#[derive(Debug, Clone)]
+pub struct ErrorReport {
+    /// The error that was thrown
+    pub error: CapturedError,
+
+    /// The scopes from the root of the app to the scope that threw the error
+    pub scope_path: Vec<ScopeId>,
+
+    /// The names of the components in [`Self::scope_path`]
+    pub component_names: Vec<&'static str>,
+}
+
+impl ErrorReport {
+    fn new(runtime: &Runtime, scope: ScopeId, error: CapturedError) -> Self {
+        let mut scope_path = Vec::new();
+        let mut component_names = Vec::new();
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            let Some(state) = runtime.try_get_state(id) else {
+                break;
+            };
+            scope_path.push(id);
+            component_names.push(short_name(state.name));
+            current = state.parent_id;
+        }
+        scope_path.reverse();
+        component_names.reverse();
+
+        Self {
+            error,
+            scope_path,
+            component_names,
+        }
+    }
+
+    /// The scope that threw the error
+    pub fn scope(&self) -> Option<ScopeId> {
+        self.scope_path.last().copied()
+    }
+}
+
+/// Strip the module path from a component name, so `app::pages::Profile` becomes `Profile`
+fn short_name(name: &'static str) -> &'static str {
+    let path_end = name.find('<').unwrap_or(name.len());
+    match name[..path_end].rfind("::") {
+        Some(separator) => &name[separator + 2..],
+        None => name,
+    }
+}
+
+impl Display for ErrorReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (in {})",
+            self.error,
+            self.component_names.join(" > ")
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct ErrorReporter(Rc<dyn Fn(ErrorReport)>);
+impl PartialEq for ErrorReporter {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl<F: Fn(ErrorReport) + 'static> From<F> for ErrorReporter {
+    fn from(value: F) -> Self {
+        Self(Rc::new(value))
+    }
+}
+
 fn default_handler(errors: ErrorContext) -> Element {
     static TEMPLATE: Template = Template {
         roots: &[TemplateNode::Element {
@@ -196,10 +292,13 @@ fn default_handler(errors: ErrorContext) -> Element {
     ))
 }
 
+/// The props of the [`ErrorBoundary`] component.
 #[derive(Clone)]
 pub struct ErrorBoundaryProps {
     children: Element,
     handle_error: ErrorHandler,
+    reset_keys: Option<u64>,
+    on_error: Option<ErrorReporter>,
 }
 
 /// Create a new error boundary component that catches any errors thrown from child components
@@ -258,7 +357,8 @@ pub struct ErrorBoundaryProps {
 /// ## Resetting the error boundary
 ///
 /// Once the error boundary catches an error, it will render the rsx returned from the handle_error function instead of the children. To reset the error boundary,
-/// you can call the [`ErrorContext::clear_errors`] method. This will clear all errors and re-render the children.
+/// you can call the [`ErrorContext::clear_errors`] method. This will clear all errors and re-render the children. To run
+/// the failed components again from scratch, call [`ErrorContext::retry`] instead, which also remounts the children.
 ///
 /// ```rust, no_run
 /// # use dioxus::prelude::*;
@@ -307,9 +407,47 @@ pub struct ErrorBoundaryProps {
 ///     }
 /// }
 /// ```
+///
+/// ## Reset keys and reporting
+///
+/// The `reset_keys` prop takes any hashable value. When it changes while the boundary is showing an error, the boundary
+/// retries automatically. This is useful to recover once the input that caused the error changes, like the current page.
+///
+/// The `on_error` prop is called with an [`ErrorReport`] for every error thrown into the boundary, including the path of
+/// components that threw it. Use it to send errors to an error tracker.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus_core::ErrorReport;
+/// #[component]
+/// fn Profile(user_id: ReadSignal<u32>) -> Element {
+///     rsx! {
+///         ErrorBoundary {
+///             reset_keys: user_id(),
+///             on_error: |report: ErrorReport| eprintln!("{report}"),
+///             UserDetails { user_id }
+///         }
+///     }
+/// }
+/// # #[component]
+/// # fn UserDetails(user_id: ReadSignal<u32>) -> Element { VNode::empty() }
+/// ```
 #[allow(non_upper_case_globals, non_snake_case)]
 pub fn ErrorBoundary(props: ErrorBoundaryProps) -> Element {
     let error_boundary = use_error_boundary_provider();
+    use_hook(|| *error_boundary.reporter.borrow_mut() = props.on_error.clone());
+    if *error_boundary.reporter.borrow() != props.on_error {
+        *error_boundary.reporter.borrow_mut() = props.on_error.clone();
+    }
+
+    // Retry once the reset keys change. Errors are only cleared if the boundary is showing one
+    let last_reset_keys = use_hook(|| Rc::new(Cell::new(props.reset_keys)));
+    if last_reset_keys.replace(props.reset_keys) != props.reset_keys
+        && error_boundary.error.borrow().is_some()
+    {
+        error_boundary.retry();
+    }
+
     let errors = error_boundary.error();
     let has_errors = errors.is_some();
 
@@ -325,10 +463,18 @@ pub fn ErrorBoundary(props: ErrorBoundaryProps) -> Element {
                 node_paths: &[&[0u8]],
                 attr_paths: &[],
             };
+            // Key the children with the retry generation so a retry remounts them
+            let generation = error_boundary.generation.get();
+            let children = VNode::new(
+                Some(generation.to_string()),
+                TEMPLATE,
+                Box::new([(props.children).into_dyn_node()]),
+                Default::default(),
+            );
             VNode::new(
                 None,
                 TEMPLATE,
-                Box::new([(props.children).into_dyn_node()]),
+                Box::new([DynamicNode::Fragment(vec![children])]),
                 Default::default(),
             )
         })
@@ -336,35 +482,71 @@ pub fn ErrorBoundary(props: ErrorBoundaryProps) -> Element {
 }
 
 impl ErrorBoundaryProps {
-    /**
-    Create a builder for building `ErrorBoundaryProps`.
-    On the builder, call `.children(...)`(optional), `.handle_error(...)`(optional) to set the values of the fields.
-    Finally, call `.build()` to create the instance of `ErrorBoundaryProps`.
-                        */
-    #[allow(dead_code)]
-    pub fn builder() -> ErrorBoundaryPropsBuilder<((), ())> {
-        ErrorBoundaryPropsBuilder { fields: ((), ()) }
+    /// Create a builder for the props. Every field is optional.
+    pub fn builder() -> ErrorBoundaryPropsBuilder {
+        ErrorBoundaryPropsBuilder {
+            children: VNode::empty(),
+            handle_error: ErrorHandler(Rc::new(default_handler)),
+            reset_keys: None,
+            on_error: None,
+        }
     }
 }
 
-#[must_use]
-#[doc(hidden)]
-#[allow(dead_code, non_camel_case_types, non_snake_case)]
-pub struct ErrorBoundaryPropsBuilder<TypedBuilderFields> {
-    fields: TypedBuilderFields,
+/// The builder for [`ErrorBoundaryProps`].
+#[derive(Clone)]
+pub struct ErrorBoundaryPropsBuilder {
+    children: Element,
+    handle_error: ErrorHandler,
+    reset_keys: Option<u64>,
+    on_error: Option<ErrorReporter>,
 }
-impl<TypedBuilderFields> Clone for ErrorBoundaryPropsBuilder<TypedBuilderFields>
-where
-    TypedBuilderFields: Clone,
-{
-    fn clone(&self) -> Self {
+
+impl ErrorBoundaryPropsBuilder {
+    /// The children to render while there is no error.
+    pub fn children(self, children: Element) -> Self {
+        Self { children, ..self }
+    }
+
+    /// Render the error instead of the children. Defaults to the error messages in red.
+    pub fn handle_error(self, handle_error: impl Into<ErrorHandler>) -> Self {
         Self {
-            fields: self.fields.clone(),
+            handle_error: handle_error.into(),
+            ..self
+        }
+    }
+
+    /// Retry once this value changes while the boundary is showing an error.
+    pub fn reset_keys(self, reset_keys: impl Hash) -> Self {
+        let mut hasher = DefaultHasher::new();
+        reset_keys.hash(&mut hasher);
+        Self {
+            reset_keys: Some(hasher.finish()),
+            ..self
+        }
+    }
+
+    /// Called with an [`ErrorReport`] for every error thrown into the boundary.
+    pub fn on_error(self, on_error: impl Into<ErrorReporter>) -> Self {
+        Self {
+            on_error: Some(on_error.into()),
+            ..self
+        }
+    }
+
+    /// Build the props.
+    pub fn build(self) -> ErrorBoundaryProps {
+        ErrorBoundaryProps {
+            children: self.children,
+            handle_error: self.handle_error,
+            reset_keys: self.reset_keys,
+            on_error: self.on_error,
         }
     }
 }
+
 impl Properties for ErrorBoundaryProps {
-    type Builder = ErrorBoundaryPropsBuilder<((), ())>;
+    type Builder = ErrorBoundaryPropsBuilder;
     fn builder() -> Self::Builder {
         ErrorBoundaryProps::builder()
     }
@@ -373,90 +555,3 @@ impl Properties for ErrorBoundaryProps {
         false
     }
 }
-#[doc(hidden)]
-#[allow(dead_code, non_camel_case_types, non_snake_case)]
-pub trait ErrorBoundaryPropsBuilder_Optional<T> {
-    fn into_value<F: FnOnce() -> T>(self, default: F) -> T;
-}
-impl<T> ErrorBoundaryPropsBuilder_Optional<T> for () {
-    fn into_value<F: FnOnce() -> T>(self, default: F) -> T {
-        default()
-    }
-}
-impl<T> ErrorBoundaryPropsBuilder_Optional<T> for (T,) {
-    fn into_value<F: FnOnce() -> T>(self, _: F) -> T {
-        self.0
-    }
-}
-#[allow(dead_code, non_camel_case_types, missing_docs)]
-impl<__handle_error> ErrorBoundaryPropsBuilder<((), __handle_error)> {
-    pub fn children(
-        self,
-        children: Element,
-    ) -> ErrorBoundaryPropsBuilder<((Element,), __handle_error)> {
-        let children = (children,);
-        let (_, handle_error) = self.fields;
-        ErrorBoundaryPropsBuilder {
-            fields: (children, handle_error),
-        }
-    }
-}
-#[doc(hidden)]
-#[allow(dead_code, non_camel_case_types, non_snake_case)]
-pub enum ErrorBoundaryPropsBuilder_Error_Repeated_field_children {}
-#[doc(hidden)]
-#[allow(dead_code, non_camel_case_types, missing_docs)]
-impl<__handle_error> ErrorBoundaryPropsBuilder<((Element,), __handle_error)> {
-    #[deprecated(note = "Repeated field children")]
-    pub fn children(
-        self,
-        _: ErrorBoundaryPropsBuilder_Error_Repeated_field_children,
-    ) -> ErrorBoundaryPropsBuilder<((Element,), __handle_error)> {
-        self
-    }
-}
-#[allow(dead_code, non_camel_case_types, missing_docs)]
-impl<__children> ErrorBoundaryPropsBuilder<(__children, ())> {
-    pub fn handle_error(
-        self,
-        handle_error: impl ::core::convert::Into<ErrorHandler>,
-    ) -> ErrorBoundaryPropsBuilder<(__children, (ErrorHandler,))> {
-        let handle_error = (handle_error.into(),);
-        let (children, _) = self.fields;
-        ErrorBoundaryPropsBuilder {
-            fields: (children, handle_error),
-        }
-    }
-}
-#[doc(hidden)]
-#[allow(dead_code, non_camel_case_types, non_snake_case)]
-pub enum ErrorBoundaryPropsBuilder_Error_Repeated_field_handle_error {}
-#[doc(hidden)]
-#[allow(dead_code, non_camel_case_types, missing_docs)]
-impl<__children> ErrorBoundaryPropsBuilder<(__children, (ErrorHandler,))> {
-    #[deprecated(note = "Repeated field handle_error")]
-    pub fn handle_error(
-        self,
-        _: ErrorBoundaryPropsBuilder_Error_Repeated_field_handle_error,
-    ) -> ErrorBoundaryPropsBuilder<(__children, (ErrorHandler,))> {
-        self
-    }
-}
-#[allow(dead_code, non_camel_case_types, missing_docs)]
-impl<
-        __handle_error: ErrorBoundaryPropsBuilder_Optional<ErrorHandler>,
-        __children: ErrorBoundaryPropsBuilder_Optional<Element>,
-    > ErrorBoundaryPropsBuilder<(__children, __handle_error)>
-{
-    pub fn build(self) -> ErrorBoundaryProps {
-        let (children, handle_error) = self.fields;
-        let children = ErrorBoundaryPropsBuilder_Optional::into_value(children, VNode::empty);
-        let handle_error = ErrorBoundaryPropsBuilder_Optional::into_value(handle_error, || {
-            ErrorHandler(Rc::new(default_handler))
-        });
-        ErrorBoundaryProps {
-            children,
-            handle_error,
-        }
-    }
-}
@@ -2,6 +2,7 @@
 //!
 //! Each scope in dioxus has a single [Owner]
 
+use crate::{Runtime, ScopeId};
 use generational_box::{AnyStorage, Owner, SyncStorage, UnsyncStorage};
 use std::{
     any::{Any, TypeId},
@@ -83,3 +84,52 @@ pub fn current_owner<S: AnyStorage>() -> Owner<S> {
 
     crate::Runtime::current().current_owner()
 }
+
+/// The live generational boxes owned by a scope. See [`Runtime::owner_sizes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScopeOwnerSize {
+    /// The scope that owns the boxes
+    pub scope: ScopeId,
+    /// The name of the component the scope renders
+    pub name: &'static str,
+    /// The number of live boxes in [`UnsyncStorage`]
+    pub unsync: usize,
+    /// The number of live boxes in [`SyncStorage`]
+    pub sync: usize,
+}
+
+impl ScopeOwnerSize {
+    /// Get the total number of live boxes owned by the scope
+    pub fn total(&self) -> usize {
+        self.unsync + self.sync
+    }
+}
+
+impl Runtime {
+    /// Get the number of live generational boxes owned by each scope that has an owner.
+    ///
+    /// Scopes that stay mounted, like the root scope, should not keep growing while the app is idle. State created in
+    /// event handlers is owned by the scope of the handler, so it is only dropped when that scope is. Use
+    /// [`Owner::allocations`] or [`AllocationSnapshot`](generational_box::AllocationSnapshot) with the
+    /// `debug_allocations` feature of `generational-box` to find where the leaked boxes were created.
+    pub fn owner_sizes(&self) -> Vec<ScopeOwnerSize> {
+        self.scope_states
+            .borrow()
+            .iter()
+            .flatten()
+            .filter_map(|scope| {
+                let unsync = scope.has_context::<Owner<UnsyncStorage>>();
+                let sync = scope.has_context::<Owner<SyncStorage>>();
+                if unsync.is_none() && sync.is_none() {
+                    return None;
+                }
+                Some(ScopeOwnerSize {
+                    scope: scope.id,
+                    name: scope.name,
+                    unsync: unsync.map_or(0, |owner| owner.len()),
+                    sync: sync.map_or(0, |owner| owner.len()),
+                })
+            })
+            .collect()
+    }
+}
@@ -1,5 +1,9 @@
 use crate::innerlude::CapturedError;
-use crate::{innerlude::SuspendedFuture, runtime::Runtime, Element, ScopeId, Task};
+use crate::{
+    innerlude::{RenderPriority, SuspendedFuture},
+    runtime::Runtime,
+    Element, ScopeId, Task,
+};
 use std::future::Future;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -307,6 +311,53 @@ pub fn needs_update_any(id: ScopeId) {
     Runtime::with_current_scope(|cx| cx.needs_update_any(id));
 }
 
+/// Run a closure, marking every update it causes as a low priority transition.
+///
+/// Transitions are rendered after all other updates. Renderers that render with
+/// [`VirtualDom::render_with_deadline`](crate::VirtualDom::render_with_deadline) keep handling user input while a
+/// transition renders. This is useful for expensive updates that don't need to be shown immediately, like filtering a
+/// large table while the user types into the filter.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn app() -> Element {
+///     let mut filter = use_signal(String::new);
+///     let mut deferred_filter = use_signal(String::new);
+///
+///     rsx! {
+///         input {
+///             value: "{filter}",
+///             oninput: move |event| {
+///                 // The input updates right away, the table once all urgent updates are rendered
+///                 filter.set(event.value());
+///                 start_transition(|| deferred_filter.set(event.value()));
+///             },
+///         }
+///         Table { filter: deferred_filter() }
+///     }
+/// }
+///
+/// #[component]
+/// fn Table(filter: String) -> Element {
+///     rsx! {
+///         for row in (0..5000).filter(|row| row.to_string().contains(&filter)) {
+///             div { "{row}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn start_transition<O>(f: impl FnOnce() -> O) -> O {
+    RenderPriority::Transition.run(f)
+}
+
+/// Set the priority of every update to the current component.
+///
+/// Updates to the component are never rendered with a higher priority than this. Setting the priority to
+/// [`RenderPriority::Transition`] makes every update to the component a transition, even if it was caused by user input.
+pub fn set_render_priority(priority: RenderPriority) {
+    Runtime::with_current_scope(|cx| cx.render_priority.set(priority));
+}
+
 /// Schedule an update for the current component.
 ///
 /// Note: Unlike [`needs_update`], the function returned by this method will work outside of the dioxus runtime.
@@ -0,0 +1,141 @@
+use crate::innerlude::*;
+
+/// The name of the element that wraps each island in server rendered html.
+pub const ISLAND_TAG: &str = "dx-island";
+
+/// When an island is hydrated on the client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum IslandHydration {
+    /// Hydrate the island as soon as the page loads
+    #[default]
+    Load,
+
+    /// Hydrate the island once it scrolls into view
+    Visible,
+
+    /// Hydrate the island once the browser is idle
+    Idle,
+
+    /// Hydrate the island the first time the user interacts with it. The interaction that triggers hydration is not
+    /// replayed.
+    Interaction,
+}
+
+impl IslandHydration {
+    /// Get the name of the strategy as it is written in the html
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IslandHydration::Load => "load",
+            IslandHydration::Visible => "visible",
+            IslandHydration::Idle => "idle",
+            IslandHydration::Interaction => "interaction",
+        }
+    }
+
+    /// Parse the name of a strategy from the html
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "load" => Some(IslandHydration::Load),
+            "visible" => Some(IslandHydration::Visible),
+            "idle" => Some(IslandHydration::Idle),
+            "interaction" => Some(IslandHydration::Interaction),
+            _ => None,
+        }
+    }
+}
+
+/// Render a component as an island: an interactive part of a static page that is hydrated on its own.
+///
+/// This is called by components declared with `#[island]`. When the page is server rendered without hydration, the
+/// renderer wraps the output of the island in a [`ISLAND_TAG`] element with its name, serialized props and hydration
+/// strategy. The client finds those elements and hydrates each one as a separate [`VirtualDom`] that only runs the
+/// component. Everywhere else, the component renders like any other component.
+pub fn render_island<P: Properties + 'static>(
+    name: &'static str,
+    hydrate: IslandHydration,
+    serialized_props: String,
+    component: fn(P) -> Element,
+    props: P,
+) -> Element {
+    static TEMPLATE: Template = Template {
+        roots: &[TemplateNode::Dynamic { id: 0 }],
+        node_paths: &[&[0]],
+        attr_paths: &[],
+    };
+
+    let children = VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([DynamicNode::Component(VComponent::new(
+            component, props, name,
+        ))]),
+        Box::new([]),
+    );
+    let props = IslandProps {
+        name,
+        hydrate,
+        props: serialized_props,
+        children: Element::Ok(children),
+    };
+    Element::Ok(VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([DynamicNode::Component(VComponent::new(
+            Island, props, "Island",
+        ))]),
+        Box::new([]),
+    ))
+}
+
+/// The boundary of an island. The children render in place; renderers read the props to mark where the island is.
+#[allow(non_snake_case)]
+fn Island(props: IslandProps) -> Element {
+    props.children
+}
+
+/// The props of an island boundary. See [`render_island`].
+#[derive(Clone, PartialEq)]
+pub struct IslandProps {
+    name: &'static str,
+    hydrate: IslandHydration,
+    props: String,
+    children: Element,
+}
+
+impl IslandProps {
+    /// The name the island is registered under on the client
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// When the island should be hydrated
+    pub fn hydrate(&self) -> IslandHydration {
+        self.hydrate
+    }
+
+    /// The serialized props of the island
+    pub fn props(&self) -> &str {
+        &self.props
+    }
+}
+
+impl Properties for IslandProps {
+    type Builder = ();
+    fn builder() -> Self::Builder {}
+    fn memoize(&mut self, new: &Self) -> bool {
+        let equal = self == new;
+        if !equal {
+            *self = new.clone();
+        }
+        equal
+    }
+}
+
+impl VComponent {
+    /// Get the props of the island boundary if this component is one
+    ///
+    /// This is useful for renderers that need to mark islands, such as SSR
+    pub fn island(&self) -> Option<&IslandProps> {
+        self.props.props().downcast_ref::<IslandProps>()
+    }
+}
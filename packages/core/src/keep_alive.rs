@@ -0,0 +1,294 @@
+use crate::innerlude::*;
+use std::{cell::RefCell, rc::Rc};
+
+/// Keep the children rendered under previous cache keys alive while they are hidden.
+///
+/// ## Details
+///
+/// Normally, when a conditional switches from one component to another, the old component is dropped along with its
+/// hooks, signals and tasks. Switching back mounts it again from scratch. `KeepAlive` caches the children it rendered
+/// for each `cache_key` instead. When the key changes, the old children are hidden, and their tasks and rerenders are
+/// paused. When the key changes back, they are shown again without rerunning any hooks, so form input, scroll position
+/// and fetched data are preserved.
+///
+/// At most `max` subtrees are cached. Once the limit is reached, the subtree that was shown least recently is dropped.
+///
+/// ## Example
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn app() -> Element {
+///     let mut tab = use_signal(|| "profile");
+///     rsx! {
+///         button { onclick: move |_| tab.set("profile"), "Profile" }
+///         button { onclick: move |_| tab.set("settings"), "Settings" }
+///         KeepAlive { cache_key: tab(), max: 5,
+///             if tab() == "profile" {
+///                 Profile {}
+///             } else {
+///                 Settings {}
+///             }
+///         }
+///     }
+/// }
+/// # fn Profile() -> Element { rsx! { input {} } }
+/// # fn Settings() -> Element { rsx! { input {} } }
+/// ```
+///
+/// ## Usage
+///
+/// Each cached subtree is mounted inside a `div` with `display: contents`, which is switched to `display: none` while
+/// the subtree is hidden. Tasks spawned in a hidden subtree start paused, and components in it that are marked dirty
+/// rerender once the subtree is shown again.
+///
+/// The children are cached as they were last rendered, so they must render the content for the current key themselves.
+/// For the router, render the component for the current route instead of an `Outlet`, which always renders the
+/// current route.
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn KeepAlive(props: KeepAliveProps) -> Element {
+    static TEMPLATE: Template = Template {
+        roots: &[TemplateNode::Dynamic { id: 0 }],
+        node_paths: &[&[0]],
+        attr_paths: &[],
+    };
+
+    let cache = use_hook(|| Rc::new(RefCell::new(KeepAliveCache::default())));
+    let mut cache = cache.borrow_mut();
+    cache.show(&props.cache_key, props.children, props.max);
+
+    let entries = cache
+        .entries
+        .iter()
+        .map(|entry| {
+            let props = KeepAliveEntryProps {
+                active: entry.key == props.cache_key,
+                children: entry.children.clone(),
+            };
+            VNode::new(
+                Some(entry.key.clone()),
+                TEMPLATE,
+                Box::new([DynamicNode::Component(VComponent::new(
+                    KeepAliveEntry,
+                    props,
+                    "KeepAliveEntry",
+                ))]),
+                Box::new([]),
+            )
+        })
+        .collect();
+
+    Element::Ok(VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([DynamicNode::Fragment(entries)]),
+        Box::new([]),
+    ))
+}
+
+/// The props of the [`KeepAlive`] component. They are created with [`KeepAliveBuilder`].
+#[derive(Clone, PartialEq)]
+pub struct KeepAliveProps {
+    cache_key: String,
+    max: usize,
+    children: Element,
+}
+
+/// The builder for [`KeepAliveProps`]. `CACHE_KEY` tracks whether the required `cache_key` was set, so the props can
+/// only be built once it is.
+pub struct KeepAliveBuilder<const CACHE_KEY: bool> {
+    cache_key: String,
+    max: usize,
+    children: Element,
+}
+
+impl<const CACHE_KEY: bool> KeepAliveBuilder<CACHE_KEY> {
+    /// The key the children are cached under. Children rendered under a different key are kept alive but hidden.
+    pub fn cache_key(self, cache_key: impl ToString) -> KeepAliveBuilder<true> {
+        KeepAliveBuilder {
+            cache_key: cache_key.to_string(),
+            max: self.max,
+            children: self.children,
+        }
+    }
+
+    /// The maximum number of subtrees to cache, including the one that is shown. Defaults to 10.
+    pub fn max(self, max: usize) -> Self {
+        Self { max, ..self }
+    }
+
+    /// The children to render for the current key.
+    pub fn children(self, children: Element) -> Self {
+        Self { children, ..self }
+    }
+}
+
+impl KeepAliveBuilder<true> {
+    /// Build the props once the cache key is set.
+    pub fn build(self) -> KeepAliveProps {
+        KeepAliveProps {
+            cache_key: self.cache_key,
+            max: self.max,
+            children: self.children,
+        }
+    }
+}
+
+impl Properties for KeepAliveProps {
+    type Builder = KeepAliveBuilder<false>;
+    fn builder() -> Self::Builder {
+        KeepAliveBuilder {
+            cache_key: String::new(),
+            max: 10,
+            children: VNode::empty(),
+        }
+    }
+    fn memoize(&mut self, new: &Self) -> bool {
+        let equal = self == new;
+        if !equal {
+            *self = new.clone();
+        }
+        equal
+    }
+}
+
+#[derive(Default)]
+struct KeepAliveCache {
+    // Entries stay in the order they were first shown so switching between them never moves any nodes
+    entries: Vec<CachedEntry>,
+    shown: u64,
+}
+
+struct CachedEntry {
+    key: String,
+    children: Element,
+    last_shown: u64,
+}
+
+impl KeepAliveCache {
+    fn show(&mut self, key: &str, children: Element, max: usize) {
+        self.shown += 1;
+        match self.entries.iter_mut().find(|entry| entry.key == key) {
+            Some(entry) => {
+                entry.children = children;
+                entry.last_shown = self.shown;
+            }
+            None => self.entries.push(CachedEntry {
+                key: key.to_string(),
+                children,
+                last_shown: self.shown,
+            }),
+        }
+
+        // Drop the least recently shown subtrees. The entry that was just shown is always the most recent one
+        while self.entries.len() > max.max(1) {
+            let (oldest, _) = self
+                .entries
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, entry)| entry.last_shown)
+                .unwrap();
+            self.entries.remove(oldest);
+        }
+    }
+}
+
+/// A single cached subtree. This is its own scope so the tasks of the subtree can be found through their parent scopes.
+#[allow(non_snake_case)]
+fn KeepAliveEntry(props: KeepAliveEntryProps) -> Element {
+    static TEMPLATE: Template = Template {
+        roots: &[TemplateNode::Element {
+            tag: "div",
+            namespace: None,
+            attrs: &[TemplateAttribute::Dynamic { id: 0 }],
+            children: &[TemplateNode::Dynamic { id: 0 }],
+        }],
+        node_paths: &[&[0, 0]],
+        attr_paths: &[&[0]],
+    };
+
+    Runtime::current().set_children_hidden(current_scope_id(), !props.active);
+
+    let display = if props.active { "contents" } else { "none" };
+    Element::Ok(VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([props.children.into_dyn_node()]),
+        Box::new([Box::new([Attribute::new(
+            "display",
+            display,
+            Some("style"),
+            false,
+        )])]),
+    ))
+}
+
+#[derive(Clone, PartialEq)]
+struct KeepAliveEntryProps {
+    active: bool,
+    children: Element,
+}
+
+impl Properties for KeepAliveEntryProps {
+    type Builder = ();
+    fn builder() -> Self::Builder {}
+    fn memoize(&mut self, new: &Self) -> bool {
+        let equal = self == new;
+        if !equal {
+            *self = new.clone();
+        }
+        equal
+    }
+}
+
+impl Runtime {
+    /// Check if a scope is inside a hidden [`KeepAlive`] entry
+    pub(crate) fn is_hidden(&self, id: ScopeId) -> bool {
+        let scopes = self.scope_states.borrow();
+        let scope = |id: ScopeId| scopes.get(id.0).and_then(|scope| scope.as_ref());
+        let mut current = scope(id).and_then(|scope| scope.parent_id);
+        while let Some(scope) = current.and_then(scope) {
+            if scope.hides_children.get() {
+                return true;
+            }
+            current = scope.parent_id;
+        }
+        false
+    }
+
+    /// Hide or show the scopes under a [`KeepAlive`] entry. Once they are shown, their tasks are woken and the scopes
+    /// that skipped a rerender are queued again
+    fn set_children_hidden(&self, root: ScopeId, hidden: bool) {
+        let scopes = self.scope_states.borrow();
+        let Some(state) = scopes.get(root.0).and_then(|scope| scope.as_ref()) else {
+            return;
+        };
+        if state.hides_children.replace(hidden) == hidden || hidden {
+            return;
+        }
+
+        let is_under = |mut id: ScopeId| loop {
+            let Some(parent) = scopes
+                .get(id.0)
+                .and_then(|scope| scope.as_ref())
+                .and_then(|scope| scope.parent_id)
+            else {
+                return false;
+            };
+            if parent == root {
+                return true;
+            }
+            id = parent;
+        };
+
+        for scope in scopes.iter().flatten().filter(|scope| is_under(scope.id)) {
+            if scope.skipped_render.take() {
+                self.shown_scopes.borrow_mut().push(scope.id);
+            }
+            for task in scope.spawned_tasks.borrow().iter() {
+                _ = self
+                    .sender
+                    .unbounded_send(SchedulerMsg::TaskNotified(task.id));
+            }
+        }
+    }
+}
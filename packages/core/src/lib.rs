@@ -12,11 +12,18 @@ mod events;
 mod fragment;
 mod generational_box;
 mod global_context;
+mod island;
+mod keep_alive;
 mod launch;
 mod mutations;
 mod nodes;
+mod portal;
+#[cfg(feature = "profiler")]
+mod profiler;
 mod properties;
 mod reactive_context;
+#[cfg(feature = "serialize")]
+mod recording;
 mod render_error;
 mod root_wrapper;
 mod runtime;
@@ -24,9 +31,12 @@ mod scheduler;
 mod scope_arena;
 mod scope_context;
 mod scopes;
+mod sleep;
 mod suspense;
 mod tasks;
 mod virtual_dom;
+#[cfg(feature = "testing")]
+mod virtual_time;
 
 mod hotreload_utils;
 
@@ -51,6 +61,9 @@ pub mod internal {
 
     #[doc(hidden)]
     pub use generational_box;
+
+    #[doc(hidden)]
+    pub use crate::sleep::sleep;
 }
 
 pub(crate) mod innerlude {
@@ -62,9 +75,12 @@ pub(crate) mod innerlude {
     pub use crate::fragment::*;
     pub use crate::generational_box::*;
     pub use crate::global_context::*;
+    pub use crate::island::*;
+    pub use crate::keep_alive::*;
     pub use crate::launch::*;
     pub use crate::mutations::*;
     pub use crate::nodes::*;
+    pub use crate::portal::*;
     pub use crate::properties::*;
     pub use crate::reactive_context::*;
     pub use crate::render_error::*;
@@ -74,6 +90,8 @@ pub(crate) mod innerlude {
     pub use crate::suspense::*;
     pub use crate::tasks::*;
     pub use crate::virtual_dom::*;
+    #[cfg(feature = "testing")]
+    pub use crate::virtual_time::*;
 
     pub use anyhow::anyhow;
     pub use anyhow::Context as AnyhowContext;
@@ -96,19 +114,33 @@ pub use crate::innerlude::{
     anyhow, consume_context, consume_context_from_scope, current_owner, current_scope_id,
     fc_to_builder, generation, has_context, needs_update, needs_update_any, parent_scope,
     provide_context, provide_create_error_boundary, provide_root_context, queue_effect,
-    remove_future, schedule_update, schedule_update_any, spawn, spawn_forever, spawn_isomorphic,
-    suspend, throw_error, try_consume_context, use_after_render, use_before_render, use_drop,
-    use_hook, use_hook_with_cleanup, with_owner, AnyValue, AnyhowContext, Attribute,
-    AttributeValue, Callback, CapturedError, Component, ComponentFunction, DynamicNode, Element,
-    ElementId, ErrorBoundary, ErrorContext, Event, EventHandler, Fragment, HasAttributes,
-    IntoAttributeValue, IntoDynNode, LaunchConfig, ListenerCallback, MarkerWrapper, Mutation,
-    Mutations, NoOpMutations, OptionStringFromMarker, Properties, ReactiveContext, RenderError,
-    Result, Runtime, RuntimeGuard, ScopeId, ScopeState, SpawnIfAsync, SubscriberList, Subscribers,
-    SuperFrom, SuperInto, SuspendedFuture, SuspenseBoundary, SuspenseBoundaryProps,
-    SuspenseContext, Task, Template, TemplateAttribute, TemplateNode, VComponent, VNode,
-    VNodeInner, VPlaceholder, VText, VirtualDom, WriteMutations,
+    remove_future, render_island, schedule_update, schedule_update_any, set_render_priority, spawn,
+    spawn_forever, spawn_isomorphic, start_transition, suspend, throw_error, try_consume_context,
+    use_after_render, use_before_render, use_drop, use_hook, use_hook_with_cleanup, with_owner,
+    AnyValue, AnyhowContext, Attribute, AttributeValue, Callback, CapturedError, Component,
+    ComponentFunction, DynamicNode, Element, ElementId, ErrorBoundary, ErrorContext, ErrorReport,
+    Event, EventHandler, Fragment, HasAttributes, IntoAttributeValue, IntoDynNode, IslandHydration,
+    IslandProps, KeepAlive, KeepAliveProps, LaunchConfig, ListenerCallback, MarkerWrapper,
+    Mutation, Mutations, NoOpMutations, OptionStringFromMarker, Portal, PortalProps, Properties,
+    ReactiveContext, RenderError, RenderPriority, Result, Runtime, RuntimeGuard, ScopeId,
+    ScopeOwnerSize, ScopeState, SpawnIfAsync, SubscriberList, Subscribers, SuperFrom, SuperInto,
+    SuspendedFuture, SuspenseBoundary, SuspenseBoundaryProps, SuspenseContext, Task, Template,
+    TemplateAttribute, TemplateNode, VComponent, VNode, VNodeInner, VPlaceholder, VText,
+    VirtualDom, WriteMutations, ISLAND_TAG, PORTAL_ATTRIBUTE,
+};
+
+#[cfg(feature = "serialize")]
+pub use crate::recording::{
+    MutationRecorder, MutationReplayer, RecordedAttributeValue, RecordedMutation,
+    RecordedMutations, ReplayError, MUTATION_FORMAT_VERSION,
 };
 
+#[cfg(feature = "testing")]
+pub use crate::virtual_time::{virtual_sleep, TestDom, VirtualClock, VirtualSleep};
+
+#[cfg(feature = "profiler")]
+pub use crate::profiler::{RenderCause, RenderProfile, ScopeRender};
+
 /// Equivalent to `Ok::<_, dioxus::CapturedError>(value)`.
 ///
 /// This simplifies creation of an `dioxus::Result` in places where type
@@ -115,6 +115,21 @@ pub trait WriteMutations {
     ///
     /// Id: The ID of the root node to push.
     fn push_root(&mut self, id: ElementId);
+
+    /// Move an element into the element with the given id, leaving a placeholder in its place.
+    ///
+    /// This is written for the element a [`Portal`](crate::Portal) renders its children into, after the element and its
+    /// children were created. Later mutations that target the element by its ID should use the placeholder to position
+    /// nodes, and removing or replacing the element should remove the placeholder too. If the element was already moved,
+    /// it should be moved into the new target.
+    ///
+    /// Renderers that don't support portals can ignore this mutation, which renders the children in place.
+    ///
+    /// Id: The ID of the element to move.
+    /// Target: The id attribute of the element to move the element into.
+    fn mount_portal(&mut self, id: ElementId, target: &str) {
+        _ = (id, target);
+    }
 }
 
 /// A `Mutation` represents a single instruction for the renderer to use to modify the UI tree to match the state
@@ -279,6 +294,15 @@ pub enum Mutation {
         /// The ID of the root node to push.
         id: ElementId,
     },
+
+    /// Move an element into another element, leaving a placeholder in its place.
+    MountPortal {
+        /// The ID of the element to move.
+        id: ElementId,
+
+        /// The id attribute of the element to move the element into.
+        target: String,
+    },
 }
 
 /// A static list of mutations that can be applied to the DOM. Note: this list does not contain any `Any` attribute values
@@ -378,6 +402,13 @@ impl WriteMutations for Mutations {
     fn push_root(&mut self, id: ElementId) {
         self.edits.push(Mutation::PushRoot { id })
     }
+
+    fn mount_portal(&mut self, id: ElementId, target: &str) {
+        self.edits.push(Mutation::MountPortal {
+            id,
+            target: target.into(),
+        })
+    }
 }
 
 /// A struct that ignores all mutations
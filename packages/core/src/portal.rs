@@ -0,0 +1,134 @@
+use crate::innerlude::*;
+
+/// The name of the attribute that marks the element a [`Portal`] renders its children into.
+///
+/// The value of the attribute is the id of the target element. The attribute is never written to
+/// the element; the diffing engine turns it into [`WriteMutations::mount_portal`] instead.
+pub const PORTAL_ATTRIBUTE: &str = "dioxus-portal";
+
+/// Render children into a different element of the page.
+///
+/// ## Details
+///
+/// The children of a portal stay part of the component tree they are rendered in: they can read
+/// context from their parents, events bubble from them to the parents of the portal, and they are
+/// dropped with the component that renders the portal. Only their nodes are mounted somewhere else,
+/// under the element with the id `target`.
+///
+/// This is useful for modals, tooltips and dropdowns that need to escape the `overflow` and
+/// `z-index` of the components they are rendered in.
+///
+/// ## Example
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// fn app() -> Element {
+///     let mut open = use_signal(|| false);
+///     rsx! {
+///         button { onclick: move |_| open.set(true), "Open" }
+///         if open() {
+///             Portal { target: "modals",
+///                 div { class: "modal", onclick: move |_| open.set(false), "Close" }
+///             }
+///         }
+///         div { id: "modals" }
+///     }
+/// }
+/// ```
+///
+/// ## Usage
+///
+/// The children are mounted inside a `div` that is appended to the target. Renderers that don't
+/// support portals, and portals whose target doesn't exist when they are mounted, render the
+/// children in place. Server side rendering renders the children into the target if the target is
+/// part of the same page, and in place otherwise.
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn Portal(props: PortalProps) -> Element {
+    static TEMPLATE: Template = Template {
+        roots: &[TemplateNode::Element {
+            tag: "div",
+            namespace: None,
+            attrs: &[TemplateAttribute::Dynamic { id: 0 }],
+            children: &[TemplateNode::Dynamic { id: 0 }],
+        }],
+        node_paths: &[&[0, 0]],
+        attr_paths: &[&[0]],
+    };
+
+    Element::Ok(VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([props.children.into_dyn_node()]),
+        Box::new([Box::new([Attribute::new(
+            PORTAL_ATTRIBUTE,
+            props.target,
+            None,
+            false,
+        )])]),
+    ))
+}
+
+/// The props of the [`Portal`] component.
+#[derive(Clone, PartialEq)]
+pub struct PortalProps {
+    target: String,
+    children: Element,
+}
+
+pub struct PortalBuilder<const TARGET: bool> {
+    target: String,
+    children: Element,
+}
+
+impl<const TARGET: bool> PortalBuilder<TARGET> {
+    /// The id of the element to render the children into.
+    pub fn target(self, target: impl ToString) -> PortalBuilder<true> {
+        PortalBuilder {
+            target: target.to_string(),
+            children: self.children,
+        }
+    }
+
+    /// The children to render into the target.
+    pub fn children(self, children: Element) -> Self {
+        Self { children, ..self }
+    }
+}
+
+impl PortalBuilder<true> {
+    pub fn build(self) -> PortalProps {
+        PortalProps {
+            target: self.target,
+            children: self.children,
+        }
+    }
+}
+
+impl Properties for PortalProps {
+    type Builder = PortalBuilder<false>;
+    fn builder() -> Self::Builder {
+        PortalBuilder {
+            target: String::new(),
+            children: VNode::empty(),
+        }
+    }
+    fn memoize(&mut self, new: &Self) -> bool {
+        let equal = self == new;
+        if !equal {
+            *self = new.clone();
+        }
+        equal
+    }
+}
+
+/// The target of a portal attribute, if the attribute is the one [`Portal`] renders.
+pub(crate) fn portal_target(attribute: &Attribute) -> Option<&str> {
+    match &attribute.value {
+        AttributeValue::Text(target)
+            if attribute.name == PORTAL_ATTRIBUTE && attribute.namespace.is_none() =>
+        {
+            Some(target)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,445 @@
+//! A profiler that records how long each scope takes to render and diff, how many mutations it writes, and why it rendered.
+//!
+//! Profiling is only available with the `profiler` feature. Start recording with [`Runtime::start_profiling`], and read the
+//! renders back with [`Runtime::take_render_profile`] or [`Runtime::stop_profiling`]:
+//!
+//! ```rust, ignore
+//! let mut dom = VirtualDom::new(app);
+//! dom.runtime().start_profiling();
+//! dom.rebuild_in_place();
+//!
+//! let profile = dom.runtime().stop_profiling();
+//! std::fs::write("profile.json", profile.to_chrome_trace()).unwrap();
+//! ```
+//!
+//! The chrome trace can be opened in `chrome://tracing` or <https://ui.perfetto.dev>.
+
+use crate::innerlude::*;
+use rustc_hash::FxHashMap;
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Write as _,
+    rc::Rc,
+    time::Duration,
+};
+use web_time::Instant;
+
+/// Why a scope rendered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderCause {
+    /// The scope rendered for the first time
+    Mount,
+
+    /// The parent of the scope rendered and passed it new props
+    Props,
+
+    /// An event handler marked the scope as dirty. Contains the name of the event.
+    Event(String),
+
+    /// A reactive context marked the scope as dirty, for example because a signal the component read changed. Contains a
+    /// description of the context, which includes where the context was created in debug builds.
+    ReactiveContext(String),
+
+    /// The scope was marked as dirty directly, for example with [`needs_update`](crate::needs_update) or
+    /// [`VirtualDom::mark_dirty`]
+    Manual,
+}
+
+impl std::fmt::Display for RenderCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderCause::Mount => write!(f, "mount"),
+            RenderCause::Props => write!(f, "props changed"),
+            RenderCause::Event(name) => write!(f, "{name} event"),
+            RenderCause::ReactiveContext(context) => write!(f, "{context}"),
+            RenderCause::Manual => write!(f, "marked dirty"),
+        }
+    }
+}
+
+/// A single render of a scope
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeRender {
+    /// The scope that rendered
+    pub scope: ScopeId,
+
+    /// The name of the component
+    pub name: &'static str,
+
+    /// Why the scope rendered
+    pub cause: RenderCause,
+
+    /// When the component started running, relative to when profiling started
+    pub render_start: Duration,
+
+    /// How long the component took to run
+    pub render: Duration,
+
+    /// When the diff started, relative to when profiling started
+    pub diff_start: Duration,
+
+    /// How long the diff took, including any child scopes that rendered during the diff
+    pub diff: Duration,
+
+    /// How many mutations the diff wrote, including mutations written by child scopes that rendered during the diff
+    pub mutations: usize,
+}
+
+/// The renders recorded by the profiler
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderProfile {
+    /// Every render in the order the diffs finished
+    pub renders: Vec<ScopeRender>,
+}
+
+impl RenderProfile {
+    /// Get every render of a scope
+    pub fn scope(&self, scope: ScopeId) -> impl Iterator<Item = &ScopeRender> {
+        self.renders
+            .iter()
+            .filter(move |render| render.scope == scope)
+    }
+
+    /// Get each render as a pair of [chrome trace events](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU),
+    /// one for the component running and one for the diff
+    pub fn chrome_trace_events(&self) -> impl Iterator<Item = String> + '_ {
+        self.renders.iter().flat_map(|render| {
+            let args = format!(
+                r#""args":{{"scope":{},"cause":{},"mutations":{}}}"#,
+                render.scope.0,
+                json_string(&render.cause.to_string()),
+                render.mutations
+            );
+            [
+                trace_event(
+                    render.name,
+                    "render",
+                    render.render_start,
+                    render.render,
+                    &args,
+                ),
+                trace_event(render.name, "diff", render.diff_start, render.diff, &args),
+            ]
+        })
+    }
+
+    /// Export the profile in the [chrome trace event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU)
+    pub fn to_chrome_trace(&self) -> String {
+        let events = self.chrome_trace_events().collect::<Vec<_>>();
+        format!(r#"{{"traceEvents":[{}]}}"#, events.join(","))
+    }
+}
+
+fn trace_event(
+    name: &str,
+    category: &str,
+    start: Duration,
+    duration: Duration,
+    args: &str,
+) -> String {
+    format!(
+        r#"{{"name":{},"cat":"{category}","ph":"X","ts":{},"dur":{},"pid":1,"tid":1,{args}}}"#,
+        json_string(name),
+        start.as_secs_f64() * 1_000_000.0,
+        duration.as_secs_f64() * 1_000_000.0,
+    )
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl Runtime {
+    /// Start recording renders. Anything recorded before is cleared.
+    pub fn start_profiling(&self) {
+        *self.profiler.borrow_mut() = Profiler {
+            started: Some(Instant::now()),
+            ..Default::default()
+        };
+    }
+
+    /// Stop recording renders and return everything recorded since profiling started or the profile was last taken
+    pub fn stop_profiling(&self) -> RenderProfile {
+        let profile = self.take_render_profile();
+        *self.profiler.borrow_mut() = Profiler::default();
+        profile
+    }
+
+    /// Check if the profiler is recording renders
+    pub fn is_profiling(&self) -> bool {
+        self.profiler.borrow().started.is_some()
+    }
+
+    /// Take everything recorded since profiling started or the profile was last taken, without stopping the profiler
+    pub fn take_render_profile(&self) -> RenderProfile {
+        RenderProfile {
+            renders: std::mem::take(&mut self.profiler.borrow_mut().renders),
+        }
+    }
+}
+
+/// The state of the profiler, stored in the runtime
+#[derive(Default)]
+pub(crate) struct Profiler {
+    started: Option<Instant>,
+    renders: Vec<ScopeRender>,
+
+    // Why each dirty scope was marked dirty
+    causes: FxHashMap<ScopeId, RenderCause>,
+
+    // The scopes that ran, but haven't finished diffing yet
+    running: FxHashMap<ScopeId, (RenderCause, Duration, Duration)>,
+
+    // The event that is currently being handled
+    event: Option<String>,
+}
+
+thread_local! {
+    // The reactive context that is currently marking scopes as dirty
+    static TRIGGER: RefCell<Option<String>> = const { RefCell::new(None) };
+
+    // The number of mutations written since the thread started
+    static MUTATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn profiling_runtime() -> Option<Rc<Runtime>> {
+    Runtime::try_current().filter(|runtime| runtime.is_profiling())
+}
+
+/// Record why a scope was marked as dirty in the current runtime
+pub(crate) fn note_dirty(scope: ScopeId) {
+    if let Some(runtime) = Runtime::try_current() {
+        note_dirty_in(&runtime, scope);
+    }
+}
+
+/// Record why a scope was marked as dirty. Only the first cause is kept until the scope renders
+pub(crate) fn note_dirty_in(runtime: &Runtime, scope: ScopeId) {
+    if !runtime.is_profiling() {
+        return;
+    }
+    let mut profiler = runtime.profiler.borrow_mut();
+    let cause = match &profiler.event {
+        Some(event) => RenderCause::Event(event.clone()),
+        None => TRIGGER
+            .with(|trigger| trigger.borrow().clone())
+            .map(RenderCause::ReactiveContext)
+            .unwrap_or(RenderCause::Manual),
+    };
+    profiler.causes.entry(scope).or_insert(cause);
+}
+
+/// Marks every scope marked dirty while it is alive as dirtied by an event
+pub(crate) struct EventGuard(Option<Rc<Runtime>>);
+
+impl EventGuard {
+    pub(crate) fn new(name: &str) -> Self {
+        let runtime = profiling_runtime();
+        if let Some(runtime) = &runtime {
+            runtime.profiler.borrow_mut().event = Some(name.to_string());
+        }
+        Self(runtime)
+    }
+}
+
+impl Drop for EventGuard {
+    fn drop(&mut self) {
+        if let Some(runtime) = &self.0 {
+            runtime.profiler.borrow_mut().event = None;
+        }
+    }
+}
+
+/// Marks every scope marked dirty while it is alive as dirtied by a reactive context
+pub(crate) struct TriggerGuard(Option<Option<String>>);
+
+impl TriggerGuard {
+    pub(crate) fn new(context: &ReactiveContext) -> Self {
+        if profiling_runtime().is_none() {
+            return Self(None);
+        }
+        let previous = TRIGGER.with(|trigger| trigger.replace(Some(context.to_string())));
+        Self(Some(previous))
+    }
+}
+
+impl Drop for TriggerGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            TRIGGER.with(|trigger| *trigger.borrow_mut() = previous);
+        }
+    }
+}
+
+/// Start timing a component run
+pub(crate) fn start_render(runtime: &Runtime) -> Option<Instant> {
+    runtime.is_profiling().then(Instant::now)
+}
+
+/// Finish timing a component run. The render is recorded once the diff finishes
+pub(crate) fn finish_render(
+    runtime: &Runtime,
+    scope: ScopeId,
+    first_render: bool,
+    start: Option<Instant>,
+) {
+    let Some(start) = start else {
+        return;
+    };
+    let mut profiler = runtime.profiler.borrow_mut();
+    let Some(started) = profiler.started else {
+        return;
+    };
+    let cause = match first_render {
+        true => RenderCause::Mount,
+        false => profiler.causes.remove(&scope).unwrap_or(RenderCause::Props),
+    };
+    profiler.running.insert(
+        scope,
+        (
+            cause,
+            start.saturating_duration_since(started),
+            start.elapsed(),
+        ),
+    );
+}
+
+/// Times the diff of a scope and records the render once it is dropped
+pub(crate) struct DiffTimer(Option<(Rc<Runtime>, ScopeId, Instant, usize)>);
+
+impl DiffTimer {
+    pub(crate) fn start(runtime: &Rc<Runtime>, scope: ScopeId) -> Self {
+        Self(runtime.is_profiling().then(|| {
+            let mutations = MUTATIONS.with(|count| count.get());
+            (runtime.clone(), scope, Instant::now(), mutations)
+        }))
+    }
+}
+
+impl Drop for DiffTimer {
+    fn drop(&mut self) {
+        let Some((runtime, scope, start, mutations_before)) = self.0.take() else {
+            return;
+        };
+        let name = runtime
+            .try_get_state(scope)
+            .map(|scope| scope.name)
+            .unwrap_or_default();
+        let mut profiler = runtime.profiler.borrow_mut();
+        let Some(started) = profiler.started else {
+            return;
+        };
+        // Scopes that are created or diffed without rerunning were already recorded
+        let Some((cause, render_start, render)) = profiler.running.remove(&scope) else {
+            return;
+        };
+        profiler.renders.push(ScopeRender {
+            scope,
+            name,
+            cause,
+            render_start,
+            render,
+            diff_start: start.saturating_duration_since(started),
+            diff: start.elapsed(),
+            mutations: MUTATIONS.with(|count| count.get()) - mutations_before,
+        });
+    }
+}
+
+/// Counts the mutations written to the inner writer
+pub(crate) struct CountMutations<'a, W>(pub(crate) &'a mut W);
+
+impl<W> CountMutations<'_, W> {
+    fn count(&mut self) -> &mut W {
+        MUTATIONS.with(|count| count.set(count.get() + 1));
+        self.0
+    }
+}
+
+impl<W: WriteMutations> WriteMutations for CountMutations<'_, W> {
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.count().append_children(id, m)
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        self.count().assign_node_id(path, id)
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.count().create_placeholder(id)
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        self.count().create_text_node(value, id)
+    }
+
+    fn load_template(&mut self, template: Template, index: usize, id: ElementId) {
+        self.count().load_template(template, index, id)
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        self.count().replace_node_with(id, m)
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        self.count().replace_placeholder_with_nodes(path, m)
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        self.count().insert_nodes_after(id, m)
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        self.count().insert_nodes_before(id, m)
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        self.count().set_attribute(name, ns, value, id)
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        self.count().set_node_text(value, id)
+    }
+
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.count().create_event_listener(name, id)
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.count().remove_event_listener(name, id)
+    }
+
+    fn remove_node(&mut self, id: ElementId) {
+        self.count().remove_node(id)
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.count().push_root(id)
+    }
+
+    fn mount_portal(&mut self, id: ElementId, target: &str) {
+        self.count().mount_portal(id, target)
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{current_scope_id, scope_context::Scope, tasks::SchedulerMsg, Runtime, ScopeId};
+use crate::{
+    current_scope_id, scope_context::Scope, tasks::SchedulerMsg, RenderPriority, Runtime, ScopeId,
+};
 use futures_channel::mpsc::UnboundedReceiver;
 use generational_box::{BorrowMutError, GenerationalBox, SyncStorage};
 use std::{
@@ -103,8 +105,13 @@ impl ReactiveContext {
     pub(crate) fn new_for_scope(scope: &Scope, runtime: &Runtime) -> Self {
         let id = scope.id;
         let sender = runtime.sender.clone();
+        // Signals can be written from other threads that don't know the priority of the update
+        let priority = RenderPriority::current();
         let update_scope = move || {
-            _ = sender.unbounded_send(SchedulerMsg::Immediate(id));
+            #[cfg(feature = "profiler")]
+            crate::profiler::note_dirty(id);
+            let priority = RenderPriority::current_or(priority);
+            _ = sender.unbounded_send(SchedulerMsg::Immediate(id, priority));
         };
 
         // Otherwise, create a new context at the current scope
@@ -215,6 +222,8 @@ impl ReactiveContext {
     ///
     /// Returns true if the context was marked as dirty, or false if the context has been dropped
     pub fn mark_dirty(&self) -> bool {
+        #[cfg(feature = "profiler")]
+        let _trigger = crate::profiler::TriggerGuard::new(self);
         if let Ok(mut self_write) = self.inner.try_write() {
             #[cfg(debug_assertions)]
             {
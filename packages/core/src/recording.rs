@@ -0,0 +1,541 @@
+//! A stable, versioned serialization of the edits the [`VirtualDom`] writes. Enable it with the `serialize` feature.
+//!
+//! [`MutationRecorder`] is a [`WriteMutations`] implementation that records the edit stream in batches. Each
+//! [`RecordedMutations`] batch carries the templates it uses for the first time, so batches can be sent to a remote
+//! renderer or saved as golden files in order. [`MutationReplayer`] applies the recorded batches to any other
+//! [`WriteMutations`] implementation:
+//!
+//! ```rust
+//! # use dioxus::prelude::*;
+//! # use dioxus_core::{MutationRecorder, MutationReplayer, Mutations, RecordedMutations};
+//! let mut dom = VirtualDom::new(|| rsx! { div { "hello" } });
+//! let mut recorder = MutationRecorder::new();
+//! dom.rebuild(&mut recorder);
+//!
+//! let batch = recorder.take();
+//! let json = serde_json::to_string(&batch).unwrap();
+//! let batch: RecordedMutations = serde_json::from_str(&json).unwrap();
+//!
+//! let mut mutations = Mutations::default();
+//! MutationReplayer::new().replay(&batch, &mut mutations).unwrap();
+//! assert!(!mutations.edits.is_empty());
+//! ```
+
+use crate::innerlude::*;
+use rustc_hash::FxHashMap;
+use std::{
+    collections::HashSet,
+    sync::{Mutex, OnceLock},
+};
+
+/// The version of the recorded mutation format. It changes whenever the format changes in a way that older readers can
+/// not understand. Deserializing a batch with a newer version fails.
+pub const MUTATION_FORMAT_VERSION: u32 = 1;
+
+/// A batch of recorded edits with the templates they use for the first time. Created by [`MutationRecorder::take`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RecordedMutations {
+    /// The version of the format this batch was recorded with. See [`MUTATION_FORMAT_VERSION`].
+    #[serde(deserialize_with = "deserialize_version")]
+    pub version: u32,
+
+    /// The templates that are used for the first time in this batch.
+    ///
+    /// Templates are numbered in the order they appear across every batch of the stream, starting at zero.
+    pub templates: Vec<Template>,
+
+    /// The edits in the order they were written
+    pub edits: Vec<RecordedMutation>,
+}
+
+impl Default for RecordedMutations {
+    fn default() -> Self {
+        Self {
+            version: MUTATION_FORMAT_VERSION,
+            templates: Vec::new(),
+            edits: Vec::new(),
+        }
+    }
+}
+
+impl RecordedMutations {
+    /// Check if the batch has no edits and no new templates
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty() && self.edits.is_empty()
+    }
+}
+
+fn deserialize_version<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    let version = u32::deserialize(deserializer)?;
+    if version > MUTATION_FORMAT_VERSION {
+        return Err(serde::de::Error::custom(format!(
+            "unsupported mutation format version {version}, expected at most {MUTATION_FORMAT_VERSION}"
+        )));
+    }
+    Ok(version)
+}
+
+/// A single recorded edit. This mirrors [`Mutation`], except templates are referenced by their number in the stream.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum RecordedMutation {
+    /// See [`WriteMutations::append_children`]
+    AppendChildren {
+        /// The ID of the element being mounted to
+        id: ElementId,
+
+        /// The number of nodes on the stack to append to the target element
+        m: usize,
+    },
+
+    /// See [`WriteMutations::assign_node_id`]
+    AssignId {
+        /// The path of the child of the topmost node on the stack
+        path: Vec<u8>,
+
+        /// The ID we're assigning to this element/placeholder
+        id: ElementId,
+    },
+
+    /// See [`WriteMutations::create_placeholder`]
+    CreatePlaceholder {
+        /// The ID we're assigning to this placeholder
+        id: ElementId,
+    },
+
+    /// See [`WriteMutations::create_text_node`]
+    CreateTextNode {
+        /// The text content of this text node
+        value: String,
+
+        /// The ID we're assigning to this text node
+        id: ElementId,
+    },
+
+    /// See [`WriteMutations::load_template`]
+    LoadTemplate {
+        /// The number of the template in the stream
+        template: usize,
+
+        /// The index of the root we're loading from the template
+        index: usize,
+
+        /// The ID we're assigning to the loaded root
+        id: ElementId,
+    },
+
+    /// See [`WriteMutations::replace_node_with`]
+    ReplaceWith {
+        /// The ID of the node we're going to replace
+        id: ElementId,
+
+        /// The number of nodes on the stack to replace the target element with
+        m: usize,
+    },
+
+    /// See [`WriteMutations::replace_placeholder_with_nodes`]
+    ReplacePlaceholder {
+        /// The path of the child of the topmost node on the stack
+        path: Vec<u8>,
+
+        /// The number of nodes on the stack to replace the target element with
+        m: usize,
+    },
+
+    /// See [`WriteMutations::insert_nodes_after`]
+    InsertAfter {
+        /// The ID of the node to insert after
+        id: ElementId,
+
+        /// The number of nodes on the stack to insert after the target node
+        m: usize,
+    },
+
+    /// See [`WriteMutations::insert_nodes_before`]
+    InsertBefore {
+        /// The ID of the node to insert before
+        id: ElementId,
+
+        /// The number of nodes on the stack to insert before the target node
+        m: usize,
+    },
+
+    /// See [`WriteMutations::set_attribute`]
+    SetAttribute {
+        /// The name of the attribute to set
+        name: String,
+
+        /// The (optional) namespace of the attribute
+        ns: Option<String>,
+
+        /// The value of the attribute
+        value: RecordedAttributeValue,
+
+        /// The ID of the node to set the attribute of
+        id: ElementId,
+    },
+
+    /// See [`WriteMutations::set_node_text`]
+    SetText {
+        /// The textcontent of the node
+        value: String,
+
+        /// The ID of the node to set the textcontent of
+        id: ElementId,
+    },
+
+    /// See [`WriteMutations::create_event_listener`]
+    NewEventListener {
+        /// The name of the event to listen for
+        name: String,
+
+        /// The ID of the node to attach the listener to
+        id: ElementId,
+    },
+
+    /// See [`WriteMutations::remove_event_listener`]
+    RemoveEventListener {
+        /// The name of the event to remove
+        name: String,
+
+        /// The ID of the node to remove the listener from
+        id: ElementId,
+    },
+
+    /// See [`WriteMutations::remove_node`]
+    Remove {
+        /// The ID of the node to remove
+        id: ElementId,
+    },
+
+    /// See [`WriteMutations::push_root`]
+    PushRoot {
+        /// The ID of the root node to push
+        id: ElementId,
+    },
+
+    /// See [`WriteMutations::mount_portal`]
+    MountPortal {
+        /// The ID of the element to move
+        id: ElementId,
+
+        /// The id attribute of the element to move the element into
+        target: String,
+    },
+}
+
+/// The serializable subset of [`AttributeValue`]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum RecordedAttributeValue {
+    /// Text attribute
+    Text(String),
+
+    /// A float
+    Float(f64),
+
+    /// Signed integer
+    Int(i64),
+
+    /// Boolean
+    Bool(bool),
+
+    /// A "none" value, resulting in the removal of an attribute from the dom
+    None,
+}
+
+impl From<&AttributeValue> for RecordedAttributeValue {
+    fn from(value: &AttributeValue) -> Self {
+        match value {
+            AttributeValue::Text(s) => RecordedAttributeValue::Text(s.clone()),
+            AttributeValue::Float(n) => RecordedAttributeValue::Float(*n),
+            AttributeValue::Int(n) => RecordedAttributeValue::Int(*n),
+            AttributeValue::Bool(b) => RecordedAttributeValue::Bool(*b),
+            AttributeValue::None => RecordedAttributeValue::None,
+            _ => panic!("Cannot serialize attribute value"),
+        }
+    }
+}
+
+impl From<&RecordedAttributeValue> for AttributeValue {
+    fn from(value: &RecordedAttributeValue) -> Self {
+        match value {
+            RecordedAttributeValue::Text(s) => AttributeValue::Text(s.clone()),
+            RecordedAttributeValue::Float(n) => AttributeValue::Float(*n),
+            RecordedAttributeValue::Int(n) => AttributeValue::Int(*n),
+            RecordedAttributeValue::Bool(b) => AttributeValue::Bool(*b),
+            RecordedAttributeValue::None => AttributeValue::None,
+        }
+    }
+}
+
+/// Records the edits written by the [`VirtualDom`] into [`RecordedMutations`] batches.
+///
+/// The recorder remembers every template it has seen, so each template is only included in the first batch that uses it.
+/// Batches must be replayed in the order they were taken.
+#[derive(Default)]
+pub struct MutationRecorder {
+    templates: FxHashMap<Template, usize>,
+    batch: RecordedMutations,
+}
+
+impl MutationRecorder {
+    /// Create a new recorder for a fresh stream
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take the edits recorded since the last call
+    pub fn take(&mut self) -> RecordedMutations {
+        std::mem::take(&mut self.batch)
+    }
+
+    fn push(&mut self, edit: RecordedMutation) {
+        self.batch.edits.push(edit);
+    }
+}
+
+impl WriteMutations for MutationRecorder {
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.push(RecordedMutation::AppendChildren { id, m })
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        self.push(RecordedMutation::AssignId {
+            path: path.to_vec(),
+            id,
+        })
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.push(RecordedMutation::CreatePlaceholder { id })
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        self.push(RecordedMutation::CreateTextNode {
+            value: value.into(),
+            id,
+        })
+    }
+
+    fn load_template(&mut self, template: Template, index: usize, id: ElementId) {
+        let next = self.templates.len();
+        let number = *self.templates.entry(template).or_insert_with(|| {
+            self.batch.templates.push(template);
+            next
+        });
+        self.push(RecordedMutation::LoadTemplate {
+            template: number,
+            index,
+            id,
+        })
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        self.push(RecordedMutation::ReplaceWith { id, m })
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        self.push(RecordedMutation::ReplacePlaceholder {
+            path: path.to_vec(),
+            m,
+        })
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        self.push(RecordedMutation::InsertAfter { id, m })
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        self.push(RecordedMutation::InsertBefore { id, m })
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        self.push(RecordedMutation::SetAttribute {
+            name: name.into(),
+            ns: ns.map(Into::into),
+            value: value.into(),
+            id,
+        })
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        self.push(RecordedMutation::SetText {
+            value: value.into(),
+            id,
+        })
+    }
+
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.push(RecordedMutation::NewEventListener {
+            name: name.into(),
+            id,
+        })
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.push(RecordedMutation::RemoveEventListener {
+            name: name.into(),
+            id,
+        })
+    }
+
+    fn remove_node(&mut self, id: ElementId) {
+        self.push(RecordedMutation::Remove { id })
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.push(RecordedMutation::PushRoot { id })
+    }
+
+    fn mount_portal(&mut self, id: ElementId, target: &str) {
+        self.push(RecordedMutation::MountPortal {
+            id,
+            target: target.into(),
+        })
+    }
+}
+
+/// Applies recorded batches to a [`WriteMutations`] implementation.
+///
+/// The replayer keeps the templates of the stream, so batches must be replayed in the order they were recorded.
+#[derive(Default)]
+pub struct MutationReplayer {
+    templates: Vec<Template>,
+}
+
+impl MutationReplayer {
+    /// Create a new replayer for a fresh stream
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a batch of edits to `to`.
+    ///
+    /// Returns an error without applying any edits if the batch loads a template that neither it nor an earlier batch
+    /// of the stream introduced.
+    pub fn replay(
+        &mut self,
+        batch: &RecordedMutations,
+        to: &mut impl WriteMutations,
+    ) -> Result<(), ReplayError> {
+        let known = self.templates.len() + batch.templates.len();
+        if let Some(template) = batch.edits.iter().find_map(|edit| match edit {
+            RecordedMutation::LoadTemplate { template, .. } if *template >= known => {
+                Some(*template)
+            }
+            _ => None,
+        }) {
+            return Err(ReplayError::UnknownTemplate { template });
+        }
+        self.templates.extend_from_slice(&batch.templates);
+
+        for edit in &batch.edits {
+            match edit {
+                RecordedMutation::AppendChildren { id, m } => to.append_children(*id, *m),
+                RecordedMutation::AssignId { path, id } => {
+                    to.assign_node_id(intern_path(path), *id)
+                }
+                RecordedMutation::CreatePlaceholder { id } => to.create_placeholder(*id),
+                RecordedMutation::CreateTextNode { value, id } => to.create_text_node(value, *id),
+                RecordedMutation::LoadTemplate {
+                    template,
+                    index,
+                    id,
+                } => to.load_template(self.templates[*template], *index, *id),
+                RecordedMutation::ReplaceWith { id, m } => to.replace_node_with(*id, *m),
+                RecordedMutation::ReplacePlaceholder { path, m } => {
+                    to.replace_placeholder_with_nodes(intern_path(path), *m)
+                }
+                RecordedMutation::InsertAfter { id, m } => to.insert_nodes_after(*id, *m),
+                RecordedMutation::InsertBefore { id, m } => to.insert_nodes_before(*id, *m),
+                RecordedMutation::SetAttribute {
+                    name,
+                    ns,
+                    value,
+                    id,
+                } => to.set_attribute(
+                    intern_str(name),
+                    ns.as_deref().map(intern_str),
+                    &value.into(),
+                    *id,
+                ),
+                RecordedMutation::SetText { value, id } => to.set_node_text(value, *id),
+                RecordedMutation::NewEventListener { name, id } => {
+                    to.create_event_listener(intern_str(name), *id)
+                }
+                RecordedMutation::RemoveEventListener { name, id } => {
+                    to.remove_event_listener(intern_str(name), *id)
+                }
+                RecordedMutation::Remove { id } => to.remove_node(*id),
+                RecordedMutation::PushRoot { id } => to.push_root(*id),
+                RecordedMutation::MountPortal { id, target } => to.mount_portal(*id, target),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned by [`MutationReplayer::replay`] when a batch doesn't fit the stream it is replayed into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReplayError {
+    /// The batch loads a template that was not recorded in this stream. The batches were likely replayed out of order.
+    UnknownTemplate {
+        /// The number of the missing template
+        template: usize,
+    },
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::UnknownTemplate { template } => {
+                write!(f, "template {template} was not recorded in this stream")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+// Renderers expect static names and paths. Each distinct value is leaked once, so replaying a long stream doesn't grow
+// memory beyond the set of names and paths it uses.
+static INTERNED_STRS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+static INTERNED_PATHS: OnceLock<Mutex<HashSet<&'static [u8]>>> = OnceLock::new();
+
+fn intern_str(value: &str) -> &'static str {
+    let mut interned = INTERNED_STRS.get_or_init(Default::default).lock().unwrap();
+    match interned.get(value) {
+        Some(value) => value,
+        None => {
+            let value: &'static str = Box::leak(value.into());
+            interned.insert(value);
+            value
+        }
+    }
+}
+
+fn intern_path(path: &[u8]) -> &'static [u8] {
+    let mut interned = INTERNED_PATHS.get_or_init(Default::default).lock().unwrap();
+    match interned.get(path) {
+        Some(path) => path,
+        None => {
+            let path: &'static [u8] = Box::leak(path.into());
+            interned.insert(path);
+            path
+        }
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
     SuspenseContext,
 };
 use crate::{
-    innerlude::{LocalTask, SchedulerMsg},
+    innerlude::{LocalTask, RenderPriority, SchedulerMsg},
     scope_context::Scope,
     scopes::ScopeId,
     Task,
@@ -60,6 +60,10 @@ pub struct Runtime {
     // Tasks that are waiting to be polled
     pub(crate) dirty_tasks: RefCell<BTreeSet<DirtyTasks>>,
 
+    // Scopes that skipped a rerender while they were hidden and were shown again. They are queued the next time the
+    // VirtualDom looks for work
+    pub(crate) shown_scopes: RefCell<Vec<ScopeId>>,
+
     // The element ids that are used in the renderer
     // These mark a specific place in a whole rsx block
     pub(crate) elements: RefCell<Slab<Option<ElementRef>>>,
@@ -68,6 +72,14 @@ pub struct Runtime {
     // We need to store this information on the virtual dom so that we know what nodes are mounted where when we bubble events
     // Each mount is associated with a whole rsx block. [`VirtualDom::elements`] link to a specific node in the block
     pub(crate) mounts: RefCell<Slab<VNodeMount>>,
+
+    // The clock that virtual_sleep uses instead of real time
+    #[cfg(feature = "testing")]
+    pub(crate) virtual_clock: RefCell<Option<crate::innerlude::VirtualClock>>,
+
+    // The renders recorded by the profiler
+    #[cfg(feature = "profiler")]
+    pub(crate) profiler: RefCell<crate::profiler::Profiler>,
 }
 
 impl Runtime {
@@ -87,8 +99,13 @@ impl Runtime {
             suspended_tasks: Default::default(),
             pending_effects: Default::default(),
             dirty_tasks: Default::default(),
+            shown_scopes: Default::default(),
             elements: RefCell::new(elements),
             mounts: Default::default(),
+            #[cfg(feature = "testing")]
+            virtual_clock: Default::default(),
+            #[cfg(feature = "profiler")]
+            profiler: Default::default(),
         })
     }
 
@@ -362,7 +379,8 @@ fn MyComponent() -> Element {{
     /// this method does not give any indication as to the success of the listener call. If the listener is not found,
     /// nothing will happen.
     ///
-    /// It is up to the listeners themselves to mark nodes as dirty.
+    /// It is up to the listeners themselves to mark nodes as dirty. Updates they cause are rendered with
+    /// [`RenderPriority::UserInput`] unless they are inside a [`start_transition`](crate::start_transition).
     ///
     /// If you have multiple events, you can call this method multiple times before calling "render_with_deadline"
     #[instrument(skip(self, event), level = "trace", name = "Runtime::handle_event")]
@@ -371,11 +389,15 @@ fn MyComponent() -> Element {{
         let elements = self.elements.borrow();
 
         if let Some(Some(parent_path)) = elements.get(element.0).copied() {
-            if event.propagates() {
-                self.handle_bubbling_event(parent_path, name, event);
-            } else {
-                self.handle_non_bubbling_event(parent_path, name, event);
-            }
+            #[cfg(feature = "profiler")]
+            let _event = crate::profiler::EventGuard::new(name);
+            RenderPriority::UserInput.run(|| {
+                if event.propagates() {
+                    self.handle_bubbling_event(parent_path, name, event);
+                } else {
+                    self.handle_non_bubbling_event(parent_path, name, event);
+                }
+            });
         }
     }
 
@@ -563,6 +585,7 @@ fn MyComponent() -> Element {{
     pub fn throw_error(&self, id: ScopeId, error: impl Into<CapturedError> + 'static) {
         let error = error.into();
         if let Some(cx) = self.consume_context::<crate::ErrorContext>(id) {
+            cx.report(self, id, &error);
             cx.insert_error(error)
         } else {
             tracing::error!(
@@ -75,13 +75,27 @@
 //! 3. Effects:
 //!    Description: Effects should always run after all changes to the DOM have been applied.
 //!    Priority: These are the lowest priority tasks in the scheduler. They are run after all other dirty scopes and futures have been resolved. Other tasks may cause components to rerun, which would update the DOM. These effects should only run after the DOM has been updated.
+//!
+//! ## Render priorities
+//!
+//! Every time a scope is marked dirty, the update is tagged with a [`RenderPriority`]. Updates caused by event handlers are
+//! [`RenderPriority::UserInput`], updates inside of [`start_transition`](crate::start_transition) are [`RenderPriority::Transition`],
+//! and everything else is [`RenderPriority::Normal`]. A scope can also lower the priority of all of its updates with
+//! [`set_render_priority`](crate::set_render_priority).
+//!
+//! Dirty scopes are still rerun from the root down, but scopes that are only dirty because of a transition are queued separately
+//! and deferred until all other work is done. The one exception is a transition scope that is the parent of a more urgent dirty
+//! scope or task: it reruns first so the child never observes out of date state from its parent (see goal 1).
+//!
+//! [`VirtualDom::render_with_deadline`] uses these priorities to handle user input before transitions and to yield back to the
+//! renderer once its time budget runs out.
 
 use crate::innerlude::Effect;
 use crate::ScopeId;
 use crate::Task;
 use crate::VirtualDom;
 use std::borrow::Borrow;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::hash::Hash;
 
@@ -121,6 +135,55 @@ impl Hash for ScopeOrder {
     }
 }
 
+/// How urgently an update to a scope should be rendered. See the [module level documentation](self) for more information.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RenderPriority {
+    /// Updates caused by user input, like typing into a text field. These are rendered first.
+    UserInput,
+
+    /// Updates that are not caused by user input or marked as a transition.
+    #[default]
+    Normal,
+
+    /// Updates that can wait until all other updates are rendered, like filtering a large table.
+    Transition,
+}
+
+thread_local! {
+    static CURRENT_PRIORITY: Cell<Option<RenderPriority>> = const { Cell::new(None) };
+}
+
+impl RenderPriority {
+    /// Get the priority of updates that are scheduled right now on this thread
+    pub fn current() -> Self {
+        Self::current_or(RenderPriority::Normal)
+    }
+
+    /// Get the priority of updates that are scheduled right now on this thread, or `fallback` if this thread is not
+    /// inside of an event handler or transition.
+    ///
+    /// Update closures that can be sent to other threads capture the priority they were created with and use it as the
+    /// fallback.
+    pub(crate) fn current_or(fallback: Self) -> Self {
+        CURRENT_PRIORITY
+            .with(|current| current.get())
+            .unwrap_or(fallback)
+    }
+
+    /// Run a closure, tagging every update it schedules with this priority
+    pub(crate) fn run<O>(self, f: impl FnOnce() -> O) -> O {
+        struct Restore(Option<RenderPriority>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT_PRIORITY.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(CURRENT_PRIORITY.with(|current| current.replace(Some(self))));
+        f()
+    }
+}
+
 impl VirtualDom {
     /// Queue a task to be polled
     pub(crate) fn queue_task(&mut self, task: Task, order: ScopeOrder) {
@@ -136,13 +199,58 @@ impl VirtualDom {
     }
 
     /// Queue a scope to be rerendered
-    pub(crate) fn queue_scope(&mut self, order: ScopeOrder) {
-        self.dirty_scopes.insert(order);
+    pub(crate) fn queue_scope(&mut self, order: ScopeOrder, priority: RenderPriority) {
+        // If the scope is already dirty, the most urgent update wins
+        if priority == RenderPriority::Transition {
+            if !self.dirty_scopes.contains(&order) {
+                self.deferred_scopes.insert(order);
+            }
+        } else {
+            self.deferred_scopes.remove(&order);
+            self.dirty_scopes.insert(order);
+        }
+    }
+
+    /// Remove a scope from the queue without rerunning it
+    pub(crate) fn unqueue_scope(&mut self, order: ScopeOrder) {
+        self.dirty_scopes.remove(&order);
+        self.deferred_scopes.remove(&order);
     }
 
     /// Check if there are any dirty scopes
     pub(crate) fn has_dirty_scopes(&self) -> bool {
-        !self.dirty_scopes.is_empty()
+        !self.dirty_scopes.is_empty() || !self.deferred_scopes.is_empty()
+    }
+
+    /// Check if there are any dirty scopes or queued tasks
+    pub(crate) fn has_pending_work(&self) -> bool {
+        self.has_dirty_scopes()
+            || self
+                .runtime
+                .dirty_tasks
+                .borrow()
+                .iter()
+                .any(|tasks| !tasks.tasks_queued.borrow().is_empty())
+    }
+
+    /// Find the highest deferred transition that is an ancestor of (or the same as) a scope with more urgent work. It
+    /// needs to rerun first so the urgent work never observes out of date state from its parent
+    fn deferred_ancestor(&self, order: ScopeOrder) -> Option<ScopeOrder> {
+        if self.deferred_scopes.is_empty() {
+            return None;
+        }
+
+        let mut highest = None;
+        let mut current = Some(order.id);
+        while let Some(id) = current {
+            let scope = self.runtime.try_get_state(id)?;
+            let order = ScopeOrder::new(scope.height(), id);
+            if self.deferred_scopes.contains(&order) {
+                highest = Some(order);
+            }
+            current = scope.parent_id();
+        }
+        highest
     }
 
     /// Take the top task from the highest scope
@@ -176,14 +284,33 @@ impl VirtualDom {
     }
 
     /// Take any work from the highest scope. This may include rerunning the scope and/or running tasks
+    ///
+    /// Transitions are only returned once there is no more urgent work left. Scopes inside a hidden
+    /// [`KeepAlive`](crate::KeepAlive) entry are skipped until they are shown again
     pub(crate) fn pop_work(&mut self) -> Option<Work> {
-        let dirty_scope = self.dirty_scopes.first();
-        // Make sure the top dirty scope is valid
-        #[cfg(debug_assertions)]
-        if let Some(scope) = dirty_scope {
-            assert!(self.scopes.contains(scope.id.0));
+        let shown = std::mem::take(&mut *self.runtime.shown_scopes.borrow_mut());
+        for id in shown {
+            let Some(scope) = self.runtime.try_get_state(id) else {
+                continue;
+            };
+            let order = ScopeOrder::new(scope.height(), id);
+            drop(scope);
+            self.queue_scope(order, RenderPriority::Normal);
+        }
+
+        loop {
+            match self.pop_any_work()? {
+                Work::RerunScope(order) if self.runtime.is_hidden(order.id) => {
+                    if let Some(scope) = self.runtime.try_get_state(order.id) {
+                        scope.skipped_render.set(true);
+                    }
+                }
+                work => return Some(work),
+            }
         }
+    }
 
+    fn pop_any_work(&mut self) -> Option<Work> {
         // Find the height of the highest dirty scope
         let dirty_task = {
             let mut dirty_tasks = self.runtime.dirty_tasks.borrow_mut();
@@ -200,25 +327,33 @@ impl VirtualDom {
             dirty_task.map(|task| task.order)
         };
 
-        match (dirty_scope, dirty_task) {
-            (Some(scope), Some(task)) => {
-                let tasks_order = task.borrow();
-                match scope.cmp(tasks_order) {
-                    std::cmp::Ordering::Less => {
-                        let scope = self.dirty_scopes.pop_first().unwrap();
-                        Some(Work::RerunScope(scope))
-                    }
-                    std::cmp::Ordering::Equal | std::cmp::Ordering::Greater => {
-                        Some(Work::PollTask(self.pop_task().unwrap()))
-                    }
-                }
-            }
-            (Some(_), None) => {
-                let scope = self.dirty_scopes.pop_first().unwrap();
-                Some(Work::RerunScope(scope))
-            }
-            (None, Some(_)) => Some(Work::PollTask(self.pop_task().unwrap())),
+        // Find the most urgent work from the root down. Transitions are deferred until there is no urgent work left
+        let urgent = match (self.dirty_scopes.first().copied(), dirty_task) {
+            (Some(scope), Some(task)) if scope < task => Some((scope, false)),
+            (_, Some(task)) => Some((task, true)),
+            (Some(scope), None) => Some((scope, false)),
             (None, None) => None,
+        };
+
+        let Some((order, is_task)) = urgent else {
+            return self.deferred_scopes.pop_first().map(Work::RerunScope);
+        };
+
+        if let Some(transition) = self.deferred_ancestor(order) {
+            self.deferred_scopes.remove(&transition);
+            return Some(Work::RerunScope(transition));
+        }
+
+        // Make sure the top dirty scope is valid
+        #[cfg(debug_assertions)]
+        if !is_task {
+            assert!(self.scopes.contains(order.id.0));
+        }
+
+        if is_task {
+            Some(Work::PollTask(self.pop_task().unwrap()))
+        } else {
+            Some(Work::RerunScope(self.dirty_scopes.take(&order).unwrap()))
         }
     }
 }
@@ -49,6 +49,8 @@ impl VirtualDom {
         crate::Runtime::current();
 
         self.runtime.clone().with_scope_on_stack(scope_id, || {
+            #[cfg(feature = "profiler")]
+            let render_start = crate::profiler::start_render(&self.runtime);
             let scope = &self.scopes[scope_id.0];
             let output = {
                 let scope_state = scope.state();
@@ -98,8 +100,17 @@ impl VirtualDom {
             }
 
             // remove this scope from dirty scopes
-            self.dirty_scopes
-                .remove(&ScopeOrder::new(scope_state.height, scope_id));
+            let order = ScopeOrder::new(scope_state.height, scope_id);
+            self.dirty_scopes.remove(&order);
+            self.deferred_scopes.remove(&order);
+
+            #[cfg(feature = "profiler")]
+            crate::profiler::finish_render(
+                &self.runtime,
+                scope_id,
+                scope.last_rendered_node.is_none(),
+                render_start,
+            );
             output
         })
     }
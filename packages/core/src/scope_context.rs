@@ -1,5 +1,5 @@
 use crate::{
-    innerlude::{SchedulerMsg, SuspenseContext},
+    innerlude::{RenderPriority, SchedulerMsg, SuspenseContext},
     Runtime, ScopeId, Task,
 };
 use generational_box::{AnyStorage, Owner};
@@ -48,6 +48,12 @@ pub(crate) struct Scope {
     pub(crate) parent_id: Option<ScopeId>,
     pub(crate) height: u32,
     pub(crate) render_count: Cell<usize>,
+    pub(crate) render_priority: Cell<RenderPriority>,
+
+    // Set by a hidden KeepAlive entry. Scopes under this scope don't rerender and their tasks are paused
+    pub(crate) hides_children: Cell<bool>,
+    // Set if the scope was marked dirty while it was hidden. It reruns once it is shown again
+    pub(crate) skipped_render: Cell<bool>,
 
     // Note: the order of the hook and context fields is important. The hooks field must be dropped before the contexts field in case a hook drop implementation tries to access a context.
     pub(crate) hooks: RefCell<Vec<Box<dyn Any>>>,
@@ -77,6 +83,9 @@ impl Scope {
             parent_id,
             height,
             render_count: Cell::new(0),
+            render_priority: Cell::new(RenderPriority::Normal),
+            hides_children: Cell::new(false),
+            skipped_render: Cell::new(false),
             shared_contexts: RefCell::new(vec![]),
             spawned_tasks: RefCell::new(FxHashSet::default()),
             hooks: RefCell::new(vec![]),
@@ -138,8 +147,10 @@ impl Scope {
 
     /// Mark this scope as dirty, and schedule a render for it.
     pub(crate) fn needs_update_any(&self, id: ScopeId) {
+        #[cfg(feature = "profiler")]
+        crate::profiler::note_dirty(id);
         self.sender()
-            .unbounded_send(SchedulerMsg::Immediate(id))
+            .unbounded_send(SchedulerMsg::Immediate(id, RenderPriority::current()))
             .expect("Scheduler to exist if scope exists");
     }
 
@@ -152,7 +163,14 @@ impl Scope {
     /// [`subscribe`](crate::reactive_context::ReactiveContext::subscribe) to the [`current`](crate::reactive_context::ReactiveContext::current) [`ReactiveContext`](crate::reactive_context::ReactiveContext) instead.
     pub(crate) fn schedule_update(&self) -> Arc<dyn Fn() + Send + Sync + 'static> {
         let (chan, id) = (self.sender(), self.id);
-        Arc::new(move || drop(chan.unbounded_send(SchedulerMsg::Immediate(id))))
+        // The thread the closure is called from may not know the priority of the update
+        let priority = RenderPriority::current();
+        Arc::new(move || {
+            #[cfg(feature = "profiler")]
+            crate::profiler::note_dirty(id);
+            let priority = RenderPriority::current_or(priority);
+            drop(chan.unbounded_send(SchedulerMsg::Immediate(id, priority)))
+        })
     }
 
     /// Schedule an update for any component given its [`ScopeId`].
@@ -165,8 +183,12 @@ impl Scope {
     /// If the desired behavior is to schedule invalidation of the current rendering of a component, use [`ReactiveContext`](crate::reactive_context::ReactiveContext) instead.
     pub(crate) fn schedule_update_any(&self) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
         let chan = self.sender();
+        let priority = RenderPriority::current();
         Arc::new(move |id| {
-            _ = chan.unbounded_send(SchedulerMsg::Immediate(id));
+            #[cfg(feature = "profiler")]
+            crate::profiler::note_dirty(id);
+            let priority = RenderPriority::current_or(priority);
+            _ = chan.unbounded_send(SchedulerMsg::Immediate(id, priority));
         })
     }
 
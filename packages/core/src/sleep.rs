@@ -0,0 +1,152 @@
+//! The timer the dioxus crates share. It is only exported from [`crate::internal`] for the other dioxus crates.
+
+use std::time::Duration;
+
+/// Wait for `duration` to pass.
+///
+/// Renderers run on different async runtimes, so the real timer is the browser's timer on the web, and a single timer
+/// thread shared by all sleeps everywhere else. With the `testing` feature, the sleep waits on the virtual clock of the
+/// current runtime instead if one is installed.
+pub async fn sleep(duration: Duration) {
+    #[cfg(feature = "testing")]
+    if let Some(sleep) = crate::virtual_time::virtual_sleep(duration) {
+        return sleep.await;
+    }
+
+    real_sleep(duration).await
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn real_sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn real_sleep(duration: Duration) -> timer::Sleep {
+    timer::Sleep::new(duration)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod timer {
+    use std::{
+        collections::BTreeMap,
+        future::Future,
+        pin::Pin,
+        sync::{Condvar, Mutex, MutexGuard, Once, PoisonError},
+        task::{Context, Poll, Waker},
+        time::{Duration, Instant},
+    };
+
+    /// The wakers of all pending sleeps, ordered by their deadline, then by the order they were registered
+    static TIMERS: Mutex<Timers> = Mutex::new(Timers {
+        next_id: 0,
+        wakers: BTreeMap::new(),
+    });
+
+    /// Notifies the timer thread when a sleep ends before the ones it waits for
+    static CHANGED: Condvar = Condvar::new();
+
+    struct Timers {
+        next_id: u64,
+        wakers: BTreeMap<(Instant, u64), Waker>,
+    }
+
+    fn timers() -> MutexGuard<'static, Timers> {
+        TIMERS.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Wake every sleep once its deadline has passed
+    fn run() {
+        let mut timers = timers();
+        loop {
+            let now = Instant::now();
+            let mut due = Vec::new();
+            while let Some(entry) = timers.wakers.first_entry() {
+                if entry.key().0 > now {
+                    break;
+                }
+                due.push(entry.remove());
+            }
+
+            if !due.is_empty() {
+                drop(timers);
+                due.into_iter().for_each(Waker::wake);
+                timers = self::timers();
+                continue;
+            }
+
+            timers = match timers.wakers.keys().next() {
+                Some(&(deadline, _)) => {
+                    CHANGED
+                        .wait_timeout(timers, deadline - now)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => CHANGED.wait(timers).unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+
+    /// A future that completes once its deadline has passed on the shared timer thread
+    pub(super) struct Sleep {
+        deadline: Instant,
+        id: Option<u64>,
+    }
+
+    impl Sleep {
+        pub(super) fn new(duration: Duration) -> Self {
+            let now = Instant::now();
+            // Durations that don't fit in an `Instant` are as good as forever
+            let deadline = now
+                .checked_add(duration)
+                .unwrap_or_else(|| now + Duration::from_secs(60 * 60 * 24 * 365 * 30));
+            Self { deadline, id: None }
+        }
+    }
+
+    impl Future for Sleep {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if Instant::now() >= self.deadline {
+                return Poll::Ready(());
+            }
+
+            static START: Once = Once::new();
+            START.call_once(|| {
+                std::thread::Builder::new()
+                    .name("dioxus-timer".to_string())
+                    .spawn(run)
+                    .expect("Failed to spawn the timer thread");
+            });
+
+            let mut timers = timers();
+            let id = match self.id {
+                Some(id) => id,
+                None => {
+                    let id = timers.next_id;
+                    timers.next_id += 1;
+                    self.id = Some(id);
+                    id
+                }
+            };
+            let earliest = timers.wakers.keys().next().map(|(deadline, _)| *deadline);
+            timers
+                .wakers
+                .insert((self.deadline, id), cx.waker().clone());
+            if !earliest.is_some_and(|earliest| earliest <= self.deadline) {
+                CHANGED.notify_one();
+            }
+
+            Poll::Pending
+        }
+    }
+
+    impl Drop for Sleep {
+        fn drop(&mut self) {
+            if let Some(id) = self.id {
+                timers().wakers.remove(&(self.deadline, id));
+            }
+        }
+    }
+}
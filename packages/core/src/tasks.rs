@@ -1,6 +1,6 @@
 use crate::innerlude::Effect;
-use crate::innerlude::ScopeOrder;
 use crate::innerlude::{remove_future, spawn, Runtime};
+use crate::innerlude::{RenderPriority, ScopeOrder};
 use crate::scope_context::ScopeStatus;
 use crate::scope_context::SuspenseLocation;
 use crate::ScopeId;
@@ -63,14 +63,14 @@ impl Task {
         self.set_active(true);
     }
 
-    /// Check if the task is paused.
+    /// Check if the task is paused, either with [`Task::pause`] or because it is inside a hidden
+    /// [`KeepAlive`](crate::KeepAlive) entry.
     pub fn paused(&self) -> bool {
         Runtime::with(|rt| {
-            if let Some(task) = rt.tasks.borrow().get(self.id) {
-                !task.active.get()
-            } else {
-                false
-            }
+            let Some(task) = rt.tasks.borrow().get(self.id).cloned() else {
+                return false;
+            };
+            !task.active.get() || rt.is_hidden(task.scope)
         })
     }
 
@@ -274,8 +274,8 @@ impl Runtime {
             return Poll::Ready(());
         };
 
-        // If a task woke up but is paused, we can just ignore it
-        if !task.active.get() {
+        // If a task woke up but is paused, we can just ignore it. Hidden tasks are woken again when they are shown
+        if !task.active.get() || self.is_hidden(task.scope) {
             return Poll::Pending;
         }
 
@@ -381,8 +381,8 @@ pub(crate) enum SchedulerMsg {
     #[allow(unused)]
     AllDirty,
 
-    /// Immediate updates from Components that mark them as dirty
-    Immediate(ScopeId),
+    /// Immediate updates from Components that mark them as dirty, with the priority of the update
+    Immediate(ScopeId, RenderPriority),
 
     /// A task has woken and needs to be progressed
     TaskNotified(slotmap::DefaultKey),
@@ -6,7 +6,9 @@ use crate::properties::RootProps;
 use crate::root_wrapper::RootScopeWrapper;
 use crate::{
     arena::ElementId,
-    innerlude::{NoOpMutations, SchedulerMsg, ScopeOrder, ScopeState, VProps, WriteMutations},
+    innerlude::{
+        NoOpMutations, RenderPriority, SchedulerMsg, ScopeOrder, ScopeState, VProps, WriteMutations,
+    },
     runtime::{Runtime, RuntimeGuard},
     scopes::ScopeId,
     ComponentFunction, Element, Mutations,
@@ -207,6 +209,9 @@ pub struct VirtualDom {
 
     pub(crate) dirty_scopes: BTreeSet<ScopeOrder>,
 
+    // Scopes that are only dirty because of a transition. They are rerun once there is no more urgent work
+    pub(crate) deferred_scopes: BTreeSet<ScopeOrder>,
+
     pub(crate) runtime: Rc<Runtime>,
 
     // The scopes that have been resolved since the last render
@@ -237,7 +242,7 @@ impl VirtualDom {
     /// let dom = VirtualDom::new(Example);
     /// ```
     ///
-    /// Note: the VirtualDom is not progressed, you must either "run_with_deadline" or use "rebuild" to progress it.
+    /// Note: the VirtualDom is not progressed, you must either "render_with_deadline" or use "rebuild" to progress it.
     pub fn new(app: fn() -> Element) -> Self {
         Self::new_with_props(app, ())
     }
@@ -268,7 +273,7 @@ impl VirtualDom {
     /// let dom = VirtualDom::new_with_props(Example, SomeProps { name: "world" });
     /// ```
     ///
-    /// Note: the VirtualDom is not progressed on creation. You must either "run_with_deadline" or use "rebuild" to progress it.
+    /// Note: the VirtualDom is not progressed on creation. You must either "render_with_deadline" or use "rebuild" to progress it.
     ///
     /// ```rust, no_run
     /// # use dioxus::prelude::*;
@@ -313,6 +318,7 @@ impl VirtualDom {
             runtime: Runtime::new(tx),
             scopes: Default::default(),
             dirty_scopes: Default::default(),
+            deferred_scopes: Default::default(),
             resolved_scopes: Default::default(),
         };
 
@@ -385,22 +391,39 @@ impl VirtualDom {
         }
 
         for order in orders {
-            self.queue_scope(order);
+            #[cfg(feature = "profiler")]
+            crate::profiler::note_dirty_in(&self.runtime, order.id);
+            self.queue_scope(order, RenderPriority::Normal);
         }
     }
 
     /// Manually mark a scope as requiring a re-render
     ///
-    /// Whenever the Runtime "works", it will re-render this scope
+    /// Whenever the Runtime "works", it will re-render this scope. The update has the [`RenderPriority::current`] priority.
     pub fn mark_dirty(&mut self, id: ScopeId) {
+        self.mark_dirty_with_priority(id, RenderPriority::current());
+    }
+
+    /// Mark a scope as requiring a re-render with a specific priority
+    ///
+    /// The priority is lowered to the priority the scope set with [`set_render_priority`](crate::set_render_priority).
+    pub fn mark_dirty_with_priority(&mut self, id: ScopeId, priority: RenderPriority) {
         let Some(scope) = self.runtime.try_get_state(id) else {
             return;
         };
 
-        tracing::event!(tracing::Level::TRACE, "Marking scope {:?} as dirty", id);
+        tracing::event!(
+            tracing::Level::TRACE,
+            "Marking scope {:?} as dirty with priority {:?}",
+            id,
+            priority
+        );
         let order = ScopeOrder::new(scope.height(), id);
+        let priority = priority.max(scope.render_priority.get());
         drop(scope);
-        self.queue_scope(order);
+        #[cfg(feature = "profiler")]
+        crate::profiler::note_dirty_in(&self.runtime, id);
+        self.queue_scope(order, priority);
     }
 
     /// Mark a task as dirty
@@ -465,7 +488,7 @@ impl VirtualDom {
     #[instrument(skip(self), level = "trace", name = "VirtualDom::wait_for_event")]
     async fn wait_for_event(&mut self) {
         match self.rx.next().await.expect("channel should never close") {
-            SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+            SchedulerMsg::Immediate(id, priority) => self.mark_dirty_with_priority(id, priority),
             SchedulerMsg::TaskNotified(id) => {
                 // Instead of running the task immediately, we insert it into the runtime's task queue.
                 // The task may be marked dirty at the same time as the scope that owns the task is dropped.
@@ -481,7 +504,9 @@ impl VirtualDom {
         // Prevent a task from deadlocking the runtime by repeatedly queueing itself
         while let Ok(Some(msg)) = self.rx.try_next() {
             match msg {
-                SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+                SchedulerMsg::Immediate(id, priority) => {
+                    self.mark_dirty_with_priority(id, priority)
+                }
                 SchedulerMsg::TaskNotified(task) => self.mark_task_dirty(Task::from_id(task)),
                 SchedulerMsg::EffectQueued => {}
                 SchedulerMsg::AllDirty => self.mark_all_dirty(),
@@ -578,6 +603,8 @@ impl VirtualDom {
     #[instrument(skip(self, to), level = "trace", name = "VirtualDom::rebuild")]
     pub fn rebuild(&mut self, to: &mut impl WriteMutations) {
         let _runtime = RuntimeGuard::new(self.runtime.clone());
+        #[cfg(feature = "profiler")]
+        let to = &mut crate::profiler::CountMutations(to);
         let new_nodes = self
             .runtime
             .clone()
@@ -603,8 +630,10 @@ impl VirtualDom {
         self.process_events();
 
         // Next, diff any dirty scopes
-        // We choose not to poll the deadline since we complete pretty quickly anyways
+        // This always finishes all of the work. Use render_with_deadline to yield back to the renderer part of the way through
         let _runtime = RuntimeGuard::new(self.runtime.clone());
+        #[cfg(feature = "profiler")]
+        let to = &mut crate::profiler::CountMutations(to);
         while let Some(work) = self.pop_work() {
             match work {
                 Work::PollTask(task) => {
@@ -624,6 +653,82 @@ impl VirtualDom {
         self.runtime.finish_render();
     }
 
+    /// Render dirty scopes until all work is done or the deadline is reached, returning `true` if all work was done.
+    ///
+    /// The deadline is checked after every scope that reruns, so the mutations written so far are always complete, and at
+    /// least one piece of work is done on every call. Updates are rendered in order of their [`RenderPriority`]: user input
+    /// first, transitions last. If this returns `false`, apply the mutations, let the renderer handle any pending input, and
+    /// call this method again to continue where it left off. Effects only run once all of the work is done.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_core::*;
+    /// # fn app() -> Element { rsx! { div {} } }
+    /// # async fn handle_input() {}
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let mut dom = VirtualDom::new(app);
+    /// dom.rebuild(&mut NoOpMutations);
+    ///
+    /// loop {
+    ///     dom.wait_for_work().await;
+    ///
+    ///     // Render for at most 8ms before handling input again
+    ///     let start = std::time::Instant::now();
+    ///     let done = dom.render_with_deadline(&mut NoOpMutations, || {
+    ///         start.elapsed() > std::time::Duration::from_millis(8)
+    ///     });
+    ///
+    ///     if !done {
+    ///         handle_input().await;
+    ///     }
+    /// }
+    /// # });
+    /// ```
+    #[instrument(
+        skip(self, to, deadline),
+        level = "trace",
+        name = "VirtualDom::render_with_deadline"
+    )]
+    pub fn render_with_deadline(
+        &mut self,
+        to: &mut impl WriteMutations,
+        mut deadline: impl FnMut() -> bool,
+    ) -> bool {
+        self.process_events();
+
+        let _runtime = RuntimeGuard::new(self.runtime.clone());
+        #[cfg(feature = "profiler")]
+        let to = &mut crate::profiler::CountMutations(to);
+        let mut done = true;
+        while let Some(work) = self.pop_work() {
+            match work {
+                Work::PollTask(task) => {
+                    _ = self.runtime.handle_task_wakeup(task);
+                    self.queue_events();
+                }
+                Work::RerunScope(scope) => {
+                    self.runtime.clone().while_rendering(|| {
+                        self.run_and_diff_scope(Some(to), scope.id);
+                    });
+
+                    // Only yield between scopes so the mutations never contain half of a diff
+                    if deadline() {
+                        self.queue_events();
+                        done = !self.has_pending_work();
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Effects only run once the render is complete
+        if done {
+            self.runtime.finish_render();
+        }
+        done
+    }
+
     /// [`Self::render_immediate`] to a vector of mutations for testing purposes
     pub fn render_immediate_to_vec(&mut self) -> Mutations {
         let mut mutations = Mutations::default();
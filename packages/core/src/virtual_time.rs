@@ -0,0 +1,271 @@
+//! A virtual clock and a deterministic executor for testing components that wait on timers. Enable it with the `testing`
+//! feature.
+//!
+//! [`TestDom`] drives a [`VirtualDom`] on the current thread without an async runtime. Time only moves forward when the
+//! test calls [`TestDom::advance`], so timers, debouncing and `use_future` loops can be tested without real sleeps.
+//!
+//! The timers of the dioxus crates wait on the virtual clock if one is installed. Libraries with their own timer can
+//! check for a virtual clock with [`virtual_sleep`] first:
+//!
+//! ```rust, ignore
+//! async fn sleep(duration: Duration) {
+//!     if let Some(sleep) = dioxus_core::virtual_sleep(duration) {
+//!         return sleep.await;
+//!     }
+//!     tokio::time::sleep(duration).await
+//! }
+//! ```
+
+use crate::innerlude::*;
+use futures_util::task::noop_waker_ref;
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::{pin, Pin},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+/// A clock that only moves forward when it is advanced manually.
+///
+/// Install it in a runtime with [`Runtime::set_virtual_clock`] to make [`virtual_sleep`] use it. [`TestDom`] does this for you.
+#[derive(Clone, Default)]
+pub struct VirtualClock {
+    inner: Arc<Mutex<ClockState>>,
+}
+
+#[derive(Default)]
+struct ClockState {
+    now: Duration,
+    next_id: u64,
+    // Timers ordered by their deadline, then by the order they were created
+    timers: BTreeMap<(Duration, u64), Waker>,
+}
+
+impl VirtualClock {
+    /// Create a new clock starting at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the time that has passed on this clock since it was created
+    pub fn now(&self) -> Duration {
+        self.inner.lock().unwrap().now
+    }
+
+    /// Create a future that completes once the clock has advanced by `duration`
+    pub fn sleep(&self, duration: Duration) -> VirtualSleep {
+        let mut state = self.inner.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        VirtualSleep {
+            clock: self.clone(),
+            deadline: state.now + duration,
+            id,
+        }
+    }
+
+    /// Get the deadline of the earliest pending timer
+    pub fn next_timer(&self) -> Option<Duration> {
+        let state = self.inner.lock().unwrap();
+        state.timers.keys().next().map(|(deadline, _)| *deadline)
+    }
+
+    /// Move the clock forward to `time` and wake every timer that is due. The clock never moves backwards.
+    pub fn advance_to(&self, time: Duration) {
+        let due = {
+            let mut state = self.inner.lock().unwrap();
+            let now = state.now.max(time);
+            state.now = now;
+            let pending = state.timers.split_off(&(now, u64::MAX));
+            std::mem::replace(&mut state.timers, pending)
+        };
+        for waker in due.into_values() {
+            waker.wake();
+        }
+    }
+
+    /// Move the clock forward by `duration` and wake every timer that is due
+    pub fn advance(&self, duration: Duration) {
+        self.advance_to(self.now() + duration);
+    }
+}
+
+/// A future that completes once a [`VirtualClock`] reaches its deadline. Created with [`VirtualClock::sleep`] or [`virtual_sleep`].
+pub struct VirtualSleep {
+    clock: VirtualClock,
+    deadline: Duration,
+    id: u64,
+}
+
+impl Future for VirtualSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.clock.inner.lock().unwrap();
+        if state.now >= self.deadline {
+            return Poll::Ready(());
+        }
+        state
+            .timers
+            .insert((self.deadline, self.id), cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Drop for VirtualSleep {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.clock.inner.lock() {
+            state.timers.remove(&(self.deadline, self.id));
+        }
+    }
+}
+
+/// Sleep on the virtual clock of the current runtime if one is installed.
+///
+/// Returns `None` outside of a runtime or if the runtime uses real time. Sleep primitives should fall back to a real timer
+/// in that case.
+pub fn virtual_sleep(duration: Duration) -> Option<VirtualSleep> {
+    Runtime::try_current()?
+        .virtual_clock()
+        .map(|clock| clock.sleep(duration))
+}
+
+impl Runtime {
+    /// Install a virtual clock that [`virtual_sleep`] uses instead of real time, or remove it with `None`
+    pub fn set_virtual_clock(&self, clock: Option<VirtualClock>) {
+        *self.virtual_clock.borrow_mut() = clock;
+    }
+
+    /// Get the virtual clock installed in this runtime
+    pub fn virtual_clock(&self) -> Option<VirtualClock> {
+        self.virtual_clock.borrow().clone()
+    }
+}
+
+/// A [`VirtualDom`] driven by a deterministic, single threaded executor with a [`VirtualClock`].
+///
+/// Every method runs tasks and renders until there is no more work that can finish without time passing, and returns the
+/// mutations that were written along the way.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_core::{virtual_sleep, Mutation, TestDom};
+/// # use std::time::Duration;
+/// fn app() -> Element {
+///     let mut seconds = use_signal(|| 0);
+///     use_future(move || async move {
+///         loop {
+///             virtual_sleep(Duration::from_secs(1)).unwrap().await;
+///             seconds += 1;
+///         }
+///     });
+///     rsx! { "{seconds}" }
+/// }
+///
+/// let mut dom = TestDom::new(app);
+/// dom.rebuild();
+///
+/// let mutations = dom.advance(Duration::from_secs(3));
+/// assert!(matches!(
+///     mutations.edits.last(),
+///     Some(Mutation::SetText { value, .. }) if value == "3"
+/// ));
+/// ```
+pub struct TestDom {
+    dom: VirtualDom,
+    clock: VirtualClock,
+}
+
+impl TestDom {
+    /// Create a new test dom for a component without props
+    pub fn new(app: fn() -> Element) -> Self {
+        Self::from_dom(VirtualDom::new(app))
+    }
+
+    /// Create a new test dom for a component with props
+    pub fn new_with_props<P: Clone + 'static, M: 'static>(
+        root: impl ComponentFunction<P, M>,
+        root_props: P,
+    ) -> Self {
+        Self::from_dom(VirtualDom::new_with_props(root, root_props))
+    }
+
+    /// Drive an existing [`VirtualDom`] with a new virtual clock
+    pub fn from_dom(dom: VirtualDom) -> Self {
+        let clock = VirtualClock::new();
+        dom.runtime().set_virtual_clock(Some(clock.clone()));
+        Self { dom, clock }
+    }
+
+    /// Build the initial tree and run until idle
+    pub fn rebuild(&mut self) -> Mutations {
+        let mut mutations = Mutations::default();
+        self.dom.rebuild(&mut mutations);
+        self.run_until_idle_into(&mut mutations);
+        mutations
+    }
+
+    /// Run tasks and render dirty scopes until nothing can make progress without the clock advancing
+    pub fn run_until_idle(&mut self) -> Mutations {
+        let mut mutations = Mutations::default();
+        self.run_until_idle_into(&mut mutations);
+        mutations
+    }
+
+    /// Advance the clock by `duration`, running until idle after each timer fires.
+    ///
+    /// Timers fire in the order of their deadlines, and the clock reads the deadline of each timer while it runs. Timers
+    /// created while advancing fire too if they are due before the end of `duration`.
+    pub fn advance(&mut self, duration: Duration) -> Mutations {
+        let mut mutations = Mutations::default();
+        let target = self.clock.now() + duration;
+        self.run_until_idle_into(&mut mutations);
+        while let Some(deadline) = self.clock.next_timer().filter(|next| *next <= target) {
+            self.clock.advance_to(deadline);
+            self.run_until_idle_into(&mut mutations);
+        }
+        self.clock.advance_to(target);
+        self.run_until_idle_into(&mut mutations);
+        mutations
+    }
+
+    /// Get the time that has passed since the test dom was created
+    pub fn now(&self) -> Duration {
+        self.clock.now()
+    }
+
+    /// Get the virtual clock that drives this test dom
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    /// Get the virtual dom being tested
+    pub fn dom(&self) -> &VirtualDom {
+        &self.dom
+    }
+
+    /// Get the virtual dom being tested mutably, for example to send events
+    pub fn dom_mut(&mut self) -> &mut VirtualDom {
+        &mut self.dom
+    }
+
+    fn run_until_idle_into(&mut self, to: &mut Mutations) {
+        let mut cx = Context::from_waker(noop_waker_ref());
+        loop {
+            // Waiting for work is cancel safe, so it can be dropped as soon as it is pending
+            let ready = pin!(self.dom.wait_for_work()).poll(&mut cx).is_ready();
+            if !ready {
+                break;
+            }
+            self.dom.render_immediate(to);
+        }
+    }
+}
+
+impl From<VirtualDom> for TestDom {
+    fn from(dom: VirtualDom) -> Self {
+        Self::from_dom(dom)
+    }
+}
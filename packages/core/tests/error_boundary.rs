@@ -75,3 +75,130 @@ fn clear_error_boundary() {
 
     assert_eq!(out, "We should see this");
 }
+
+#[test]
+fn retry_remounts_children() {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    static SHOULD_THROW: AtomicBool = AtomicBool::new(true);
+    static MOUNTS: AtomicUsize = AtomicUsize::new(0);
+
+    thread_local! {
+        static BOUNDARY: std::cell::RefCell<Option<ErrorContext>> = const { std::cell::RefCell::new(None) };
+    }
+
+    fn app() -> Element {
+        rsx! {
+            ErrorBoundary {
+                handle_error: |error: ErrorContext| {
+                    BOUNDARY.with(|boundary| *boundary.borrow_mut() = Some(error));
+                    rsx! { "failed" }
+                },
+                Flaky {}
+            }
+        }
+    }
+
+    #[component]
+    fn Flaky() -> Element {
+        use_hook(|| MOUNTS.fetch_add(1, Ordering::SeqCst));
+        if SHOULD_THROW.load(Ordering::SeqCst) {
+            return Err(CapturedError::from_display("flaky").into());
+        }
+        rsx! { "recovered" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "failed");
+
+    SHOULD_THROW.store(false, Ordering::SeqCst);
+    BOUNDARY.with(|boundary| boundary.borrow().as_ref().unwrap().retry());
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(dioxus_ssr::render(&dom), "recovered");
+    assert_eq!(MOUNTS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn reset_keys_retry_when_changed() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static PAGE: AtomicUsize = AtomicUsize::new(0);
+
+    fn app() -> Element {
+        rsx! {
+            ErrorBoundary {
+                reset_keys: PAGE.load(Ordering::SeqCst),
+                handle_error: |_: ErrorContext| rsx! { "failed" },
+                Page {}
+            }
+        }
+    }
+
+    #[component]
+    fn Page() -> Element {
+        if PAGE.load(Ordering::SeqCst) == 0 {
+            return Err(CapturedError::from_display("missing page").into());
+        }
+        rsx! { "page" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "failed");
+
+    // Rerendering with the same keys keeps the error
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "failed");
+
+    PAGE.store(1, Ordering::SeqCst);
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&dom), "page");
+}
+
+#[test]
+fn on_error_reports_the_component_path() {
+    use dioxus_core::ErrorReport;
+
+    thread_local! {
+        static REPORTS: std::cell::RefCell<Vec<ErrorReport>> = const { std::cell::RefCell::new(Vec::new()) };
+    }
+
+    fn app() -> Element {
+        rsx! {
+            ErrorBoundary {
+                on_error: |report: ErrorReport| REPORTS.with(|reports| reports.borrow_mut().push(report)),
+                Wrapper {}
+            }
+        }
+    }
+
+    #[component]
+    fn Wrapper() -> Element {
+        rsx! { Thrower {} }
+    }
+
+    #[component]
+    fn Thrower() -> Element {
+        Err(CapturedError::from_display("broken").into())
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    REPORTS.with(|reports| {
+        let reports = reports.borrow();
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.error.to_string(), "broken");
+        assert!(report
+            .component_names
+            .ends_with(&["ErrorBoundary", "Wrapper", "Thrower"]));
+        assert_eq!(report.scope_path.len(), report.component_names.len());
+    });
+}
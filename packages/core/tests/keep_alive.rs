@@ -0,0 +1,138 @@
+//! KeepAlive keeps hidden subtrees mounted and drops the least recently shown ones over its limit
+
+use dioxus::prelude::*;
+use dioxus_core::{current_scope_id, generation, NoOpMutations, Task};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+thread_local! {
+    static MOUNTED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+    static DROPPED: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+#[component]
+fn Tab(id: usize) -> Element {
+    use_hook(|| MOUNTED.with(|mounted| mounted.borrow_mut().push(id)));
+    use_drop(move || DROPPED.with(|dropped| dropped.borrow_mut().push(id)));
+    rsx! { "{id}" }
+}
+
+#[test]
+fn hidden_subtrees_are_not_remounted() {
+    let mut dom = VirtualDom::new(|| {
+        let tab = [0, 1, 0, 2][generation() % 4];
+        rsx! {
+            KeepAlive { cache_key: tab, max: 2,
+                Tab { id: tab }
+            }
+        }
+    });
+
+    dom.rebuild_in_place();
+    for _ in 0..3 {
+        dom.mark_dirty(ScopeId::APP);
+        dom.render_immediate(&mut NoOpMutations);
+    }
+
+    // Switching back to tab 0 reuses it. Showing tab 2 drops tab 1, the least recently shown tab
+    assert_eq!(MOUNTED.with(|mounted| mounted.take()), [0, 1, 2]);
+    assert_eq!(DROPPED.with(|dropped| dropped.take()), [1]);
+}
+
+#[test]
+fn hidden_tasks_are_paused() {
+    thread_local! {
+        static TASK: Cell<Option<Task>> = const { Cell::new(None) };
+    }
+
+    #[component]
+    fn Ticker() -> Element {
+        use_hook(|| TASK.set(Some(spawn(std::future::pending()))));
+        rsx! {}
+    }
+
+    let shown = Rc::new(Cell::new(true));
+    let mut dom = VirtualDom::new_with_props(
+        |shown: Rc<Cell<bool>>| {
+            let key = if shown.get() { "ticker" } else { "other" };
+            rsx! {
+                KeepAlive { cache_key: key,
+                    if shown.get() {
+                        Ticker {}
+                    }
+                }
+            }
+        },
+        shown.clone(),
+    );
+    dom.rebuild_in_place();
+    let task = TASK.get().unwrap();
+    assert!(!dom.in_runtime(|| task.paused()));
+
+    shown.set(false);
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut NoOpMutations);
+    assert!(dom.in_runtime(|| task.paused()));
+
+    shown.set(true);
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut NoOpMutations);
+    assert!(!dom.in_runtime(|| task.paused()));
+}
+
+#[test]
+fn hidden_scopes_skip_tasks_and_rerenders_until_shown() {
+    thread_local! {
+        static SCOPE: Cell<Option<ScopeId>> = const { Cell::new(None) };
+        static RENDERS: Cell<usize> = const { Cell::new(0) };
+        static POLLS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[component]
+    fn Counter() -> Element {
+        use_hook(|| SCOPE.set(Some(current_scope_id())));
+        RENDERS.set(RENDERS.get() + 1);
+        rsx! {}
+    }
+
+    let shown = Rc::new(Cell::new(true));
+    let mut dom = VirtualDom::new_with_props(
+        |shown: Rc<Cell<bool>>| {
+            let key = if shown.get() { "counter" } else { "other" };
+            rsx! {
+                KeepAlive { cache_key: key,
+                    if shown.get() {
+                        Counter {}
+                    }
+                }
+            }
+        },
+        shown.clone(),
+    );
+    dom.rebuild_in_place();
+    let scope = SCOPE.get().unwrap();
+
+    shown.set(false);
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut NoOpMutations);
+
+    // Tasks spawned and rerenders queued while the counter is hidden wait until it is shown again
+    let task = dom.in_scope(scope, || {
+        spawn(async {
+            POLLS.set(POLLS.get() + 1);
+        })
+    });
+    dom.mark_dirty(scope);
+    dom.render_immediate(&mut NoOpMutations);
+    assert!(dom.in_runtime(|| task.paused()));
+    assert_eq!(RENDERS.get(), 1);
+    assert_eq!(POLLS.get(), 0);
+
+    shown.set(true);
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(RENDERS.get(), 2);
+
+    dom.process_events();
+    assert_eq!(POLLS.get(), 1);
+}
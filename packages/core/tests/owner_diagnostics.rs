@@ -0,0 +1,74 @@
+//! Unmounting a component frees all of the state it created
+// Creation locations are only recorded in debug builds. The dev dependency on generational-box enables the
+// debug_allocations feature that global snapshots need.
+#![cfg(debug_assertions)]
+
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use std::cell::Cell;
+
+thread_local! {
+    static SHOW_PAGE: Cell<bool> = const { Cell::new(false) };
+}
+
+fn app() -> Element {
+    if SHOW_PAGE.with(Cell::get) {
+        rsx! { Page {} }
+    } else {
+        rsx! { "home" }
+    }
+}
+
+#[component]
+fn Page() -> Element {
+    let items = use_signal(|| vec![1, 2, 3]);
+    let total = use_memo(move || items.read().iter().sum::<i32>());
+    use_hook(|| {
+        for _ in 0..3 {
+            Signal::new(0);
+        }
+    });
+    rsx! { "{total}" }
+}
+
+// Component names include their module path
+fn is_page(name: &str) -> bool {
+    name.rsplit("::").next() == Some("Page")
+}
+
+fn show_page(dom: &mut VirtualDom, show: bool) {
+    SHOW_PAGE.with(|show_page| show_page.set(show));
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut NoOpMutations);
+}
+
+#[test]
+fn navigating_away_frees_everything() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut NoOpMutations);
+
+    // Visit the page once first. The hot reload state of each rsx block is created the first time it renders and lives
+    // as long as the app
+    show_page(&mut dom, true);
+    show_page(&mut dom, false);
+    let before = AllocationSnapshot::capture();
+
+    show_page(&mut dom, true);
+    let page = dom
+        .runtime()
+        .owner_sizes()
+        .into_iter()
+        .find(|owner| is_page(owner.name))
+        .unwrap();
+    assert!(page.total() >= 5);
+    assert!(!before.diff(&AllocationSnapshot::capture()).is_empty());
+
+    show_page(&mut dom, false);
+    let diff = before.diff(&AllocationSnapshot::capture());
+    assert!(diff.is_empty(), "leaked boxes:\n{diff}");
+    assert!(dom
+        .runtime()
+        .owner_sizes()
+        .iter()
+        .all(|owner| !is_page(owner.name)));
+}
@@ -0,0 +1,55 @@
+//! Portals mount their element into a different part of the page
+
+use dioxus::prelude::*;
+use dioxus_core::{generation, Mutation::*, PORTAL_ATTRIBUTE};
+
+#[test]
+fn portal_mounts_after_children() {
+    let mut dom = VirtualDom::new(|| {
+        rsx! {
+            Portal { target: "modals",
+                div { "hello {1}" }
+            }
+        }
+    });
+
+    let edits = dom.rebuild_to_vec().edits;
+
+    // The portal attribute is never written to the element
+    assert!(!edits
+        .iter()
+        .any(|edit| matches!(edit, SetAttribute { name, .. } if *name == PORTAL_ATTRIBUTE)));
+
+    // The element is moved once its children are in place
+    let mount = edits
+        .iter()
+        .position(|edit| matches!(edit, MountPortal { target, .. } if target == "modals"))
+        .expect("the portal should be mounted");
+    let children = edits
+        .iter()
+        .rposition(|edit| matches!(edit, ReplacePlaceholder { .. }))
+        .expect("the children should be loaded");
+    assert!(children < mount);
+}
+
+#[test]
+fn portal_target_changes() {
+    let mut dom = VirtualDom::new(|| {
+        let target = if generation() % 2 == 0 { "a" } else { "b" };
+        rsx! {
+            Portal { target, div {} }
+        }
+    });
+
+    let edits = dom.rebuild_to_vec().edits;
+    assert!(edits
+        .iter()
+        .any(|edit| matches!(edit, MountPortal { target, .. } if target == "a")));
+
+    dom.mark_dirty(ScopeId::APP);
+    let edits = dom.render_immediate_to_vec().edits;
+    assert!(edits
+        .iter()
+        .any(|edit| matches!(edit, MountPortal { target, .. } if target == "b")));
+    assert!(!edits.iter().any(|edit| matches!(edit, SetAttribute { .. })));
+}
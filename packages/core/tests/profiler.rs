@@ -0,0 +1,66 @@
+//! The profiler records every render with its cause. Run with `--features profiler`
+#![cfg(feature = "profiler")]
+
+use dioxus::prelude::*;
+use dioxus_core::{generation, NoOpMutations, RenderCause};
+
+#[test]
+fn records_mounts_and_rerenders() {
+    let mut dom = VirtualDom::new(|| {
+        rsx! {
+            div { "{generation()}" }
+            Child {}
+        }
+    });
+    dom.runtime().start_profiling();
+
+    dom.rebuild_in_place();
+    let profile = dom.runtime().take_render_profile();
+    assert!(profile
+        .renders
+        .iter()
+        .all(|render| render.cause == RenderCause::Mount));
+    assert_eq!(profile.scope(ScopeId::APP).count(), 1);
+    assert!(profile.renders.iter().any(|render| render.name == "Child"));
+
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut NoOpMutations);
+    let profile = dom.runtime().stop_profiling();
+    assert_eq!(profile.renders.len(), 1);
+    let render = &profile.renders[0];
+    assert_eq!(render.cause, RenderCause::Manual);
+    assert_eq!(render.mutations, 1);
+    assert!(!dom.runtime().is_profiling());
+}
+
+#[test]
+fn signal_writes_record_the_reactive_context() {
+    let mut dom = VirtualDom::new(|| {
+        let mut count = use_signal(|| 0);
+        use_hook(move || spawn(async move { count += 1 }));
+        rsx! { "{count}" }
+    });
+    dom.rebuild_in_place();
+    dom.runtime().start_profiling();
+
+    dom.render_immediate(&mut NoOpMutations);
+    let profile = dom.runtime().stop_profiling();
+    assert_eq!(profile.renders[0].scope, ScopeId::APP);
+    assert!(matches!(
+        profile.renders[0].cause,
+        RenderCause::ReactiveContext(_)
+    ));
+}
+
+#[test]
+fn exports_chrome_trace() {
+    let mut dom = VirtualDom::new(|| rsx! { "hello" });
+    dom.runtime().start_profiling();
+    dom.rebuild_in_place();
+
+    let trace = dom.runtime().stop_profiling().to_chrome_trace();
+    assert!(trace.starts_with(r#"{"traceEvents":[{"name":"#));
+    assert!(trace.contains(r#""cat":"render""#));
+    assert!(trace.contains(r#""cat":"diff""#));
+    assert!(trace.contains(r#""cause":"mount""#));
+}
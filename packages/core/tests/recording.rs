@@ -0,0 +1,116 @@
+//! Recorded edit streams can be serialized and replayed into any renderer. Run with `--features serialize`
+#![cfg(feature = "serialize")]
+
+use dioxus::prelude::*;
+use dioxus_core::{
+    MutationRecorder, MutationReplayer, Mutations, RecordedMutation, RecordedMutations,
+    ReplayError, MUTATION_FORMAT_VERSION,
+};
+use std::{cell::Cell, rc::Rc};
+
+fn app(count: Rc<Cell<i32>>) -> Element {
+    let count = count.get();
+    rsx! {
+        div { class: "counter", onclick: move |_| {},
+            "{count}"
+            if count % 2 == 0 {
+                span { "even" }
+            }
+        }
+    }
+}
+
+fn round_trip(batch: RecordedMutations) -> RecordedMutations {
+    let json = serde_json::to_string(&batch).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn replayed_streams_match_the_original_edits() {
+    let count = Rc::new(Cell::new(0));
+    let mut expected = VirtualDom::new_with_props(app, count.clone());
+    let mut direct = Mutations::default();
+    expected.rebuild(&mut direct);
+    count.set(1);
+    expected.mark_dirty(ScopeId::APP);
+    expected.render_immediate(&mut direct);
+
+    let count = Rc::new(Cell::new(0));
+    let mut dom = VirtualDom::new_with_props(app, count.clone());
+    let mut recorder = MutationRecorder::new();
+    let mut replayer = MutationReplayer::new();
+    let mut replayed = Mutations::default();
+
+    dom.rebuild(&mut recorder);
+    let first = round_trip(recorder.take());
+    assert_eq!(first.version, MUTATION_FORMAT_VERSION);
+    assert!(!first.templates.is_empty());
+    replayer.replay(&first, &mut replayed).unwrap();
+
+    count.set(1);
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut recorder);
+    let second = round_trip(recorder.take());
+    assert!(second.templates.is_empty());
+    replayer.replay(&second, &mut replayed).unwrap();
+
+    assert_eq!(replayed, direct);
+}
+
+#[test]
+fn templates_are_only_sent_once() {
+    let mut dom = VirtualDom::new(|| {
+        rsx! {
+            for i in 0..3 {
+                div { key: "{i}", "{i}" }
+            }
+        }
+    });
+    let mut recorder = MutationRecorder::new();
+    dom.rebuild(&mut recorder);
+    let batch = recorder.take();
+
+    let loads = batch
+        .edits
+        .iter()
+        .filter(|edit| matches!(edit, RecordedMutation::LoadTemplate { .. }))
+        .count();
+    assert_eq!(loads, 3);
+    // Every div shares the same template
+    assert_eq!(batch.templates.len(), 1);
+    assert!(recorder.take().is_empty());
+}
+
+#[test]
+fn newer_versions_are_rejected() {
+    let json = format!(
+        r#"{{"version":{},"templates":[],"edits":[]}}"#,
+        MUTATION_FORMAT_VERSION + 1
+    );
+    let error = serde_json::from_str::<RecordedMutations>(&json).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unsupported mutation format version"));
+}
+
+#[test]
+fn batches_with_unknown_templates_are_rejected() {
+    let mut dom = VirtualDom::new_with_props(app, Rc::new(Cell::new(0)));
+    let mut recorder = MutationRecorder::new();
+    dom.rebuild(&mut recorder);
+    let batch = recorder.take();
+
+    // A batch from the middle of a stream loads templates that earlier batches introduced
+    let missing = RecordedMutations { templates: Vec::new(), ..batch.clone() };
+    let mut replayer = MutationReplayer::new();
+    let mut replayed = Mutations::default();
+    assert_eq!(
+        replayer.replay(&missing, &mut replayed),
+        Err(ReplayError::UnknownTemplate { template: 0 })
+    );
+    assert!(replayed.edits.is_empty());
+
+    // A rejected batch doesn't break the stream
+    replayer.replay(&batch, &mut replayed).unwrap();
+    assert!(!replayed.edits.is_empty());
+}
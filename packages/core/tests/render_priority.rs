@@ -0,0 +1,128 @@
+//! Updates are rendered in order of priority, and rendering with a deadline yields between scopes
+
+use dioxus::prelude::*;
+use dioxus_core::{
+    current_scope_id, needs_update, schedule_update, set_render_priority, NoOpMutations,
+    RenderPriority,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+thread_local! {
+    static RENDERED: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    static SCOPES: RefCell<Vec<ScopeId>> = const { RefCell::new(Vec::new()) };
+}
+
+fn take_rendered() -> Vec<&'static str> {
+    RENDERED.with(|rendered| std::mem::take(&mut *rendered.borrow_mut()))
+}
+
+fn app() -> Element {
+    RENDERED.with(|rendered| rendered.borrow_mut().push("app"));
+    rsx! {
+        Child { name: "first" }
+        Child { name: "second" }
+    }
+}
+
+#[component]
+fn Child(name: &'static str) -> Element {
+    use_hook(|| SCOPES.with(|scopes| scopes.borrow_mut().push(current_scope_id())));
+    RENDERED.with(|rendered| rendered.borrow_mut().push(name));
+    rsx! { "{name}" }
+}
+
+fn rebuild() -> (VirtualDom, Vec<ScopeId>) {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    take_rendered();
+    let scopes = SCOPES.with(|scopes| std::mem::take(&mut *scopes.borrow_mut()));
+    (dom, scopes)
+}
+
+#[test]
+fn transitions_render_last() {
+    let (mut dom, scopes) = rebuild();
+
+    dom.in_scope(scopes[0], || start_transition(needs_update));
+    dom.in_scope(scopes[1], needs_update);
+
+    // The deadline is always reached, so only one scope renders per call
+    assert!(!dom.render_with_deadline(&mut NoOpMutations, || true));
+    assert_eq!(take_rendered(), ["second"]);
+    assert!(dom.render_with_deadline(&mut NoOpMutations, || true));
+    assert_eq!(take_rendered(), ["first"]);
+}
+
+#[test]
+fn transition_parents_render_before_urgent_children() {
+    let (mut dom, scopes) = rebuild();
+
+    dom.mark_dirty_with_priority(ScopeId::APP, RenderPriority::Transition);
+    dom.mark_dirty_with_priority(scopes[1], RenderPriority::UserInput);
+
+    assert!(!dom.render_with_deadline(&mut NoOpMutations, || true));
+    assert_eq!(take_rendered(), ["app"]);
+    assert!(dom.render_with_deadline(&mut NoOpMutations, || true));
+    assert_eq!(take_rendered(), ["second"]);
+}
+
+#[test]
+fn scope_priority_lowers_updates() {
+    let (mut dom, scopes) = rebuild();
+
+    dom.in_scope(scopes[0], || {
+        set_render_priority(RenderPriority::Transition)
+    });
+    dom.mark_dirty_with_priority(scopes[0], RenderPriority::UserInput);
+    dom.mark_dirty(scopes[1]);
+
+    dom.render_immediate(&mut NoOpMutations);
+    assert_eq!(take_rendered(), ["second", "first"]);
+}
+
+#[test]
+fn deadline_renders_everything_in_time() {
+    let (mut dom, scopes) = rebuild();
+
+    dom.mark_dirty(scopes[0]);
+    dom.mark_dirty(scopes[1]);
+
+    assert!(dom.render_with_deadline(&mut NoOpMutations, || false));
+    assert_eq!(take_rendered(), ["first", "second"]);
+}
+
+#[test]
+fn updates_from_other_threads_keep_their_priority() {
+    let (mut dom, scopes) = rebuild();
+
+    let update = dom.in_scope(scopes[0], || start_transition(schedule_update));
+    std::thread::spawn(move || update()).join().unwrap();
+    dom.in_scope(scopes[1], needs_update);
+
+    assert!(!dom.render_with_deadline(&mut NoOpMutations, || true));
+    assert_eq!(take_rendered(), ["second"]);
+    assert!(dom.render_with_deadline(&mut NoOpMutations, || true));
+    assert_eq!(take_rendered(), ["first"]);
+}
+
+#[test]
+fn deadline_waits_for_queued_tasks() {
+    let (mut dom, scopes) = rebuild();
+
+    let polled = Rc::new(Cell::new(false));
+    dom.in_scope(scopes[1], || {
+        let polled = polled.clone();
+        spawn(async move { polled.set(true) })
+    });
+    dom.mark_dirty(scopes[0]);
+
+    // The task is still queued once the deadline is reached, so the render isn't done
+    assert!(!dom.render_with_deadline(&mut NoOpMutations, || true));
+    assert_eq!(take_rendered(), ["first"]);
+    assert!(!polled.get());
+    assert!(dom.render_with_deadline(&mut NoOpMutations, || true));
+    assert!(polled.get());
+}
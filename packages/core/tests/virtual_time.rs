@@ -0,0 +1,141 @@
+//! The test dom runs timers on a virtual clock, so time only passes when the test advances it. Run with `--features testing`
+#![cfg(feature = "testing")]
+
+use dioxus::prelude::*;
+use dioxus_core::{internal::sleep, virtual_sleep, Mutation, Task, TestDom};
+use std::time::{Duration, Instant};
+
+fn last_text(mutations: &dioxus_core::Mutations) -> Option<&str> {
+    mutations.edits.iter().rev().find_map(|edit| match edit {
+        Mutation::SetText { value, .. } => Some(value.as_str()),
+        _ => None,
+    })
+}
+
+#[test]
+fn timers_fire_in_order() {
+    let mut dom = TestDom::new(|| {
+        let mut ticks = use_signal(|| 0);
+        use_hook(|| {
+            spawn(async move {
+                loop {
+                    virtual_sleep(Duration::from_secs(1)).unwrap().await;
+                    ticks += 1;
+                }
+            })
+        });
+        rsx! { "{ticks}" }
+    });
+    dom.rebuild();
+
+    assert!(dom.advance(Duration::from_millis(999)).edits.is_empty());
+    assert_eq!(last_text(&dom.advance(Duration::from_millis(1))), Some("1"));
+
+    // Every timer that is due fires, even if it was created while advancing
+    let mutations = dom.advance(Duration::from_secs(3));
+    let texts: Vec<_> = mutations
+        .edits
+        .iter()
+        .filter_map(|edit| match edit {
+            Mutation::SetText { value, .. } => Some(value.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(texts, ["2", "3", "4"]);
+    assert_eq!(dom.now(), Duration::from_secs(4));
+}
+
+#[test]
+fn debounced_updates_wait_for_quiet() {
+    let mut dom = TestDom::new(|| {
+        let mut input = use_signal(|| 0);
+        let mut debounced = use_signal(|| 0);
+        let mut pending = use_signal(|| None::<Task>);
+
+        // Simulate the user typing every 100ms
+        use_hook(|| {
+            spawn(async move {
+                for _ in 0..5 {
+                    virtual_sleep(Duration::from_millis(100)).unwrap().await;
+                    input += 1;
+                }
+            })
+        });
+
+        use_effect(move || {
+            let value = input();
+            if let Some(task) = pending.take() {
+                task.cancel();
+            }
+            pending.set(Some(spawn(async move {
+                virtual_sleep(Duration::from_millis(300)).unwrap().await;
+                debounced.set(value);
+            })));
+        });
+
+        rsx! { "{debounced}" }
+    });
+    dom.rebuild();
+
+    // The user stops typing after 500ms, so the value settles 300ms later
+    dom.advance(Duration::from_millis(799));
+    assert_eq!(dom.clock().next_timer(), Some(Duration::from_millis(800)));
+    assert_eq!(last_text(&dom.advance(Duration::from_millis(1))), Some("5"));
+    assert!(dom.clock().next_timer().is_none());
+}
+
+#[test]
+fn real_runtimes_have_no_virtual_clock() {
+    let dom = VirtualDom::new(|| rsx! {});
+    assert!(dom
+        .in_runtime(|| virtual_sleep(Duration::from_secs(1)))
+        .is_none());
+}
+
+#[test]
+fn sleep_waits_on_the_virtual_clock() {
+    let mut dom = TestDom::new(|| {
+        let mut ticks = use_signal(|| 0);
+        use_hook(|| {
+            spawn(async move {
+                loop {
+                    sleep(Duration::from_secs(60)).await;
+                    ticks += 1;
+                }
+            })
+        });
+        rsx! { "{ticks}" }
+    });
+    dom.rebuild();
+
+    // An hour passes instantly
+    assert_eq!(
+        last_text(&dom.advance(Duration::from_secs(60 * 60))),
+        Some("60")
+    );
+}
+
+#[tokio::test]
+async fn sleep_falls_back_to_a_real_timer() {
+    let start = Instant::now();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    for millis in [60, 20, 40] {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(millis)).await;
+            _ = tx.send(millis);
+        });
+    }
+
+    // A sleep that is dropped early doesn't hold back the others
+    let cancelled = tokio::time::timeout(Duration::from_millis(1), sleep(Duration::from_secs(60)));
+    assert!(cancelled.await.is_err());
+    drop(tx);
+
+    let mut order = Vec::new();
+    while let Some(millis) = rx.recv().await {
+        order.push(millis);
+    }
+    assert_eq!(order, [20, 40, 60]);
+    assert!(start.elapsed() >= Duration::from_millis(60));
+}
@@ -37,6 +37,9 @@ pub enum ClientMsg {
         level: String,
         messages: Vec<String>,
     },
+
+    /// Renders recorded by the dioxus-core profiler, as chrome trace events
+    RenderProfile { events: Vec<String> },
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
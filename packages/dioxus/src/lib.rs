@@ -174,7 +174,7 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "macro")))]
     #[allow(deprecated)]
     #[doc(inline)]
-    pub use dioxus_core_macro::{component, rsx, Props};
+    pub use dioxus_core_macro::{component, island, rsx, Props};
 
     #[cfg(feature = "launch")]
     #[cfg_attr(docsrs, doc(cfg(feature = "launch")))]
@@ -207,7 +207,7 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "fullstack")))]
     #[doc(inline)]
     pub use dioxus_fullstack::{
-        self as dioxus_fullstack, delete, get, patch, post, put, server, use_loader,
+        self as dioxus_fullstack, delete, get, island, patch, post, put, server, use_loader,
         use_server_cached, use_server_future, HttpError, OrHttpError, ServerFnError,
         ServerFnResult, StatusCode,
     };
@@ -240,10 +240,10 @@ pub mod prelude {
 
     #[doc(inline)]
     pub use dioxus_core::{
-        consume_context, provide_context, spawn, suspend, try_consume_context, use_drop, use_hook,
-        AnyhowContext, Attribute, Callback, Component, Element, ErrorBoundary, ErrorContext, Event,
-        EventHandler, Fragment, HasAttributes, IntoDynNode, RenderError, Result, ScopeId,
-        SuspenseBoundary, SuspenseContext, VNode, VirtualDom,
+        consume_context, provide_context, spawn, start_transition, suspend, try_consume_context,
+        use_drop, use_hook, AnyhowContext, Attribute, Callback, Component, Element, ErrorBoundary,
+        ErrorContext, Event, EventHandler, Fragment, HasAttributes, IntoDynNode, KeepAlive, Portal,
+        RenderError, Result, ScopeId, SuspenseBoundary, SuspenseContext, VNode, VirtualDom,
     };
 
     #[cfg(feature = "logger")]
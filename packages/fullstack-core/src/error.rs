@@ -21,6 +21,12 @@ use crate::HttpError;
 /// ```
 pub type ServerFnResult<T = ()> = std::result::Result<T, ServerFnError>;
 
+/// The header the client sends the version of the server function it calls in.
+///
+/// Requests without the header call version 1. Responses to calls of an unknown version list the
+/// versions the server serves in the same header.
+pub const SERVER_FN_VERSION_HEADER: &str = "x-server-fn-version";
+
 /// The error type for the server function system. This enum encompasses all possible errors that can occur
 /// during the registration, invocation, and processing of server functions.
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -81,6 +87,21 @@ pub enum ServerFnError {
     /// Occurs on the server if there is an error creating an HTTP response.
     #[error("error creating response {0}")]
     Response(String),
+
+    /// Occurs when the client calls a version of a server function that the server doesn't serve,
+    /// usually because the client was built before the server was updated. The client should reload
+    /// to get the current version of the app.
+    ///
+    /// Adding this variant is a breaking change: `ServerFnError` is not `#[non_exhaustive]`, so code
+    /// that matches every variant without a wildcard arm needs a new arm for it.
+    #[error("server function version {requested} is not supported by the server (supported versions: {supported:?}), reload the app")]
+    VersionMismatch {
+        /// The version the client called.
+        requested: u32,
+
+        /// The versions the server serves.
+        supported: Vec<u32>,
+    },
 }
 
 impl ServerFnError {
@@ -152,6 +173,7 @@ impl From<ServerFnError> for http::StatusCode {
             | ServerFnError::Args(_)
             | ServerFnError::MissingArg(_)
             | ServerFnError::Response(_) => http::StatusCode::INTERNAL_SERVER_ERROR,
+            ServerFnError::VersionMismatch { .. } => http::StatusCode::PRECONDITION_FAILED,
         }
     }
 }
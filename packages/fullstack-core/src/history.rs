@@ -120,6 +120,18 @@ impl<H: History> History for FullstackHistory<H> {
         self.history.updater(callback)
     }
 
+    fn start_view_transition(&self, update: Box<dyn FnOnce()>) {
+        self.history.start_view_transition(update)
+    }
+
+    fn current_index(&self) -> Option<usize> {
+        self.history.current_index()
+    }
+
+    fn handles_scroll_restoration(&self) -> bool {
+        self.history.handles_scroll_restoration()
+    }
+
     fn include_prevent_default(&self) -> bool {
         self.history.include_prevent_default()
     }
@@ -0,0 +1,81 @@
+//! Islands are interactive components on an otherwise static page. Each island ships its props in the server rendered
+//! html and is hydrated on the client as its own small [`VirtualDom`].
+//!
+//! Declare an island with the `#[island]` attribute, render the page without hydration on the server, and hydrate the
+//! islands on the client with `dioxus::web::hydrate_islands`:
+//!
+//! ```rust, ignore
+//! #[island(hydrate = visible)]
+//! fn Counter(start: i32) -> Element {
+//!     let mut count = use_signal(|| start);
+//!     rsx! { button { onclick: move |_| count += 1, "{count}" } }
+//! }
+//!
+//! // On the client
+//! dioxus::web::hydrate_islands([island(Counter)]);
+//! ```
+
+use dioxus_core::{Element, VirtualDom};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// The props of a component declared with `#[island]`. This is implemented by the macro.
+pub trait Island: Clone + 'static {
+    /// The unique name of the island. This is the same on the server and the client.
+    const NAME: &'static str;
+
+    /// Serialize the props into the html
+    fn serialize(&self) -> String;
+
+    /// Deserialize the props from the html
+    fn deserialize(props: &str) -> Option<Self>;
+
+    /// Render the body of the island, without the island boundary
+    fn render(self) -> Element;
+}
+
+/// An island that can be hydrated on the client. Create one with [`island`].
+#[derive(Clone, Copy)]
+pub struct IslandDefinition {
+    name: &'static str,
+    mount: fn(&str) -> Option<VirtualDom>,
+}
+
+impl IslandDefinition {
+    /// The name of the island
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Create a virtual dom for the island from its serialized props. Returns `None` if the props don't match the island.
+    pub fn mount(&self, props: &str) -> Option<VirtualDom> {
+        (self.mount)(props)
+    }
+}
+
+/// Get the definition of a component declared with `#[island]` so it can be hydrated on the client.
+pub fn island<P: Island>(_component: fn(P) -> Element) -> IslandDefinition {
+    IslandDefinition {
+        name: P::NAME,
+        mount: |props| {
+            let props = P::deserialize(props)?;
+            Some(VirtualDom::new_with_props(P::render, props))
+        },
+    }
+}
+
+/// Serialize the props of an island. Used by the `#[island]` macro.
+#[doc(hidden)]
+pub fn serialize_island_props<T: Serialize>(props: &T) -> String {
+    serde_json::to_string(props).unwrap_or_else(|err| {
+        tracing::error!("Failed to serialize island props: {err}");
+        String::new()
+    })
+}
+
+/// Deserialize the props of an island. Used by the `#[island]` macro.
+#[doc(hidden)]
+pub fn deserialize_island_props<T: DeserializeOwned>(props: &str) -> Option<T> {
+    serde_json::from_str(props)
+        .inspect_err(|err| tracing::error!("Failed to deserialize island props: {err}"))
+        .ok()
+}
@@ -0,0 +1,100 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt::Display;
+
+/// The path the server serves the status of background jobs under.
+///
+/// `GET {JOBS_PATH}/{id}` returns the [`JobStatus`] of a job as JSON, and
+/// `GET {JOBS_PATH}/{id}/events` streams every change of the status as server-sent events.
+pub const JOBS_PATH: &str = "/api/jobs";
+
+/// The id of a background job, returned when the job is enqueued on the server.
+///
+/// Ids are random, so they can be handed to the client to poll the status of the job.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JobId(pub String);
+
+impl JobId {
+    /// The id as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for JobId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for JobId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+/// Where a background job is in its lifecycle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// The job is waiting for a free worker.
+    Queued,
+    /// The job is running.
+    Running,
+    /// The last attempt failed and the job will run again after a delay.
+    Retrying,
+    /// The job finished successfully.
+    Succeeded,
+    /// The job failed and has no attempts left.
+    Failed,
+}
+
+impl JobState {
+    /// Returns true if the job succeeded or failed and will not change anymore.
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed)
+    }
+}
+
+/// The status of a background job, as reported by the job status endpoint.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JobStatus {
+    /// The id of the job.
+    pub id: JobId,
+
+    /// The name of the kind of job.
+    pub name: String,
+
+    /// Where the job is in its lifecycle.
+    pub state: JobState,
+
+    /// How many times the job started running, including the current attempt.
+    pub attempts: u32,
+
+    /// How many times the job may run before it fails.
+    pub max_attempts: u32,
+
+    /// The progress the job reported, from 0.0 to 1.0.
+    pub progress: Option<f32>,
+
+    /// The last message the job reported with its progress.
+    pub message: Option<String>,
+
+    /// The output of the job, once it succeeded.
+    pub output: Option<serde_json::Value>,
+
+    /// The error of the last failed attempt.
+    pub error: Option<String>,
+}
+
+impl JobStatus {
+    /// Returns true if the job succeeded or failed and will not change anymore.
+    pub fn is_finished(&self) -> bool {
+        self.state.is_finished()
+    }
+
+    /// Deserialize the output of the job, if it succeeded.
+    pub fn output<T: DeserializeOwned>(&self) -> Option<Result<T, serde_json::Error>> {
+        self.output.clone().map(serde_json::from_value)
+    }
+}
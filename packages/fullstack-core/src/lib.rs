@@ -5,6 +5,8 @@ pub mod document;
 pub mod history;
 
 mod errors;
+mod islands;
+mod jobs;
 mod loader;
 mod server_cached;
 mod server_future;
@@ -12,6 +14,8 @@ mod streaming;
 mod transport;
 
 pub use crate::errors::*;
+pub use crate::islands::*;
+pub use crate::jobs::*;
 pub use crate::loader::*;
 pub use crate::server_cached::*;
 pub use crate::server_future::*;
@@ -86,6 +86,69 @@ use syn::{
 ///     unimplemented!()
 /// }
 /// ```
+///
+/// ## Rate limits and idempotency
+///
+/// The `rate_limit` and `idempotent` arguments add the built-in `RateLimitLayer` and
+/// `IdempotencyLayer` to the server function:
+///
+/// - `rate_limit = "10/min"` allows ten calls per minute from each client. The period can be a
+///   number of `s`, `min`, `h` or `d`, like `"100/15min"`. Calls over the limit are rejected with
+///   `429 Too Many Requests` and a `Retry-After` header.
+/// - `rate_limit_by = "ip"` sets how clients are told apart: `"ip"` (the default), `"global"`,
+///   `"header:<name>"` or `"cookie:<name>"`.
+/// - `idempotent` replays the response of a call to repeated calls with the same
+///   `Idempotency-Key` header for five minutes. Calls without the header always run.
+///   `idempotent = "1h"` keeps responses for a different duration.
+///
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// #[server(rate_limit = "10/min", rate_limit_by = "header:authorization", idempotent)]
+/// pub async fn place_order(item: String) -> ServerFnResult<u64> {
+///     unimplemented!()
+/// }
+/// ```
+///
+/// ## Versioning
+///
+/// Changing the arguments of a server function breaks clients that still run the old version of
+/// the app during a deploy. `version = 2` makes the client send the version of the server function
+/// with every call. Keep the previous version with the same `endpoint` to serve old clients, and
+/// adapt its arguments to the new version:
+///
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// #[server(endpoint = "create_user", version = 2)]
+/// pub async fn create_user(name: String, email: String) -> ServerFnResult<u64> {
+///     unimplemented!()
+/// }
+///
+/// #[server(endpoint = "create_user", version = 1)]
+/// pub async fn create_user_v1(name: String) -> ServerFnResult<u64> {
+///     create_user(name, String::new()).await
+/// }
+/// ```
+///
+/// Calls of a version the server doesn't serve are rejected with
+/// `ServerFnError::VersionMismatch`, and the web client reloads the page to get the current
+/// version of the app. Calls without a version, from clients built before the server function
+/// was versioned, are version 1.
+///
+/// ## Authentication
+///
+/// The `auth` argument adds the `AuthLayer`, which needs a `SessionLayer` on the router:
+///
+/// - `auth` rejects calls without a logged in user with `401 Unauthorized`.
+/// - `auth = guard` also calls `async fn guard(session: Session) -> bool`, and rejects calls it
+///   returns false for with `403 Forbidden`.
+///
+/// ```rust,ignore
+/// # use dioxus::prelude::*;
+/// #[server(auth = is_admin)]
+/// pub async fn delete_user(id: u64) -> ServerFnResult<()> {
+///     unimplemented!()
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn server(attr: proc_macro::TokenStream, mut item: TokenStream) -> TokenStream {
     // Parse the attribute list using the old server_fn arg parser.
@@ -110,6 +173,8 @@ pub fn server(attr: proc_macro::TokenStream, mut item: TokenStream) -> TokenStre
         route_lit: args.fn_path,
         oapi_options: None,
         server_args: args.server_args,
+        builtin_layers: args.builtin_layers,
+        version: args.version,
         prefix: Some(prefix),
         _input_encoding: args.input,
         _output_encoding: args.output,
@@ -220,6 +285,26 @@ fn route_impl_with_route(
         })
         .collect::<Result<Vec<_>>>()?;
 
+    // The built-in layers wrap the user's middleware, so rejected calls never reach it
+    let middleware_layers = middleware_layers
+        .into_iter()
+        .chain(
+            route
+                .builtin_layers
+                .iter()
+                .map(|layer| quote! { .layer(#layer) }),
+        )
+        .collect::<Vec<_>>();
+
+    // Versioned server functions send their version with every call, and are registered with it
+    let (with_client_version, with_server_version) = match &route.version {
+        Some(version) => (
+            quote! { .with_server_fn_version(#version) },
+            quote! { .with_version(#version) },
+        ),
+        None => (quote! {}, quote! {}),
+    };
+
     // don't re-emit the middleware attribute on the inner
     function
         .attrs
@@ -247,8 +332,9 @@ fn route_impl_with_route(
         .collect::<Punctuated<_, Token![,]>>();
     // .collect::<Punctuated<_, Token![,]>>();
 
-    let route = CompiledRoute::from_route(route, &function, false, method_from_macro)?;
-    let query_params_struct = route.query_params_struct(false);
+    let with_aide = cfg!(feature = "openapi");
+    let route = CompiledRoute::from_route(route, &function, with_aide, method_from_macro)?;
+    let query_params_struct = route.query_params_struct();
     let method_ident = &route.method;
     let body_json_args = route.remaining_pattypes_named(&function.sig.inputs);
     let body_json_names = body_json_args
@@ -260,7 +346,7 @@ fn route_impl_with_route(
         .collect::<Vec<_>>();
     let body_json_types = body_json_args
         .iter()
-        .map(|pat_type| &pat_type.1.ty)
+        .map(|pat_type| &*pat_type.1.ty)
         .collect::<Vec<_>>();
     let route_docs = route.to_doc_comments();
 
@@ -437,6 +523,16 @@ fn route_impl_with_route(
 
     let extracted_as_server_headers = route.extracted_as_server_headers(query_tokens.clone());
 
+    // Describe the server function in the OpenAPI document if the `openapi` feature is enabled
+    let (openapi_operation, with_openapi) =
+        match route.openapi_operation(&function, &body_json_names, &body_json_types, &out_ty) {
+            Some(operation) => (
+                operation,
+                quote! { .with_openapi(__openapi_operation__ #ty_generics) },
+            ),
+            None => (quote! {}, quote! {}),
+        };
+
     Ok(quote! {
         #(#fn_docs)*
         #route_docs
@@ -505,7 +601,7 @@ fn route_impl_with_route(
                     dioxus_fullstack::http::Method::#method_ident,
                     #query_endpoint,
                     &#query_tokens,
-                );
+                ) #with_client_version;
 
                 let response = (&&&&&&&&&&&&&&ServerFnEncoder::<___Body_Serialize___<#(#body_json_types,)*>, (#(#body_json_types,)*)>::new())
                     .fetch_client(client, ___Body_Serialize___ { #(#body_json_names,)* }, #unpack_closure)
@@ -548,6 +644,8 @@ fn route_impl_with_route(
                     })
                 }
 
+                #openapi_operation
+
                 dioxus_server::inventory::submit! {
                     dioxus_server::ServerFunction::new(
                         dioxus_server::http::Method::#method_ident,
@@ -557,6 +655,8 @@ fn route_impl_with_route(
                                 #(#middleware_layers)*
                         }
                     )
+                    #with_server_version
+                    #with_openapi
                 }
 
                 // Extract the server arguments from the context if needed.
@@ -636,7 +736,7 @@ impl CompiledRoute {
         if !with_aide && route.oapi_options.is_some() {
             return Err(syn::Error::new(
                 Span::call_site(),
-                "Use `api_route` instead of `route` to use OpenAPI options",
+                "Enable the `openapi` feature of dioxus to use OpenAPI options",
             ));
         } else if with_aide && route.oapi_options.is_none() {
             route.oapi_options = Some(OapiOptions {
@@ -778,7 +878,7 @@ impl CompiledRoute {
         out
     }
 
-    pub fn query_params_struct(&self, with_aide: bool) -> TokenStream2 {
+    pub fn query_params_struct(&self) -> TokenStream2 {
         let fields = self.query_params.iter().map(|item| {
             let name = &item.name;
             let binding = &item.binding;
@@ -794,18 +894,9 @@ impl CompiledRoute {
                 quote! { #binding: #ty, }
             }
         });
-        let derive = match with_aide {
-            true => quote! {
-                #[derive(serde::Deserialize, serde::Serialize, ::schemars::JsonSchema)]
-                #[serde(crate = "serde")]
-            },
-            false => quote! {
-                #[derive(serde::Deserialize, serde::Serialize)]
-                #[serde(crate = "serde")]
-            },
-        };
         quote! {
-            #derive
+            #[derive(serde::Deserialize, serde::Serialize)]
+            #[serde(crate = "serde")]
             struct __QueryParams__ {
                 #(#fields)*
             }
@@ -917,6 +1008,126 @@ impl CompiledRoute {
         )
     }
 
+    /// Generate the function that describes this route in the OpenAPI document. Types are described
+    /// through autoref specialization so arguments without a `JsonSchema` implementation still compile.
+    fn openapi_operation(
+        &self,
+        function: &ItemFn,
+        body_names: &[Ident],
+        body_types: &[&Type],
+        out_ty: &Type,
+    ) -> Option<TokenStream2> {
+        let options = self.oapi_options.as_ref()?;
+        let schema_of = |ty: &Type| {
+            quote! { (&&dioxus_server::openapi::SchemaOf::<#ty>::new()).schema(__generator) }
+        };
+
+        let id = options.id.as_ref().map(|(_, id)| quote! { .id(#id) });
+        let summary = options
+            .summary
+            .as_ref()
+            .map(|(_, summary)| quote! { .summary(#summary) });
+        let description = options
+            .description
+            .as_ref()
+            .map(|(_, description)| quote! { .description(#description) });
+        let hidden = options
+            .hidden
+            .as_ref()
+            .map(|(_, hidden)| quote! { .hidden(#hidden) });
+        let tags = options
+            .tags
+            .iter()
+            .flat_map(|(_, tags)| tags.0.iter())
+            .map(|tag| quote! { .tag(#tag) });
+        let security = options
+            .security
+            .iter()
+            .flat_map(|(_, security)| security.0.iter())
+            .map(|(scheme, scopes)| {
+                let scopes = &scopes.0;
+                quote! { .security(#scheme, &[#(#scopes),*]) }
+            });
+        let responses = options
+            .responses
+            .iter()
+            .flat_map(|(_, responses)| responses.0.iter())
+            .map(|(status, ty)| {
+                let schema = schema_of(ty);
+                quote! { .response(#status, #schema) }
+            });
+        let transform = options.transform.as_ref().map(|(_, transform)| {
+            quote! { let __operation = (#transform)(__operation); }
+        });
+
+        let path_params = self
+            .path_params
+            .iter()
+            .filter_map(|(_slash, param)| match param {
+                PathParam::Capture(lit, _, _, ty, _) | PathParam::WildCard(lit, _, _, _, ty, _) => {
+                    let schema = schema_of(ty);
+                    Some(quote! { .path_parameter(#lit, #schema) })
+                }
+                PathParam::Static(_) => None,
+            });
+        let query_params = self.query_params.iter().map(|param| {
+            let schema = schema_of(&param.ty);
+            if param.catch_all {
+                quote! { .query_parameters(#schema, __generator) }
+            } else {
+                let name = &param.name;
+                let required = !is_option(&param.ty);
+                quote! { .query_parameter(#name, #schema, #required) }
+            }
+        });
+        // A single argument can be sent as is with the content type of its `BodyContentType` impl
+        let body_content_type = match body_types {
+            [ty] => Some(quote! {
+                let __operation = match (&&dioxus_fullstack::ContentTypeOf::<#ty>::new()).content_type() {
+                    Some(__content_type) => __operation.body_content_type(__content_type),
+                    None => __operation,
+                };
+            }),
+            _ => None,
+        };
+        let body_fields = body_names.iter().zip(body_types).map(|(name, ty)| {
+            let name = name.to_string();
+            let schema = schema_of(ty);
+            let required = !is_option(ty);
+            quote! { .body_field(#name, #schema, #required) }
+        });
+
+        let (impl_generics, _, where_clause) = function.sig.generics.split_for_impl();
+
+        Some(quote! {
+            fn __openapi_operation__ #impl_generics(
+                __generator: &mut dioxus_server::openapi::SchemaGenerator,
+            ) -> dioxus_server::openapi::Operation #where_clause {
+                use dioxus_fullstack::{WithContentType as _, WithoutContentType as _};
+                use dioxus_server::openapi::{
+                    WithResponseSchema as _, WithSchema as _, WithoutResponseSchema as _,
+                    WithoutSchema as _,
+                };
+
+                let __operation = dioxus_server::openapi::Operation::new()
+                    #id
+                    #summary
+                    #description
+                    #hidden
+                    #(#tags)*
+                    #(#security)*
+                    #(#path_params)*
+                    #(#query_params)*
+                    #(#body_fields)*
+                    .response(200, (&&dioxus_server::openapi::SchemaOf::<#out_ty>::new()).response_schema(__generator))
+                    #(#responses)*;
+                #body_content_type
+                #transform
+                __operation
+            }
+        })
+    }
+
     fn url_without_queries_for_format(&self) -> Option<String> {
         // If there's a prefix, then it's an old-style route, and we can't generate a format string.
         if self.prefix.is_some() {
@@ -1286,22 +1497,39 @@ impl Parse for OapiOptions {
 }
 
 impl OapiOptions {
+    /// Fill in the options that weren't passed to the macro: the operation id defaults to the name
+    /// of the function, the summary to the first paragraph of its doc comment and the description
+    /// to the rest of the doc comment.
     fn merge_with_fn(&mut self, function: &ItemFn) {
-        if self.description.is_none() {
-            self.description = doc_iter(&function.attrs)
-                .skip(2)
-                .map(|item| item.value())
-                .reduce(|mut acc, item| {
-                    acc.push('\n');
-                    acc.push_str(&item);
-                    acc
-                })
-                .map(|item| (parse_quote!(description), parse_quote!(#item)))
+        let docs = doc_iter(&function.attrs)
+            .map(|line| {
+                let line = line.value();
+                line.strip_prefix(' ')
+                    .unwrap_or(&line)
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let docs = docs.trim();
+        let (summary, description) = match docs.split_once("\n\n") {
+            Some((summary, description)) => (summary.trim(), description.trim()),
+            None => (docs, ""),
+        };
+
+        if self.summary.is_none() && !summary.is_empty() {
+            // A summary is a single line, so join the lines of the first paragraph
+            let summary = summary.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+            self.summary = Some((
+                parse_quote!(summary),
+                LitStr::new(&summary, Span::call_site()),
+            ));
         }
-        if self.summary.is_none() {
-            self.summary = doc_iter(&function.attrs)
-                .next()
-                .map(|item| (parse_quote!(summary), item.clone()))
+        if self.description.is_none() && !description.is_empty() {
+            self.description = Some((
+                parse_quote!(description),
+                LitStr::new(description, Span::call_site()),
+            ));
         }
         if self.id.is_none() {
             let id = &function.sig.ident;
@@ -1310,6 +1538,20 @@ impl OapiOptions {
     }
 }
 
+/// Whether the type is an `Option`, which makes the argument optional in the OpenAPI document.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// The content type of the request body of a server function with a single body argument of type
+/// `ty`, if the argument is a payload that is sent as is instead of as a field of a JSON object.
 fn doc_iter(attrs: &[Attribute]) -> impl Iterator<Item = &LitStr> + '_ {
     attrs
         .iter()
@@ -1336,6 +1578,10 @@ struct Route {
     prefix: Option<LitStr>,
     oapi_options: Option<OapiOptions>,
     server_args: Punctuated<FnArg, Comma>,
+    /// Layers added by arguments of the `#[server]` macro, like `rate_limit`.
+    builtin_layers: Vec<TokenStream2>,
+    /// The version of the server function, set with `#[server(version = 2)]`.
+    version: Option<LitInt>,
 
     // todo: support these since `server_fn` had them
     _input_encoding: Option<Type>,
@@ -1379,6 +1625,8 @@ impl Parse for Route {
             route_lit: Some(route_lit),
             oapi_options,
             server_args,
+            builtin_layers: Vec::new(),
+            version: None,
             prefix: None,
             _input_encoding: None,
             _output_encoding: None,
@@ -1508,6 +1756,11 @@ struct ServerFnArgs {
     /// Server-only extractors (e.g., headers: HeaderMap, cookies: Cookies).
     /// These are arguments that exist purely on the server side.
     server_args: Punctuated<FnArg, Comma>,
+    /// The auth, rate limit and idempotency layers configured with `auth`, `rate_limit` and
+    /// `idempotent`.
+    builtin_layers: Vec<TokenStream2>,
+    /// The version of the server function.
+    version: Option<LitInt>,
 }
 
 impl Parse for ServerFnArgs {
@@ -1528,6 +1781,11 @@ impl Parse for ServerFnArgs {
         let mut impl_from: Option<LitBool> = None;
         let mut impl_deref: Option<LitBool> = None;
         let mut protocol: Option<Type> = None;
+        let mut rate_limit: Option<LitStr> = None;
+        let mut rate_limit_by: Option<LitStr> = None;
+        let mut idempotent: Option<Option<LitStr>> = None;
+        let mut auth: Option<Option<syn::Path>> = None;
+        let mut version: Option<LitInt> = None;
 
         let mut use_key_and_value = false;
         let mut arg_pos = 0;
@@ -1670,9 +1928,74 @@ impl Parse for ServerFnArgs {
                             ));
                         }
                         protocol = Some(stream.parse()?);
+                    } else if key == "rate_limit" {
+                        if rate_limit.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `rate_limit`",
+                            ));
+                        }
+                        rate_limit = Some(stream.parse()?);
+                    } else if key == "rate_limit_by" {
+                        if rate_limit_by.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `rate_limit_by`",
+                            ));
+                        }
+                        rate_limit_by = Some(stream.parse()?);
+                    } else if key == "idempotent" {
+                        if idempotent.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `idempotent`",
+                            ));
+                        }
+                        idempotent = Some(Some(stream.parse()?));
+                    } else if key == "auth" {
+                        if auth.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `auth`",
+                            ));
+                        }
+                        auth = Some(Some(stream.parse()?));
+                    } else if key == "version" {
+                        if version.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `version`",
+                            ));
+                        }
+                        let lit: LitInt = stream.parse()?;
+                        if lit.base10_parse::<u32>()? == 0 {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                "server function versions start at 1",
+                            ));
+                        }
+                        version = Some(lit);
                     } else {
                         return Err(lookahead.error());
                     }
+                } else if key_or_value == "idempotent" {
+                    if idempotent.is_some() {
+                        return Err(syn::Error::new(
+                            key_or_value.span(),
+                            "keyword argument repeated: `idempotent`",
+                        ));
+                    }
+                    use_key_and_value = true;
+                    idempotent = Some(None);
+                } else if key_or_value == "auth" {
+                    if auth.is_some() {
+                        return Err(syn::Error::new(
+                            key_or_value.span(),
+                            "keyword argument repeated: `auth`",
+                        ));
+                    }
+                    use_key_and_value = true;
+                    auth = Some(None);
                 } else {
                     let value = key_or_value;
                     if use_key_and_value {
@@ -1754,6 +2077,45 @@ impl Parse for ServerFnArgs {
             }
         }
 
+        // Each layer wraps the ones before it, so the last layer runs first. Auth runs before everything else so
+        // responses are never replayed or counted for callers that are not allowed to call the function.
+        let mut builtin_layers = Vec::new();
+        if let Some(ttl) = idempotent {
+            let ttl = ttl
+                .map(|ttl| {
+                    let seconds = parse_duration_secs(&ttl)?;
+                    Ok::<_, syn::Error>(quote! { .ttl(::std::time::Duration::from_secs(#seconds)) })
+                })
+                .transpose()?;
+            builtin_layers.push(quote! { dioxus_server::IdempotencyLayer::new() #ttl });
+        }
+        match (rate_limit, rate_limit_by) {
+            (Some(rate_limit), rate_limit_by) => {
+                let (requests, seconds) = parse_rate_limit(&rate_limit)?;
+                let key = rate_limit_by
+                    .map(|key| parse_rate_limit_key(&key))
+                    .transpose()?;
+                let key = key.map(|key| quote! { .key(#key) });
+                builtin_layers.push(quote! {
+                    dioxus_server::RateLimitLayer::new(dioxus_server::Quota::new(
+                        #requests,
+                        ::std::time::Duration::from_secs(#seconds),
+                    )) #key
+                });
+            }
+            (None, Some(rate_limit_by)) => {
+                return Err(syn::Error::new(
+                    rate_limit_by.span(),
+                    "`rate_limit_by` requires a `rate_limit`",
+                ));
+            }
+            (None, None) => {}
+        }
+        if let Some(guard) = auth {
+            let guard = guard.map(|guard| quote! { .guard(#guard) });
+            builtin_layers.push(quote! { dioxus_fullstack::AuthLayer::new() #guard });
+        }
+
         Ok(Self {
             struct_name,
             prefix,
@@ -1769,10 +2131,94 @@ impl Parse for ServerFnArgs {
             impl_deref,
             protocol,
             server_args,
+            builtin_layers,
+            version,
         })
     }
 }
 
+/// Parse a duration like `"30s"`, `"5min"` or `"1h"` into seconds. A missing number means one unit.
+fn parse_duration_secs(lit: &LitStr) -> syn::Result<u64> {
+    let value = lit.value();
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (count, unit) = value.split_at(split);
+    let count = match count {
+        "" => 1,
+        count => count
+            .parse::<u64>()
+            .map_err(|_| syn::Error::new(lit.span(), "invalid number in duration"))?,
+    };
+    let unit = match unit.trim() {
+        "s" | "sec" | "secs" | "second" | "seconds" => 1,
+        "m" | "min" | "mins" | "minute" | "minutes" => 60,
+        "h" | "hr" | "hour" | "hours" => 60 * 60,
+        "d" | "day" | "days" => 24 * 60 * 60,
+        _ => {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected a duration like \"30s\", \"5min\", \"1h\" or \"1d\"",
+            ))
+        }
+    };
+    match count.checked_mul(unit) {
+        Some(0) => Err(syn::Error::new(lit.span(), "the duration must not be zero")),
+        Some(seconds) => Ok(seconds),
+        None => Err(syn::Error::new(lit.span(), "the duration is too long")),
+    }
+}
+
+/// Parse a rate limit like `"10/min"` or `"100/15min"` into a number of requests and a period in
+/// seconds.
+fn parse_rate_limit(lit: &LitStr) -> syn::Result<(u32, u64)> {
+    let value = lit.value();
+    let Some((requests, period)) = value.split_once('/') else {
+        return Err(syn::Error::new(
+            lit.span(),
+            "expected a rate limit like \"10/min\"",
+        ));
+    };
+    let requests = requests
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|requests| *requests > 0)
+        .ok_or_else(|| {
+            syn::Error::new(
+                lit.span(),
+                "the number of requests must be a positive integer",
+            )
+        })?;
+    let period = parse_duration_secs(&LitStr::new(period, lit.span()))?;
+    Ok((requests, period))
+}
+
+/// Parse the `rate_limit_by` argument into a `RateLimitKey`.
+fn parse_rate_limit_key(lit: &LitStr) -> syn::Result<TokenStream2> {
+    let value = lit.value();
+    match value.split_once(':') {
+        None if value == "ip" => Ok(quote! { dioxus_server::RateLimitKey::Ip }),
+        None if value == "global" => Ok(quote! { dioxus_server::RateLimitKey::Global }),
+        Some(("header", name)) if !name.is_empty() => {
+            let name = name.to_ascii_lowercase();
+            let is_token = |b: u8| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b);
+            if !name.bytes().all(is_token) {
+                return Err(syn::Error::new(lit.span(), "invalid header name"));
+            }
+            Ok(quote! { dioxus_server::RateLimitKey::header(#name) })
+        }
+        Some(("cookie", name)) if !name.is_empty() => {
+            Ok(quote! { dioxus_server::RateLimitKey::cookie(#name) })
+        }
+        _ => Err(syn::Error::new(
+            lit.span(),
+            "expected \"ip\", \"global\", \"header:<name>\" or \"cookie:<name>\"",
+        )),
+    }
+}
+
 /// An argument type in a server function.
 #[allow(unused)]
 // todo - we used to support a number of these attributes and pass them along to serde. bring them back.
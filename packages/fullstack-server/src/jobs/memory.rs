@@ -0,0 +1,90 @@
+use super::{JobId, JobRecord, JobStore};
+use dashmap::DashMap;
+use futures_util::future::BoxFuture;
+
+/// A [`JobStore`] that keeps jobs in the memory of the server.
+///
+/// Jobs are lost when the server restarts, and finished jobs are kept until then.
+#[derive(Default)]
+pub struct MemoryJobStore {
+    jobs: DashMap<JobId, JobRecord>,
+}
+
+impl MemoryJobStore {
+    /// Create a new, empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl JobStore for MemoryJobStore {
+    fn save<'a>(&'a self, record: &'a JobRecord) -> BoxFuture<'a, anyhow::Result<()>> {
+        self.jobs.insert(record.status.id.clone(), record.clone());
+        Box::pin(async { Ok(()) })
+    }
+
+    fn load<'a>(&'a self, id: &'a JobId) -> BoxFuture<'a, anyhow::Result<Option<JobRecord>>> {
+        let record = self.jobs.get(id).map(|record| record.clone());
+        Box::pin(async move { Ok(record) })
+    }
+
+    fn unfinished(&self) -> BoxFuture<'_, anyhow::Result<Vec<JobRecord>>> {
+        let records = self
+            .jobs
+            .iter()
+            .filter(|record| !record.status.is_finished())
+            .map(|record| record.clone())
+            .collect();
+        Box::pin(async move { Ok(records) })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jobs::{test::record, JobState};
+
+    #[tokio::test]
+    async fn saves_and_loads_jobs() {
+        let store = MemoryJobStore::new();
+        let id = JobId("a".to_string());
+        assert!(store.load(&id).await.unwrap().is_none());
+
+        store
+            .save(&record("a", "job", JobState::Queued))
+            .await
+            .unwrap();
+        let mut loaded = store.load(&id).await.unwrap().unwrap();
+        assert_eq!(loaded.status.state, JobState::Queued);
+
+        // Saving again replaces the job
+        loaded.status.state = JobState::Succeeded;
+        store.save(&loaded).await.unwrap();
+        let loaded = store.load(&id).await.unwrap().unwrap();
+        assert_eq!(loaded.status.state, JobState::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn lists_unfinished_jobs() {
+        let store = MemoryJobStore::new();
+        for (id, state) in [
+            ("queued", JobState::Queued),
+            ("running", JobState::Running),
+            ("retrying", JobState::Retrying),
+            ("succeeded", JobState::Succeeded),
+            ("failed", JobState::Failed),
+        ] {
+            store.save(&record(id, "job", state)).await.unwrap();
+        }
+
+        let mut ids: Vec<_> = store
+            .unfinished()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|record| record.status.id.0)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, ["queued", "retrying", "running"]);
+    }
+}
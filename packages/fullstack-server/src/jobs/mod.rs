@@ -0,0 +1,759 @@
+//! Background jobs for long running work started from server functions.
+//!
+//! A job is a serializable value that implements [`Job`]. Server functions enqueue jobs with
+//! [`enqueue_job`] and return the [`JobId`] to the client right away. The job then runs on a
+//! worker of the [`JobRunner`] and is retried with exponential backoff if it fails.
+//!
+//! ```rust, ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct GenerateReport {
+//!     month: u32,
+//! }
+//!
+//! impl Job for GenerateReport {
+//!     const NAME: &'static str = "generate_report";
+//!     type Output = String;
+//!
+//!     async fn run(self, ctx: JobContext) -> anyhow::Result<String> {
+//!         for day in 1..=30 {
+//!             // ...
+//!             ctx.progress(day as f32 / 30.0, format!("Day {day}")).await;
+//!         }
+//!         Ok(format!("/reports/{}.pdf", self.month))
+//!     }
+//! }
+//!
+//! #[post("/api/reports")]
+//! async fn start_report(month: u32) -> Result<JobId> {
+//!     Ok(enqueue_job(GenerateReport { month }).await?)
+//! }
+//! ```
+//!
+//! [`DioxusRouterExt::serve_jobs`](crate::DioxusRouterExt::serve_jobs) serves the [`JobStatus`] of
+//! every job at `GET /api/jobs/{id}` and streams its changes as server-sent events at
+//! `GET /api/jobs/{id}/events`. On the client, `job_status` and `job_events` call these endpoints. Anyone who knows the id of a job can read its status, so the
+//! output of a job should not contain secrets.
+//!
+//! Jobs are kept in a [`JobStore`]. By default this is a [`MemoryJobStore`], which loses jobs when
+//! the server restarts. With the `sqlite` feature, a [`SqliteJobStore`] keeps them in a file, and
+//! the jobs that were queued or running when the server stopped run again when it starts:
+//!
+//! ```rust, ignore
+//! dioxus::serve(|| async move {
+//!     let runner = JobRunner::new(SqliteJobStore::open("jobs.db")?).register::<GenerateReport>();
+//!     _ = runner.install();
+//!     Ok(dioxus::server::router(app).serve_jobs())
+//! });
+//! ```
+//!
+//! Jobs that were stored by a previous run are only picked up once their kind is registered, either
+//! with [`JobRunner::register`] or by enqueueing a job of the same kind.
+
+mod memory;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use memory::*;
+#[cfg(feature = "sqlite")]
+pub use sqlite::*;
+
+pub use dioxus_fullstack_core::{JobId, JobState, JobStatus, JOBS_PATH};
+
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    Json,
+};
+use futures_util::{future::BoxFuture, Stream, StreamExt};
+use parking_lot::{Mutex, RwLock};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::{broadcast, mpsc};
+
+/// The longest delay between two attempts of a job.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+/// Work that runs in the background on the server.
+///
+/// The job is serialized when it is enqueued, and deserialized again for every attempt.
+pub trait Job: Serialize + DeserializeOwned + Send + 'static {
+    /// The name of this kind of job. It is stored with the job to find the code that runs it, so it
+    /// must be unique and should not change between versions of the server.
+    const NAME: &'static str;
+
+    /// How many times the job may run before it fails.
+    const MAX_ATTEMPTS: u32 = 3;
+
+    /// The output of the job, which is reported to the client in [`JobStatus::output`].
+    type Output: Serialize + Send;
+
+    /// Run the job.
+    fn run(self, ctx: JobContext) -> impl Future<Output = anyhow::Result<Self::Output>> + Send;
+
+    /// How long to wait before the next attempt after attempt number `attempt` failed.
+    ///
+    /// The default starts at one second and doubles after every attempt, up to five minutes.
+    fn retry_delay(attempt: u32) -> Duration {
+        Duration::from_secs(1)
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(MAX_RETRY_DELAY)
+    }
+}
+
+/// A job as it is kept in a [`JobStore`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobRecord {
+    /// The status that is reported to the client.
+    pub status: JobStatus,
+
+    /// The serialized job.
+    pub payload: serde_json::Value,
+
+    /// When the job may run next, in milliseconds since the unix epoch.
+    pub run_at: u64,
+}
+
+/// A store that keeps jobs and their status.
+///
+/// See the [module documentation](crate::jobs) for the stores that come with the runner.
+pub trait JobStore: Send + Sync + 'static {
+    /// Insert or replace the job with the id of `record`.
+    fn save<'a>(&'a self, record: &'a JobRecord) -> BoxFuture<'a, anyhow::Result<()>>;
+
+    /// Look up a job by its id.
+    fn load<'a>(&'a self, id: &'a JobId) -> BoxFuture<'a, anyhow::Result<Option<JobRecord>>>;
+
+    /// All jobs that did not succeed or fail yet. These are queued again when the runner starts.
+    fn unfinished(&self) -> BoxFuture<'_, anyhow::Result<Vec<JobRecord>>>;
+}
+
+/// Passed to a running [`Job`] to report its progress.
+pub struct JobContext {
+    runner: JobRunner,
+    record: Arc<Mutex<JobRecord>>,
+}
+
+impl JobContext {
+    /// The id of the running job.
+    pub fn id(&self) -> JobId {
+        self.record.lock().status.id.clone()
+    }
+
+    /// The number of the current attempt, starting at one.
+    pub fn attempt(&self) -> u32 {
+        self.record.lock().status.attempts
+    }
+
+    /// Report the progress of the job, from 0.0 to 1.0, with a message for the user.
+    pub async fn progress(&self, progress: f32, message: impl Into<String>) {
+        let record = {
+            let mut record = self.record.lock();
+            record.status.progress = Some(progress.clamp(0.0, 1.0));
+            record.status.message = Some(message.into());
+            record.clone()
+        };
+        self.runner.save(&record).await;
+    }
+}
+
+type Handler = Arc<
+    dyn Fn(serde_json::Value, JobContext) -> BoxFuture<'static, Result<serde_json::Value, String>>
+        + Send
+        + Sync,
+>;
+
+/// The code that runs one kind of job.
+#[derive(Clone)]
+struct Kind {
+    handler: Handler,
+    retry_delay: fn(u32) -> Duration,
+}
+
+/// Runs background jobs on a pool of workers.
+///
+/// The runner that [`enqueue_job`] and the job status endpoints use is set with
+/// [`JobRunner::install`]. If no runner is installed, a runner with a [`MemoryJobStore`] is used.
+#[derive(Clone)]
+pub struct JobRunner {
+    store: Arc<dyn JobStore>,
+    workers: usize,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    kinds: RwLock<HashMap<&'static str, Kind>>,
+    /// Jobs that were loaded from the store before their kind was registered.
+    parked: Mutex<HashMap<String, Vec<JobId>>>,
+    queue: mpsc::UnboundedSender<JobId>,
+    receiver: Mutex<Option<mpsc::UnboundedReceiver<JobId>>>,
+    updates: broadcast::Sender<JobStatus>,
+    started: AtomicBool,
+}
+
+static RUNNER: OnceLock<JobRunner> = OnceLock::new();
+
+impl JobRunner {
+    /// Create a runner that keeps its jobs in `store`, with one worker per CPU core.
+    pub fn new(store: impl JobStore) -> Self {
+        let (queue, receiver) = mpsc::unbounded_channel();
+        Self {
+            store: Arc::new(store),
+            workers: std::thread::available_parallelism()
+                .map(usize::from)
+                .unwrap_or(1),
+            shared: Arc::new(Shared {
+                kinds: Default::default(),
+                parked: Default::default(),
+                queue,
+                receiver: Mutex::new(Some(receiver)),
+                updates: broadcast::channel(256).0,
+                started: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Set how many jobs may run at the same time.
+    pub fn workers(self, workers: usize) -> Self {
+        Self {
+            workers: workers.max(1),
+            ..self
+        }
+    }
+
+    /// Register a kind of job, so jobs of this kind that were stored by a previous run of the
+    /// server run again.
+    pub fn register<J: Job>(self) -> Self {
+        self.register_handler::<J>();
+        self
+    }
+
+    /// Make this the runner used by [`enqueue_job`] and the job status endpoints.
+    ///
+    /// This can only be done once, before any job is enqueued. Otherwise the runner is returned.
+    pub fn install(self) -> Result<(), Self> {
+        RUNNER.set(self)
+    }
+
+    /// The installed runner, or a runner with a [`MemoryJobStore`] if none was installed.
+    pub fn global() -> &'static JobRunner {
+        RUNNER.get_or_init(|| JobRunner::new(MemoryJobStore::new()))
+    }
+
+    /// Store `job` and queue it to run on a worker.
+    pub async fn enqueue<J: Job>(&self, job: J) -> anyhow::Result<JobId> {
+        self.register_handler::<J>();
+        self.start();
+
+        let id = new_job_id();
+        let record = JobRecord {
+            status: JobStatus {
+                id: id.clone(),
+                name: J::NAME.to_string(),
+                state: JobState::Queued,
+                attempts: 0,
+                max_attempts: J::MAX_ATTEMPTS.max(1),
+                progress: None,
+                message: None,
+                output: None,
+                error: None,
+            },
+            payload: serde_json::to_value(job)?,
+            run_at: now(),
+        };
+        self.store.save(&record).await?;
+        _ = self.shared.updates.send(record.status);
+        _ = self.shared.queue.send(id.clone());
+
+        Ok(id)
+    }
+
+    /// The status of a job, or `None` if there is no job with this id.
+    pub async fn status(&self, id: &JobId) -> anyhow::Result<Option<JobStatus>> {
+        self.start();
+        Ok(self.store.load(id).await?.map(|record| record.status))
+    }
+
+    /// A stream of the status of a job. It starts with the current status, yields every change,
+    /// and ends once the job succeeded or failed.
+    pub fn updates(&self, id: JobId) -> impl Stream<Item = JobStatus> + Send + 'static {
+        enum State {
+            Start,
+            Listening,
+            Done,
+        }
+
+        let updates = self.shared.updates.subscribe();
+        futures_util::stream::unfold(
+            (State::Start, self.clone(), id, updates),
+            |(state, runner, id, mut updates)| async move {
+                let status = match state {
+                    State::Done => return None,
+                    State::Start => runner.status(&id).await.ok().flatten()?,
+                    State::Listening => loop {
+                        match updates.recv().await {
+                            Ok(status) if status.id == id => break status,
+                            Ok(_) => continue,
+                            // Missed some updates, so read the latest status from the store
+                            Err(broadcast::error::RecvError::Lagged(_)) => {
+                                break runner.status(&id).await.ok().flatten()?
+                            }
+                            Err(broadcast::error::RecvError::Closed) => return None,
+                        }
+                    },
+                };
+
+                let state = match status.is_finished() {
+                    true => State::Done,
+                    false => State::Listening,
+                };
+                Some((status, (state, runner, id, updates)))
+            },
+        )
+    }
+
+    fn register_handler<J: Job>(&self) {
+        if self.shared.kinds.read().contains_key(J::NAME) {
+            return;
+        }
+
+        let handler: Handler = Arc::new(|payload, ctx| {
+            Box::pin(async move {
+                let job: J = serde_json::from_value(payload)
+                    .map_err(|err| format!("Failed to deserialize job: {err}"))?;
+                let output = job.run(ctx).await.map_err(|err| format!("{err:#}"))?;
+                serde_json::to_value(output).map_err(|err| err.to_string())
+            })
+        });
+        let kind = Kind {
+            handler,
+            retry_delay: J::retry_delay,
+        };
+        self.shared.kinds.write().insert(J::NAME, kind);
+
+        // Queue the stored jobs of this kind that were waiting for their handler
+        let parked = self.shared.parked.lock().remove(J::NAME);
+        for id in parked.into_iter().flatten() {
+            _ = self.shared.queue.send(id);
+        }
+    }
+
+    /// The code for jobs named `name`, or `None` if the kind isn't registered yet. The job is then
+    /// parked until [`JobRunner::register_handler`] queues it again.
+    fn kind_or_park(&self, name: &str, id: &JobId) -> Option<Kind> {
+        if let Some(kind) = self.shared.kinds.read().get(name) {
+            return Some(kind.clone());
+        }
+
+        // Check again while holding the lock, because the kind may have been registered and the
+        // parked jobs drained since the first check
+        let mut parked = self.shared.parked.lock();
+        if let Some(kind) = self.shared.kinds.read().get(name) {
+            return Some(kind.clone());
+        }
+        parked.entry(name.to_string()).or_default().push(id.clone());
+        None
+    }
+
+    /// Start the workers if this is the first use of the runner inside a tokio runtime.
+    fn start(&self) {
+        if tokio::runtime::Handle::try_current().is_err()
+            || self.shared.started.swap(true, Ordering::SeqCst)
+        {
+            return;
+        }
+
+        let Some(receiver) = self.shared.receiver.lock().take() else {
+            return;
+        };
+        tokio::spawn(self.clone().dispatch(receiver));
+    }
+
+    async fn dispatch(self, mut receiver: mpsc::UnboundedReceiver<JobId>) {
+        // Pick up the jobs that were queued or running when the server stopped
+        match self.store.unfinished().await {
+            Ok(records) => {
+                for record in records {
+                    self.schedule(record.status.id, record.run_at);
+                }
+            }
+            Err(err) => tracing::error!("Failed to load unfinished jobs: {err:#}"),
+        }
+
+        let workers = Arc::new(tokio::sync::Semaphore::new(self.workers));
+        while let Some(id) = receiver.recv().await {
+            let Ok(permit) = workers.clone().acquire_owned().await else {
+                break;
+            };
+            let runner = self.clone();
+            tokio::spawn(async move {
+                runner.run(id).await;
+                drop(permit);
+            });
+        }
+    }
+
+    /// Queue a job once `run_at` has passed.
+    fn schedule(&self, id: JobId, run_at: u64) {
+        let delay = run_at.saturating_sub(now());
+        if delay == 0 {
+            _ = self.shared.queue.send(id);
+            return;
+        }
+
+        let queue = self.shared.queue.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            _ = queue.send(id);
+        });
+    }
+
+    async fn run(&self, id: JobId) {
+        let mut record = match self.store.load(&id).await {
+            Ok(Some(record)) if !record.status.is_finished() => record,
+            Ok(_) => return,
+            Err(err) => {
+                tracing::error!("Failed to load job {id}: {err:#}");
+                return;
+            }
+        };
+
+        let Some(kind) = self.kind_or_park(&record.status.name, &id) else {
+            return;
+        };
+
+        record.status.state = JobState::Running;
+        record.status.attempts += 1;
+        self.save(&record).await;
+
+        let payload = record.payload.clone();
+        let record = Arc::new(Mutex::new(record));
+        let ctx = JobContext {
+            runner: self.clone(),
+            record: record.clone(),
+        };
+
+        // Run the job in its own task so a panic fails the attempt instead of the worker
+        let result = match tokio::spawn((kind.handler)(payload, ctx)).await {
+            Ok(result) => result,
+            Err(err) => Err(format!("The job panicked: {err}")),
+        };
+
+        let (record, retry_at) = {
+            let mut record = record.lock();
+            let status = &mut record.status;
+            let mut retry_at = None;
+            match result {
+                Ok(output) => {
+                    status.state = JobState::Succeeded;
+                    status.progress = Some(1.0);
+                    status.output = Some(output);
+                    status.error = None;
+                }
+                Err(error) if status.attempts < status.max_attempts => {
+                    let delay = (kind.retry_delay)(status.attempts);
+                    status.state = JobState::Retrying;
+                    status.error = Some(error);
+                    retry_at = Some(now() + delay.as_millis() as u64);
+                }
+                Err(error) => {
+                    status.state = JobState::Failed;
+                    status.error = Some(error);
+                }
+            }
+            if let Some(retry_at) = retry_at {
+                record.run_at = retry_at;
+            }
+            (record.clone(), retry_at)
+        };
+
+        self.save(&record).await;
+        if let Some(retry_at) = retry_at {
+            self.schedule(id, retry_at);
+        }
+    }
+
+    /// Save a record and tell the clients that follow the job.
+    async fn save(&self, record: &JobRecord) {
+        if let Err(err) = self.store.save(record).await {
+            tracing::error!("Failed to save job {}: {err:#}", record.status.id);
+        }
+        _ = self.shared.updates.send(record.status.clone());
+    }
+}
+
+/// Enqueue a job on the installed [`JobRunner`].
+pub async fn enqueue_job<J: Job>(job: J) -> anyhow::Result<JobId> {
+    JobRunner::global().enqueue(job).await
+}
+
+/// The status of a job on the installed [`JobRunner`], or `None` if there is no job with this id.
+pub async fn job_status(id: &JobId) -> anyhow::Result<Option<JobStatus>> {
+    JobRunner::global().status(id).await
+}
+
+/// Serves `GET /api/jobs/{id}`.
+pub(crate) async fn status_handler(Path(id): Path<String>) -> Response {
+    match job_status(&JobId(id)).await {
+        Ok(Some(status)) => Json(status).into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(err) => {
+            tracing::error!("Failed to load job status: {err:#}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Serves `GET /api/jobs/{id}/events`.
+pub(crate) async fn events_handler(Path(id): Path<String>) -> Response {
+    let id = JobId(id);
+    match job_status(&id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(err) => {
+            tracing::error!("Failed to load job status: {err:#}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+
+    let events = JobRunner::global()
+        .updates(id)
+        .map(|status| Event::default().json_data(status));
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// A random version 4 UUID. Anyone who knows the id can read the status of the job, so it comes
+/// from the random number generator of the operating system.
+fn new_job_id() -> JobId {
+    JobId(uuid::Uuid::new_v4().simple().to_string())
+}
+
+/// The current time in milliseconds since the unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    /// A record of a job named `name` in `state`, for the store tests.
+    pub(crate) fn record(id: &str, name: &str, state: JobState) -> JobRecord {
+        JobRecord {
+            status: JobStatus {
+                id: JobId(id.to_string()),
+                name: name.to_string(),
+                state,
+                attempts: 0,
+                max_attempts: 3,
+                progress: None,
+                message: None,
+                output: None,
+                error: None,
+            },
+            payload: serde_json::json!({ "value": 1 }),
+            run_at: 0,
+        }
+    }
+
+    static FLAKY_RUNS: AtomicU32 = AtomicU32::new(0);
+
+    /// Fails the first two attempts.
+    #[derive(Serialize, Deserialize)]
+    struct Flaky {
+        value: u32,
+    }
+
+    impl Job for Flaky {
+        const NAME: &'static str = "flaky";
+        type Output = u32;
+
+        async fn run(self, ctx: JobContext) -> anyhow::Result<u32> {
+            FLAKY_RUNS.fetch_add(1, Ordering::SeqCst);
+            ctx.progress(0.5, "Halfway").await;
+            match ctx.attempt() {
+                1 | 2 => anyhow::bail!("attempt {} failed", ctx.attempt()),
+                _ => Ok(self.value * 2),
+            }
+        }
+
+        fn retry_delay(_: u32) -> Duration {
+            Duration::from_millis(10)
+        }
+    }
+
+    /// Never succeeds.
+    #[derive(Serialize, Deserialize)]
+    struct Broken;
+
+    impl Job for Broken {
+        const NAME: &'static str = "broken";
+        const MAX_ATTEMPTS: u32 = 2;
+        type Output = ();
+
+        async fn run(self, _: JobContext) -> anyhow::Result<()> {
+            anyhow::bail!("always broken")
+        }
+
+        fn retry_delay(_: u32) -> Duration {
+            Duration::from_millis(10)
+        }
+    }
+
+    /// Used for jobs that were stored before their kind was registered.
+    #[derive(Serialize, Deserialize)]
+    struct Stored {
+        value: u32,
+    }
+
+    impl Job for Stored {
+        const NAME: &'static str = "stored";
+        type Output = u32;
+
+        async fn run(self, _: JobContext) -> anyhow::Result<u32> {
+            Ok(self.value)
+        }
+    }
+
+    async fn finish(runner: &JobRunner, id: JobId) -> Vec<JobStatus> {
+        tokio::time::timeout(
+            Duration::from_secs(10),
+            runner.updates(id).collect::<Vec<_>>(),
+        )
+        .await
+        .expect("the job did not finish")
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_five_minutes() {
+        let delays: Vec<_> = (1..=10).map(Stored::retry_delay).collect();
+        let secs: Vec<_> = delays.iter().map(Duration::as_secs).collect();
+        assert_eq!(secs, [1, 2, 4, 8, 16, 32, 64, 128, 256, 300]);
+        assert_eq!(Stored::retry_delay(0), Duration::from_secs(1));
+        assert_eq!(Stored::retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn job_ids_are_unique_uuids() {
+        let a = new_job_id();
+        let b = new_job_id();
+        assert_ne!(a, b);
+        assert!(uuid::Uuid::parse_str(a.as_str()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn failed_attempts_are_retried() {
+        let runner = JobRunner::new(MemoryJobStore::new());
+        let id = runner.enqueue(Flaky { value: 21 }).await.unwrap();
+        let updates = finish(&runner, id.clone()).await;
+
+        let last = updates.last().unwrap();
+        assert_eq!(last.state, JobState::Succeeded);
+        assert_eq!(last.attempts, 3);
+        assert_eq!(last.output::<u32>().unwrap().unwrap(), 42);
+        assert_eq!(last.error, None);
+        assert_eq!(FLAKY_RUNS.load(Ordering::SeqCst), 3);
+
+        let retries: Vec<_> = updates
+            .iter()
+            .filter(|status| status.state == JobState::Retrying)
+            .map(|status| status.error.clone().unwrap())
+            .collect();
+        assert_eq!(retries, ["attempt 1 failed", "attempt 2 failed"]);
+        assert!(updates
+            .iter()
+            .any(|status| status.message.as_deref() == Some("Halfway")));
+
+        // The final status is stored
+        assert_eq!(runner.status(&id).await.unwrap().as_ref(), Some(last));
+    }
+
+    #[tokio::test]
+    async fn jobs_fail_after_the_last_attempt() {
+        let runner = JobRunner::new(MemoryJobStore::new());
+        let id = runner.enqueue(Broken).await.unwrap();
+        let updates = finish(&runner, id).await;
+
+        let last = updates.last().unwrap();
+        assert_eq!(last.state, JobState::Failed);
+        assert_eq!(last.attempts, 2);
+        assert_eq!(last.max_attempts, 2);
+        assert_eq!(last.error.as_deref(), Some("always broken"));
+    }
+
+    #[tokio::test]
+    async fn updates_end_for_finished_and_unknown_jobs() {
+        let runner = JobRunner::new(MemoryJobStore::new());
+        let id = runner.enqueue(Stored { value: 1 }).await.unwrap();
+        finish(&runner, id.clone()).await;
+
+        // A finished job yields its final status once
+        let updates = finish(&runner, id).await;
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].state, JobState::Succeeded);
+
+        let unknown = finish(&runner, JobId("unknown".to_string())).await;
+        assert!(unknown.is_empty());
+    }
+
+    #[tokio::test]
+    async fn stored_jobs_run_once_their_kind_is_registered() {
+        let store = MemoryJobStore::new();
+        let mut stored = record("parked", Stored::NAME, JobState::Running);
+        stored.payload = serde_json::json!({ "value": 7 });
+        store.save(&stored).await.unwrap();
+
+        // The runner picks up the job when it starts, but can't run it yet
+        let runner = JobRunner::new(store);
+        runner.start();
+        let id = JobId("parked".to_string());
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !runner.shared.parked.lock().contains_key(Stored::NAME) {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("the job was not parked");
+        assert_eq!(
+            runner.status(&id).await.unwrap().unwrap().state,
+            JobState::Running
+        );
+
+        let runner = runner.register::<Stored>();
+        let last = finish(&runner, id).await.pop().unwrap();
+        assert_eq!(last.state, JobState::Succeeded);
+        assert_eq!(last.output::<u32>().unwrap().unwrap(), 7);
+        assert!(runner.shared.parked.lock().is_empty());
+    }
+
+    #[test]
+    fn jobs_are_not_parked_after_their_kind_is_registered() {
+        let runner = JobRunner::new(MemoryJobStore::new());
+        let id = JobId("job".to_string());
+        assert!(runner.kind_or_park(Stored::NAME, &id).is_none());
+        assert_eq!(runner.shared.parked.lock()[Stored::NAME], [id.clone()]);
+
+        // Registering drains the parked jobs, and later lookups find the kind
+        runner.register_handler::<Stored>();
+        assert!(runner.shared.parked.lock().is_empty());
+        assert!(runner.kind_or_park(Stored::NAME, &id).is_some());
+        assert!(runner.shared.parked.lock().is_empty());
+    }
+}
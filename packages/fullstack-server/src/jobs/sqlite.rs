@@ -0,0 +1,177 @@
+use super::{JobId, JobRecord, JobStore};
+use futures_util::future::BoxFuture;
+use rusqlite::{Connection, OptionalExtension};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+/// A [`JobStore`] that keeps jobs in a SQLite database file.
+///
+/// Jobs survive restarts of the server. The jobs that were queued or running when the server
+/// stopped run again when it starts.
+#[derive(Clone)]
+pub struct SqliteJobStore {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl SqliteJobStore {
+    /// Open or create the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Keep jobs in an existing connection. The `dioxus_jobs` table is created if it doesn't exist.
+    pub fn from_connection(connection: Connection) -> anyhow::Result<Self> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS dioxus_jobs (
+                id TEXT PRIMARY KEY,
+                finished INTEGER NOT NULL,
+                record TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS dioxus_jobs_finished ON dioxus_jobs (finished);",
+        )?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Run a query on a blocking thread.
+    async fn with_connection<T: Send + 'static>(
+        &self,
+        query: impl FnOnce(&Connection) -> anyhow::Result<T> + Send + 'static,
+    ) -> anyhow::Result<T> {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let connection = connection.lock().unwrap_or_else(|err| err.into_inner());
+            query(&connection)
+        })
+        .await?
+    }
+}
+
+impl JobStore for SqliteJobStore {
+    fn save<'a>(&'a self, record: &'a JobRecord) -> BoxFuture<'a, anyhow::Result<()>> {
+        let id = record.status.id.to_string();
+        let finished = record.status.is_finished();
+        let record = serde_json::to_string(record);
+        Box::pin(async move {
+            let record = record?;
+            self.with_connection(move |connection| {
+                connection.execute(
+                    "INSERT OR REPLACE INTO dioxus_jobs (id, finished, record) VALUES (?1, ?2, ?3)",
+                    (id, finished, record),
+                )?;
+                Ok(())
+            })
+            .await
+        })
+    }
+
+    fn load<'a>(&'a self, id: &'a JobId) -> BoxFuture<'a, anyhow::Result<Option<JobRecord>>> {
+        let id = id.to_string();
+        Box::pin(self.with_connection(move |connection| {
+            let record: Option<String> = connection
+                .query_row(
+                    "SELECT record FROM dioxus_jobs WHERE id = ?1",
+                    [id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            Ok(record
+                .map(|record| serde_json::from_str(&record))
+                .transpose()?)
+        }))
+    }
+
+    fn unfinished(&self) -> BoxFuture<'_, anyhow::Result<Vec<JobRecord>>> {
+        Box::pin(self.with_connection(|connection| {
+            let mut statement =
+                connection.prepare("SELECT record FROM dioxus_jobs WHERE finished = 0")?;
+            let records = statement
+                .query_map([], |row| row.get::<_, String>(0))?
+                .map(|record| Ok(serde_json::from_str(&record?)?))
+                .collect::<anyhow::Result<Vec<JobRecord>>>()?;
+            Ok(records)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::jobs::{test::record, JobState};
+
+    fn store() -> SqliteJobStore {
+        SqliteJobStore::from_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn saves_and_loads_jobs() {
+        let store = store();
+        let id = JobId("a".to_string());
+        assert!(store.load(&id).await.unwrap().is_none());
+
+        store
+            .save(&record("a", "job", JobState::Queued))
+            .await
+            .unwrap();
+        let mut loaded = store.load(&id).await.unwrap().unwrap();
+        assert_eq!(loaded.status.state, JobState::Queued);
+        assert_eq!(loaded.payload, serde_json::json!({ "value": 1 }));
+
+        // Saving again replaces the job
+        loaded.status.state = JobState::Succeeded;
+        loaded.status.output = Some(serde_json::json!("done"));
+        store.save(&loaded).await.unwrap();
+        let loaded = store.load(&id).await.unwrap().unwrap();
+        assert_eq!(loaded.status.state, JobState::Succeeded);
+        assert_eq!(loaded.status.output, Some(serde_json::json!("done")));
+    }
+
+    #[tokio::test]
+    async fn lists_unfinished_jobs() {
+        let store = store();
+        for (id, state) in [
+            ("queued", JobState::Queued),
+            ("running", JobState::Running),
+            ("succeeded", JobState::Succeeded),
+            ("failed", JobState::Failed),
+        ] {
+            store.save(&record(id, "job", state)).await.unwrap();
+        }
+
+        // A job that finishes is no longer listed
+        let mut running = record("running", "job", JobState::Running);
+        running.status.state = JobState::Failed;
+        store.save(&running).await.unwrap();
+
+        let ids: Vec<_> = store
+            .unfinished()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|record| record.status.id.0)
+            .collect();
+        assert_eq!(ids, ["queued"]);
+    }
+
+    #[tokio::test]
+    async fn keeps_jobs_in_the_database_file() {
+        let path = std::env::temp_dir().join(format!("dioxus-jobs-{}.db", uuid::Uuid::new_v4()));
+        SqliteJobStore::open(&path)
+            .unwrap()
+            .save(&record("a", "job", JobState::Retrying))
+            .await
+            .unwrap();
+
+        let reopened = SqliteJobStore::open(&path).unwrap();
+        let unfinished = reopened.unfinished().await.unwrap();
+        assert_eq!(unfinished.len(), 1);
+        assert_eq!(unfinished[0].status.id.as_str(), "a");
+
+        drop(reopened);
+        _ = std::fs::remove_file(path);
+    }
+}
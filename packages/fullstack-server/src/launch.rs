@@ -137,6 +137,11 @@ pub async fn serve_router(
 ) {
     dioxus_logger::initialize_default();
 
+    // `dx client` runs the server to read the manifest of its server functions
+    if crate::manifest::write_requested_manifest() {
+        std::process::exit(0);
+    }
+
     let listener = TcpListener::bind(addr)
         .await
         .with_context(|| format!("Failed to bind to address {addr}"))
@@ -43,6 +43,20 @@ pub use launch::serve;
 pub mod serverfn;
 pub use serverfn::*;
 
+pub mod middleware;
+pub use middleware::*;
+
+#[cfg(feature = "openapi")]
+pub mod openapi;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod jobs;
+#[cfg(not(target_arch = "wasm32"))]
+pub use jobs::*;
+
+pub mod manifest;
+pub use manifest::server_fn_manifest;
+
 pub mod isrg;
 pub use isrg::*;
 
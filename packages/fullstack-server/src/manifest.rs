@@ -0,0 +1,99 @@
+//! A machine readable description of the server functions of an app.
+//!
+//! The manifest is an OpenAPI 3.1 document. With the `openapi` feature, it is the full document from
+//! `OpenApi::document`, including the schemas of the arguments
+//! and return types. Without the feature, it only lists the paths and methods of the server
+//! functions, and `dx client` refuses to generate a client from it.
+//!
+//! `dx client` generates typed clients from this manifest. It launches the server with the
+//! [`SERVER_FN_MANIFEST_ENV`](dioxus_cli_config::SERVER_FN_MANIFEST_ENV) environment variable set,
+//! which makes [`launch`](crate::launch), [`serve`](crate::serve) and
+//! [`register_server_functions`](crate::DioxusRouterExt::register_server_functions) write the
+//! manifest to that path and exit instead of serving the app.
+
+use serde_json::Value;
+use std::path::Path;
+
+/// Build the manifest of all registered server functions.
+pub fn server_fn_manifest() -> Value {
+    let title = dioxus_cli_config::product_name()
+        .or_else(dioxus_cli_config::app_title)
+        .unwrap_or_else(|| "Server functions".to_string());
+
+    #[cfg(feature = "openapi")]
+    {
+        crate::openapi::OpenApi::new(title, "0.0.0").document()
+    }
+
+    #[cfg(not(feature = "openapi"))]
+    {
+        use crate::ServerFunction;
+        use serde_json::{json, Map};
+
+        let mut paths = Map::new();
+        for func in ServerFunction::collect() {
+            let (path, params) = openapi_path(func.path());
+            let parameters: Vec<Value> = params
+                .iter()
+                .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": true }))
+                .collect();
+            let item = paths
+                .entry(path)
+                .or_insert_with(|| Value::Object(Map::new()));
+            item[func.method().as_str().to_ascii_lowercase()] = json!({
+                "parameters": parameters,
+                "responses": { "200": { "description": "OK" } },
+            });
+        }
+
+        json!({
+            "openapi": "3.1.0",
+            "info": { "title": title, "version": "0.0.0" },
+            "paths": paths,
+        })
+    }
+}
+
+/// Write the manifest if the CLI asked for it. Returns `true` if the manifest was written and the
+/// server should exit.
+pub(crate) fn write_requested_manifest() -> bool {
+    let Some(path) = dioxus_cli_config::server_fn_manifest_path() else {
+        return false;
+    };
+
+    if let Err(err) = write_manifest(&path) {
+        tracing::error!("Failed to write the server function manifest to {path:?}: {err}");
+        std::process::exit(1);
+    }
+
+    true
+}
+
+fn write_manifest(path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let manifest = serde_json::to_vec_pretty(&server_fn_manifest())?;
+    std::fs::write(path, manifest)
+}
+
+/// Convert an axum path like `/users/{id}/{*rest}` to an OpenAPI path like `/users/{id}/{rest}`,
+/// and collect the names of the path parameters.
+pub(crate) fn openapi_path(path: &str) -> (String, Vec<String>) {
+    let mut params = Vec::new();
+    let segments = path.split('/').map(|segment| {
+        match segment
+            .strip_prefix('{')
+            .and_then(|segment| segment.strip_suffix('}'))
+        {
+            Some(name) => {
+                let name = name.trim_start_matches('*');
+                params.push(name.to_string());
+                format!("{{{name}}}")
+            }
+            None => segment.to_string(),
+        }
+    });
+    let path = segments.collect::<Vec<_>>().join("/");
+    (path, params)
+}
@@ -0,0 +1,435 @@
+use super::{client_ip, endpoint, fingerprint, DEFAULT_STORE};
+use axum::{
+    body::{Body, HttpBody},
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use bytes::Bytes;
+use dioxus_fullstack_core::HttpError;
+use futures_util::future::BoxFuture;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use http_body_util::BodyExt;
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+
+/// The header clients can set to mark retries of the same request.
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// The header set on responses that were replayed from the [`IdempotencyStore`].
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// Response bodies larger than this are not buffered, so the response is not stored.
+const MAX_BODY_SIZE: u64 = 1024 * 1024;
+
+/// A response that was stored by the [`IdempotencyLayer`].
+#[derive(Clone, Debug)]
+pub struct CachedResponse {
+    /// The status code of the response.
+    pub status: StatusCode,
+
+    /// The headers of the response, without `Set-Cookie` headers.
+    pub headers: HeaderMap,
+
+    /// The body of the response.
+    pub body: Bytes,
+}
+
+/// The state of an idempotency key, returned by [`IdempotencyStore::begin`].
+#[derive(Clone, Debug)]
+pub enum IdempotencyState {
+    /// The key wasn't used before and is now reserved for the current request.
+    New,
+
+    /// Another request with the same key is running.
+    InProgress,
+
+    /// A request with the same key completed with this response.
+    Completed(CachedResponse),
+}
+
+/// A store for the responses of idempotent server functions.
+///
+/// See the [module documentation](crate::middleware) for how to replace the default
+/// [`MemoryStore`](crate::MemoryStore).
+pub trait IdempotencyStore: Send + Sync + 'static {
+    /// Look up `key`. If the key is unused or expired, it is reserved for `ttl` and
+    /// [`IdempotencyState::New`] is returned.
+    fn begin<'a>(&'a self, key: &'a str, ttl: Duration) -> BoxFuture<'a, IdempotencyState>;
+
+    /// Store the response of the request that reserved `key` for `ttl`.
+    fn complete<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> BoxFuture<'a, ()>;
+
+    /// Release the reservation of `key` without storing a response, so the next request with the
+    /// same key runs again.
+    fn abandon<'a>(&'a self, key: &'a str) -> BoxFuture<'a, ()>;
+}
+
+/// A layer that runs repeated calls of a server function only once and replays the response to the
+/// repeated calls.
+///
+/// Calls are the same if they come from the same client and have the same `Idempotency-Key`
+/// header. Calls without the header are passed through. Clients are told apart by their
+/// `Authorization` and `Cookie` headers and their IP address.
+///
+/// A call that arrives while the first one is still running is rejected with `409 Conflict`.
+/// Responses are stored unless they have a server error status or a body larger than 1 MiB.
+/// Replayed responses carry an `Idempotent-Replayed: true` header. `Set-Cookie` headers are not
+/// stored, so sessions and other cookies are only set by the first response.
+///
+/// This layer is added by `#[server(idempotent)]`, but it can also be added to any server
+/// function with `#[middleware(IdempotencyLayer::new())]`.
+#[derive(Clone)]
+pub struct IdempotencyLayer {
+    ttl: Duration,
+    store: Option<Arc<dyn IdempotencyStore>>,
+}
+
+impl Default for IdempotencyLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdempotencyLayer {
+    /// Create a layer that keeps responses for five minutes.
+    pub fn new() -> Self {
+        Self {
+            ttl: Duration::from_secs(5 * 60),
+            store: None,
+        }
+    }
+
+    /// Set how long responses are replayed.
+    pub fn ttl(self, ttl: Duration) -> Self {
+        Self { ttl, ..self }
+    }
+
+    /// Keep the responses of this layer in `store` instead of the store provided by the router.
+    pub fn store(self, store: impl IdempotencyStore) -> Self {
+        Self {
+            store: Some(Arc::new(store)),
+            ..self
+        }
+    }
+}
+
+impl<S> Layer<S> for IdempotencyLayer {
+    type Service = Idempotency<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Idempotency {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service created by [`IdempotencyLayer`].
+#[derive(Clone)]
+pub struct Idempotency<S> {
+    inner: S,
+    layer: IdempotencyLayer,
+}
+
+impl<S> Service<Request> for Idempotency<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Take the service that was driven to readiness and leave a clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+
+        Box::pin(async move {
+            let store = layer
+                .store
+                .clone()
+                .or_else(|| {
+                    request
+                        .extensions()
+                        .get::<Arc<dyn IdempotencyStore>>()
+                        .cloned()
+                })
+                .unwrap_or_else(|| DEFAULT_STORE.clone() as Arc<dyn IdempotencyStore>);
+
+            let Some(key) = idempotency_key(&request) else {
+                return inner.call(request).await;
+            };
+
+            match store.begin(&key, layer.ttl).await {
+                IdempotencyState::New => {}
+                IdempotencyState::InProgress => {
+                    return Ok(HttpError::conflict::<()>(
+                        "A request with the same idempotency key is in progress",
+                    )
+                    .unwrap_err()
+                    .into_response())
+                }
+                IdempotencyState::Completed(cached) => return Ok(replay(cached)),
+            }
+
+            // Release the key if the request is cancelled before it completes
+            let mut reservation = Reservation {
+                store: store.clone(),
+                key: Some(key),
+            };
+
+            let response = inner.call(request).await?;
+            let key = reservation.key.take().unwrap();
+            let (parts, body) = response.into_parts();
+
+            let cacheable = !parts.status.is_server_error()
+                && parts.status != StatusCode::TOO_MANY_REQUESTS
+                && body
+                    .size_hint()
+                    .exact()
+                    .is_some_and(|size| size <= MAX_BODY_SIZE);
+            if !cacheable {
+                store.abandon(&key).await;
+                return Ok(Response::from_parts(parts, body));
+            }
+
+            let body = match body.collect().await {
+                Ok(body) => body.to_bytes(),
+                Err(err) => {
+                    store.abandon(&key).await;
+                    return Ok(HttpError::internal_server_error::<()>(format!(
+                        "Failed to read the response body: {err}"
+                    ))
+                    .unwrap_err()
+                    .into_response());
+                }
+            };
+            let mut headers = parts.headers.clone();
+            headers.remove(http::header::SET_COOKIE);
+            let cached = CachedResponse {
+                status: parts.status,
+                headers,
+                body: body.clone(),
+            };
+            store.complete(&key, cached, layer.ttl).await;
+
+            Ok(Response::from_parts(parts, Body::from(body)))
+        })
+    }
+}
+
+/// Compute the idempotency key of a request from its `Idempotency-Key` header and the client that
+/// sent it. Requests without the header have no key.
+fn idempotency_key(request: &Request) -> Option<String> {
+    let headers = request.headers();
+    let explicit = headers.get(IDEMPOTENCY_KEY)?;
+    let client = client_ip(request)
+        .map(|ip| ip.to_string())
+        .unwrap_or_default();
+    let authorization = headers
+        .get(http::header::AUTHORIZATION)
+        .map(|value| value.as_bytes())
+        .unwrap_or_default();
+    let cookie = headers
+        .get(http::header::COOKIE)
+        .map(|value| value.as_bytes())
+        .unwrap_or_default();
+    let route = format!("{}|{}", endpoint(request), request.uri());
+
+    Some(fingerprint(&[
+        route.as_bytes(),
+        client.as_bytes(),
+        authorization,
+        cookie,
+        explicit.as_bytes(),
+    ]))
+}
+
+fn replay(cached: CachedResponse) -> Response {
+    let mut response = Response::new(Body::from(cached.body));
+    *response.status_mut() = cached.status;
+    *response.headers_mut() = cached.headers;
+    response
+        .headers_mut()
+        .insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    response
+}
+
+/// Abandons the key of a request that was dropped before it completed.
+///
+/// The key is released on the current tokio runtime. Without a runtime the reservation expires
+/// with its ttl instead.
+struct Reservation {
+    store: Arc<dyn IdempotencyStore>,
+    key: Option<String>,
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            if let Ok(handle) = tokio::runtime::Handle::try_current() {
+                let store = self.store.clone();
+                handle.spawn(async move { store.abandon(&key).await });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MemoryStore;
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use tower::{service_fn, ServiceExt};
+
+    /// A service that counts its calls and responds with the call number, or fails if the body is
+    /// `fail`.
+    fn service(
+        calls: Arc<AtomicUsize>,
+    ) -> impl Service<Request, Response = Response, Error = Infallible, Future: Send> + Clone {
+        let layer = IdempotencyLayer::new().store(MemoryStore::new());
+        layer.layer(service_fn(move |request: Request| {
+            let calls = calls.clone();
+            async move {
+                let body = request.into_body().collect().await.unwrap().to_bytes();
+                let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                let status = match &body[..] {
+                    b"fail" => StatusCode::INTERNAL_SERVER_ERROR,
+                    _ => StatusCode::OK,
+                };
+                Ok::<_, Infallible>((status, call.to_string()).into_response())
+            }
+        }))
+    }
+
+    fn request(key: Option<&str>, body: &'static str) -> Request {
+        let mut request = Request::builder().method("POST").uri("/api/orders");
+        if let Some(key) = key {
+            request = request.header(IDEMPOTENCY_KEY, key);
+        }
+        request.body(Body::from(body)).unwrap()
+    }
+
+    async fn call(
+        service: &(impl Service<Request, Response = Response, Error = Infallible> + Clone),
+        request: Request,
+    ) -> (bool, String) {
+        let response = service.clone().oneshot(request).await.unwrap();
+        let replayed = response.headers().contains_key(IDEMPOTENT_REPLAYED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (replayed, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn repeated_keys_are_replayed() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let service = service(calls.clone());
+
+        assert_eq!(
+            call(&service, request(Some("a"), "")).await,
+            (false, "1".into())
+        );
+        assert_eq!(
+            call(&service, request(Some("a"), "")).await,
+            (true, "1".into())
+        );
+        assert_eq!(
+            call(&service, request(Some("b"), "")).await,
+            (false, "2".into())
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn requests_without_a_key_are_passed_through() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let service = service(calls.clone());
+
+        assert_eq!(
+            call(&service, request(None, "x")).await,
+            (false, "1".into())
+        );
+        assert_eq!(
+            call(&service, request(None, "x")).await,
+            (false, "2".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_not_stored() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let service = service(calls.clone());
+
+        assert_eq!(
+            call(&service, request(Some("a"), "fail")).await,
+            (false, "1".into())
+        );
+        assert_eq!(
+            call(&service, request(Some("a"), "fail")).await,
+            (false, "2".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn cookies_are_not_replayed() {
+        let layer = IdempotencyLayer::new().store(MemoryStore::new());
+        let service = layer.layer(service_fn(|_: Request| async {
+            Ok::<_, Infallible>(([(http::header::SET_COOKIE, "session=1")], "ok").into_response())
+        }));
+
+        let first = service
+            .clone()
+            .oneshot(request(Some("a"), ""))
+            .await
+            .unwrap();
+        assert!(first.headers().contains_key(http::header::SET_COOKIE));
+
+        let replayed = service.oneshot(request(Some("a"), "")).await.unwrap();
+        assert!(replayed.headers().contains_key(IDEMPOTENT_REPLAYED));
+        assert!(!replayed.headers().contains_key(http::header::SET_COOKIE));
+    }
+
+    #[test]
+    fn dropping_a_reservation_without_a_runtime_does_not_panic() {
+        drop(Reservation {
+            store: Arc::new(MemoryStore::new()),
+            key: Some("a".into()),
+        });
+    }
+
+    #[tokio::test]
+    async fn clients_are_told_apart() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let service = service(calls.clone());
+
+        let mut alice = request(Some("a"), "");
+        alice.headers_mut().insert(
+            http::header::AUTHORIZATION,
+            HeaderValue::from_static("alice"),
+        );
+        let mut bob = request(Some("a"), "");
+        bob.headers_mut()
+            .insert(http::header::AUTHORIZATION, HeaderValue::from_static("bob"));
+
+        assert_eq!(call(&service, alice).await, (false, "1".into()));
+        assert_eq!(call(&service, bob).await, (false, "2".into()));
+    }
+}
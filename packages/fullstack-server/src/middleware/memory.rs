@@ -0,0 +1,245 @@
+use super::{CachedResponse, IdempotencyState, IdempotencyStore, Quota, RateLimitStore};
+use dashmap::DashMap;
+use futures_util::future::BoxFuture;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+/// How many writes happen between two sweeps of expired entries.
+const SWEEP_INTERVAL: usize = 1024;
+
+enum IdempotencyEntry {
+    InProgress {
+        expires: Instant,
+    },
+    Completed {
+        response: CachedResponse,
+        expires: Instant,
+    },
+}
+
+impl IdempotencyEntry {
+    fn expires(&self) -> Instant {
+        match self {
+            Self::InProgress { expires } | Self::Completed { expires, .. } => *expires,
+        }
+    }
+}
+
+/// A [`RateLimitStore`] and [`IdempotencyStore`] that keeps its state in the memory of the server.
+///
+/// The state is lost when the server restarts and is not shared between multiple servers.
+#[derive(Default)]
+pub struct MemoryStore {
+    /// The theoretical arrival time of the next request for each rate limit key.
+    arrivals: DashMap<String, Instant>,
+    responses: DashMap<String, IdempotencyEntry>,
+    writes: AtomicUsize,
+}
+
+impl MemoryStore {
+    /// Create a new, empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check the rate limit for `key` with the generic cell rate algorithm. Every request moves the
+    /// arrival time of the next request one interval into the future. Requests are allowed while
+    /// that time is less than one period ahead.
+    fn check_at(&self, key: &str, quota: Quota, now: Instant) -> Result<(), Duration> {
+        self.sweep(now);
+
+        let interval = quota.interval();
+        let tolerance = quota.period().saturating_sub(interval);
+        let mut arrival = self.arrivals.entry(key.to_string()).or_insert(now);
+        let next = (*arrival).max(now);
+        let wait = next - now;
+        if wait > tolerance {
+            return Err(wait - tolerance);
+        }
+        *arrival = next + interval;
+        Ok(())
+    }
+
+    /// Occasionally remove entries that expired.
+    fn sweep(&self, now: Instant) {
+        if self.writes.fetch_add(1, Ordering::Relaxed) % SWEEP_INTERVAL != 0 {
+            return;
+        }
+        self.arrivals.retain(|_, arrival| *arrival > now);
+        self.responses.retain(|_, entry| entry.expires() > now);
+    }
+}
+
+impl RateLimitStore for MemoryStore {
+    fn check<'a>(&'a self, key: &'a str, quota: Quota) -> BoxFuture<'a, Result<(), Duration>> {
+        let result = self.check_at(key, quota, Instant::now());
+        Box::pin(async move { result })
+    }
+}
+
+impl IdempotencyStore for MemoryStore {
+    fn begin<'a>(&'a self, key: &'a str, ttl: Duration) -> BoxFuture<'a, IdempotencyState> {
+        let now = Instant::now();
+        self.sweep(now);
+
+        let state = match self.responses.entry(key.to_string()) {
+            dashmap::mapref::entry::Entry::Occupied(entry) if entry.get().expires() > now => {
+                match entry.get() {
+                    IdempotencyEntry::InProgress { .. } => IdempotencyState::InProgress,
+                    IdempotencyEntry::Completed { response, .. } => {
+                        IdempotencyState::Completed(response.clone())
+                    }
+                }
+            }
+            entry => {
+                entry.insert(IdempotencyEntry::InProgress { expires: now + ttl });
+                IdempotencyState::New
+            }
+        };
+        Box::pin(async move { state })
+    }
+
+    fn complete<'a>(
+        &'a self,
+        key: &'a str,
+        response: CachedResponse,
+        ttl: Duration,
+    ) -> BoxFuture<'a, ()> {
+        self.responses.insert(
+            key.to_string(),
+            IdempotencyEntry::Completed {
+                response,
+                expires: Instant::now() + ttl,
+            },
+        );
+        Box::pin(async {})
+    }
+
+    fn abandon<'a>(&'a self, key: &'a str) -> BoxFuture<'a, ()> {
+        self.responses.remove_if(key, |_, entry| {
+            matches!(entry, IdempotencyEntry::InProgress { .. })
+        });
+        Box::pin(async {})
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quota_allows_a_burst_then_spreads_requests_out() {
+        let store = MemoryStore::new();
+        let quota = Quota::new(3, Duration::from_secs(3));
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(store.check_at("client", quota, start), Ok(()));
+        }
+        assert_eq!(
+            store.check_at("client", quota, start),
+            Err(Duration::from_secs(1))
+        );
+
+        // One request becomes available every interval
+        let later = start + Duration::from_millis(500);
+        assert_eq!(
+            store.check_at("client", quota, later),
+            Err(Duration::from_millis(500))
+        );
+        let later = start + Duration::from_secs(1);
+        assert_eq!(store.check_at("client", quota, later), Ok(()));
+        assert!(store.check_at("client", quota, later).is_err());
+
+        // The full quota is available again after a quiet period
+        let later = start + Duration::from_secs(10);
+        for _ in 0..3 {
+            assert_eq!(store.check_at("client", quota, later), Ok(()));
+        }
+    }
+
+    #[test]
+    fn rejected_requests_are_not_recorded() {
+        let store = MemoryStore::new();
+        let quota = Quota::per_second(1);
+        let start = Instant::now();
+
+        assert_eq!(store.check_at("client", quota, start), Ok(()));
+        for _ in 0..10 {
+            assert!(store.check_at("client", quota, start).is_err());
+        }
+        assert_eq!(
+            store.check_at("client", quota, start + Duration::from_secs(1)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn keys_have_separate_quotas() {
+        let store = MemoryStore::new();
+        let quota = Quota::per_minute(1);
+        let now = Instant::now();
+
+        assert_eq!(store.check_at("a", quota, now), Ok(()));
+        assert!(store.check_at("a", quota, now).is_err());
+        assert_eq!(store.check_at("b", quota, now), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn idempotency_keys_are_reserved_until_completed_or_abandoned() {
+        let store = MemoryStore::new();
+        let ttl = Duration::from_secs(60);
+
+        assert!(matches!(
+            store.begin("key", ttl).await,
+            IdempotencyState::New
+        ));
+        assert!(matches!(
+            store.begin("key", ttl).await,
+            IdempotencyState::InProgress
+        ));
+
+        store.abandon("key").await;
+        assert!(matches!(
+            store.begin("key", ttl).await,
+            IdempotencyState::New
+        ));
+
+        let response = CachedResponse {
+            status: http::StatusCode::CREATED,
+            headers: Default::default(),
+            body: "created".into(),
+        };
+        store.complete("key", response, ttl).await;
+        match store.begin("key", ttl).await {
+            IdempotencyState::Completed(cached) => {
+                assert_eq!(cached.status, http::StatusCode::CREATED);
+                assert_eq!(cached.body, "created");
+            }
+            _ => panic!("expected the completed response"),
+        }
+
+        // Completed responses are not abandoned
+        store.abandon("key").await;
+        assert!(matches!(
+            store.begin("key", ttl).await,
+            IdempotencyState::Completed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn idempotency_keys_expire() {
+        let store = MemoryStore::new();
+
+        assert!(matches!(
+            store.begin("key", Duration::ZERO).await,
+            IdempotencyState::New
+        ));
+        assert!(matches!(
+            store.begin("key", Duration::from_secs(60)).await,
+            IdempotencyState::New
+        ));
+    }
+}
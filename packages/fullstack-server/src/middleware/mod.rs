@@ -0,0 +1,189 @@
+//! Built-in layers for server functions.
+//!
+//! These layers can be added to any server function with `#[middleware(...)]`, or configured
+//! directly in the `#[server]` macro:
+//!
+//! ```rust, ignore
+//! // Allow ten calls per minute from each IP address and replay the response of repeated calls
+//! #[server(rate_limit = "10/min", idempotent)]
+//! async fn create_order(order: Order) -> Result<OrderId> {
+//!     // ...
+//! }
+//! ```
+//!
+//! Both layers keep their state in a store that is shared between all server functions. By default,
+//! this is a [`MemoryStore`]. To share the state between multiple servers, implement
+//! [`RateLimitStore`] and [`IdempotencyStore`] for a shared database and add it to the router as an
+//! extension:
+//!
+//! ```rust, ignore
+//! let store = Arc::new(RedisStore::new(client));
+//! let router = axum::Router::new()
+//!     .serve_dioxus_application(ServeConfig::new(), app)
+//!     .layer(axum::Extension(store.clone() as Arc<dyn RateLimitStore>))
+//!     .layer(axum::Extension(store as Arc<dyn IdempotencyStore>));
+//! ```
+//!
+//! Clients are told apart by the address of their connection, which requires serving the router
+//! with `into_make_service_with_connect_info::<SocketAddr>()`. Behind a reverse proxy, every
+//! connection comes from the proxy. Opt into reading the address from the headers set by the proxy
+//! with [`ClientIpSource::ProxyHeaders`]:
+//!
+//! ```rust, ignore
+//! let router = axum::Router::new()
+//!     .serve_dioxus_application(ServeConfig::new(), app)
+//!     .layer(axum::Extension(ClientIpSource::ProxyHeaders));
+//! ```
+
+mod idempotency;
+mod memory;
+mod rate_limit;
+
+pub use idempotency::*;
+pub use memory::*;
+pub use rate_limit::*;
+
+use axum::extract::{ConnectInfo, MatchedPath, Request};
+use sha2::{Digest, Sha256};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, LazyLock, Once},
+};
+
+/// The store used when the router doesn't provide one.
+static DEFAULT_STORE: LazyLock<Arc<MemoryStore>> = LazyLock::new(Default::default);
+
+/// Where the layers read the IP address of the client from. Add it to the router as an extension to
+/// change it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClientIpSource {
+    /// The address of the connection. This requires serving the router with
+    /// `into_make_service_with_connect_info::<SocketAddr>()`.
+    #[default]
+    ConnectInfo,
+
+    /// The last address in the `X-Forwarded-For` header, or the `X-Real-IP` header, which are set
+    /// by the reverse proxy in front of the server.
+    ///
+    /// Only use this behind a proxy that sets these headers. Otherwise clients can send any address
+    /// they want and get a fresh rate limit with every request.
+    ProxyHeaders,
+}
+
+/// The IP address of the client that sent the request. See [`ClientIpSource`].
+pub(crate) fn client_ip(request: &Request) -> Option<IpAddr> {
+    let source = request
+        .extensions()
+        .get::<ClientIpSource>()
+        .copied()
+        .unwrap_or_default();
+
+    if source == ClientIpSource::ProxyHeaders {
+        let headers = request.headers();
+        // Proxies append the address they received the request from, so the last entry is the
+        // only one that wasn't written by the client
+        let forwarded = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .next_back();
+        let ip = forwarded
+            .or_else(|| {
+                headers
+                    .get("x-real-ip")
+                    .and_then(|value| value.to_str().ok())
+            })
+            .and_then(|value| value.trim().parse().ok());
+        if ip.is_some() {
+            return ip;
+        }
+    }
+
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(address)) => Some(address.ip()),
+        None => {
+            static WARN: Once = Once::new();
+            WARN.call_once(|| {
+                tracing::warn!(
+                    "The IP address of the client is unknown, so all clients share one rate limit. \
+                     Serve the router with `into_make_service_with_connect_info::<SocketAddr>()` or \
+                     add `ClientIpSource::ProxyHeaders` as an extension behind a reverse proxy."
+                )
+            });
+            None
+        }
+    }
+}
+
+/// The route the request matched, which is shared by all calls of a server function.
+pub(crate) fn endpoint(request: &Request) -> String {
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str())
+        .unwrap_or_else(|| request.uri().path());
+    format!("{} {}", request.method(), path)
+}
+
+/// The hex encoded SHA-256 digest of `parts`. Stores only ever see fingerprints, so secrets like
+/// authorization headers are not stored in plain text. The digest is stable, so servers built with
+/// different versions of Rust can share a store.
+pub(crate) fn fingerprint(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        // Prefix every part with its length so the boundaries between parts can't be moved
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::body::Body;
+
+    fn request(headers: &[(&str, &str)]) -> Request {
+        let mut request = Request::builder().uri("/api/test");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn proxy_headers_are_ignored_by_default() {
+        let mut request = request(&[("x-forwarded-for", "1.2.3.4"), ("x-real-ip", "1.2.3.4")]);
+        assert_eq!(client_ip(&request), None);
+
+        let connection: SocketAddr = "10.0.0.1:1234".parse().unwrap();
+        request.extensions_mut().insert(ConnectInfo(connection));
+        assert_eq!(client_ip(&request), Some(connection.ip()));
+    }
+
+    #[test]
+    fn proxy_headers_are_opt_in() {
+        let mut forwarded = request(&[("x-forwarded-for", "6.6.6.6, 1.2.3.4")]);
+        forwarded
+            .extensions_mut()
+            .insert(ClientIpSource::ProxyHeaders);
+        assert_eq!(client_ip(&forwarded), "1.2.3.4".parse().ok());
+
+        let mut real_ip = request(&[("x-real-ip", "5.6.7.8")]);
+        real_ip
+            .extensions_mut()
+            .insert(ClientIpSource::ProxyHeaders);
+        assert_eq!(client_ip(&real_ip), "5.6.7.8".parse().ok());
+    }
+
+    #[test]
+    fn fingerprints_are_sha256_of_the_parts() {
+        assert_eq!(
+            fingerprint(&[]),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_ne!(fingerprint(&[b"ab", b"c"]), fingerprint(&[b"a", b"bc"]));
+        assert_eq!(fingerprint(&[b"secret"]).len(), 64);
+    }
+}
@@ -0,0 +1,278 @@
+use super::{client_ip, endpoint, fingerprint, DEFAULT_STORE};
+use axum::{
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use dioxus_fullstack_core::HttpError;
+use futures_util::future::BoxFuture;
+use http::{header::RETRY_AFTER, HeaderName, HeaderValue};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+
+/// The number of requests allowed in a period of time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quota {
+    requests: u32,
+    period: Duration,
+}
+
+impl Quota {
+    /// Allow `requests` requests every `period`. Requests are spread out evenly, so once the quota is
+    /// used up, one more request is allowed every `period / requests`.
+    pub const fn new(requests: u32, period: Duration) -> Self {
+        Self { requests, period }
+    }
+
+    /// Allow `requests` requests per second.
+    pub const fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    /// Allow `requests` requests per minute.
+    pub const fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    /// Allow `requests` requests per hour.
+    pub const fn per_hour(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60 * 60))
+    }
+
+    /// The number of requests allowed per period.
+    pub fn requests(&self) -> u32 {
+        self.requests
+    }
+
+    /// The length of the period.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// The time it takes for one request of the quota to become available again.
+    pub fn interval(&self) -> Duration {
+        self.period / self.requests.max(1)
+    }
+}
+
+/// A store that keeps track of how many requests each client made.
+///
+/// See the [module documentation](crate::middleware) for how to replace the default
+/// [`MemoryStore`](crate::MemoryStore).
+pub trait RateLimitStore: Send + Sync + 'static {
+    /// Record a request for `key`. If the quota for `key` is used up, the request is not recorded
+    /// and the time until the next request is allowed is returned.
+    fn check<'a>(&'a self, key: &'a str, quota: Quota) -> BoxFuture<'a, Result<(), Duration>>;
+}
+
+/// How the requests of different clients are told apart.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum RateLimitKey {
+    /// Every IP address has its own quota. See [`ClientIpSource`](crate::ClientIpSource) for where
+    /// the address comes from.
+    #[default]
+    Ip,
+
+    /// All clients share one quota.
+    Global,
+
+    /// Every value of the header has its own quota, for example an API key or the `Authorization`
+    /// header. Requests without the header are limited by IP address.
+    Header(HeaderName),
+
+    /// Every value of the cookie has its own quota, for example a session id. Requests without the
+    /// cookie are limited by IP address.
+    Cookie(String),
+}
+
+impl RateLimitKey {
+    /// Limit requests per value of the header `name`.
+    pub fn header(name: &'static str) -> Self {
+        Self::Header(HeaderName::from_static(name))
+    }
+
+    /// Limit requests per value of the cookie `name`.
+    pub fn cookie(name: impl Into<String>) -> Self {
+        Self::Cookie(name.into())
+    }
+
+    fn key(&self, request: &Request) -> String {
+        let ip = || match client_ip(request) {
+            Some(ip) => format!("ip:{ip}"),
+            None => "ip:unknown".to_string(),
+        };
+
+        match self {
+            Self::Ip => ip(),
+            Self::Global => "global".to_string(),
+            Self::Header(name) => match request.headers().get(name) {
+                Some(value) => format!("header:{}", fingerprint(&[value.as_bytes()])),
+                None => ip(),
+            },
+            Self::Cookie(name) => {
+                let value = request
+                    .headers()
+                    .get_all(http::header::COOKIE)
+                    .iter()
+                    .filter_map(|header| header.to_str().ok())
+                    .flat_map(|header| header.split(';'))
+                    .filter_map(|cookie| cookie.trim().split_once('='))
+                    .find(|(cookie, _)| cookie == name)
+                    .map(|(_, value)| value);
+                match value {
+                    Some(value) => format!("cookie:{}", fingerprint(&[value.as_bytes()])),
+                    None => ip(),
+                }
+            }
+        }
+    }
+}
+
+/// A layer that limits how often each client can call a server function.
+///
+/// Requests over the quota are rejected with `429 Too Many Requests` and a `Retry-After` header.
+/// This layer is added by `#[server(rate_limit = "10/min")]`, but it can also be added to any
+/// server function with `#[middleware(RateLimitLayer::new(Quota::per_minute(10)))]`.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    quota: Quota,
+    key: RateLimitKey,
+    store: Option<Arc<dyn RateLimitStore>>,
+}
+
+impl RateLimitLayer {
+    /// Limit each IP address to `quota`.
+    pub fn new(quota: Quota) -> Self {
+        Self {
+            quota,
+            key: RateLimitKey::Ip,
+            store: None,
+        }
+    }
+
+    /// Set how the requests of different clients are told apart. Defaults to [`RateLimitKey::Ip`].
+    pub fn key(self, key: RateLimitKey) -> Self {
+        Self { key, ..self }
+    }
+
+    /// Keep the state of this layer in `store` instead of the store provided by the router.
+    pub fn store(self, store: impl RateLimitStore) -> Self {
+        Self {
+            store: Some(Arc::new(store)),
+            ..self
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service created by [`RateLimitLayer`].
+#[derive(Clone)]
+pub struct RateLimit<S> {
+    inner: S,
+    layer: RateLimitLayer,
+}
+
+impl<S> Service<Request> for RateLimit<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Take the service that was driven to readiness and leave a clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+
+        Box::pin(async move {
+            let store = layer
+                .store
+                .clone()
+                .or_else(|| {
+                    request
+                        .extensions()
+                        .get::<Arc<dyn RateLimitStore>>()
+                        .cloned()
+                })
+                .unwrap_or_else(|| DEFAULT_STORE.clone() as Arc<dyn RateLimitStore>);
+            let key = format!("{}|{}", endpoint(&request), layer.key.key(&request));
+
+            if let Err(retry_after) = store.check(&key, layer.quota).await {
+                let mut response = HttpError::too_many_requests::<()>("Rate limit exceeded")
+                    .unwrap_err()
+                    .into_response();
+                // Round up so clients never retry too early
+                let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+                response
+                    .headers_mut()
+                    .insert(RETRY_AFTER, HeaderValue::from(seconds));
+                return Ok(response);
+            }
+
+            inner.call(request).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ClientIpSource, MemoryStore};
+    use axum::body::Body;
+    use http::StatusCode;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt};
+
+    fn request(ip: &str) -> Request {
+        let mut request = Request::builder()
+            .uri("/api/orders")
+            .header("x-forwarded-for", ip)
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ClientIpSource::ProxyHeaders);
+        request
+    }
+
+    #[tokio::test]
+    async fn requests_over_the_quota_are_rejected() {
+        let layer = RateLimitLayer::new(Quota::per_minute(2)).store(MemoryStore::new());
+        let service = layer.layer(service_fn(|_: Request| async {
+            Ok::<_, Infallible>(StatusCode::OK.into_response())
+        }));
+
+        for _ in 0..2 {
+            let response = service.clone().oneshot(request("1.2.3.4")).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = service.clone().oneshot(request("1.2.3.4")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "30");
+
+        // Other clients have their own quota
+        let response = service.clone().oneshot(request("5.6.7.8")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,673 @@
+//! Generate an OpenAPI 3.1 document that describes the registered server functions.
+//!
+//! When the `openapi` feature is enabled, every `#[get]`, `#[post]`, `#[server]`, etc. function
+//! registers an [`Operation`] along with its [`ServerFunction`]. The operation is built from the
+//! route, the arguments and return type of the function, and the OpenAPI options passed to the macro:
+//!
+//! ```rust, ignore
+//! /// Get a user by id
+//! ///
+//! /// Returns the full profile of the user.
+//! #[get("/api/users/{id}?fields" { tags: ["users"], responses: { 404: NotFound } })]
+//! async fn get_user(id: u32, fields: Option<String>) -> Result<User> {
+//!     todo!()
+//! }
+//! ```
+//!
+//! Argument and return types that implement [`JsonSchema`] are described with their schema. Other
+//! types, like websockets or streams, are described as accepting any value.
+//!
+//! The operation id defaults to the name of the function, the summary to the first paragraph of its
+//! doc comment and the description to the rest of the doc comment. The request body is a JSON object
+//! with a field for every argument, unless the only argument is a payload like `Cbor<T>` or
+//! `MultipartFormData`, which is described with its own content type.
+//!
+//! The document is served with [`DioxusRouterExt::serve_openapi`](crate::DioxusRouterExt::serve_openapi)
+//! or built directly with [`OpenApi::document`].
+
+use crate::{manifest::openapi_path, ServerFunction};
+use schemars::generate::SchemaSettings;
+use serde_json::{json, Map, Value};
+use std::{collections::BTreeMap, collections::HashSet, marker::PhantomData};
+
+pub use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+/// The OpenAPI version of the generated document.
+const OPENAPI_VERSION: &str = "3.1.0";
+
+/// The location of shared schemas in the generated document.
+const SCHEMAS_PATH: &str = "/components/schemas";
+
+/// The top level information of an OpenAPI document.
+///
+/// # Example
+/// ```rust, no_run
+/// use dioxus_server::openapi::OpenApi;
+///
+/// let document = OpenApi::new("My API", "1.0.0")
+///     .description("The API of my app")
+///     .server("https://example.com")
+///     .document();
+/// println!("{}", serde_json::to_string_pretty(&document).unwrap());
+/// ```
+#[derive(Clone, Debug)]
+pub struct OpenApi {
+    title: String,
+    version: String,
+    description: Option<String>,
+    servers: Vec<String>,
+    security_schemes: Map<String, Value>,
+}
+
+impl OpenApi {
+    /// Create a new document with the given title and API version.
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            servers: Vec::new(),
+            security_schemes: Map::new(),
+        }
+    }
+
+    /// Set the description of the API.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a server the API is served from.
+    pub fn server(mut self, url: impl Into<String>) -> Self {
+        self.servers.push(url.into());
+        self
+    }
+
+    /// Add a security scheme that operations can refer to with the `security` option.
+    ///
+    /// The scheme is an OpenAPI [security scheme object](https://spec.openapis.org/oas/v3.1.0#security-scheme-object),
+    /// for example `json!({ "type": "http", "scheme": "bearer" })`.
+    pub fn security_scheme(mut self, name: impl Into<String>, scheme: Value) -> Self {
+        self.security_schemes.insert(name.into(), scheme);
+        self
+    }
+
+    /// Build the document from all registered server functions.
+    pub fn document(&self) -> Value {
+        let mut generator = schema_generator();
+        let mut paths = Map::new();
+        let mut seen = HashSet::new();
+
+        for func in ServerFunction::collect() {
+            if !seen.insert(format!("{} {}", func.method(), func.path())) {
+                continue;
+            }
+
+            let operation = func.openapi_operation(&mut generator).unwrap_or_default();
+            if operation.hidden {
+                continue;
+            }
+
+            let (path, path_params) = openapi_path(func.path());
+            let item = paths
+                .entry(path)
+                .or_insert_with(|| Value::Object(Map::new()));
+            item[func.method().as_str().to_ascii_lowercase()] = operation.into_value(&path_params);
+        }
+
+        let mut info = json!({
+            "title": self.title,
+            "version": self.version,
+        });
+        if let Some(description) = &self.description {
+            info["description"] = json!(description);
+        }
+
+        let mut components = json!({
+            "schemas": generator.take_definitions(true),
+        });
+        if !self.security_schemes.is_empty() {
+            components["securitySchemes"] = Value::Object(self.security_schemes.clone());
+        }
+
+        let mut document = json!({
+            "openapi": OPENAPI_VERSION,
+            "info": info,
+            "paths": paths,
+            "components": components,
+        });
+        if !self.servers.is_empty() {
+            document["servers"] = self
+                .servers
+                .iter()
+                .map(|url| json!({ "url": url }))
+                .collect();
+        }
+
+        document
+    }
+}
+
+/// A schema generator that places shared schemas in the components of the document.
+fn schema_generator() -> SchemaGenerator {
+    let mut settings = SchemaSettings::draft2020_12();
+    settings.definitions_path = SCHEMAS_PATH.into();
+    settings.meta_schema = None;
+    settings.into_generator()
+}
+
+/// The description of one server function in the OpenAPI document.
+///
+/// Operations are created by the server function macros. The `transform` option of the macro
+/// receives the generated operation and can modify it further:
+///
+/// ```rust, ignore
+/// #[post("/api/login" { transform: |op| op.tag("auth").response(401, None) })]
+/// async fn login(username: String, password: String) -> Result<()> {
+///     todo!()
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Operation {
+    id: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    tags: Vec<String>,
+    hidden: bool,
+    security: Vec<(String, Vec<String>)>,
+    parameters: Vec<Parameter>,
+    body: Vec<BodyField>,
+    body_content_type: Option<String>,
+    responses: BTreeMap<u16, Option<Value>>,
+}
+
+#[derive(Clone, Debug)]
+struct Parameter {
+    name: String,
+    location: &'static str,
+    schema: Value,
+    required: bool,
+}
+
+#[derive(Clone, Debug)]
+struct BodyField {
+    name: String,
+    schema: Value,
+    required: bool,
+}
+
+impl Operation {
+    /// Create an empty operation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the unique id of the operation. Defaults to the name of the server function.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set the summary of the operation. Defaults to the first paragraph of the doc comment.
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Set the description of the operation. Defaults to the rest of the doc comment.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a tag to the operation.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Leave the operation out of the document.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Require the security scheme `scheme` with the given scopes for this operation.
+    pub fn security(mut self, scheme: impl Into<String>, scopes: &[&str]) -> Self {
+        let scopes = scopes.iter().map(|scope| scope.to_string()).collect();
+        self.security.push((scheme.into(), scopes));
+        self
+    }
+
+    /// Add a path parameter. A missing schema accepts any value.
+    pub fn path_parameter(mut self, name: impl Into<String>, schema: Option<Schema>) -> Self {
+        self.parameters.push(Parameter {
+            name: name.into(),
+            location: "path",
+            schema: schema_or_any(schema),
+            required: true,
+        });
+        self
+    }
+
+    /// Add a query parameter. A missing schema accepts any value.
+    pub fn query_parameter(
+        mut self,
+        name: impl Into<String>,
+        schema: Option<Schema>,
+        required: bool,
+    ) -> Self {
+        self.parameters.push(Parameter {
+            name: name.into(),
+            location: "query",
+            schema: schema_or_any(schema),
+            required,
+        });
+        self
+    }
+
+    /// Add a query parameter for every property of an object schema. This describes a query that
+    /// is extracted into a single struct.
+    pub fn query_parameters(mut self, schema: Option<Schema>, generator: &SchemaGenerator) -> Self {
+        let Some(object) = schema.and_then(|schema| resolve_object(schema.into(), generator))
+        else {
+            return self;
+        };
+
+        let required: HashSet<&str> = object
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let properties = object.get("properties").and_then(Value::as_object);
+        for (name, schema) in properties.into_iter().flatten() {
+            self.parameters.push(Parameter {
+                name: name.clone(),
+                location: "query",
+                schema: schema.clone(),
+                required: required.contains(name.as_str()),
+            });
+        }
+        self
+    }
+
+    /// Add a field to the JSON object in the request body. A missing schema accepts any value.
+    pub fn body_field(
+        mut self,
+        name: impl Into<String>,
+        schema: Option<Schema>,
+        required: bool,
+    ) -> Self {
+        self.body.push(BodyField {
+            name: name.into(),
+            schema: schema_or_any(schema),
+            required,
+        });
+        self
+    }
+
+    /// Set the content type of the request body. Defaults to `application/json`, where the body is
+    /// an object with a field for every argument. Other content types are used by server functions
+    /// with a single argument that is sent as is, like `Cbor<T>` or `MultipartFormData`.
+    pub fn body_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.body_content_type = Some(content_type.into());
+        self
+    }
+
+    /// Add a response with the given status code. A missing schema leaves the content of the
+    /// response undescribed.
+    pub fn response(mut self, status: u16, schema: Option<Schema>) -> Self {
+        self.responses.insert(status, schema.map(Value::from));
+        self
+    }
+
+    /// Convert the operation into an OpenAPI operation object. Path parameters that weren't
+    /// described by the operation are added so the document stays valid.
+    fn into_value(self, path_params: &[String]) -> Value {
+        let mut operation = Map::new();
+        if let Some(id) = self.id {
+            operation.insert("operationId".into(), json!(id));
+        }
+        if let Some(summary) = self.summary {
+            operation.insert("summary".into(), json!(summary));
+        }
+        if let Some(description) = self.description {
+            operation.insert("description".into(), json!(description));
+        }
+        if !self.tags.is_empty() {
+            operation.insert("tags".into(), json!(self.tags));
+        }
+        if !self.security.is_empty() {
+            let security: Vec<Value> = self
+                .security
+                .into_iter()
+                .map(|(scheme, scopes)| {
+                    let mut requirement = Map::new();
+                    requirement.insert(scheme, json!(scopes));
+                    Value::Object(requirement)
+                })
+                .collect();
+            operation.insert("security".into(), json!(security));
+        }
+
+        let mut parameters = self.parameters;
+        for name in path_params {
+            let described = parameters
+                .iter()
+                .any(|param| param.location == "path" && &param.name == name);
+            if !described {
+                parameters.push(Parameter {
+                    name: name.clone(),
+                    location: "path",
+                    schema: json!(true),
+                    required: true,
+                });
+            }
+        }
+        if !parameters.is_empty() {
+            let parameters: Vec<Value> = parameters
+                .into_iter()
+                .map(|param| {
+                    json!({
+                        "name": param.name,
+                        "in": param.location,
+                        "required": param.required,
+                        "schema": param.schema,
+                    })
+                })
+                .collect();
+            operation.insert("parameters".into(), json!(parameters));
+        }
+
+        let body_content_type = self
+            .body_content_type
+            .filter(|content_type| content_type != "application/json");
+        if let (false, Some(content_type)) = (self.body.is_empty(), body_content_type) {
+            let mut content = Map::new();
+            content.insert(content_type, json!({}));
+            operation.insert(
+                "requestBody".into(),
+                json!({ "required": true, "content": content }),
+            );
+        } else if !self.body.is_empty() {
+            let required: Vec<&str> = self
+                .body
+                .iter()
+                .filter(|field| field.required)
+                .map(|field| field.name.as_str())
+                .collect();
+            let properties: Map<String, Value> = self
+                .body
+                .iter()
+                .map(|field| (field.name.clone(), field.schema.clone()))
+                .collect();
+            operation.insert(
+                "requestBody".into(),
+                json!({
+                    "required": true,
+                    "content": {
+                        "application/json": {
+                            "schema": {
+                                "type": "object",
+                                "properties": properties,
+                                "required": required,
+                            }
+                        }
+                    }
+                }),
+            );
+        }
+
+        let mut responses = self.responses;
+        if responses.is_empty() {
+            responses.insert(200, None);
+        }
+        let responses: Map<String, Value> = responses
+            .into_iter()
+            .map(|(status, schema)| {
+                let description = http::StatusCode::from_u16(status)
+                    .ok()
+                    .and_then(|status| status.canonical_reason())
+                    .unwrap_or("Response");
+                let mut response = json!({ "description": description });
+                if let Some(schema) = schema {
+                    response["content"] = json!({ "application/json": { "schema": schema } });
+                }
+                (status.to_string(), response)
+            })
+            .collect();
+        operation.insert("responses".into(), Value::Object(responses));
+
+        Value::Object(operation)
+    }
+}
+
+fn schema_or_any(schema: Option<Schema>) -> Value {
+    schema.map(Value::from).unwrap_or(json!(true))
+}
+
+/// Follow a reference to a shared schema and return the schema if it describes an object.
+fn resolve_object(schema: Value, generator: &SchemaGenerator) -> Option<Map<String, Value>> {
+    let schema = match schema.get("$ref").and_then(Value::as_str) {
+        Some(reference) => {
+            let name = reference.strip_prefix(&format!("#{SCHEMAS_PATH}/"))?;
+            generator.definitions().get(name)?.clone()
+        }
+        None => schema,
+    };
+    match schema {
+        Value::Object(object) if object.contains_key("properties") => Some(object),
+        _ => None,
+    }
+}
+
+/// Selects the schema of `T` if it implements [`JsonSchema`]. Used by the server function macros
+/// through autoref specialization, so types without a schema are still accepted:
+///
+/// ```rust, ignore
+/// use dioxus_server::openapi::{SchemaOf, WithSchema as _, WithoutSchema as _};
+/// let schema = (&&SchemaOf::<T>::new()).schema(&mut generator);
+/// ```
+#[doc(hidden)]
+pub struct SchemaOf<T>(PhantomData<fn() -> T>);
+
+impl<T> SchemaOf<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait WithSchema {
+    fn schema(&self, generator: &mut SchemaGenerator) -> Option<Schema>;
+}
+
+impl<T: JsonSchema> WithSchema for &SchemaOf<T> {
+    fn schema(&self, generator: &mut SchemaGenerator) -> Option<Schema> {
+        Some(generator.subschema_for::<T>())
+    }
+}
+
+#[doc(hidden)]
+pub trait WithoutSchema {
+    fn schema(&self, _generator: &mut SchemaGenerator) -> Option<Schema> {
+        None
+    }
+}
+
+impl<T> WithoutSchema for SchemaOf<T> {}
+
+/// The schema of the successful response of a server function that returns `Result<T, E>`.
+#[doc(hidden)]
+pub trait WithResponseSchema {
+    fn response_schema(&self, generator: &mut SchemaGenerator) -> Option<Schema>;
+}
+
+impl<T: JsonSchema, E> WithResponseSchema for &SchemaOf<Result<T, E>> {
+    fn response_schema(&self, generator: &mut SchemaGenerator) -> Option<Schema> {
+        Some(generator.subschema_for::<T>())
+    }
+}
+
+#[doc(hidden)]
+pub trait WithoutResponseSchema {
+    fn response_schema(&self, _generator: &mut SchemaGenerator) -> Option<Schema> {
+        None
+    }
+}
+
+impl<T> WithoutResponseSchema for SchemaOf<T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Filter {
+        name: String,
+        limit: Option<u32>,
+    }
+
+    #[test]
+    fn converts_axum_paths() {
+        assert_eq!(
+            openapi_path("/api/users"),
+            ("/api/users".to_string(), vec![])
+        );
+        assert_eq!(
+            openapi_path("/api/users/{id}/files/{*path}"),
+            (
+                "/api/users/{id}/files/{path}".to_string(),
+                vec!["id".to_string(), "path".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn describes_operations() {
+        let mut generator = schema_generator();
+        let operation = Operation::new()
+            .id("get_user")
+            .summary("Get a user by id")
+            .description("Returns the full profile of the user.")
+            .tag("users")
+            .security("bearer", &["read"])
+            .path_parameter("id", Some(generator.subschema_for::<String>()))
+            .query_parameter("fields", Some(generator.subschema_for::<String>()), false)
+            .response(200, Some(generator.subschema_for::<String>()))
+            .response(404, None)
+            .into_value(&["id".to_string()]);
+
+        assert_eq!(
+            operation,
+            json!({
+                "operationId": "get_user",
+                "summary": "Get a user by id",
+                "description": "Returns the full profile of the user.",
+                "tags": ["users"],
+                "security": [{ "bearer": ["read"] }],
+                "parameters": [
+                    {
+                        "name": "id",
+                        "in": "path",
+                        "required": true,
+                        "schema": { "type": "string" },
+                    },
+                    {
+                        "name": "fields",
+                        "in": "query",
+                        "required": false,
+                        "schema": { "type": "string" },
+                    },
+                ],
+                "responses": {
+                    "200": {
+                        "description": "OK",
+                        "content": { "application/json": { "schema": { "type": "string" } } },
+                    },
+                    "404": { "description": "Not Found" },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn adds_undescribed_path_parameters_and_a_default_response() {
+        let operation = Operation::new().into_value(&["id".to_string()]);
+        assert_eq!(
+            operation,
+            json!({
+                "parameters": [{ "name": "id", "in": "path", "required": true, "schema": true }],
+                "responses": { "200": { "description": "OK" } },
+            })
+        );
+    }
+
+    #[test]
+    fn describes_json_bodies_as_objects() {
+        let mut generator = schema_generator();
+        let operation = Operation::new()
+            .body_field("name", Some(generator.subschema_for::<String>()), true)
+            .body_field("note", None, false)
+            .into_value(&[]);
+
+        assert_eq!(
+            operation["requestBody"],
+            json!({
+                "required": true,
+                "content": {
+                    "application/json": {
+                        "schema": {
+                            "type": "object",
+                            "properties": {
+                                "name": { "type": "string" },
+                                "note": true,
+                            },
+                            "required": ["name"],
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn describes_bodies_with_other_content_types() {
+        let operation = Operation::new()
+            .body_field("data", None, true)
+            .body_content_type("application/cbor")
+            .into_value(&[]);
+
+        assert_eq!(
+            operation["requestBody"],
+            json!({ "required": true, "content": { "application/cbor": {} } })
+        );
+    }
+
+    #[test]
+    fn expands_query_structs_into_parameters() {
+        let mut generator = schema_generator();
+        let schema = generator.subschema_for::<Filter>();
+        let operation = Operation::new()
+            .query_parameters(Some(schema), &generator)
+            .into_value(&[]);
+
+        let mut parameters: Vec<_> = operation["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|param| {
+                (
+                    param["name"].as_str().unwrap(),
+                    param["required"].as_bool().unwrap(),
+                )
+            })
+            .collect();
+        parameters.sort();
+        assert_eq!(parameters, [("limit", false), ("name", true)]);
+    }
+}
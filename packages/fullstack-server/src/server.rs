@@ -93,6 +93,53 @@ pub trait DioxusRouterExt {
     #[allow(dead_code)]
     fn register_server_functions(self) -> Router<FullstackState>;
 
+    /// Serves an OpenAPI 3.1 JSON document that describes all registered server functions at `path`.
+    ///
+    /// The document is generated once from the server functions compiled into the binary. See the
+    /// [`openapi`](crate::openapi) module for how server functions are described.
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_server::{DioxusRouterExt, openapi::OpenApi};
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let addr = dioxus::cli_config::fullstack_address_or_localhost();
+    ///     let router = axum::Router::new()
+    ///         .register_server_functions()
+    ///         // Serve the document at /openapi.json
+    ///         .serve_openapi("/openapi.json", OpenApi::new("My API", "1.0.0"))
+    ///         .with_state(dioxus_server::FullstackState::headless());
+    ///     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    ///     axum::serve(listener, router).await.unwrap();
+    /// }
+    /// ```
+    #[cfg(feature = "openapi")]
+    fn serve_openapi(self, path: &str, openapi: crate::openapi::OpenApi) -> Router<FullstackState>;
+
+    /// Serves the status of background jobs at `/api/jobs/{id}` and streams its changes at
+    /// `/api/jobs/{id}/events`.
+    ///
+    /// Only apps that enqueue [jobs](crate::jobs) need these routes, so they are not added by
+    /// [`DioxusRouterExt::register_server_functions`].
+    ///
+    /// # Example
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # use dioxus_server::DioxusRouterExt;
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let addr = dioxus::cli_config::fullstack_address_or_localhost();
+    ///     let router = axum::Router::new()
+    ///         .register_server_functions()
+    ///         .serve_jobs()
+    ///         .with_state(dioxus_server::FullstackState::headless());
+    ///     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    ///     axum::serve(listener, router).await.unwrap();
+    /// }
+    /// ```
+    fn serve_jobs(self) -> Router<FullstackState>;
+
     /// Serves a Dioxus application without static assets.
     /// Sets up server function routes and rendering endpoints only.
     ///
@@ -127,21 +174,77 @@ pub trait DioxusRouterExt {
 #[cfg(not(target_arch = "wasm32"))]
 impl DioxusRouterExt for Router<FullstackState> {
     fn register_server_functions(mut self) -> Router<FullstackState> {
-        use std::collections::HashSet;
+        use std::collections::HashMap;
 
-        let mut seen = HashSet::new();
+        // `dx client` runs the server to read the manifest of its server functions. Servers with a
+        // custom `main` build their router here, so exit before they start serving.
+        if crate::manifest::write_requested_manifest() {
+            std::process::exit(0);
+        }
 
+        // Group the server functions by endpoint, so the versions of an endpoint share one route
+        let mut endpoints: Vec<(http::Method, &'static str, Vec<&ServerFunction>)> = Vec::new();
+        let mut index = HashMap::new();
         for func in ServerFunction::collect() {
-            if seen.insert(format!("{} {}", func.method(), func.path())) {
-                tracing::info!("Registering: {} {}", func.method(), func.path());
+            let key = (func.method(), func.path());
+            match index.get(&key) {
+                Some(&i) => endpoints[i].2.push(func),
+                None => {
+                    index.insert(key, endpoints.len());
+                    endpoints.push((func.method(), func.path(), vec![func]));
+                }
+            }
+        }
 
-                self = self.route(func.path(), func.method_router())
+        for (method, path, mut functions) in endpoints {
+            if functions.iter().all(|func| func.version().is_none()) {
+                tracing::info!("Registering: {method} {path}");
+                self = self.route(path, functions[0].method_router());
+                continue;
             }
+
+            // Keep the first server function of every version
+            functions.sort_by_key(|func| func.version().unwrap_or(1));
+            functions.dedup_by_key(|func| func.version().unwrap_or(1));
+            for func in &functions {
+                tracing::info!(
+                    "Registering: {method} {path} (version {})",
+                    func.version().unwrap_or(1)
+                );
+            }
+
+            self = self.route(
+                path,
+                ServerFunction::versioned_method_router(method, &functions),
+            );
         }
 
         self
     }
 
+    fn serve_jobs(self) -> Router<FullstackState> {
+        self.route(
+            &format!("{}/{{id}}", crate::JOBS_PATH),
+            get(crate::jobs::status_handler),
+        )
+        .route(
+            &format!("{}/{{id}}/events", crate::JOBS_PATH),
+            get(crate::jobs::events_handler),
+        )
+    }
+
+    #[cfg(feature = "openapi")]
+    fn serve_openapi(self, path: &str, openapi: crate::openapi::OpenApi) -> Router<FullstackState> {
+        let document = openapi.document().to_string();
+        self.route(
+            path,
+            get(move || {
+                let document = document.clone();
+                async move { ([(CONTENT_TYPE, "application/json")], document) }
+            }),
+        )
+    }
+
     fn serve_static_assets(self) -> Router<FullstackState> {
         let Some(public_path) = public_path() else {
             return self;
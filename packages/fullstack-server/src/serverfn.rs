@@ -5,9 +5,10 @@ use axum::{
     response::Response,
     routing::MethodRouter,
 };
-use dioxus_fullstack_core::FullstackContext;
-use http::{Method, StatusCode};
-use std::{pin::Pin, prelude::rust_2024::Future};
+use dioxus_fullstack_core::{FullstackContext, ServerFnError, SERVER_FN_VERSION_HEADER};
+use http::{header::CONTENT_TYPE, Method, StatusCode};
+use std::{pin::Pin, prelude::rust_2024::Future, sync::Arc};
+use tower::ServiceExt;
 
 /// A function endpoint that can be called from the client.
 #[derive(Clone)]
@@ -15,6 +16,9 @@ pub struct ServerFunction {
     path: &'static str,
     method: Method,
     handler: fn() -> MethodRouter<FullstackState>,
+    version: Option<u32>,
+    #[cfg(feature = "openapi")]
+    openapi: Option<fn(&mut crate::openapi::SchemaGenerator) -> crate::openapi::Operation>,
 }
 
 impl ServerFunction {
@@ -28,9 +32,42 @@ impl ServerFunction {
             path,
             method,
             handler,
+            version: None,
+            #[cfg(feature = "openapi")]
+            openapi: None,
         }
     }
 
+    /// Serve this server function as the given version of its endpoint.
+    ///
+    /// This is used by the server function macros for functions with `#[server(version = N)]`.
+    pub const fn with_version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Attach the OpenAPI operation that describes this server function.
+    ///
+    /// This is used by the server function macros when the `openapi` feature is enabled.
+    #[cfg(feature = "openapi")]
+    pub const fn with_openapi(
+        mut self,
+        operation: fn(&mut crate::openapi::SchemaGenerator) -> crate::openapi::Operation,
+    ) -> Self {
+        self.openapi = Some(operation);
+        self
+    }
+
+    /// Build the OpenAPI operation that describes this server function, if the server function
+    /// was compiled with the `openapi` feature.
+    #[cfg(feature = "openapi")]
+    pub fn openapi_operation(
+        &self,
+        generator: &mut crate::openapi::SchemaGenerator,
+    ) -> Option<crate::openapi::Operation> {
+        self.openapi.map(|operation| operation(generator))
+    }
+
     /// The path of the server function.
     pub fn path(&self) -> &'static str {
         self.path
@@ -41,6 +78,11 @@ impl ServerFunction {
         self.method.clone()
     }
 
+    /// The version of the endpoint this server function serves, if it is versioned.
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
     /// Collect all globally registered server functions
     pub fn collect() -> Vec<&'static ServerFunction> {
         inventory::iter::<ServerFunction>().collect()
@@ -56,6 +98,60 @@ impl ServerFunction {
         (self.handler)()
     }
 
+    /// Create a `MethodRouter` that serves several versions of the same endpoint.
+    ///
+    /// Requests are dispatched by the version in the `x-server-fn-version` header, and requests
+    /// without the header call version 1. Unversioned server functions serve version 1. Calls of a
+    /// version that none of the server functions serve are rejected with `412 Precondition Failed`
+    /// and [`ServerFnError::VersionMismatch`], which makes web clients reload to the current build.
+    pub fn versioned_method_router(
+        method: Method,
+        functions: &[&ServerFunction],
+    ) -> MethodRouter<FullstackState> {
+        let versions: Arc<Vec<(u32, MethodRouter<FullstackState>)>> = Arc::new(
+            functions
+                .iter()
+                .map(|func| (func.version.unwrap_or(1), func.method_router()))
+                .collect(),
+        );
+
+        axum::routing::method_routing::on(
+            method
+                .try_into()
+                .expect("MethodFilter only supports standard HTTP methods"),
+            move |State(state): State<FullstackState>, request: Request| {
+                let versions = versions.clone();
+                async move {
+                    let requested = request
+                        .headers()
+                        .get(SERVER_FN_VERSION_HEADER)
+                        .and_then(|version| version.to_str().ok())
+                        .and_then(|version| version.parse().ok())
+                        .unwrap_or(1);
+
+                    let router = versions
+                        .iter()
+                        .find(|(version, _)| *version == requested)
+                        .map(|(_, router)| router.clone());
+
+                    match router {
+                        Some(router) => router
+                            .with_state(state)
+                            .oneshot(request)
+                            .await
+                            .unwrap_or_else(|err| match err {}),
+                        None => {
+                            let mut supported: Vec<u32> =
+                                versions.iter().map(|(version, _)| *version).collect();
+                            supported.sort_unstable();
+                            version_mismatch(requested, supported)
+                        }
+                    }
+                }
+            },
+        )
+    }
+
     /// Creates a new `MethodRouter` for the given method and !Send handler.
     ///
     /// This is used internally by the `ServerFunction` to create the method router that this
@@ -146,6 +242,31 @@ impl ServerFunction {
     }
 }
 
+/// The response to a call of a version of a server function that the server doesn't serve.
+fn version_mismatch(requested: u32, supported: Vec<u32>) -> Response {
+    let header = supported
+        .iter()
+        .map(|version| version.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let error = ServerFnError::VersionMismatch {
+        requested,
+        supported,
+    };
+    let body = serde_json::json!({
+        "message": error.to_string(),
+        "code": StatusCode::PRECONDITION_FAILED.as_u16(),
+        "data": error,
+    });
+
+    Response::builder()
+        .status(StatusCode::PRECONDITION_FAILED)
+        .header(CONTENT_TYPE, "application/json")
+        .header(SERVER_FN_VERSION_HEADER, header)
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
 impl inventory::Collect for ServerFunction {
     #[inline]
     fn registry() -> &'static inventory::Registry {
@@ -153,3 +274,77 @@ impl inventory::Collect for ServerFunction {
         &REGISTRY
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::{routing::post, Router};
+
+    fn greet_v1() -> MethodRouter<FullstackState> {
+        post(|| async { "v1" })
+    }
+
+    fn greet_v2() -> MethodRouter<FullstackState> {
+        post(|| async { "v2" })
+    }
+
+    async fn call(version: Option<&str>) -> Response {
+        let v1 = ServerFunction::new(Method::POST, "/api/greet", greet_v1);
+        let v2 = ServerFunction::new(Method::POST, "/api/greet", greet_v2).with_version(2);
+        let router = Router::new()
+            .route(
+                "/api/greet",
+                ServerFunction::versioned_method_router(Method::POST, &[&v1, &v2]),
+            )
+            .with_state(FullstackState::headless());
+
+        let mut request = http::Request::builder()
+            .method(Method::POST)
+            .uri("/api/greet");
+        if let Some(version) = version {
+            request = request.header(SERVER_FN_VERSION_HEADER, version);
+        }
+        router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body(response: Response) -> String {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn dispatches_by_version_header() {
+        assert_eq!(body(call(Some("1")).await).await, "v1");
+        assert_eq!(body(call(Some("2")).await).await, "v2");
+    }
+
+    #[tokio::test]
+    async fn missing_header_calls_version_one() {
+        assert_eq!(body(call(None).await).await, "v1");
+        assert_eq!(body(call(Some("latest")).await).await, "v1");
+    }
+
+    #[tokio::test]
+    async fn unknown_versions_are_rejected_with_the_supported_versions() {
+        let response = call(Some("3")).await;
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        assert_eq!(response.headers()[SERVER_FN_VERSION_HEADER], "1, 2");
+
+        let payload: serde_json::Value = serde_json::from_str(&body(response).await).unwrap();
+        assert_eq!(payload["code"], 412);
+        let error: ServerFnError = serde_json::from_value(payload["data"].clone()).unwrap();
+        assert_eq!(
+            error,
+            ServerFnError::VersionMismatch {
+                requested: 3,
+                supported: vec![1, 2],
+            }
+        );
+        assert_eq!(payload["message"], error.to_string());
+    }
+}
@@ -3,6 +3,7 @@
 use crate::{reqwest_error_to_request_error, StreamingError};
 use bytes::Bytes;
 use dioxus_fullstack_core::RequestError;
+use dioxus_signals::{SyncSignal, WritableExt};
 use futures::Stream;
 use futures::{TryFutureExt, TryStreamExt};
 use headers::{ContentType, Header};
@@ -15,8 +16,13 @@ use url::Url;
 
 pub static GLOBAL_REQUEST_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
 
+/// The cookies of the native client, shared by all requests.
+#[cfg(not(target_arch = "wasm32"))]
+static COOKIE_JAR: LazyLock<std::sync::Arc<reqwest::cookie::Jar>> = LazyLock::new(Default::default);
+
 pub type ClientResult = Result<ClientResponse, RequestError>;
 
+#[derive(Clone)]
 pub struct ClientRequest {
     pub url: Url,
     pub headers: HeaderMap,
@@ -39,7 +45,7 @@ impl ClientRequest {
             server_url = "http://this.is.not.a.real.url:9000";
         }
 
-        let url = format!(
+        let url: Url = format!(
             "{server_url}{path}{params}",
             params = if query.is_empty() {
                 "".to_string()
@@ -50,7 +56,16 @@ impl ClientRequest {
         .parse()
         .unwrap();
 
-        let headers = get_request_headers();
+        let mut headers = get_request_headers();
+
+        // Send the CSRF token of the session with requests that may change state
+        if !matches!(method, Method::GET | Method::HEAD | Method::OPTIONS)
+            && !headers.contains_key(crate::CSRF_HEADER)
+        {
+            if let Some(token) = csrf_token(&url).and_then(|token| token.parse().ok()) {
+                headers.insert(crate::CSRF_HEADER, token);
+            }
+        }
 
         ClientRequest {
             method,
@@ -112,6 +127,17 @@ impl ClientRequest {
         self
     }
 
+    /// Call the given version of the server function.
+    ///
+    /// This is used by the server function macros for functions with `#[server(version = N)]`.
+    pub fn with_server_fn_version(mut self, version: u32) -> Self {
+        self.headers.insert(
+            dioxus_fullstack_core::SERVER_FN_VERSION_HEADER,
+            HeaderValue::from(version),
+        );
+        self
+    }
+
     /// Creates a new reqwest client with cookies set
     pub fn new_reqwest_client() -> reqwest::Client {
         #[allow(unused_mut)]
@@ -119,13 +145,9 @@ impl ClientRequest {
 
         #[cfg(not(target_arch = "wasm32"))]
         {
-            use std::sync::Arc;
-            use std::sync::LazyLock;
-
-            static COOKIES: LazyLock<Arc<reqwest::cookie::Jar>> =
-                LazyLock::new(|| Arc::new(reqwest::cookie::Jar::default()));
-
-            client = client.cookie_store(true).cookie_provider(COOKIES.clone());
+            client = client
+                .cookie_store(true)
+                .cookie_provider(COOKIE_JAR.clone());
         }
 
         client.build().unwrap()
@@ -148,23 +170,21 @@ impl ClientRequest {
         req
     }
 
+    /// The path and query of the url. Browsers send requests relative to the page.
+    #[cfg(feature = "web")]
+    fn path_and_query(&self) -> String {
+        match self.url.query() {
+            Some(query) => format!("{}?{query}", self.url.path()),
+            None => self.url.path().to_string(),
+        }
+    }
+
     /// Using this method attaches `X-Request-Client-Dioxus` header to the request.
     #[cfg(feature = "web")]
     pub fn new_gloo_request(&self) -> gloo_net::http::RequestBuilder {
-        let mut builder = gloo_net::http::RequestBuilder::new(
-            format!(
-                "{path}{query_string}",
-                path = self.url.path(),
-                query_string = self
-                    .url
-                    .query()
-                    .map(|query| format!("?{query}"))
-                    .unwrap_or_default()
-            )
-            .as_str(),
-        )
-        .header("X-Request-Client", "dioxus")
-        .method(self.method.clone());
+        let mut builder = gloo_net::http::RequestBuilder::new(self.path_and_query().as_str())
+            .header("X-Request-Client", "dioxus")
+            .method(self.method.clone());
 
         for (key, value) in self.headers.iter() {
             let value = match value.to_str() {
@@ -341,32 +361,72 @@ impl ClientRequest {
         {
             use wasm_bindgen::JsValue;
 
-            let stream: Vec<Bytes> = stream.try_collect().await.map_err(|e| {
-                RequestError::Request(format!("Error collecting stream for request body: {}", e))
-            })?;
+            let uint_8_array = browser::collect_body(stream).await?;
+            return self.send_js_value(JsValue::from(uint_8_array)).await;
+        }
 
-            let uint_8_array =
-                js_sys::Uint8Array::new_with_length(stream.iter().map(|b| b.len() as u32).sum());
+        unimplemented!()
+    }
 
-            let mut offset = 0;
-            for chunk in stream {
-                uint_8_array.set(&js_sys::Uint8Array::from(&chunk[..]), offset);
-                offset += chunk.len() as u32;
-            }
+    /// Sends a stream body like [`Self::send_body_stream`], adding the length of every chunk to
+    /// `progress` once the transport takes it.
+    ///
+    /// On the web, the body is buffered and sent with an `XMLHttpRequest`, since `fetch` can't report
+    /// upload progress. `progress` is updated as the browser sends the buffered body.
+    pub async fn send_body_stream_with_progress(
+        self,
+        stream: impl Stream<Item = Result<Bytes, StreamingError>> + Send + 'static,
+        mut progress: SyncSignal<UploadProgress>,
+    ) -> Result<ClientResponse, RequestError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let stream = stream.inspect_ok(move |chunk| {
+                progress.write().uploaded += chunk.len() as u64;
+            });
+            return self.send_body_stream(stream).await;
+        }
 
-            return self.send_js_value(JsValue::from(uint_8_array)).await;
+        #[cfg(feature = "web")]
+        {
+            let body = browser::collect_body(stream).await?;
+            let length = body.length() as u64;
+            return self
+                .send_js_value_with_progress(body.into(), length, progress)
+                .await;
         }
 
         unimplemented!()
     }
 
+    /// Sends a `JsValue` body like [`Self::send_js_value`], adding the bytes the browser sent to
+    /// `progress` as the upload goes. `length` is the size of the body.
+    ///
+    /// This uses an `XMLHttpRequest` instead of `fetch`, since `fetch` doesn't report upload
+    /// progress.
     #[cfg(feature = "web")]
-    pub async fn send_js_value(
+    pub async fn send_js_value_with_progress(
         self,
         value: wasm_bindgen::JsValue,
+        length: u64,
+        progress: SyncSignal<UploadProgress>,
     ) -> Result<ClientResponse, RequestError> {
-        use std::str::FromStr;
+        let (response, url) = browser::send_with_progress(
+            &self.method,
+            &self.path_and_query(),
+            &self.headers,
+            value,
+            length,
+            progress,
+        )
+        .await?;
+        browser::into_client_response(response, &url, self.extensions)
+    }
 
+    #[cfg(feature = "web")]
+    pub async fn send_js_value(
+        self,
+        value: wasm_bindgen::JsValue,
+    ) -> Result<ClientResponse, RequestError> {
         let inner = self
             .new_gloo_request()
             .body(value)
@@ -375,40 +435,34 @@ impl ClientRequest {
             .await
             .map_err(|e| RequestError::Request(e.to_string()))?;
 
-        let status = inner.status();
-        let url = inner
-            .url()
-            .parse()
-            .map_err(|e| RequestError::Request(format!("Error parsing response URL: {}", e)))?;
+        let url = inner.url();
+        browser::into_client_response(inner, &url, self.extensions)
+    }
+}
 
-        let headers = {
-            let mut map = HeaderMap::new();
-            for (key, value) in inner.headers().entries() {
-                if let Ok(header_value) = http::HeaderValue::from_str(&value) {
-                    let header = HeaderName::from_str(&key).unwrap();
-                    map.append(header, header_value);
-                }
-            }
-            map
-        };
+/// The progress of an upload, reported by [`ClientRequest::send_body_stream_with_progress`] and
+/// [`FileStream::with_progress`](crate::FileStream::with_progress).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// The number of bytes that were sent so far.
+    pub uploaded: u64,
 
-        let content_length = headers
-            .get(http::header::CONTENT_LENGTH)
-            .and_then(|val| val.to_str().ok())
-            .and_then(|s| s.parse::<u64>().ok());
+    /// The total number of bytes, if known.
+    pub total: Option<u64>,
 
-        let status = http::StatusCode::from_u16(status).unwrap_or(http::StatusCode::OK);
+    /// The id of a resumable upload, once the server accepted it. Pass it to
+    /// [`FileStream::resume`](crate::FileStream::resume) to continue the upload later.
+    pub upload_id: Option<String>,
+}
 
-        Ok(ClientResponse {
-            extensions: self.extensions,
-            response: Box::new(browser::WrappedGlooResponse {
-                inner,
-                headers,
-                status,
-                url,
-                content_length,
-            }),
-        })
+impl UploadProgress {
+    /// The fraction of the upload that was sent, between `0.0` and `1.0`.
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total {
+            Some(0) => Some(1.0),
+            Some(total) => Some(self.uploaded as f64 / total as f64),
+            None => None,
+        }
     }
 }
 
@@ -501,6 +555,31 @@ pub fn set_server_url(url: &'static str) {
     ROOT_URL.set(url).unwrap();
 }
 
+/// The CSRF token of the session, from the cookie the `SessionLayer` sets.
+#[cfg(not(target_arch = "wasm32"))]
+fn csrf_token(url: &Url) -> Option<String> {
+    use reqwest::cookie::CookieStore;
+    let cookies = COOKIE_JAR.cookies(url)?;
+    crate::session::csrf_token_from_cookies(cookies.to_str().ok()?)
+}
+
+/// The CSRF token of the session, from the cookie the `SessionLayer` sets.
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+fn csrf_token(_url: &Url) -> Option<String> {
+    use wasm_bindgen::JsCast;
+    let document = web_sys::window()?
+        .document()?
+        .dyn_into::<web_sys::HtmlDocument>()
+        .ok()?;
+    crate::session::csrf_token_from_cookies(&document.cookie().ok()?)
+}
+
+/// Without the `web` feature there is no cookie to read the token from.
+#[cfg(all(not(feature = "web"), target_arch = "wasm32"))]
+fn csrf_token(_url: &Url) -> Option<String> {
+    None
+}
+
 /// Returns the root server URL for all server functions.
 pub fn get_server_url() -> &'static str {
     ROOT_URL.get().copied().unwrap_or("")
@@ -605,16 +684,165 @@ mod native {
 
 #[cfg(feature = "web")]
 mod browser {
-    use crate::{ClientResponseDriver, StreamingError};
+    use crate::{ClientResponse, ClientResponseDriver, StreamingError, UploadProgress};
     use bytes::Bytes;
     use dioxus_fullstack_core::RequestError;
-    use futures::{Stream, StreamExt};
-    use http::{HeaderMap, StatusCode};
+    use dioxus_signals::{ReadableExt, SyncSignal, WritableExt};
+    use futures::{Stream, StreamExt, TryStreamExt};
+    use http::{HeaderMap, HeaderName, StatusCode};
     use js_sys::Uint8Array;
     use send_wrapper::SendWrapper;
     use std::{pin::Pin, prelude::rust_2024::Future};
     use wasm_bindgen::JsCast;
 
+    /// Buffer a request body, since browsers can't stream request bodies yet.
+    pub(crate) async fn collect_body(
+        stream: impl Stream<Item = Result<Bytes, StreamingError>>,
+    ) -> Result<Uint8Array, RequestError> {
+        let chunks: Vec<Bytes> = stream.try_collect().await.map_err(|e| {
+            RequestError::Request(format!("Error collecting stream for request body: {}", e))
+        })?;
+
+        let body = Uint8Array::new_with_length(chunks.iter().map(|b| b.len() as u32).sum());
+        let mut offset = 0;
+        for chunk in chunks {
+            body.set(&Uint8Array::from(&chunk[..]), offset);
+            offset += chunk.len() as u32;
+        }
+        Ok(body)
+    }
+
+    /// Wrap a fetch response from `url` in a [`ClientResponse`].
+    pub(crate) fn into_client_response(
+        inner: gloo_net::http::Response,
+        url: &str,
+        extensions: http::Extensions,
+    ) -> Result<ClientResponse, RequestError> {
+        use std::str::FromStr;
+
+        let status = inner.status();
+        let url = url
+            .parse()
+            .map_err(|e| RequestError::Request(format!("Error parsing response URL: {}", e)))?;
+
+        let headers = {
+            let mut map = HeaderMap::new();
+            for (key, value) in inner.headers().entries() {
+                if let Ok(header_value) = http::HeaderValue::from_str(&value) {
+                    let header = HeaderName::from_str(&key).unwrap();
+                    map.append(header, header_value);
+                }
+            }
+            map
+        };
+
+        let content_length = headers
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|val| val.to_str().ok())
+            .and_then(|s| s.parse::<u64>().ok());
+
+        let status = http::StatusCode::from_u16(status).unwrap_or(http::StatusCode::OK);
+
+        Ok(ClientResponse {
+            extensions,
+            response: Box::new(WrappedGlooResponse {
+                inner,
+                headers,
+                status,
+                url,
+                content_length,
+            }),
+        })
+    }
+
+    /// Send `body` with an `XMLHttpRequest`, reporting the bytes sent to `progress`. Returns the
+    /// result as a fetch response, along with the url of the response.
+    pub(crate) async fn send_with_progress(
+        method: &http::Method,
+        url: &str,
+        headers: &HeaderMap,
+        body: wasm_bindgen::JsValue,
+        length: u64,
+        mut progress: SyncSignal<UploadProgress>,
+    ) -> Result<(gloo_net::http::Response, String), RequestError> {
+        use wasm_bindgen::closure::Closure;
+        use web_sys::{ProgressEvent, XmlHttpRequest, XmlHttpRequestResponseType};
+
+        let error = |err: wasm_bindgen::JsValue| RequestError::Request(format!("{err:?}"));
+
+        let xhr = XmlHttpRequest::new().map_err(error)?;
+        xhr.open_with_async(method.as_str(), url, true)
+            .map_err(error)?;
+        xhr.set_request_header("X-Request-Client", "dioxus")
+            .map_err(error)?;
+        for (key, value) in headers.iter() {
+            match value.to_str() {
+                Ok(value) => xhr.set_request_header(key.as_str(), value).map_err(error)?,
+                Err(err) => tracing::error!("Error converting header {key} value: {}", err),
+            }
+        }
+        xhr.set_response_type(XmlHttpRequestResponseType::Arraybuffer);
+
+        let start = progress.peek().uploaded;
+        let on_progress = Closure::<dyn FnMut(ProgressEvent)>::new(move |event: ProgressEvent| {
+            progress.write().uploaded = start + (event.loaded() as u64).min(length);
+        });
+        xhr.upload()
+            .map_err(error)?
+            .set_onprogress(Some(on_progress.as_ref().unchecked_ref()));
+
+        // `loadend` fires once the request succeeded, failed or was aborted
+        let (done, finished) = futures_channel::oneshot::channel::<()>();
+        let mut done = Some(done);
+        let on_loadend = Closure::<dyn FnMut()>::new(move || {
+            if let Some(done) = done.take() {
+                _ = done.send(());
+            }
+        });
+        xhr.set_onloadend(Some(on_loadend.as_ref().unchecked_ref()));
+
+        match body.dyn_ref::<web_sys::Blob>() {
+            Some(blob) => xhr.send_with_opt_blob(Some(blob)),
+            None if body.is_undefined() => xhr.send(),
+            None => xhr.send_with_opt_buffer_source(Some(body.unchecked_ref())),
+        }
+        .map_err(error)?;
+
+        _ = finished.await;
+        drop((on_progress, on_loadend));
+
+        // A status of zero means the request never got a response
+        let status = xhr.status().map_err(error)?;
+        if status == 0 {
+            return Err(RequestError::Request("The upload failed".into()));
+        }
+        progress.write().uploaded = start + length;
+
+        let response_headers = web_sys::Headers::new().map_err(error)?;
+        let raw_headers = xhr.get_all_response_headers().map_err(error)?;
+        for line in raw_headers.split("\r\n") {
+            if let Some((name, value)) = line.split_once(':') {
+                _ = response_headers.append(name.trim(), value.trim());
+            }
+        }
+        let init = web_sys::ResponseInit::new();
+        init.set_status(status);
+        init.set_headers(&response_headers);
+
+        // Responses with these statuses can't have a body
+        let response = if matches!(status, 101 | 204 | 205 | 304) {
+            web_sys::Response::new_with_opt_str_and_init(None, &init)
+        } else {
+            let body = xhr.response().map_err(error)?;
+            let mut body = Uint8Array::new(&body).to_vec();
+            web_sys::Response::new_with_opt_u8_array_and_init(Some(&mut body), &init)
+        }
+        .map_err(error)?;
+
+        // Constructed responses don't have a url, so keep the one the request went to
+        Ok((response.into(), xhr.response_url()))
+    }
+
     pub(crate) struct WrappedGlooResponse {
         pub(crate) inner: gloo_net::http::Response,
         pub(crate) headers: HeaderMap,
@@ -1,19 +1,72 @@
 use bytes::Bytes;
 use serde::{de::DeserializeOwned, Serialize};
 
-/// A trait for encoding and decoding data.
+/// An encoding for the payloads of streams and websockets.
 ///
-/// This takes an owned self to make it easier for zero-copy encodings.
+/// This only describes the content types of the encoding. Values are encoded with [`Encodes`] and
+/// decoded with [`Decodes`], which lets encodings support a different set of types than serde, like
+/// [`RkyvEncoding`].
+///
+/// # Migrating custom encodings
+///
+/// `to_bytes`, `encode` and `decode` used to be methods of this trait. Custom serde based
+/// encodings now implement [`SerdeEncoding`] next to [`Encoding`], which provides [`Encodes`] and
+/// [`Decodes`] for every serde type:
+///
+/// ```rust, ignore
+/// impl Encoding for MyEncoding {
+///     fn content_type() -> &'static str { "application/my-encoding" }
+///     fn stream_content_type() -> &'static str { "application/stream+my-encoding" }
+/// }
+///
+/// // Previously `encode` and `decode` in `impl Encoding for MyEncoding`
+/// impl SerdeEncoding for MyEncoding {
+///     fn serialize(data: impl Serialize, buf: &mut Vec<u8>) -> Option<usize> { /* ... */ }
+///     fn deserialize<O: DeserializeOwned>(bytes: Bytes) -> Option<O> { /* ... */ }
+/// }
+/// ```
+///
+/// Code that called `E::to_bytes(value)` or `E::decode(bytes)` for a generic `E: Encoding` needs
+/// an `E: Encodes<T>` or `E: Decodes<T>` bound instead.
 pub trait Encoding: 'static {
     fn content_type() -> &'static str;
     fn stream_content_type() -> &'static str;
-    fn to_bytes(data: impl Serialize) -> Option<Bytes> {
+}
+
+/// An encoding that can encode values of type `T`.
+pub trait Encodes<T>: Encoding {
+    fn to_bytes(data: &T) -> Option<Bytes> {
         let mut buf = Vec::new();
         Self::encode(data, &mut buf)?;
         Some(buf.into())
     }
-    fn encode(data: impl Serialize, buf: &mut Vec<u8>) -> Option<usize>;
-    fn decode<O: DeserializeOwned>(bytes: Bytes) -> Option<O>;
+    fn encode(data: &T, buf: &mut Vec<u8>) -> Option<usize>;
+}
+
+/// An encoding that can decode values of type `T`.
+///
+/// This takes owned bytes to make it easier for zero-copy encodings.
+pub trait Decodes<T>: Encoding {
+    fn decode(bytes: Bytes) -> Option<T>;
+}
+
+/// An encoding built on serde that can encode and decode any type that implements [`Serialize`]
+/// and [`DeserializeOwned`].
+pub trait SerdeEncoding: Encoding {
+    fn serialize(data: impl Serialize, buf: &mut Vec<u8>) -> Option<usize>;
+    fn deserialize<O: DeserializeOwned>(bytes: Bytes) -> Option<O>;
+}
+
+impl<T: Serialize, E: SerdeEncoding> Encodes<T> for E {
+    fn encode(data: &T, buf: &mut Vec<u8>) -> Option<usize> {
+        E::serialize(data, buf)
+    }
+}
+
+impl<T: DeserializeOwned, E: SerdeEncoding> Decodes<T> for E {
+    fn decode(bytes: Bytes) -> Option<T> {
+        E::deserialize(bytes)
+    }
 }
 
 pub struct JsonEncoding;
@@ -24,14 +77,15 @@ impl Encoding for JsonEncoding {
     fn stream_content_type() -> &'static str {
         "application/stream+json"
     }
-
-    fn encode(data: impl Serialize, mut buf: &mut Vec<u8>) -> Option<usize> {
+}
+impl SerdeEncoding for JsonEncoding {
+    fn serialize(data: impl Serialize, mut buf: &mut Vec<u8>) -> Option<usize> {
         let len = buf.len();
         serde_json::to_writer(&mut buf, &data).ok()?;
         Some(buf.len() - len)
     }
 
-    fn decode<O: DeserializeOwned>(bytes: Bytes) -> Option<O> {
+    fn deserialize<O: DeserializeOwned>(bytes: Bytes) -> Option<O> {
         serde_json::from_slice(&bytes).ok()
     }
 }
@@ -44,12 +98,13 @@ impl Encoding for CborEncoding {
     fn stream_content_type() -> &'static str {
         "application/stream+cbor"
     }
-
-    fn decode<O: DeserializeOwned>(bytes: Bytes) -> Option<O> {
+}
+impl SerdeEncoding for CborEncoding {
+    fn deserialize<O: DeserializeOwned>(bytes: Bytes) -> Option<O> {
         ciborium::de::from_reader(bytes.as_ref()).ok()
     }
 
-    fn encode(data: impl Serialize, mut buf: &mut Vec<u8>) -> Option<usize> {
+    fn serialize(data: impl Serialize, mut buf: &mut Vec<u8>) -> Option<usize> {
         let len = buf.len();
         ciborium::into_writer(&data, &mut buf).ok()?;
         Some(buf.len() - len)
@@ -66,14 +121,16 @@ impl Encoding for PostcardEncoding {
     fn stream_content_type() -> &'static str {
         "application/stream+postcard"
     }
-
-    fn encode(data: impl Serialize, mut buf: &mut Vec<u8>) -> Option<usize> {
+}
+#[cfg(feature = "postcard")]
+impl SerdeEncoding for PostcardEncoding {
+    fn serialize(data: impl Serialize, mut buf: &mut Vec<u8>) -> Option<usize> {
         let len = buf.len();
         postcard::to_io(&data, &mut buf).ok()?;
         Some(buf.len() - len)
     }
 
-    fn decode<O: DeserializeOwned>(bytes: Bytes) -> Option<O> {
+    fn deserialize<O: DeserializeOwned>(bytes: Bytes) -> Option<O> {
         postcard::from_bytes(bytes.as_ref()).ok()
     }
 }
@@ -88,33 +145,103 @@ impl Encoding for MsgPackEncoding {
     fn stream_content_type() -> &'static str {
         "application/stream+msgpack"
     }
-    fn encode(data: impl Serialize, buf: &mut Vec<u8>) -> Option<usize> {
+}
+#[cfg(feature = "msgpack")]
+impl SerdeEncoding for MsgPackEncoding {
+    fn serialize(data: impl Serialize, buf: &mut Vec<u8>) -> Option<usize> {
         let len = buf.len();
         rmp_serde::encode::write(buf, &data).ok()?;
         Some(buf.len() - len)
     }
 
-    fn decode<O: DeserializeOwned>(bytes: Bytes) -> Option<O> {
+    fn deserialize<O: DeserializeOwned>(bytes: Bytes) -> Option<O> {
         rmp_serde::from_slice(&bytes).ok()
     }
 }
 
-// todo: ... add rkyv support
-// pub struct RkyvEncoding;
-// impl Encoding for RkyvEncoding {
-//     fn content_type() -> &'static str {
-//         "application/rkyv"
-//     }
-//     fn stream_content_type() -> &'static str {
-//         "application/stream+rkyv"
-//     }
-//     fn to_bytes(data: impl Serialize) -> Option<Bytes> {
-//         let mut buf = rkyv::ser::Serializer::new(rkyv::ser::AllocSerializer::new());
-//         rkyv::ser::Serializer::serialize(&mut buf, &data).ok()?;
-//         Some(Bytes::from(buf.into_inner()))
-//     }
-//     fn from_bytes<O: DeserializeOwned>(bytes: Bytes) -> Option<O> {
-//         let archived = unsafe { rkyv::archived_root::<O>(&bytes) };
-//         rkyv::Deserialize::deserialize(archived).ok()
-//     }
-// }
+/// An encoding that uses [rkyv](https://rkyv.org) to serialize values into a zero-copy archive.
+///
+/// Decoding rkyv archives is much faster than parsing serde formats, which makes it a good fit for
+/// large payloads like numeric arrays. Types need to derive `rkyv::Archive`, `rkyv::Serialize` and
+/// `rkyv::Deserialize` instead of the serde traits.
+///
+/// Archives received by the server are always validated before they are deserialized. The client
+/// trusts the archives the server sends and skips validation.
+#[cfg(feature = "rkyv")]
+pub struct RkyvEncoding;
+#[cfg(feature = "rkyv")]
+impl Encoding for RkyvEncoding {
+    fn content_type() -> &'static str {
+        "application/rkyv"
+    }
+    fn stream_content_type() -> &'static str {
+        "application/stream+rkyv"
+    }
+}
+#[cfg(feature = "rkyv")]
+impl<T: RkyvSerialize> Encodes<T> for RkyvEncoding {
+    fn encode(data: &T, buf: &mut Vec<u8>) -> Option<usize> {
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(data).ok()?;
+        buf.extend_from_slice(&bytes);
+        Some(bytes.len())
+    }
+}
+#[cfg(feature = "rkyv")]
+impl<T: RkyvDeserialize> Decodes<T> for RkyvEncoding {
+    fn decode(bytes: Bytes) -> Option<T> {
+        T::from_rkyv_bytes(&bytes)
+    }
+}
+
+/// A type that can be serialized with [`RkyvEncoding`].
+///
+/// This is implemented for every type that implements `rkyv::Serialize` for the default serializer.
+#[cfg(feature = "rkyv")]
+pub trait RkyvSerialize:
+    for<'a> rkyv::Serialize<
+    rkyv::api::high::HighSerializer<
+        rkyv::util::AlignedVec,
+        rkyv::ser::allocator::ArenaHandle<'a>,
+        rkyv::rancor::Error,
+    >,
+>
+{
+}
+#[cfg(feature = "rkyv")]
+impl<T> RkyvSerialize for T where
+    T: for<'a> rkyv::Serialize<
+        rkyv::api::high::HighSerializer<
+            rkyv::util::AlignedVec,
+            rkyv::ser::allocator::ArenaHandle<'a>,
+            rkyv::rancor::Error,
+        >,
+    >
+{
+}
+
+/// A type that can be deserialized with [`RkyvEncoding`].
+///
+/// This is implemented for every type whose archive can be validated and deserialized with the
+/// default deserializer.
+#[cfg(feature = "rkyv")]
+pub trait RkyvDeserialize: Sized {
+    /// Deserialize a value from an rkyv archive. Returns `None` if the archive is invalid.
+    fn from_rkyv_bytes(bytes: &[u8]) -> Option<Self>;
+}
+#[cfg(feature = "rkyv")]
+impl<T> RkyvDeserialize for T
+where
+    T: rkyv::Archive,
+    T::Archived: for<'a> rkyv::bytecheck::CheckBytes<rkyv::api::high::HighValidator<'a, rkyv::rancor::Error>>
+        + rkyv::Deserialize<T, rkyv::api::high::HighDeserializer<rkyv::rancor::Error>>,
+{
+    fn from_rkyv_bytes(bytes: &[u8]) -> Option<Self> {
+        // The archive needs to be aligned, but the bytes may be a slice of a larger frame or body
+        let mut aligned = rkyv::util::AlignedVec::<16>::with_capacity(bytes.len());
+        aligned.extend_from_slice(bytes);
+
+        // Archives come from the network, so they are always validated. A response from a server
+        // built with different types must fail to decode instead of being read as the wrong type.
+        rkyv::from_bytes::<T, rkyv::rancor::Error>(&aligned).ok()
+    }
+}
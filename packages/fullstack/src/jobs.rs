@@ -0,0 +1,45 @@
+use crate::{ClientRequest, FromResponse, JobId, JobStatus, RequestError, ServerEvents};
+use dioxus_fullstack_core::{ServerFnError, JOBS_PATH};
+use http::{Method, StatusCode};
+
+/// Fetch the status of a background job that a server function enqueued.
+///
+/// The server must serve the job routes with `DioxusRouterExt::serve_jobs`.
+///
+/// ```rust, ignore
+/// let id = start_report(month).await?;
+/// let status = job_status(&id).await?;
+/// ```
+pub async fn job_status(id: &JobId) -> Result<JobStatus, ServerFnError> {
+    let res = ClientRequest::new(Method::GET, format!("{JOBS_PATH}/{id}"), &())
+        .send_empty_body()
+        .await?;
+
+    let status = res.status();
+    if status != StatusCode::OK {
+        return Err(RequestError::Status(
+            format!("Failed to fetch the status of job {id}: {status}"),
+            status.as_u16(),
+        )
+        .into());
+    }
+
+    Ok(res.json::<JobStatus>().await?)
+}
+
+/// Follow the status of a background job. The stream yields the current status, every change, and
+/// ends once the job succeeded or failed.
+///
+/// ```rust, ignore
+/// let mut events = job_events(&id).await?;
+/// while let Some(Ok(status)) = events.recv().await {
+///     progress.set(status.progress.unwrap_or_default());
+/// }
+/// ```
+pub async fn job_events(id: &JobId) -> Result<ServerEvents<JobStatus>, ServerFnError> {
+    let res = ClientRequest::new(Method::GET, format!("{JOBS_PATH}/{id}/events"), &())
+        .send_empty_body()
+        .await?;
+
+    ServerEvents::from_response(res).await
+}
@@ -46,11 +46,20 @@ pub use encoding::*;
 pub mod lazy;
 pub use lazy::*;
 
+pub mod jobs;
+pub use jobs::*;
+
+pub mod session;
+pub use session::*;
+
 pub use http::{HeaderMap, HeaderValue, Method};
 
 mod client;
 pub use client::*;
 
+mod version;
+pub use version::set_reload_on_version_mismatch;
+
 pub use axum::extract::Json;
 pub use axum::response::{NoContent, Redirect};
 
@@ -92,6 +101,11 @@ pub mod payloads {
     #[cfg(feature = "msgpack")]
     pub use msgpack::*;
 
+    #[cfg(feature = "rkyv")]
+    pub mod rkyv;
+    #[cfg(feature = "rkyv")]
+    pub use self::rkyv::*;
+
     pub mod text;
     pub use text::*;
 
@@ -104,6 +118,9 @@ pub mod payloads {
     pub mod files;
     pub use files::*;
 
+    pub mod upload;
+    pub use upload::*;
+
     pub mod header;
     pub use header::*;
 
@@ -300,13 +300,9 @@ mod decode_ok {
 
                         match res {
                             Ok(RestEndpointPayload::Success(t)) => Ok(Ok(t)),
-                            Ok(RestEndpointPayload::Error(err)) => {
-                                Ok(Err(ServerFnError::ServerError {
-                                    message: err.message,
-                                    details: err.data,
-                                    code: err.code,
-                                }))
-                            }
+                            Ok(RestEndpointPayload::Error(err)) => Ok(Err(
+                                crate::version::server_error(err.message, err.code, err.data),
+                            )),
                             Err(e) => Ok(Err(e)),
                         }
                     }
@@ -443,6 +439,10 @@ mod decode_ok {
                         }
 
                         ServerFnError::Response(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+
+                        ServerFnError::VersionMismatch { .. } => {
+                            Err(StatusCode::PRECONDITION_FAILED)
+                        }
                     },
 
                     // The reqwest error case, we try to convert the reqwest error into a status code.
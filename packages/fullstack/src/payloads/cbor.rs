@@ -25,6 +25,10 @@ use serde::{de::DeserializeOwned, Serialize};
 #[must_use]
 pub struct Cbor<T>(pub T);
 
+impl<T> crate::BodyContentType for Cbor<T> {
+    const CONTENT_TYPE: &'static str = "application/cbor";
+}
+
 /// Check if the request has a valid CBOR content type header.
 ///
 /// This function validates that the `Content-Type` header is set to `application/cbor`
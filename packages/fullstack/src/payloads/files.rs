@@ -2,6 +2,12 @@ use super::*;
 use axum_core::extract::Request;
 use dioxus_fullstack_core::RequestError;
 use dioxus_html::FileData;
+use dioxus_signals::{SyncSignal, WritableExt};
+
+#[cfg(feature = "server")]
+use super::upload::{extract_upload, UPLOAD_ID, UPLOAD_LENGTH};
+use super::upload::{send_resumable, ResumableOptions};
+use crate::UploadProgress;
 
 #[cfg(feature = "server")]
 use std::path::Path;
@@ -17,6 +23,23 @@ use std::{
 /// handling large files without loading them entirely into memory.
 ///
 /// On the web, this uses the `ReadableStream` API to stream file data.
+///
+/// Large uploads can be made [resumable](FileStream::resumable). The file is then sent in chunks,
+/// and an upload that fails continues from the last chunk the server received instead of starting
+/// over. Use [`FileStream::with_progress`] to observe the progress of an upload:
+///
+/// ```rust, ignore
+/// #[post("/api/upload")]
+/// async fn upload(file: FileStream) -> Result<()> { /* ... */ }
+///
+/// let mut progress = use_signal_sync(UploadProgress::default);
+/// let onchange = move |event: FormEvent| async move {
+///     for file in event.files() {
+///         upload(FileStream::from(file).resumable().with_progress(progress)).await?;
+///     }
+///     Ok(())
+/// };
+/// ```
 pub struct FileStream {
     data: Option<FileData>,
     name: String,
@@ -25,6 +48,10 @@ pub struct FileStream {
     #[cfg(feature = "server")]
     server_body: Option<axum_core::body::BodyDataStream>,
 
+    // For uploaded files...
+    resumable: Option<ResumableOptions>,
+    progress: Option<SyncSignal<UploadProgress>>,
+
     // For downloaded files...
     #[allow(clippy::type_complexity)]
     client_body: Option<Pin<Box<dyn Stream<Item = Result<Bytes, StreamingError>> + Send>>>,
@@ -46,6 +73,44 @@ impl FileStream {
         self.content_type.as_deref()
     }
 
+    /// Upload the file in chunks with the resumable upload protocol.
+    ///
+    /// If a chunk fails, the upload continues from the last offset the server acknowledged. If it
+    /// keeps failing, the error is returned and the upload can be continued later with
+    /// [`FileStream::resume`] and the [`UploadProgress::upload_id`]. The server assembles the chunks
+    /// in its [`UploadSink`] and calls the server function once the whole file arrived.
+    pub fn resumable(mut self) -> Self {
+        self.resumable.get_or_insert_with(Default::default);
+        self
+    }
+
+    /// Set the size of the chunks of a [resumable](FileStream::resumable) upload in bytes. Defaults
+    /// to 8 MiB.
+    pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+        self.resumable
+            .get_or_insert_with(Default::default)
+            .chunk_size = chunk_size;
+        self
+    }
+
+    /// Continue a [resumable](FileStream::resumable) upload that was started earlier, for example
+    /// before the app was restarted.
+    pub fn resume(mut self, upload_id: impl Into<String>) -> Self {
+        self.resumable
+            .get_or_insert_with(Default::default)
+            .upload_id = Some(upload_id.into());
+        self
+    }
+
+    /// Report the progress of the upload to `progress`.
+    ///
+    /// Progress is reported while the body is sent. On the web, the file is sent with an
+    /// `XMLHttpRequest`, since `fetch` doesn't report upload progress.
+    pub fn with_progress(mut self, progress: SyncSignal<UploadProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
     /// Return the underlying body stream, assuming the `FileStream` was created by a server request.
     #[cfg(feature = "server")]
     pub fn body_mut(&mut self) -> Option<&mut axum_core::body::BodyDataStream> {
@@ -86,6 +151,8 @@ impl FileStream {
             content_type: Some(mime.to_string()),
             #[cfg(feature = "server")]
             server_body: Some(body),
+            resumable: None,
+            progress: None,
             client_body: None,
         })
     }
@@ -108,11 +175,17 @@ impl FileStream {
             content_type: Some(content_type),
             #[cfg(feature = "server")]
             server_body: Some(body),
+            resumable: None,
+            progress: None,
             client_body: None,
         }
     }
 }
 
+impl crate::BodyContentType for FileStream {
+    const CONTENT_TYPE: &'static str = "application/octet-stream";
+}
+
 impl IntoRequest for FileStream {
     #[allow(unreachable_code)]
     fn into_request(
@@ -126,6 +199,29 @@ impl IntoRequest for FileStream {
                 ));
             };
 
+            if let Some(options) = self.resumable {
+                let content_type = self
+                    .content_type
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+                return send_resumable(
+                    file_data,
+                    self.name,
+                    content_type,
+                    options,
+                    self.progress,
+                    builder,
+                )
+                .await;
+            }
+
+            if let Some(mut progress) = self.progress {
+                progress.set(UploadProgress {
+                    uploaded: 0,
+                    total: Some(file_data.size()),
+                    upload_id: None,
+                });
+            }
+
             #[cfg(feature = "web")]
             if cfg!(target_arch = "wasm32") {
                 use js_sys::escape;
@@ -140,22 +236,31 @@ impl IntoRequest for FileStream {
                 // Set both Content-Length and X-Content-Size for compatibility with server extraction.
                 // In browsers, content-length is often overwritten, so we set X-Content-Size as well
                 // for better compatibility with dioxus-based clients.
-                return builder
+                let builder = builder
                     .header("Content-Type", content_type)?
                     .header("Content-Length", content_length.clone())?
                     .header("X-Content-Size", content_length)?
                     .header(
                         "Content-Disposition",
                         format!("attachment; filename=\"{}\"", escape(&name)),
-                    )?
-                    .send_js_value(as_blob.clone().into())
-                    .await;
+                    )?;
+
+                return match self.progress {
+                    Some(progress) => {
+                        builder
+                            .send_js_value_with_progress(
+                                as_blob.clone().into(),
+                                as_blob.size() as u64,
+                                progress,
+                            )
+                            .await
+                    }
+                    None => builder.send_js_value(as_blob.clone().into()).await,
+                };
             }
 
             #[cfg(not(target_arch = "wasm32"))]
             {
-                use std::ascii::escape_default;
-
                 use futures::TryStreamExt;
 
                 let content_type = self
@@ -165,32 +270,26 @@ impl IntoRequest for FileStream {
                 let name = self.name;
                 let stream = file_data.byte_stream().map_err(|_| StreamingError::Failed);
 
-                // Ascii escape the filename to avoid issues with special characters.
-                let mut chars = vec![];
-                for byte in name.chars() {
-                    chars.extend(escape_default(byte as u8));
-                }
-                let filename = String::from_utf8(chars).map_err(|_| {
-                    RequestError::Request(
-                        "Failed to escape filename for Content-Disposition".into(),
-                    )
-                });
-
                 if let Some(length) = content_length {
                     builder = builder.header("Content-Length", length)?;
                 }
 
-                if let Ok(filename) = filename {
-                    builder = builder.header(
+                // Ascii escape the filename to avoid issues with special characters.
+                let builder = builder
+                    .header(
                         "Content-Disposition",
-                        format!("attachment; filename=\"{}\"", filename),
-                    )?;
-                }
-
-                return builder
-                    .header("Content-Type", content_type)?
-                    .send_body_stream(stream)
-                    .await;
+                        super::upload::attachment_disposition(&name),
+                    )?
+                    .header("Content-Type", content_type)?;
+
+                return match self.progress {
+                    Some(progress) => {
+                        builder
+                            .send_body_stream_with_progress(stream, progress)
+                            .await
+                    }
+                    None => builder.send_body_stream(stream).await,
+                };
             }
 
             unimplemented!("FileStream::into_request is only implemented for web targets");
@@ -199,7 +298,7 @@ impl IntoRequest for FileStream {
 }
 
 impl<S> FromRequest<S> for FileStream {
-    type Rejection = ServerFnError;
+    type Rejection = FileStreamRejection;
 
     fn from_request(
         req: Request,
@@ -208,6 +307,11 @@ impl<S> FromRequest<S> for FileStream {
         async move {
             tracing::info!("Extracting FileUpload from request: {:?}", req);
 
+            #[cfg(feature = "server")]
+            if req.headers().contains_key(UPLOAD_ID) || req.headers().contains_key(UPLOAD_LENGTH) {
+                return extract_upload(req).await;
+            }
+
             let disposition = req.headers().get("Content-Disposition");
             let filename = match disposition.map(|s| s.to_str()) {
                 Some(Ok(dis)) => {
@@ -238,6 +342,8 @@ impl<S> FromRequest<S> for FileStream {
                 name: filename,
                 content_type,
                 size,
+                resumable: None,
+                progress: None,
                 client_body: None,
                 #[cfg(feature = "server")]
                 server_body: Some(req.into_body().into_data_stream()),
@@ -298,6 +404,8 @@ impl FromResponse for FileStream {
                 name,
                 size,
                 content_type,
+                resumable: None,
+                progress: None,
                 client_body: Some(Box::pin(res.bytes_stream())),
                 #[cfg(feature = "server")]
                 server_body: None,
@@ -346,6 +454,8 @@ impl From<FileData> for FileStream {
             content_type: value.content_type().map(|s| s.to_string()),
             size: Some(value.size()),
             data: Some(value),
+            resumable: None,
+            progress: None,
             client_body: None,
             #[cfg(feature = "server")]
             server_body: None,
@@ -2,6 +2,10 @@ use super::*;
 
 pub use axum::extract::Form;
 
+impl<T> crate::BodyContentType for Form<T> {
+    const CONTENT_TYPE: &'static str = "application/x-www-form-urlencoded";
+}
+
 impl<T> IntoRequest for Form<T>
 where
     T: Serialize + 'static + DeserializeOwned,
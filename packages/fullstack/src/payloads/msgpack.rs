@@ -22,6 +22,10 @@ use serde::{de::DeserializeOwned, Serialize};
 #[derive(Debug, Clone, Copy, Default, Deref, DerefMut, From)]
 pub struct MsgPack<T>(pub T);
 
+impl<T> crate::BodyContentType for MsgPack<T> {
+    const CONTENT_TYPE: &'static str = "application/msgpack";
+}
+
 impl<T, S> FromRequest<S> for MsgPack<T>
 where
     T: DeserializeOwned,
@@ -75,6 +79,10 @@ where
 #[derive(Debug, Clone, Copy, Default, Deref, DerefMut, From)]
 pub struct MsgPackRaw<T>(pub T);
 
+impl<T> crate::BodyContentType for MsgPackRaw<T> {
+    const CONTENT_TYPE: &'static str = "application/msgpack";
+}
+
 impl<T, S> FromRequest<S> for MsgPackRaw<T>
 where
     T: DeserializeOwned,
@@ -60,6 +60,10 @@ impl MultipartFormData {
     }
 }
 
+impl<S> crate::BodyContentType for MultipartFormData<S> {
+    const CONTENT_TYPE: &'static str = "multipart/form-data";
+}
+
 impl<S> IntoRequest for MultipartFormData<S> {
     fn into_request(
         self,
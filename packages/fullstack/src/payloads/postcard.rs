@@ -26,6 +26,10 @@ use std::future::Future;
 /// [order-of-extractors]: mod@crate::extract#the-order-of-extractors
 pub struct Postcard<T>(pub T);
 
+impl<T> crate::BodyContentType for Postcard<T> {
+    const CONTENT_TYPE: &'static str = "application/postcard";
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum PostcardRejection {
     #[error("Expected request with `Content-Type: application/postcard`")]
@@ -0,0 +1,263 @@
+use crate::{
+    ClientRequest, ClientResponse, Decodes, Encodes, Encoding, FromResponse, IntoRequest,
+    RkyvDeserialize, RkyvEncoding, RkyvSerialize,
+};
+use axum::{
+    body::Bytes,
+    extract::{rejection::BytesRejection, FromRequest, Request},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use dioxus_fullstack_core::{RequestError, ServerFnError};
+use std::future::Future;
+
+/// Rkyv Extractor / Response.
+///
+/// When used as an extractor, it can validate and deserialize request bodies into some type that
+/// implements `rkyv::Archive` and `rkyv::Deserialize`. The request will be rejected (and a
+/// [`RkyvRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: application/rkyv` (or similar) header.
+/// - The body isn't a valid archive of the target type.
+/// - Buffering the request body fails.
+///
+/// When used as a response, it serializes the value with [`RkyvEncoding`] and sets the
+/// `Content-Type: application/rkyv` header.
+///
+/// Rkyv can be used as the input or output of a server function:
+///
+/// ```rust, ignore
+/// #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+/// struct Samples(Vec<f64>);
+///
+/// #[post("/api/samples")]
+/// async fn samples(input: Rkyv<Samples>) -> Result<Rkyv<Samples>> {
+///     Ok(input)
+/// }
+/// ```
+///
+/// ⚠️ Since parsing Rkyv requires consuming the request body, the `Rkyv` extractor must be
+/// *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: mod@crate::extract#the-order-of-extractors
+#[must_use]
+pub struct Rkyv<T>(pub T);
+
+impl<T> crate::BodyContentType for Rkyv<T> {
+    const CONTENT_TYPE: &'static str = "application/rkyv";
+}
+
+/// Check if the request has a valid Rkyv content type header.
+fn is_valid_rkyv_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers.get(header::CONTENT_TYPE) else {
+        return false;
+    };
+
+    let Ok(content_type) = content_type.to_str() else {
+        return false;
+    };
+
+    let Ok(mime) = content_type.parse::<mime::Mime>() else {
+        return false;
+    };
+
+    mime.type_() == "application"
+        && (mime.subtype() == "rkyv" || mime.suffix().is_some_and(|name| name == "rkyv"))
+}
+
+impl<S, T> FromRequest<S> for Rkyv<T>
+where
+    S: Send + Sync,
+    T: RkyvDeserialize,
+{
+    type Rejection = RkyvRejection;
+
+    /// Extract and validate an Rkyv archive from the request body.
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !is_valid_rkyv_content_type(req.headers()) {
+            return Err(RkyvRejection::MissingRkyvContentType);
+        }
+        let bytes = Bytes::from_request(req, state).await?;
+        let value =
+            <RkyvEncoding as Decodes<T>>::decode(bytes).ok_or(RkyvRejection::FailedToParseRkyv)?;
+        Ok(Rkyv(value))
+    }
+}
+
+impl<T> IntoResponse for Rkyv<T>
+where
+    T: RkyvSerialize,
+{
+    /// Serialize the value into an Rkyv archive. Returns a 500 Internal Server Error if
+    /// serialization fails.
+    fn into_response(self) -> Response {
+        match <RkyvEncoding as Encodes<T>>::to_bytes(&self.0) {
+            None => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                [(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static(mime::TEXT_PLAIN_UTF_8.as_ref()),
+                )],
+                "Failed to serialize to Rkyv".to_string(),
+            )
+                .into_response(),
+            Some(bytes) => (
+                [(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static(RkyvEncoding::content_type()),
+                )],
+                bytes,
+            )
+                .into_response(),
+        }
+    }
+}
+
+impl<T> IntoRequest for Rkyv<T>
+where
+    T: RkyvSerialize + 'static,
+{
+    fn into_request(
+        self,
+        request: ClientRequest,
+    ) -> impl Future<Output = Result<ClientResponse, RequestError>> + 'static {
+        async move {
+            let bytes = <RkyvEncoding as Encodes<T>>::to_bytes(&self.0)
+                .ok_or_else(|| RequestError::Body("Failed to serialize to Rkyv".into()))?;
+            request
+                .header(header::CONTENT_TYPE, RkyvEncoding::content_type())?
+                .send_raw_bytes(bytes)
+                .await
+        }
+    }
+}
+
+impl<T> FromResponse for Rkyv<T>
+where
+    T: RkyvDeserialize,
+{
+    fn from_response(res: ClientResponse) -> impl Future<Output = Result<Self, ServerFnError>> {
+        async move {
+            let bytes = res.bytes().await?;
+            let value = <RkyvEncoding as Decodes<T>>::decode(bytes)
+                .ok_or_else(|| ServerFnError::Deserialization("Invalid Rkyv archive".into()))?;
+            Ok(Rkyv(value))
+        }
+    }
+}
+
+impl<T> From<T> for Rkyv<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+/// Rejection type for Rkyv extraction failures.
+#[derive(thiserror::Error, Debug)]
+pub enum RkyvRejection {
+    /// The request is missing the required `Content-Type: application/rkyv` header.
+    #[error("Expected request with `Content-Type: application/rkyv`")]
+    MissingRkyvContentType,
+
+    /// The request body is not a valid archive of the expected type.
+    #[error("Invalid Rkyv data")]
+    FailedToParseRkyv,
+
+    /// Failed to read the request body bytes.
+    #[error(transparent)]
+    BytesRejection(#[from] BytesRejection),
+}
+
+impl IntoResponse for RkyvRejection {
+    fn into_response(self) -> Response {
+        use RkyvRejection::*;
+        match self {
+            MissingRkyvContentType => {
+                (StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string()).into_response()
+            }
+            FailedToParseRkyv => (StatusCode::BAD_REQUEST, self.to_string()).into_response(),
+            BytesRejection(rejection) => rejection.into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::body::Body;
+
+    #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Debug, PartialEq)]
+    struct Samples {
+        name: String,
+        values: Vec<f64>,
+    }
+
+    fn samples() -> Samples {
+        Samples {
+            name: "temperature".into(),
+            values: vec![20.5, 21.0, 19.75],
+        }
+    }
+
+    fn request(content_type: &str, body: Bytes) -> Request {
+        Request::builder()
+            .method("POST")
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[test]
+    fn archives_round_trip() {
+        let bytes = <RkyvEncoding as Encodes<Samples>>::to_bytes(&samples()).unwrap();
+        let decoded = <RkyvEncoding as Decodes<Samples>>::decode(bytes);
+        assert_eq!(decoded, Some(samples()));
+    }
+
+    #[test]
+    fn malformed_archives_are_rejected() {
+        let bytes = <RkyvEncoding as Encodes<Samples>>::to_bytes(&samples()).unwrap();
+
+        // Truncated archives
+        for len in [0, 1, bytes.len() / 2, bytes.len() - 1] {
+            let truncated = bytes.slice(..len);
+            assert_eq!(<RkyvEncoding as Decodes<Samples>>::decode(truncated), None);
+        }
+
+        // Archives of a different type
+        let other = <RkyvEncoding as Encodes<u8>>::to_bytes(&255).unwrap();
+        assert_eq!(<RkyvEncoding as Decodes<Samples>>::decode(other), None);
+
+        // Garbage
+        let garbage = Bytes::from_static(&[0xff; 64]);
+        assert_eq!(<RkyvEncoding as Decodes<Samples>>::decode(garbage), None);
+    }
+
+    #[tokio::test]
+    async fn extractor_validates_requests() {
+        let bytes = <RkyvEncoding as Encodes<Samples>>::to_bytes(&samples()).unwrap();
+        let Rkyv(value) = Rkyv::<Samples>::from_request(request("application/rkyv", bytes), &())
+            .await
+            .unwrap();
+        assert_eq!(value, samples());
+
+        let rejection = Rkyv::<Samples>::from_request(
+            request("application/rkyv", Bytes::from_static(&[1, 2, 3])),
+            &(),
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(matches!(rejection, RkyvRejection::FailedToParseRkyv));
+
+        let rejection = Rkyv::<Samples>::from_request(
+            request("application/json", Bytes::from_static(b"{}")),
+            &(),
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(matches!(rejection, RkyvRejection::MissingRkyvContentType));
+    }
+}
@@ -4,12 +4,18 @@ use axum::{
     response::sse::{Event, KeepAlive},
     BoxError,
 };
+use dioxus_core::{spawn, use_hook};
+use dioxus_hooks::use_signal;
+use dioxus_signals::{CopyValue, ReadSignal, ReadableExt, WritableExt};
 use futures::io::AsyncBufReadExt;
 use futures::Stream;
 use futures::{StreamExt, TryStreamExt};
+use futures_channel::mpsc::UnboundedReceiver;
 use http::{header::CONTENT_TYPE, HeaderValue, StatusCode};
 use serde::de::DeserializeOwned;
+use std::future::Future;
 use std::pin::Pin;
+use std::rc::Rc;
 use std::task::{Context, Poll};
 use std::time::Duration;
 
@@ -253,6 +259,188 @@ impl EventBuffer {
     }
 }
 
+/// The id of the last event a reconnecting client received.
+///
+/// [`use_server_events`] passes this to the function that connects to the server. Take it as an
+/// argument of the server function and pass it to `SseTx::with_replay` to send the events the client
+/// missed while it was disconnected:
+///
+/// ```rust, ignore
+/// #[get("/api/feed?last_event_id")]
+/// async fn feed(last_event_id: LastEventId) -> Result<ServerEvents<FeedItem>> {
+///     Ok(ServerEvents::new(move |tx| async move {
+///         let Ok(mut tx) = tx.with_replay(&FEED_REPLAY, last_event_id).await else {
+///             return;
+///         };
+///         // ...
+///     }))
+/// }
+///
+/// let events = use_server_events(feed);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct LastEventId(pub Option<String>);
+
+/// The state of the connection of a [`use_server_events`] hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerEventsState {
+    /// The first connection is being established.
+    Connecting,
+
+    /// The connection is open and receiving events.
+    Open,
+
+    /// The connection was lost and the hook is waiting to reconnect. `attempt` counts the failed
+    /// attempts since the last successful connection.
+    Reconnecting { attempt: u32 },
+
+    /// The server ended the stream of events. The hook does not reconnect.
+    Closed,
+}
+
+/// The delay before the first reconnect if the server didn't set a `retry` time.
+const DEFAULT_RETRY: Duration = Duration::from_secs(1);
+
+/// The longest delay between two reconnects.
+const MAX_RETRY: Duration = Duration::from_secs(30);
+
+/// A hook that receives a stream of Server-Sent Events and reconnects when the connection is lost.
+///
+/// This hook takes a function that returns a future which resolves to a `ServerEvents<T>` - usually a
+/// server function. When the connection drops with an error, the function is called again after a
+/// delay that doubles with every failed attempt, starting from the `retry` time sent by the server.
+/// The function receives the [`LastEventId`] of the last received event, so the server can replay the
+/// events the client missed with `SseTx::with_replay`.
+///
+/// If the server ends the stream normally, the hook doesn't reconnect and the state becomes
+/// [`ServerEventsState::Closed`].
+///
+/// ```rust, ignore
+/// let mut events = use_server_events(feed);
+/// let mut items = use_signal(Vec::new);
+/// use_future(move || async move {
+///     while let Some(Ok(item)) = events.recv().await {
+///         items.push(item);
+///     }
+/// });
+/// ```
+pub fn use_server_events<T, E, F>(
+    mut connect: impl FnMut(LastEventId) -> F + 'static,
+) -> UseServerEvents<T>
+where
+    T: DeserializeOwned + 'static,
+    E: 'static,
+    F: Future<Output = Result<ServerEvents<T>, E>> + 'static,
+{
+    let mut status = use_signal(|| ServerEventsState::Connecting);
+    let mut last_event_id = use_signal(|| None::<String>);
+    let (tx, rx) = use_hook(|| {
+        let (tx, rx) = futures_channel::mpsc::unbounded();
+        (tx, Rc::new(futures::lock::Mutex::new(rx)))
+    });
+
+    use_hook(|| {
+        spawn(async move {
+            let mut retry = DEFAULT_RETRY;
+            let mut attempt = 0;
+
+            loop {
+                // Tell the server which events we already received
+                let last_id = LastEventId(last_event_id.peek().clone());
+                if let Ok(mut events) = connect(last_id).await {
+                    status.set(ServerEventsState::Open);
+                    attempt = 0;
+
+                    let ended = loop {
+                        match events.next_event().await {
+                            Some(Ok(event)) => {
+                                if let Some(id) = event.last_event_id.as_ref() {
+                                    last_event_id.set(Some(id.clone()));
+                                }
+                                if let Some(new_retry) = event.retry {
+                                    retry = new_retry;
+                                }
+                                let data = serde_json::from_str(&event.data).map_err(|err| {
+                                    ServerFnError::Serialization(format!(
+                                        "failed to deserialize event data: {err}"
+                                    ))
+                                });
+                                _ = tx.unbounded_send(data);
+                            }
+                            Some(Err(_)) => break false,
+                            None => break true,
+                        }
+                    };
+
+                    if ended {
+                        status.set(ServerEventsState::Closed);
+                        return;
+                    }
+                }
+
+                attempt += 1;
+                status.set(ServerEventsState::Reconnecting { attempt });
+                let delay = retry.saturating_mul(1 << (attempt - 1).min(16));
+                dioxus_core::internal::sleep(delay.min(MAX_RETRY)).await;
+            }
+        })
+    });
+
+    UseServerEvents {
+        status: use_hook(|| ReadSignal::new(status)),
+        last_event_id: use_hook(|| ReadSignal::new(last_event_id)),
+        events: use_hook(|| CopyValue::new(rx)),
+    }
+}
+
+/// The return type of the [`use_server_events`] hook.
+pub struct UseServerEvents<T: 'static> {
+    status: ReadSignal<ServerEventsState>,
+    last_event_id: ReadSignal<Option<String>>,
+    #[allow(clippy::type_complexity)]
+    events: CopyValue<Rc<futures::lock::Mutex<UnboundedReceiver<Result<T, ServerFnError>>>>>,
+}
+
+impl<T> UseServerEvents<T> {
+    /// Receive the next event, waiting for it if needed.
+    ///
+    /// Events received before a reconnect are kept, so no events are skipped while the hook
+    /// reconnects. Returns `None` once the server ended the stream and all events were received.
+    pub async fn recv(&self) -> Option<Result<T, ServerFnError>> {
+        let events = self.events.cloned();
+        let mut events = events.lock().await;
+        events.next().await
+    }
+
+    /// Get the current state of the connection.
+    pub fn status(&self) -> ReadSignal<ServerEventsState> {
+        self.status
+    }
+
+    /// Returns true if the connection is open.
+    pub fn is_open(&self) -> bool {
+        matches!(self.status.cloned(), ServerEventsState::Open)
+    }
+
+    /// Returns true if the connection was lost and the hook is waiting to reconnect.
+    pub fn is_reconnecting(&self) -> bool {
+        matches!(self.status.cloned(), ServerEventsState::Reconnecting { .. })
+    }
+
+    /// The id of the last event received, which is sent to the server when reconnecting.
+    pub fn last_event_id(&self) -> ReadSignal<Option<String>> {
+        self.last_event_id
+    }
+}
+
+impl<T> Copy for UseServerEvents<T> {}
+impl<T> Clone for UseServerEvents<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
 #[cfg(feature = "server")]
 pub use server_impl::*;
 
@@ -265,37 +453,68 @@ mod server_impl {
     use futures::Future;
     use futures::SinkExt;
     use futures::{Sink, TryStream};
+    use futures_channel::mpsc::SendError;
     use serde::Serialize;
 
     impl<T: 'static> ServerEvents<T> {
         /// Create a `ServerEvents` from a function that is given a sender to send events to the client.
         ///
+        /// Events are buffered without limit if the client reads slower than the function sends them.
+        /// Use [`ServerEvents::bounded`] for long-running or high-volume streams.
+        ///
         /// By default, we send a comment every 15 seconds to keep the connection alive.
         pub fn new<F, R>(f: impl FnOnce(SseTx<T>) -> F + Send + 'static) -> Self
         where
             F: Future<Output = R> + 'static,
             R: 'static + Send,
         {
-            let (tx, mut rx) = futures_channel::mpsc::unbounded();
+            let (tx, rx) = futures_channel::mpsc::unbounded();
 
             let tx = SseTx {
                 sender: tx,
+                replay: None,
                 _marker: std::marker::PhantomData,
             };
 
             // Spawn the user function in the background
             spawn_platform(move || f(tx));
 
-            // Create the stream of events, mapping the incoming events to `Ok`
-            // If the user function ends, the stream will end and the connection will be closed
-            let stream = futures::stream::poll_fn(move |cx| match rx.poll_next_unpin(cx) {
-                std::task::Poll::Ready(Some(event)) => std::task::Poll::Ready(Some(
-                    Ok(event) as Result<axum::response::sse::Event, BoxError>
-                )),
-                std::task::Poll::Ready(None) => std::task::Poll::Ready(None),
-                std::task::Poll::Pending => std::task::Poll::Pending,
-            });
+            Self::from_receiver(rx)
+        }
+
+        /// Create a `ServerEvents` from a function that is given a sender with room for `buffer`
+        /// events.
+        ///
+        /// [`SseTx::send`] waits while the buffer is full, so a slow client slows down the function
+        /// instead of letting the events pile up in memory.
+        ///
+        /// By default, we send a comment every 15 seconds to keep the connection alive.
+        pub fn bounded<F, R>(
+            buffer: usize,
+            f: impl FnOnce(BoundedSseTx<T>) -> F + Send + 'static,
+        ) -> Self
+        where
+            F: Future<Output = R> + 'static,
+            R: 'static + Send,
+        {
+            let (tx, rx) = futures_channel::mpsc::channel(buffer);
+
+            let tx = SseTx {
+                sender: tx,
+                replay: None,
+                _marker: std::marker::PhantomData,
+            };
+
+            // Spawn the user function in the background
+            spawn_platform(move || f(tx));
+
+            Self::from_receiver(rx)
+        }
 
+        /// Create the stream of events from the receiving end of the channel given to the user
+        /// function. If the user function ends, the stream will end and the connection will be closed.
+        fn from_receiver(rx: impl Stream<Item = Event> + Send + 'static) -> Self {
+            let stream = rx.map(Ok::<_, BoxError>);
             let sse = Sse::new(stream.boxed());
 
             Self {
@@ -363,34 +582,145 @@ mod server_impl {
     }
 
     /// A transmitter for sending events to the SSE stream.
-    pub struct SseTx<T> {
-        sender: futures_channel::mpsc::UnboundedSender<axum::response::sse::Event>,
+    ///
+    /// The transmitter given by [`ServerEvents::new`] never waits for the client. The one given by
+    /// [`ServerEvents::bounded`] is a [`BoundedSseTx`], which waits while its buffer is full.
+    pub struct SseTx<T, S = futures_channel::mpsc::UnboundedSender<Event>> {
+        sender: S,
+        replay: Option<ReplayBuffer>,
         _marker: std::marker::PhantomData<fn() -> T>,
     }
 
-    impl<T: Serialize> SseTx<T> {
+    /// A transmitter for sending events to the SSE stream that waits while the buffer is full.
+    pub type BoundedSseTx<T> = SseTx<T, futures_channel::mpsc::Sender<Event>>;
+
+    impl<T: Serialize, S: Sink<Event, Error = SendError> + Unpin> SseTx<T, S> {
         /// Sends an event to the SSE stream.
+        ///
+        /// Returns an error if the client disconnected.
         pub async fn send(&mut self, event: T) -> anyhow::Result<()> {
-            let event = axum::response::sse::Event::default().json_data(event)?;
-            self.sender.unbounded_send(event)?;
+            let event = self.event(event)?;
+            self.sender.send(event).await?;
             Ok(())
         }
+
+        /// Keep the events sent from now on in `replay`, and send the events the client missed.
+        ///
+        /// Every event gets an id from the buffer. When a client reconnects with the id of the last
+        /// event it received in [`LastEventId`], the events after that id are sent again before any
+        /// new events. If the id is older than the oldest event in the buffer, all buffered events
+        /// are sent.
+        ///
+        /// The buffer should outlive the connection, for example in a static or in the state of the
+        /// router, and only one transmitter should send to it at a time.
+        pub async fn with_replay(
+            mut self,
+            replay: &ReplayBuffer,
+            last_event_id: LastEventId,
+        ) -> anyhow::Result<Self> {
+            if let Some(last_event_id) = last_event_id.0 {
+                for event in replay.events_after(&last_event_id) {
+                    self.sender.send(event).await?;
+                }
+            }
+
+            self.replay = Some(replay.clone());
+            Ok(self)
+        }
+
+        /// Serialize an event, and give it an id and keep it in the replay buffer if there is one.
+        fn event(&self, data: T) -> anyhow::Result<Event> {
+            let event = axum::response::sse::Event::default().json_data(data)?;
+            Ok(match &self.replay {
+                Some(replay) => replay.push(event),
+                None => event,
+            })
+        }
+    }
+
+    /// A buffer of the most recent events of a stream, used to send the events a client missed
+    /// while it was disconnected. See [`SseTx::with_replay`].
+    ///
+    /// Cloning the buffer is cheap and the clones share the same events.
+    #[derive(Clone)]
+    pub struct ReplayBuffer {
+        inner: std::sync::Arc<std::sync::Mutex<ReplayState>>,
+    }
+
+    struct ReplayState {
+        capacity: usize,
+        next_id: u64,
+        events: std::collections::VecDeque<(u64, Event)>,
+    }
+
+    impl ReplayBuffer {
+        /// Create a buffer that keeps the last `capacity` events.
+        pub fn new(capacity: usize) -> Self {
+            Self {
+                inner: std::sync::Arc::new(std::sync::Mutex::new(ReplayState {
+                    capacity,
+                    next_id: 1,
+                    events: std::collections::VecDeque::with_capacity(capacity),
+                })),
+            }
+        }
+
+        /// The id of the most recent event, if any event was sent.
+        pub fn last_id(&self) -> Option<String> {
+            let state = self.inner.lock().unwrap();
+            state.events.back().map(|(id, _)| id.to_string())
+        }
+
+        /// Give `event` the next id and keep it, dropping the oldest event if the buffer is full.
+        fn push(&self, event: Event) -> Event {
+            let mut state = self.inner.lock().unwrap();
+            let id = state.next_id;
+            state.next_id += 1;
+
+            let event = event.id(id.to_string());
+            if state.capacity > 0 {
+                if state.events.len() == state.capacity {
+                    state.events.pop_front();
+                }
+                state.events.push_back((id, event.clone()));
+            }
+            event
+        }
+
+        /// The buffered events after `last_event_id`. Unknown ids replay nothing, since they come
+        /// from a different stream or a previous run of the server.
+        fn events_after(&self, last_event_id: &str) -> Vec<Event> {
+            let Ok(last_event_id) = last_event_id.parse::<u64>() else {
+                return Vec::new();
+            };
+
+            let state = self.inner.lock().unwrap();
+            if last_event_id >= state.next_id {
+                return Vec::new();
+            }
+            state
+                .events
+                .iter()
+                .filter(|(id, _)| *id > last_event_id)
+                .map(|(_, event)| event.clone())
+                .collect()
+        }
     }
 
-    impl<T> std::ops::Deref for SseTx<T> {
-        type Target = futures_channel::mpsc::UnboundedSender<axum::response::sse::Event>;
+    impl<T, S> std::ops::Deref for SseTx<T, S> {
+        type Target = S;
         fn deref(&self) -> &Self::Target {
             &self.sender
         }
     }
 
-    impl<T> std::ops::DerefMut for SseTx<T> {
+    impl<T, S> std::ops::DerefMut for SseTx<T, S> {
         fn deref_mut(&mut self) -> &mut Self::Target {
             &mut self.sender
         }
     }
 
-    impl<T: Serialize> Sink<T> for SseTx<T> {
+    impl<T: Serialize, S: Sink<Event, Error = SendError> + Unpin> Sink<T> for SseTx<T, S> {
         type Error = anyhow::Error;
 
         fn poll_ready(
@@ -401,8 +731,8 @@ mod server_impl {
         }
 
         fn start_send(mut self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
-            let event = axum::response::sse::Event::default().json_data(item)?;
-            self.sender.start_send(event).map_err(|e| e.into())
+            let event = self.event(item)?;
+            self.sender.start_send_unpin(event).map_err(|e| e.into())
         }
 
         fn poll_flush(
@@ -420,3 +750,95 @@ mod server_impl {
         }
     }
 }
+
+#[cfg(all(test, feature = "server"))]
+mod test {
+    use super::*;
+    use axum_core::response::IntoResponse;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[tokio::test]
+    async fn bounded_events_wait_for_slow_client() {
+        const BUFFER: usize = 2;
+        const EVENTS: usize = 16;
+
+        let sent = Arc::new(AtomicUsize::new(0));
+        let events = ServerEvents::<usize>::bounded(BUFFER, {
+            let sent = sent.clone();
+            move |mut tx| async move {
+                for event in 0..EVENTS {
+                    if tx.send(event).await.is_err() {
+                        break;
+                    }
+                    sent.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        })
+        .with_keep_alive(None);
+
+        let mut body = events.into_response().into_body().into_data_stream();
+        let mut received = 0;
+        while let Some(chunk) = body.next().await {
+            let chunk = String::from_utf8(chunk.unwrap().to_vec()).unwrap();
+            received += chunk.matches("data:").count();
+
+            // Read slower than the producer writes
+            tokio::time::sleep(Duration::from_millis(5)).await;
+
+            // The channel holds the buffer plus one slot for the sender
+            let sent = sent.load(Ordering::SeqCst);
+            assert!(
+                sent <= received + BUFFER + 1,
+                "sent {sent} events while only {received} were received"
+            );
+        }
+
+        assert_eq!(received, EVENTS);
+    }
+
+    #[tokio::test]
+    async fn replay_sends_missed_events() {
+        let replay = ReplayBuffer::new(3);
+
+        let connect = |last_event_id: Option<&str>, events: std::ops::Range<usize>| {
+            let replay = replay.clone();
+            let last_event_id = LastEventId(last_event_id.map(str::to_string));
+            ServerEvents::<usize>::new(move |tx| async move {
+                let mut tx = tx.with_replay(&replay, last_event_id).await.unwrap();
+                for event in events {
+                    tx.send(event).await.unwrap();
+                }
+            })
+            .with_keep_alive(None)
+        };
+
+        let read = |events: ServerEvents<usize>| async move {
+            let body = events.into_response().into_body().into_data_stream();
+            let chunks: Vec<_> = body.map(|chunk| chunk.unwrap()).collect().await;
+            let body = String::from_utf8(chunks.concat()).unwrap();
+            body.lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|data| data.trim().parse::<usize>().unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // The first connection sends events 0 to 4 with ids 1 to 5
+        assert_eq!(read(connect(None, 0..5)).await, [0, 1, 2, 3, 4]);
+        assert_eq!(replay.last_id().as_deref(), Some("5"));
+
+        // A client that saw id 3 gets 3 and 4 again before the new events
+        assert_eq!(read(connect(Some("3"), 5..6)).await, [3, 4, 5]);
+
+        // Ids older than the buffer replay everything that is left
+        assert_eq!(read(connect(Some("1"), 6..6)).await, [3, 4, 5]);
+
+        // Unknown ids replay nothing
+        assert_eq!(
+            read(connect(Some("other"), 6..6)).await,
+            Vec::<usize>::new()
+        );
+    }
+}
@@ -1,8 +1,8 @@
 #![allow(clippy::type_complexity)]
 
 use crate::{
-    CborEncoding, ClientRequest, ClientResponse, Encoding, FromResponse, IntoRequest, JsonEncoding,
-    ServerFnError,
+    CborEncoding, ClientRequest, ClientResponse, Decodes, Encodes, Encoding, FromResponse,
+    IntoRequest, JsonEncoding, ServerFnError,
 };
 use axum::extract::{FromRequest, Request};
 use axum_core::response::IntoResponse;
@@ -10,10 +10,9 @@ use bytes::{Buf as _, Bytes};
 use dioxus_fullstack_core::{HttpError, RequestError};
 use futures::{Stream, StreamExt};
 #[cfg(feature = "server")]
-use futures_channel::mpsc::UnboundedSender;
+use futures_channel::mpsc::{Sender, UnboundedSender};
 use headers::{ContentType, Header};
 use send_wrapper::SendWrapper;
-use serde::{de::DeserializeOwned, Serialize};
 use std::{future::Future, marker::PhantomData, pin::Pin};
 
 /// A stream of text data.
@@ -62,6 +61,13 @@ pub type JsonStream<T> = Streaming<T, JsonEncoding>;
 /// websocket message.
 pub type CborStream<T> = Streaming<T, CborEncoding>;
 
+/// A stream of Rkyv-encoded data.
+///
+/// Each item is framed like the items of a [`CborStream`], and decoded from a zero-copy archive on
+/// the receiving side. Archives received by the server are validated before they are deserialized.
+#[cfg(feature = "rkyv")]
+pub type RkyvStream<T> = Streaming<T, crate::RkyvEncoding>;
+
 /// A stream of manually chunked binary data.
 ///
 /// This encoding preserves chunk boundaries by framing each chunk with its length, using Websocket
@@ -131,6 +137,8 @@ impl<T: 'static + Send, E> Streaming<T, E> {
     /// Spawns a new task that produces items for the stream.
     ///
     /// The callback is provided an `UnboundedSender` that can be used to send items to the stream.
+    /// Items are buffered without limit if the client reads slower than the task produces them. Use
+    /// [`Streaming::spawn_bounded`] for large or long-running streams.
     #[cfg(feature = "server")]
     pub fn spawn<F>(callback: impl FnOnce(UnboundedSender<T>) -> F + Send + 'static) -> Self
     where
@@ -144,6 +152,41 @@ impl<T: 'static + Send, E> Streaming<T, E> {
         Self::new(rx)
     }
 
+    /// Spawns a new task that produces items for the stream, buffering at most `buffer` items.
+    ///
+    /// The callback is provided a bounded `Sender`. Sending with [`SinkExt::send`](futures::SinkExt::send)
+    /// waits while the buffer is full, so a slow client slows down the task instead of letting the
+    /// items pile up in memory.
+    ///
+    /// ```rust, ignore
+    /// #[get("/api/export")]
+    /// async fn export() -> Result<JsonStream<Row>> {
+    ///     Ok(JsonStream::spawn_bounded(16, |mut tx| async move {
+    ///         for row in load_rows().await {
+    ///             if tx.send(row).await.is_err() {
+    ///                 // The client disconnected
+    ///                 break;
+    ///             }
+    ///         }
+    ///     }))
+    /// }
+    /// ```
+    #[cfg(feature = "server")]
+    pub fn spawn_bounded<F>(
+        buffer: usize,
+        callback: impl FnOnce(Sender<T>) -> F + Send + 'static,
+    ) -> Self
+    where
+        F: Future<Output = ()> + 'static,
+        T: Send,
+    {
+        let (tx, rx) = futures_channel::mpsc::channel(buffer);
+
+        crate::spawn_platform(move || callback(tx));
+
+        Self::new(rx)
+    }
+
     /// Returns the next item in the stream, or `None` if the stream has ended.
     pub async fn next(&mut self) -> Option<Result<T, StreamingError>> {
         self.stream.as_mut().next().await
@@ -217,7 +260,7 @@ impl IntoResponse for Streaming<Bytes> {
     }
 }
 
-impl<T: DeserializeOwned + Serialize + 'static, E: Encoding> IntoResponse for Streaming<T, E> {
+impl<T: 'static, E: Encodes<T>> IntoResponse for Streaming<T, E> {
     fn into_response(self) -> axum_core::response::Response {
         let res = self.stream.map(|r| match r {
             Ok(res) => match encode_stream_frame::<T, E>(res) {
@@ -271,9 +314,7 @@ impl FromResponse for Streaming<Bytes> {
     }
 }
 
-impl<T: DeserializeOwned + Serialize + 'static + Send, E: Encoding> FromResponse
-    for Streaming<T, E>
-{
+impl<T: 'static + Send, E: Decodes<T>> FromResponse for Streaming<T, E> {
     fn from_response(res: ClientResponse) -> impl Future<Output = Result<Self, ServerFnError>> {
         SendWrapper::new(async move {
             Ok(Self {
@@ -351,9 +392,7 @@ impl<S> FromRequest<S> for ByteStream {
     }
 }
 
-impl<T: DeserializeOwned + Serialize + 'static + Send, E: Encoding, S> FromRequest<S>
-    for Streaming<T, E>
-{
+impl<T: 'static + Send, E: Decodes<T>, S> FromRequest<S> for Streaming<T, E> {
     type Rejection = ServerFnError;
 
     fn from_request(
@@ -382,6 +421,14 @@ impl<T: DeserializeOwned + Serialize + 'static + Send, E: Encoding, S> FromReque
     }
 }
 
+impl crate::BodyContentType for Streaming<String> {
+    const CONTENT_TYPE: &'static str = "text/plain";
+}
+
+impl crate::BodyContentType for ByteStream {
+    const CONTENT_TYPE: &'static str = "application/octet-stream";
+}
+
 impl IntoRequest for Streaming<String> {
     fn into_request(
         self,
@@ -410,9 +457,7 @@ impl IntoRequest for ByteStream {
     }
 }
 
-impl<T: DeserializeOwned + Serialize + 'static + Send, E: Encoding> IntoRequest
-    for Streaming<T, E>
-{
+impl<T: 'static + Send, E: Encodes<T>> IntoRequest for Streaming<T, E> {
     fn into_request(
         self,
         builder: ClientRequest,
@@ -450,7 +495,7 @@ impl<T, E: Encoding> std::fmt::Debug for Streaming<T, E> {
 /// HTTP response or even a websocket connection.
 ///
 /// Note that the packet is not masked, as it is assumed to be sent over a trusted connection.
-pub fn encode_stream_frame<T: Serialize, E: Encoding>(data: T) -> Option<Bytes> {
+pub fn encode_stream_frame<T, E: Encodes<T>>(data: T) -> Option<Bytes> {
     // We use full advantage of `BytesMut` here, writing a maximally full frame and then shrinking it
     // down to size at the end.
     //
@@ -460,7 +505,7 @@ pub fn encode_stream_frame<T: Serialize, E: Encoding>(data: T) -> Option<Bytes>
     // We allocate 10 extra bytes to account for framing overhead, which we'll shrink after
     let mut bytes = vec![0u8; 10];
 
-    E::encode(data, &mut bytes)?;
+    E::encode(&data, &mut bytes)?;
 
     let len = (bytes.len() - 10) as u64;
     let opcode = 0x82; // FIN + binary opcode
@@ -493,8 +538,8 @@ fn byte_stream_to_client_stream<E, T, S, E1>(
 ) -> Pin<Box<dyn Stream<Item = Result<T, StreamingError>> + Send>>
 where
     S: Stream<Item = Result<Bytes, E1>> + 'static + Send,
-    E: Encoding,
-    T: DeserializeOwned + 'static,
+    E: Decodes<T>,
+    T: 'static,
 {
     Box::pin(stream.flat_map(|bytes| {
         enum DecodeIteratorState {
@@ -541,8 +586,7 @@ where
 /// It cannot handle masked frames, as those are not produced by our encoding function.
 pub fn decode_stream_frame<T, E>(mut frame: Bytes) -> Option<T>
 where
-    E: Encoding,
-    T: DeserializeOwned,
+    E: Decodes<T>,
 {
     decode_stream_frame_multi::<T, E>(&mut frame).and_then(|r| r.ok())
 }
@@ -555,8 +599,7 @@ where
 /// or the frame is not large enough return error StreamingError::Decoding
 fn decode_stream_frame_multi<T, E>(frame: &mut Bytes) -> Option<Result<T, StreamingError>>
 where
-    E: Encoding,
-    T: DeserializeOwned,
+    E: Decodes<T>,
 {
     let (offset, payload_len) = match offset_payload_len(frame)? {
         Ok(r) => r,
@@ -628,3 +671,53 @@ fn offset_payload_len(frame: &Bytes) -> Option<Result<(usize, usize), StreamingE
     }
     Some(Ok((offset, payload_len)))
 }
+
+#[cfg(all(test, feature = "server"))]
+mod test {
+    use super::*;
+    use futures::SinkExt;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    #[tokio::test]
+    async fn bounded_stream_waits_for_slow_client() {
+        const BUFFER: usize = 4;
+        const ITEMS: usize = 32;
+
+        let sent = Arc::new(AtomicUsize::new(0));
+        let mut stream = TextStream::spawn_bounded(BUFFER, {
+            let sent = sent.clone();
+            move |mut tx| async move {
+                for item in 0..ITEMS {
+                    if tx.send(item.to_string()).await.is_err() {
+                        break;
+                    }
+                    sent.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        });
+
+        let mut received = 0;
+        while let Some(item) = stream.next().await {
+            assert_eq!(item.unwrap(), received.to_string());
+            received += 1;
+
+            // Read slower than the producer writes
+            tokio::time::sleep(Duration::from_millis(5)).await;
+
+            // The channel holds the buffer plus one slot for the sender
+            let sent = sent.load(Ordering::SeqCst);
+            assert!(
+                sent <= received + BUFFER + 1,
+                "sent {sent} items while only {received} were received"
+            );
+        }
+
+        assert_eq!(received, ITEMS);
+    }
+}
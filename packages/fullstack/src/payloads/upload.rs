@@ -0,0 +1,1021 @@
+//! The resumable upload protocol used by [`FileStream::resumable`].
+//!
+//! A resumable upload is a series of requests to the same server function:
+//!
+//! 1. The client announces the upload with an `X-Upload-Length` header and an empty body. The
+//!    server prepares the [`UploadSink`] and responds with `201 Created` and an `X-Upload-Id`.
+//! 2. The client sends the file in chunks. Each chunk carries the `X-Upload-Id` and the
+//!    `X-Upload-Offset` it starts at. The server responds with `204 No Content` and the new offset,
+//!    or `409 Conflict` and the current offset if the chunk doesn't start where the upload ended.
+//! 3. A request with an `X-Upload-Id` but no offset asks for the current offset, which lets the
+//!    client continue after a failed chunk or in a later session.
+//!
+//! Once the last chunk arrives, the sink assembles the file and the server function runs with a
+//! [`FileStream`] that reads the assembled file. Its response is the response to the last chunk.
+
+use super::*;
+use crate::UploadProgress;
+use dioxus_fullstack_core::RequestError;
+use dioxus_html::FileData;
+use dioxus_signals::{SyncSignal, WritableExt};
+use http::{header, StatusCode};
+use std::pin::Pin;
+
+#[cfg(feature = "server")]
+use futures::future::BoxFuture;
+
+#[cfg(feature = "server")]
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+pub(crate) const UPLOAD_ID: &str = "X-Upload-Id";
+pub(crate) const UPLOAD_OFFSET: &str = "X-Upload-Offset";
+pub(crate) const UPLOAD_LENGTH: &str = "X-Upload-Length";
+
+/// The client side settings of a resumable upload.
+#[derive(Clone, Debug)]
+pub(crate) struct ResumableOptions {
+    pub(crate) chunk_size: u64,
+    pub(crate) max_retries: u32,
+    pub(crate) upload_id: Option<String>,
+}
+
+impl Default for ResumableOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: 8 * 1024 * 1024,
+            max_retries: 3,
+            upload_id: None,
+        }
+    }
+}
+
+/// Build a `Content-Disposition` header value with an ascii escaped file name.
+pub(crate) fn attachment_disposition(name: &str) -> String {
+    let filename: String = name
+        .chars()
+        .flat_map(|c| std::ascii::escape_default(c as u8))
+        .map(char::from)
+        .collect();
+    format!("attachment; filename=\"{}\"", filename)
+}
+
+fn upload_offset(headers: &http::HeaderMap) -> Option<u64> {
+    headers
+        .get(UPLOAD_OFFSET)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+fn update_progress(
+    progress: &mut Option<SyncSignal<UploadProgress>>,
+    f: impl FnOnce(&mut UploadProgress),
+) {
+    if let Some(progress) = progress {
+        f(&mut progress.write());
+    }
+}
+
+/// Counts the failed requests of an upload since the last chunk the server acknowledged.
+struct Retries {
+    count: u32,
+    max: u32,
+}
+
+impl Retries {
+    fn new(max: u32) -> Self {
+        Self { count: 0, max }
+    }
+
+    /// Record a failed request. Returns false once the retries are used up.
+    fn retry(&mut self) -> bool {
+        if self.count >= self.max {
+            return false;
+        }
+        self.count += 1;
+        true
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+    }
+}
+
+/// What the client does after the server answered a chunk.
+#[derive(Debug, PartialEq)]
+enum ChunkReply {
+    /// The server acknowledged the chunk. Continue at the offset.
+    Acknowledged(u64),
+    /// The chunk didn't start where the upload ended. Continue at the offset the server expects.
+    Conflict(u64),
+    /// This was the last chunk, or the server rejected it. The response is the result.
+    Finished,
+}
+
+impl ChunkReply {
+    fn new(status: StatusCode, headers: &http::HeaderMap) -> Self {
+        match (status, upload_offset(headers)) {
+            (StatusCode::NO_CONTENT, Some(next)) => Self::Acknowledged(next),
+            (StatusCode::CONFLICT, Some(next)) => Self::Conflict(next),
+            _ => Self::Finished,
+        }
+    }
+}
+
+/// Send `file` with the resumable upload protocol.
+///
+/// Failed requests and chunks the server rejects with a conflict are retried from the offset the
+/// server acknowledged, up to `options.max_retries` times in a row. If the upload still fails,
+/// the last error or response is returned and the upload can be continued later with the id
+/// reported to `progress`.
+pub(crate) async fn send_resumable(
+    file: FileData,
+    name: String,
+    content_type: String,
+    options: ResumableOptions,
+    mut progress: Option<SyncSignal<UploadProgress>>,
+    request: ClientRequest,
+) -> ClientResult {
+    let total = file.size();
+    update_progress(&mut progress, |progress| {
+        progress.total = Some(total);
+        progress.upload_id = options.upload_id.clone();
+    });
+
+    let upload_id = match options.upload_id {
+        Some(upload_id) => upload_id,
+        None => {
+            let res = request
+                .clone()
+                .header(UPLOAD_LENGTH, total.to_string())?
+                .header(header::CONTENT_TYPE, content_type)?
+                .header(header::CONTENT_DISPOSITION, attachment_disposition(&name))?
+                .send_empty_body()
+                .await?;
+
+            let upload_id = res
+                .headers()
+                .get(UPLOAD_ID)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string());
+
+            // The server rejected the upload, so its response is the result of the server function
+            let (StatusCode::CREATED, Some(upload_id)) = (res.status(), upload_id) else {
+                return Ok(res);
+            };
+
+            update_progress(&mut progress, |progress| {
+                progress.upload_id = Some(upload_id.clone());
+            });
+            upload_id
+        }
+    };
+
+    let mut reader = ChunkReader::new(file);
+    let mut offset = None;
+    let mut retries = Retries::new(options.max_retries);
+
+    loop {
+        // Ask the server where to continue if we don't know it yet
+        let current = match offset {
+            Some(offset) => offset,
+            None => {
+                let res = request
+                    .clone()
+                    .header(UPLOAD_ID, upload_id.as_str())?
+                    .send_empty_body()
+                    .await;
+                let res = match res {
+                    Ok(res) => res,
+                    Err(err) if retries.retry() => {
+                        tracing::debug!("Retrying upload status request after error: {err}");
+                        continue;
+                    }
+                    Err(err) => return Err(err),
+                };
+                match upload_offset(res.headers()) {
+                    Some(offset) => offset,
+                    // The server doesn't know the upload
+                    None => return Ok(res),
+                }
+            }
+        };
+
+        update_progress(&mut progress, |progress| progress.uploaded = current);
+
+        let length = options.chunk_size.max(1).min(total.saturating_sub(current));
+        let chunk_request = request
+            .clone()
+            .header(UPLOAD_ID, upload_id.as_str())?
+            .header(UPLOAD_OFFSET, current.to_string())?
+            .header(header::CONTENT_TYPE, "application/offset+octet-stream")?;
+
+        match reader
+            .send_chunk(chunk_request, current, length, progress)
+            .await
+        {
+            Ok(res) => match ChunkReply::new(res.status(), res.headers()) {
+                ChunkReply::Acknowledged(next) => {
+                    retries.reset();
+                    offset = Some(next);
+                }
+                // A server that keeps disagreeing about the offset must not loop forever
+                ChunkReply::Conflict(next) if retries.retry() => {
+                    tracing::debug!("Resuming upload at offset {next} after a conflict");
+                    offset = Some(next);
+                }
+                ChunkReply::Conflict(_) | ChunkReply::Finished => return Ok(res),
+            },
+            Err(err) if retries.retry() => {
+                tracing::debug!("Retrying upload chunk after error: {err}");
+                offset = None;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Reads chunks of a file at increasing offsets.
+///
+/// On the web, chunks are slices of the underlying `Blob`. Natively, the file is read as one stream
+/// that is only reopened if a chunk needs to be sent again.
+struct ChunkReader {
+    file: FileData,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[allow(clippy::type_complexity)]
+    stream: Option<
+        Pin<Box<dyn Stream<Item = Result<Bytes, dioxus_core::CapturedError>> + Send + 'static>>,
+    >,
+    #[cfg(not(target_arch = "wasm32"))]
+    position: u64,
+    #[cfg(not(target_arch = "wasm32"))]
+    buffered: Bytes,
+}
+
+impl ChunkReader {
+    fn new(file: FileData) -> Self {
+        Self {
+            file,
+            #[cfg(not(target_arch = "wasm32"))]
+            stream: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            position: 0,
+            #[cfg(not(target_arch = "wasm32"))]
+            buffered: Bytes::new(),
+        }
+    }
+
+    async fn send_chunk(
+        &mut self,
+        request: ClientRequest,
+        offset: u64,
+        length: u64,
+        progress: Option<SyncSignal<UploadProgress>>,
+    ) -> ClientResult {
+        #[cfg(feature = "web")]
+        if cfg!(target_arch = "wasm32") {
+            use wasm_bindgen::JsCast;
+
+            let blob = self
+                .file
+                .inner()
+                .downcast_ref::<web_sys::File>()
+                .and_then(|file| file.dyn_ref::<web_sys::Blob>())
+                .ok_or_else(|| RequestError::Request("FileData is not a browser file".into()))?;
+            let chunk = blob
+                .slice_with_f64_and_f64(offset as f64, (offset + length) as f64)
+                .map_err(|_| RequestError::Request("Failed to slice the file".into()))?;
+
+            return match progress {
+                Some(progress) => {
+                    request
+                        .send_js_value_with_progress(chunk.into(), length, progress)
+                        .await
+                }
+                None => request.send_js_value(chunk.into()).await,
+            };
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            // Split the chunk so progress is reported while it is sent
+            const PIECE: usize = 64 * 1024;
+
+            let chunk = self.read(offset, length).await?;
+            let pieces: Vec<Result<Bytes, StreamingError>> = (0..chunk.len())
+                .step_by(PIECE)
+                .map(|start| Ok(chunk.slice(start..(start + PIECE).min(chunk.len()))))
+                .collect();
+            let stream = futures::stream::iter(pieces);
+
+            return match progress {
+                Some(progress) => {
+                    request
+                        .send_body_stream_with_progress(stream, progress)
+                        .await
+                }
+                None => request.send_body_stream(stream).await,
+            };
+        }
+
+        // Other wasm targets have no way to read the file
+        #[cfg(target_arch = "wasm32")]
+        Err(RequestError::Request(
+            "Resumable uploads are only supported on web and native targets".into(),
+        ))
+    }
+
+    /// Read `length` bytes starting at `offset`.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn read(&mut self, offset: u64, length: u64) -> Result<Bytes, RequestError> {
+        use futures::StreamExt;
+
+        // The server asked for bytes we already read, so start over
+        if offset < self.position {
+            self.stream = None;
+            self.position = 0;
+            self.buffered = Bytes::new();
+        }
+
+        let file = &self.file;
+        let stream = self.stream.get_or_insert_with(|| file.byte_stream());
+        let mut chunk = bytes::BytesMut::with_capacity(length as usize);
+
+        while (chunk.len() as u64) < length {
+            if self.buffered.is_empty() {
+                self.buffered = match stream.next().await {
+                    Some(Ok(bytes)) => bytes,
+                    Some(Err(err)) => {
+                        return Err(RequestError::Request(format!(
+                            "Failed to read the file: {err}"
+                        )))
+                    }
+                    None => return Err(RequestError::Request("The file ended early".into())),
+                };
+                continue;
+            }
+
+            // Skip bytes before the offset
+            if self.position < offset {
+                let skip = (offset - self.position).min(self.buffered.len() as u64);
+                let _ = self.buffered.split_to(skip as usize);
+                self.position += skip;
+                continue;
+            }
+
+            let take = (length - chunk.len() as u64).min(self.buffered.len() as u64);
+            chunk.extend_from_slice(&self.buffered.split_to(take as usize));
+            self.position += take;
+        }
+
+        Ok(chunk.freeze())
+    }
+}
+
+/// A rejection of the [`FileStream`] extractor.
+///
+/// Requests of the resumable upload protocol that don't complete the upload are answered with
+/// [`FileStreamRejection::Upload`], so the server function only runs once the whole file arrived.
+#[derive(thiserror::Error, Debug)]
+pub enum FileStreamRejection {
+    /// A request of the resumable upload protocol was handled, but the upload isn't complete.
+    #[error("Upload {id} is at offset {offset}")]
+    Upload {
+        status: StatusCode,
+        id: String,
+        offset: u64,
+    },
+
+    /// The upload id is unknown. The upload either expired or already completed.
+    #[error("Unknown upload")]
+    UnknownUpload,
+
+    /// The request doesn't follow the resumable upload protocol.
+    #[error("Invalid upload request: {0}")]
+    InvalidUpload(&'static str),
+
+    /// The [`UploadSink`] failed to store the upload.
+    #[error("Failed to store the upload: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl IntoResponse for FileStreamRejection {
+    fn into_response(self) -> axum::response::Response {
+        use FileStreamRejection::*;
+        match self {
+            Upload { status, id, offset } => (
+                status,
+                [(UPLOAD_ID, id), (UPLOAD_OFFSET, offset.to_string())],
+            )
+                .into_response(),
+            UnknownUpload => (StatusCode::NOT_FOUND, self.to_string()).into_response(),
+            InvalidUpload(_) => (StatusCode::BAD_REQUEST, self.to_string()).into_response(),
+            Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response(),
+        }
+    }
+}
+
+/// A resumable upload, as announced by the client.
+#[cfg(feature = "server")]
+#[derive(Clone, Debug)]
+pub struct UploadInfo {
+    /// The id the server assigned to the upload.
+    pub id: String,
+
+    /// The name of the file.
+    pub file_name: String,
+
+    /// The size of the file in bytes.
+    pub size: u64,
+
+    /// The content type of the file, if the client sent one.
+    pub content_type: Option<String>,
+}
+
+/// A writer returned by [`UploadSink::write`].
+#[cfg(feature = "server")]
+pub type UploadWriter = Pin<Box<dyn tokio::io::AsyncWrite + Send>>;
+
+/// Where the server assembles resumable uploads.
+///
+/// Every chunk is written with a new writer that starts at the offset the chunk starts at. The
+/// server only acknowledges bytes after the writer was shut down, so a chunk that failed may be
+/// written again over bytes the sink already received.
+///
+/// The default sink is a [`FileSystemSink`] in the temporary directory. Use a different one by
+/// adding an [`Uploads`] extension to the router:
+///
+/// ```rust, ignore
+/// let router = axum::Router::new()
+///     .serve_dioxus_application(ServeConfig::new(), app)
+///     .layer(axum::Extension(Uploads::new(FileSystemSink::new("/var/uploads"))));
+/// ```
+#[cfg(feature = "server")]
+pub trait UploadSink: Send + Sync + 'static {
+    /// Prepare the storage for a new upload.
+    fn create<'a>(&'a self, upload: &'a UploadInfo) -> BoxFuture<'a, std::io::Result<()>>;
+
+    /// Open a writer that writes to the upload, starting at `offset`.
+    fn write<'a>(
+        &'a self,
+        upload: &'a UploadInfo,
+        offset: u64,
+    ) -> BoxFuture<'a, std::io::Result<UploadWriter>>;
+
+    /// Called once every byte of the upload was received. The returned body is the body of the
+    /// [`FileStream`] the server function receives.
+    fn complete<'a>(
+        &'a self,
+        upload: &'a UploadInfo,
+    ) -> BoxFuture<'a, std::io::Result<axum_core::body::BodyDataStream>>;
+
+    /// Discard an upload that was abandoned by the client.
+    fn abort<'a>(&'a self, upload: &'a UploadInfo) -> BoxFuture<'a, std::io::Result<()>>;
+}
+
+/// An [`UploadSink`] that assembles uploads as files in a directory.
+///
+/// The assembled file is removed once the server function read the whole [`FileStream`].
+#[cfg(feature = "server")]
+#[derive(Clone, Debug)]
+pub struct FileSystemSink {
+    directory: PathBuf,
+}
+
+#[cfg(feature = "server")]
+impl FileSystemSink {
+    /// Create a sink that stores uploads in `directory`. The directory is created if it doesn't
+    /// exist.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, upload: &UploadInfo) -> PathBuf {
+        self.directory.join(format!("{}.part", upload.id))
+    }
+}
+
+#[cfg(feature = "server")]
+impl UploadSink for FileSystemSink {
+    fn create<'a>(&'a self, upload: &'a UploadInfo) -> BoxFuture<'a, std::io::Result<()>> {
+        Box::pin(async move {
+            tokio::fs::create_dir_all(&self.directory).await?;
+            tokio::fs::File::create(self.path(upload)).await?;
+            Ok(())
+        })
+    }
+
+    fn write<'a>(
+        &'a self,
+        upload: &'a UploadInfo,
+        offset: u64,
+    ) -> BoxFuture<'a, std::io::Result<UploadWriter>> {
+        Box::pin(async move {
+            use tokio::io::AsyncSeekExt;
+
+            let mut file = tokio::fs::OpenOptions::new()
+                .write(true)
+                .open(self.path(upload))
+                .await?;
+            file.seek(std::io::SeekFrom::Start(offset)).await?;
+            Ok(Box::pin(file) as UploadWriter)
+        })
+    }
+
+    fn complete<'a>(
+        &'a self,
+        upload: &'a UploadInfo,
+    ) -> BoxFuture<'a, std::io::Result<axum_core::body::BodyDataStream>> {
+        Box::pin(async move {
+            use futures::StreamExt;
+
+            let path = self.path(upload);
+            let file = tokio::fs::File::open(&path).await?;
+
+            // Remove the file once it was read to the end
+            let cleanup = futures::stream::once(async move {
+                if let Err(err) = tokio::fs::remove_file(&path).await {
+                    tracing::warn!("Failed to remove upload {}: {err}", path.display());
+                }
+            })
+            .filter_map(|_| async { None::<std::io::Result<Bytes>> });
+            let stream = tokio_util::io::ReaderStream::new(file).chain(cleanup);
+
+            Ok(axum_core::body::Body::from_stream(stream).into_data_stream())
+        })
+    }
+
+    fn abort<'a>(&'a self, upload: &'a UploadInfo) -> BoxFuture<'a, std::io::Result<()>> {
+        Box::pin(async move {
+            match tokio::fs::remove_file(self.path(upload)).await {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+#[cfg(feature = "server")]
+struct PendingUpload {
+    info: UploadInfo,
+    offset: u64,
+    last_active: Instant,
+    finished: bool,
+}
+
+#[cfg(feature = "server")]
+type PendingUploads = Mutex<HashMap<String, Arc<futures::lock::Mutex<PendingUpload>>>>;
+
+/// The resumable uploads of a server, along with the [`UploadSink`] that stores them.
+///
+/// Add it to the router as an [`axum::Extension`] to configure the sink. Without it, uploads are
+/// stored in the temporary directory.
+#[cfg(feature = "server")]
+#[derive(Clone)]
+pub struct Uploads {
+    sink: Arc<dyn UploadSink>,
+    pending: Arc<PendingUploads>,
+    expire_after: Duration,
+}
+
+#[cfg(feature = "server")]
+impl Uploads {
+    /// Store uploads in `sink`.
+    pub fn new(sink: impl UploadSink) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            pending: Default::default(),
+            expire_after: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+
+    /// Discard uploads that received no chunks for `duration`. Defaults to one day.
+    pub fn expire_after(self, duration: Duration) -> Self {
+        Self {
+            expire_after: duration,
+            ..self
+        }
+    }
+
+    fn get(&self, id: &str) -> Option<Arc<futures::lock::Mutex<PendingUpload>>> {
+        self.pending.lock().unwrap().get(id).cloned()
+    }
+
+    /// Abort uploads that expired. Uploads that are receiving a chunk right now are kept.
+    async fn expire(&self) {
+        let mut expired = vec![];
+        self.pending.lock().unwrap().retain(|_, upload| {
+            let Some(upload) = upload.try_lock() else {
+                return true;
+            };
+            if upload.last_active.elapsed() < self.expire_after {
+                return true;
+            }
+            expired.push(upload.info.clone());
+            false
+        });
+
+        for upload in expired {
+            if let Err(err) = self.sink.abort(&upload).await {
+                tracing::warn!("Failed to abort expired upload {}: {err}", upload.id);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Default for Uploads {
+    fn default() -> Self {
+        Self::new(FileSystemSink::new(
+            std::env::temp_dir().join("dioxus-uploads"),
+        ))
+    }
+}
+
+/// Upload ids are the only thing that authorizes writing to an upload, so they are random v4
+/// uuids from the operating system's secure random number generator.
+#[cfg(feature = "server")]
+fn new_upload_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Handle a request of the resumable upload protocol. This only returns a [`FileStream`] once the
+/// request completed the upload.
+#[cfg(feature = "server")]
+pub(crate) async fn extract_upload(
+    req: axum_core::extract::Request,
+) -> Result<FileStream, FileStreamRejection> {
+    use futures::StreamExt;
+    use tokio::io::AsyncWriteExt;
+
+    static DEFAULT_UPLOADS: LazyLock<Uploads> = LazyLock::new(Uploads::default);
+
+    let uploads = req
+        .extensions()
+        .get::<Uploads>()
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_UPLOADS.clone());
+    let headers = req.headers();
+
+    let Some(id) = headers.get(UPLOAD_ID).and_then(|id| id.to_str().ok()) else {
+        let size = headers
+            .get(UPLOAD_LENGTH)
+            .and_then(|length| length.to_str().ok())
+            .and_then(|length| length.parse().ok())
+            .ok_or(FileStreamRejection::InvalidUpload(
+                "expected a numeric X-Upload-Length",
+            ))?;
+        let file_name = headers
+            .get(header::CONTENT_DISPOSITION)
+            .and_then(|disposition| disposition.to_str().ok())
+            .and_then(|disposition| {
+                content_disposition::parse_content_disposition(disposition).filename_full()
+            })
+            .unwrap_or_else(|| "file".to_string());
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map(|content_type| content_type.to_string());
+
+        uploads.expire().await;
+
+        let info = UploadInfo {
+            id: new_upload_id(),
+            file_name,
+            size,
+            content_type,
+        };
+        uploads.sink.create(&info).await?;
+        uploads.pending.lock().unwrap().insert(
+            info.id.clone(),
+            Arc::new(futures::lock::Mutex::new(PendingUpload {
+                info: info.clone(),
+                offset: 0,
+                last_active: Instant::now(),
+                finished: false,
+            })),
+        );
+
+        return Err(FileStreamRejection::Upload {
+            status: StatusCode::CREATED,
+            id: info.id,
+            offset: 0,
+        });
+    };
+
+    let id = id.to_string();
+    let pending = uploads.get(&id).ok_or(FileStreamRejection::UnknownUpload)?;
+    let mut upload = pending.lock().await;
+    if upload.finished {
+        return Err(FileStreamRejection::UnknownUpload);
+    }
+    upload.last_active = Instant::now();
+
+    let offset = match headers.get(UPLOAD_OFFSET).map(|offset| offset.to_str()) {
+        Some(Ok(offset)) => offset.parse::<u64>().map_err(|_| {
+            FileStreamRejection::InvalidUpload("expected a numeric X-Upload-Offset")
+        })?,
+        Some(Err(_)) => {
+            return Err(FileStreamRejection::InvalidUpload(
+                "expected a numeric X-Upload-Offset",
+            ))
+        }
+        // A request without an offset asks where to continue
+        None => {
+            return Err(FileStreamRejection::Upload {
+                status: StatusCode::NO_CONTENT,
+                id,
+                offset: upload.offset,
+            })
+        }
+    };
+    if offset != upload.offset {
+        return Err(FileStreamRejection::Upload {
+            status: StatusCode::CONFLICT,
+            id,
+            offset: upload.offset,
+        });
+    }
+
+    let mut writer = uploads.sink.write(&upload.info, offset).await?;
+    let mut body = req.into_body().into_data_stream();
+    let mut written = 0;
+    let mut too_large = false;
+    while let Some(frame) = body.next().await {
+        // The client went away. Keep what arrived so it can continue from there.
+        let Ok(bytes) = frame else {
+            break;
+        };
+        if offset + written + bytes.len() as u64 > upload.info.size {
+            too_large = true;
+            break;
+        }
+        writer.write_all(&bytes).await?;
+        written += bytes.len() as u64;
+    }
+    writer.shutdown().await?;
+    upload.offset += written;
+
+    if too_large {
+        return Err(FileStreamRejection::InvalidUpload(
+            "the chunk extends past X-Upload-Length",
+        ));
+    }
+    if upload.offset < upload.info.size {
+        return Err(FileStreamRejection::Upload {
+            status: StatusCode::NO_CONTENT,
+            id,
+            offset: upload.offset,
+        });
+    }
+
+    upload.finished = true;
+    uploads.pending.lock().unwrap().remove(&id);
+    let body = uploads.sink.complete(&upload.info).await?;
+    let info = upload.info.clone();
+
+    Ok(FileStream::from_raw(
+        info.file_name,
+        Some(info.size),
+        info.content_type
+            .unwrap_or_else(|| "application/octet-stream".to_string()),
+        body,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn conflicts_count_as_retries() {
+        let mut retries = Retries::new(2);
+        assert!(retries.retry());
+        assert!(retries.retry());
+        assert!(!retries.retry());
+
+        retries.reset();
+        assert!(retries.retry());
+    }
+
+    #[test]
+    fn chunk_replies_follow_the_server_offset() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(UPLOAD_OFFSET, "42".parse().unwrap());
+
+        assert_eq!(
+            ChunkReply::new(StatusCode::NO_CONTENT, &headers),
+            ChunkReply::Acknowledged(42)
+        );
+        assert_eq!(
+            ChunkReply::new(StatusCode::CONFLICT, &headers),
+            ChunkReply::Conflict(42)
+        );
+        assert_eq!(
+            ChunkReply::new(StatusCode::OK, &headers),
+            ChunkReply::Finished
+        );
+        assert_eq!(
+            ChunkReply::new(StatusCode::CONFLICT, &http::HeaderMap::new()),
+            ChunkReply::Finished
+        );
+    }
+
+    /// A file that streams its contents in small pieces
+    #[cfg(not(target_arch = "wasm32"))]
+    struct TestFile(Bytes);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    impl dioxus_html::NativeFileData for TestFile {
+        fn name(&self) -> String {
+            "test.txt".into()
+        }
+        fn size(&self) -> u64 {
+            self.0.len() as u64
+        }
+        fn last_modified(&self) -> u64 {
+            0
+        }
+        fn path(&self) -> std::path::PathBuf {
+            "test.txt".into()
+        }
+        fn content_type(&self) -> Option<String> {
+            None
+        }
+        fn read_bytes(
+            &self,
+        ) -> Pin<Box<dyn Future<Output = Result<Bytes, dioxus_core::CapturedError>> + 'static>>
+        {
+            let bytes = self.0.clone();
+            Box::pin(async move { Ok(bytes) })
+        }
+        fn byte_stream(
+            &self,
+        ) -> Pin<Box<dyn Stream<Item = Result<Bytes, dioxus_core::CapturedError>> + 'static + Send>>
+        {
+            let pieces: Vec<_> = self
+                .0
+                .chunks(3)
+                .map(|piece| Ok(self.0.slice_ref(piece)))
+                .collect();
+            Box::pin(futures::stream::iter(pieces))
+        }
+        fn read_string(
+            &self,
+        ) -> Pin<Box<dyn Future<Output = Result<String, dioxus_core::CapturedError>> + 'static>>
+        {
+            let string = String::from_utf8_lossy(&self.0).into_owned();
+            Box::pin(async move { Ok(string) })
+        }
+        fn inner(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn chunks_are_read_from_the_resumed_offset() {
+        let file = FileData::new(TestFile(Bytes::from_static(b"abcdefghijklmnop")));
+        let mut reader = ChunkReader::new(file);
+
+        assert_eq!(reader.read(0, 5).await.unwrap(), "abcde");
+        assert_eq!(reader.read(5, 5).await.unwrap(), "fghij");
+
+        // The server lost the last chunk and asks for it again
+        assert_eq!(reader.read(2, 5).await.unwrap(), "cdefg");
+
+        // The server already has more than the client sent
+        assert_eq!(reader.read(12, 4).await.unwrap(), "mnop");
+        assert!(reader.read(16, 1).await.is_err());
+    }
+
+    #[cfg(feature = "server")]
+    mod server {
+        use super::*;
+        use axum_core::body::Body;
+        use futures::StreamExt;
+
+        fn uploads() -> Uploads {
+            let directory =
+                std::env::temp_dir().join(format!("dioxus-upload-test-{}", new_upload_id()));
+            Uploads::new(FileSystemSink::new(directory))
+        }
+
+        fn request(
+            uploads: &Uploads,
+            headers: &[(&str, String)],
+            body: &'static str,
+        ) -> axum_core::extract::Request {
+            let mut request = http::Request::builder().method("POST").uri("/upload");
+            for (name, value) in headers {
+                request = request.header(*name, value);
+            }
+            let mut request = request.body(Body::from(body)).unwrap();
+            request.extensions_mut().insert(uploads.clone());
+            request
+        }
+
+        fn rejection(result: Result<FileStream, FileStreamRejection>) -> (StatusCode, String, u64) {
+            match result {
+                Err(FileStreamRejection::Upload { status, id, offset }) => (status, id, offset),
+                other => panic!("expected an upload rejection, got {other:?}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn uploads_resume_at_the_acknowledged_offset() {
+            let uploads = uploads();
+
+            let (status, id, offset) = rejection(
+                extract_upload(request(
+                    &uploads,
+                    &[
+                        (UPLOAD_LENGTH, "10".into()),
+                        ("content-disposition", attachment_disposition("notes.txt")),
+                        ("content-type", "text/plain".into()),
+                    ],
+                    "",
+                ))
+                .await,
+            );
+            assert_eq!((status, offset), (StatusCode::CREATED, 0));
+
+            let chunk = |offset: u64, body| {
+                request(
+                    &uploads,
+                    &[(UPLOAD_ID, id.clone()), (UPLOAD_OFFSET, offset.to_string())],
+                    body,
+                )
+            };
+
+            let (status, _, offset) = rejection(extract_upload(chunk(0, "01234")).await);
+            assert_eq!((status, offset), (StatusCode::NO_CONTENT, 5));
+
+            // A chunk that doesn't start where the upload ended is rejected with the real offset
+            let (status, _, offset) = rejection(extract_upload(chunk(3, "34567")).await);
+            assert_eq!((status, offset), (StatusCode::CONFLICT, 5));
+
+            // A request without an offset asks where to continue
+            let (status, _, offset) =
+                rejection(extract_upload(request(&uploads, &[(UPLOAD_ID, id.clone())], "")).await);
+            assert_eq!((status, offset), (StatusCode::NO_CONTENT, 5));
+
+            let mut file = extract_upload(chunk(5, "56789")).await.unwrap();
+            assert_eq!(file.file_name(), "notes.txt");
+            assert_eq!(file.size(), Some(10));
+            assert_eq!(file.content_type(), Some("text/plain"));
+            let mut contents = Vec::new();
+            while let Some(bytes) = file.next().await {
+                contents.extend_from_slice(&bytes.unwrap());
+            }
+            assert_eq!(contents, b"0123456789");
+
+            // The upload is gone once it completed
+            assert!(matches!(
+                extract_upload(chunk(10, "")).await,
+                Err(FileStreamRejection::UnknownUpload)
+            ));
+        }
+
+        #[tokio::test]
+        async fn invalid_uploads_are_rejected() {
+            let uploads = uploads();
+
+            assert!(matches!(
+                extract_upload(request(&uploads, &[], "")).await,
+                Err(FileStreamRejection::InvalidUpload(_))
+            ));
+            assert!(matches!(
+                extract_upload(request(&uploads, &[(UPLOAD_ID, new_upload_id())], "")).await,
+                Err(FileStreamRejection::UnknownUpload)
+            ));
+
+            let (_, id, _) = rejection(
+                extract_upload(request(&uploads, &[(UPLOAD_LENGTH, "4".into())], "")).await,
+            );
+            let too_long = request(
+                &uploads,
+                &[(UPLOAD_ID, id), (UPLOAD_OFFSET, "0".into())],
+                "too long",
+            );
+            assert!(matches!(
+                extract_upload(too_long).await,
+                Err(FileStreamRejection::InvalidUpload(_))
+            ));
+        }
+
+        #[test]
+        fn upload_ids_are_random_uuids() {
+            let id = new_upload_id();
+            assert_eq!(id.len(), 32);
+            assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+            assert_ne!(id, new_upload_id());
+        }
+    }
+}
@@ -20,8 +20,18 @@
 //!
 //! Dioxus Fullstack websockets are typed in both directions, letting the happy path (`.send()` and `.recv()`)
 //! automatically serialize and deserialize messages for you.
-
-use crate::{ClientRequest, Encoding, FromResponse, IntoRequest, JsonEncoding, ServerFnError};
+//!
+//! ## Acknowledgements
+//!
+//! A server that sends faster than the client can handle fills up the buffers of the connection. To
+//! throttle the server to the speed of the client, call `TypedWebsocket::with_acknowledgements` on
+//! the server and `.ack()` on the client after handling each message. The server then waits in
+//! `.send()` while the given number of messages is unacknowledged.
+
+use crate::{
+    ClientRequest, Decodes, Encodes, Encoding, FromResponse, IntoRequest, JsonEncoding,
+    ServerFnError,
+};
 use axum::{
     extract::{FromRequest, Request},
     http::StatusCode,
@@ -37,8 +47,8 @@ use futures::{
     stream::{SplitSink, SplitStream},
     Sink, SinkExt, Stream, StreamExt, TryFutureExt,
 };
-use serde::{de::DeserializeOwned, Serialize};
 use std::{
+    collections::VecDeque,
     marker::PhantomData,
     pin::Pin,
     prelude::rust_2024::Future,
@@ -52,6 +62,10 @@ use {
     gloo_net::websocket::{futures::WebSocket as WsWebsocket, Message as WsMessage},
 };
 
+/// The frame sent by `.ack()`. Typed messages are always sent as binary frames, so this text frame
+/// can't be confused with a message.
+const ACK_FRAME: &str = "\u{6}dioxus-ack";
+
 /// A hook that provides a reactive interface to a WebSocket connection.
 ///
 /// WebSockets provide a full-duplex communication channel over a single, long-lived connection.
@@ -198,8 +212,7 @@ impl<In, Out, E> UseWebsocket<In, Out, E> {
 
     pub async fn send(&self, msg: In) -> Result<(), WebsocketError>
     where
-        In: Serialize,
-        E: Encoding,
+        E: Encodes<In>,
     {
         self.send_raw(Message::Binary(
             E::to_bytes(&msg).ok_or_else(WebsocketError::serialization)?,
@@ -207,6 +220,14 @@ impl<In, Out, E> UseWebsocket<In, Out, E> {
         .await
     }
 
+    /// Acknowledge that a message from the server was handled.
+    ///
+    /// Servers that use `TypedWebsocket::with_acknowledgements` stop sending once too many messages
+    /// are unacknowledged, so call this once for every message received from them.
+    pub async fn ack(&self) -> Result<(), WebsocketError> {
+        self.send_raw(Message::Text(ACK_FRAME.to_string())).await
+    }
+
     /// Receive the next message from the WebSocket connection, deserialized into the `Out` type.
     ///
     /// If the connection is still opening, this will wait until the connection is established.
@@ -216,8 +237,7 @@ impl<In, Out, E> UseWebsocket<In, Out, E> {
     /// are a "failure".
     pub async fn recv(&mut self) -> Result<Out, WebsocketError>
     where
-        Out: DeserializeOwned,
-        E: Encoding,
+        E: Decodes<Out>,
     {
         self.connect().await;
         let ws = self.get_connection()?;
@@ -321,12 +341,13 @@ pub struct Websocket<In = String, Out = String, E = JsonEncoding> {
 impl<I, O, E> Websocket<I, O, E> {
     pub async fn recv(&self) -> Result<O, WebsocketError>
     where
-        O: DeserializeOwned,
-        E: Encoding,
+        E: Decodes<O>,
     {
         loop {
             let msg = self.recv_raw().await?;
             match msg {
+                // Acknowledgements sent by the server are not messages
+                Message::Text(text) if text == ACK_FRAME => continue,
                 Message::Text(text) => {
                     let e: O =
                         E::decode(text.into()).ok_or_else(WebsocketError::deserialization)?;
@@ -357,13 +378,20 @@ impl<I, O, E> Websocket<I, O, E> {
     /// like JSON.
     pub async fn send(&self, msg: I) -> Result<(), WebsocketError>
     where
-        I: Serialize,
-        E: Encoding,
+        E: Encodes<I>,
     {
         let bytes = E::to_bytes(&msg).ok_or_else(WebsocketError::serialization)?;
         self.send_raw(Message::Binary(bytes)).await
     }
 
+    /// Acknowledge that a message from the server was handled.
+    ///
+    /// Servers that use `TypedWebsocket::with_acknowledgements` stop sending once too many messages
+    /// are unacknowledged, so call this once for every message received from them.
+    pub async fn ack(&self) -> Result<(), WebsocketError> {
+        self.send_raw(Message::Text(ACK_FRAME.to_string())).await
+    }
+
     /// Send a raw message over the WebSocket connection.
     ///
     /// This method allows sending text, binary, ping, pong, and close messages directly.
@@ -573,6 +601,9 @@ impl WebSocketOptions {
                         _out: PhantomData,
                         _enc: PhantomData,
                         inner: socket,
+                        window: None,
+                        unacknowledged: 0,
+                        pending: VecDeque::new(),
                     })
                 });
                 async move {
@@ -708,10 +739,53 @@ pub struct TypedWebsocket<In, Out, E = JsonEncoding> {
     _enc: std::marker::PhantomData<fn() -> E>,
 
     inner: axum::extract::ws::WebSocket,
+
+    /// The number of messages that may be unacknowledged before sending waits.
+    window: Option<usize>,
+
+    /// The number of sent messages the client has not acknowledged yet.
+    unacknowledged: usize,
+
+    /// Messages that arrived while waiting for acknowledgements.
+    pending: VecDeque<axum::extract::ws::Message>,
 }
 
 #[cfg(feature = "server")]
-impl<In: DeserializeOwned, Out: Serialize, E: Encoding> TypedWebsocket<In, Out, E> {
+impl<In, Out, E: Encodes<Out> + Decodes<In>> TypedWebsocket<In, Out, E> {
+    /// Wait for acknowledgements from the client before sending more messages.
+    ///
+    /// Once `window` typed messages are unacknowledged, `.send()` waits until the client calls
+    /// `.ack()`. This throttles the server to the speed of the client instead of filling up the
+    /// buffers of the connection. Raw messages are not counted.
+    ///
+    /// ```rust, ignore
+    /// #[get("/api/export")]
+    /// async fn export(options: WebSocketOptions) -> Result<Websocket<(), Row>> {
+    ///     Ok(options.on_upgrade(|socket| async move {
+    ///         let mut socket = socket.with_acknowledgements(8);
+    ///         for row in load_rows().await {
+    ///             if socket.send(row).await.is_err() {
+    ///                 break;
+    ///             }
+    ///         }
+    ///     }))
+    /// }
+    /// ```
+    pub fn with_acknowledgements(mut self, window: usize) -> Self {
+        self.window = Some(window.max(1));
+        self
+    }
+
+    /// The number of sent messages the client has not acknowledged yet.
+    pub fn unacknowledged(&self) -> usize {
+        self.unacknowledged
+    }
+
+    /// Acknowledge that a message from the client was handled.
+    pub async fn ack(&mut self) -> Result<(), WebsocketError> {
+        self.send_raw(Message::Text(ACK_FRAME.to_string())).await
+    }
+
     /// Receive an incoming message from the client.
     pub async fn recv(&mut self) -> Result<In, WebsocketError> {
         self.next()
@@ -720,6 +794,9 @@ impl<In: DeserializeOwned, Out: Serialize, E: Encoding> TypedWebsocket<In, Out,
     }
 
     /// Send an outgoing message.
+    ///
+    /// If acknowledgements are enabled, this waits while the window of unacknowledged messages is
+    /// full.
     pub async fn send(&mut self, msg: Out) -> Result<(), WebsocketError> {
         SinkExt::send(self, msg).await
     }
@@ -728,9 +805,7 @@ impl<In: DeserializeOwned, Out: Serialize, E: Encoding> TypedWebsocket<In, Out,
     pub async fn recv_raw(&mut self) -> Result<Message, WebsocketError> {
         use axum::extract::ws::Message as AxumMessage;
 
-        let message = self
-            .inner
-            .next()
+        let message = futures::future::poll_fn(|cx| self.poll_message(cx))
             .await
             .ok_or_else(WebsocketError::closed_away)?
             .map_err(|_| WebsocketError::AlreadyClosed)?;
@@ -779,17 +854,60 @@ impl<In: DeserializeOwned, Out: Serialize, E: Encoding> TypedWebsocket<In, Out,
     pub fn socket(&mut self) -> &mut axum::extract::ws::WebSocket {
         &mut self.inner
     }
+
+    /// Poll the next message that isn't an acknowledgement, starting with the messages that arrived
+    /// while waiting for acknowledgements.
+    fn poll_message(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<axum::extract::ws::Message, axum::Error>>> {
+        if let Some(message) = self.pending.pop_front() {
+            return Poll::Ready(Some(Ok(message)));
+        }
+
+        loop {
+            match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(message)) if is_ack(&message) => {
+                    self.unacknowledged = self.unacknowledged.saturating_sub(1)
+                }
+                message => return Poll::Ready(message),
+            }
+        }
+    }
+
+    /// Read incoming messages until the window of unacknowledged messages has room again. Other
+    /// messages are kept for the next `.recv()`.
+    fn poll_window(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), WebsocketError>> {
+        let Some(window) = self.window else {
+            return Poll::Ready(Ok(()));
+        };
+
+        while self.unacknowledged >= window {
+            match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(message)) if is_ack(&message) => self.unacknowledged -= 1,
+                Some(Ok(message)) => self.pending.push_back(message),
+                Some(Err(_)) | None => return Poll::Ready(Err(WebsocketError::AlreadyClosed)),
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
 }
 
 #[cfg(feature = "server")]
-impl<In: DeserializeOwned, Out: Serialize, E: Encoding> Stream for TypedWebsocket<In, Out, E> {
+fn is_ack(message: &axum::extract::ws::Message) -> bool {
+    matches!(message, axum::extract::ws::Message::Text(text) if text.as_str() == ACK_FRAME)
+}
+
+#[cfg(feature = "server")]
+impl<In, Out, E: Encodes<Out> + Decodes<In>> Stream for TypedWebsocket<In, Out, E> {
     type Item = Result<In, WebsocketError>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         use axum::extract::ws::Message as AxumMessage;
 
         loop {
-            match ready!(self.inner.poll_next_unpin(cx)) {
+            match ready!(self.poll_message(cx)) {
                 Some(Ok(msg)) => match msg {
                     AxumMessage::Text(utf8_bytes) => {
                         let e: In = E::decode(utf8_bytes.into())
@@ -824,10 +942,12 @@ impl<In: DeserializeOwned, Out: Serialize, E: Encoding> Stream for TypedWebsocke
 }
 
 #[cfg(feature = "server")]
-impl<In: DeserializeOwned, Out: Serialize, E: Encoding> Sink<Out> for TypedWebsocket<In, Out, E> {
+impl<In, Out, E: Encodes<Out> + Decodes<In>> Sink<Out> for TypedWebsocket<In, Out, E> {
     type Error = WebsocketError;
 
     fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.poll_window(cx))?;
+
         Pin::new(&mut self.inner)
             .poll_ready(cx)
             .map_err(|_| WebsocketError::AlreadyClosed)
@@ -842,7 +962,13 @@ impl<In: DeserializeOwned, Out: Serialize, E: Encoding> Sink<Out> for TypedWebso
 
         Pin::new(&mut self.inner)
             .start_send(Message::Binary(to_bytes))
-            .map_err(|_| WebsocketError::AlreadyClosed)
+            .map_err(|_| WebsocketError::AlreadyClosed)?;
+
+        if self.window.is_some() {
+            self.unacknowledged += 1;
+        }
+
+        Ok(())
     }
 
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
@@ -66,11 +66,7 @@ where
                     code,
                     data,
                 } = res.json().await?;
-                Err(ServerFnError::ServerError {
-                    message,
-                    code,
-                    details: data,
-                })
+                Err(crate::version::server_error(message, code, data))
             }
         }
     }
@@ -212,6 +208,52 @@ pub struct CantDecode;
 pub struct DecodeIsVerified;
 impl AssertCanDecode for DecodeIsVerified {}
 
+/// The content type of a type that is sent as the whole body of a request, like `Cbor<T>` or
+/// [`FileStream`](crate::FileStream).
+///
+/// The OpenAPI document uses this to describe the request body of server functions that take a single
+/// argument of this type. Bodies of other server functions are described as JSON objects.
+pub trait BodyContentType {
+    const CONTENT_TYPE: &'static str;
+}
+
+/// Selects [`BodyContentType::CONTENT_TYPE`] of `T` if it is implemented. Used by the server function
+/// macros through autoref specialization:
+///
+/// ```rust, ignore
+/// use dioxus_fullstack::{ContentTypeOf, WithContentType as _, WithoutContentType as _};
+/// let content_type = (&&ContentTypeOf::<T>::new()).content_type();
+/// ```
+#[doc(hidden)]
+pub struct ContentTypeOf<T>(std::marker::PhantomData<fn() -> T>);
+
+impl<T> ContentTypeOf<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait WithContentType {
+    fn content_type(&self) -> Option<&'static str>;
+}
+
+impl<T: BodyContentType> WithContentType for &ContentTypeOf<T> {
+    fn content_type(&self) -> Option<&'static str> {
+        Some(T::CONTENT_TYPE)
+    }
+}
+
+#[doc(hidden)]
+pub trait WithoutContentType {
+    fn content_type(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl<T> WithoutContentType for ContentTypeOf<T> {}
+
 #[doc(hidden)]
 pub fn assert_can_encode(_t: impl AssertCanEncode) {}
 
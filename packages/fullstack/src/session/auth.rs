@@ -0,0 +1,111 @@
+use super::Session;
+use axum::{
+    extract::Request,
+    response::{IntoResponse, Response},
+};
+use dioxus_fullstack_core::HttpError;
+use futures_util::future::BoxFuture;
+use std::{
+    future::Future,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+type Guard = Arc<dyn Fn(Session) -> BoxFuture<'static, bool> + Send + Sync>;
+
+/// A layer that only lets calls through if a user is logged in to the [`Session`], and optionally
+/// if a guard allows the call.
+///
+/// Calls without a logged in user are rejected with `401 Unauthorized`, and calls the guard rejects
+/// with `403 Forbidden`.
+///
+/// This layer is added by `#[server(auth)]` and `#[server(auth = guard)]`, but it can also be added
+/// to any server function with `#[middleware(AuthLayer::new())]`. It needs a [`SessionLayer`]
+/// on the router.
+///
+/// [`SessionLayer`]: super::SessionLayer
+#[derive(Clone, Default)]
+pub struct AuthLayer {
+    guard: Option<Guard>,
+}
+
+impl AuthLayer {
+    /// Create a layer that requires a logged in user.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also require `guard` to return true for the session of the call.
+    pub fn guard<F, Fut>(self, guard: F) -> Self
+    where
+        F: Fn(Session) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        Self {
+            guard: Some(Arc::new(move |session| Box::pin(guard(session)))),
+        }
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = Auth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Auth {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service created by [`AuthLayer`].
+#[derive(Clone)]
+pub struct Auth<S> {
+    inner: S,
+    layer: AuthLayer,
+}
+
+impl<S> Service<Request> for Auth<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Take the service that was driven to readiness and leave a clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+
+        Box::pin(async move {
+            let session = request
+                .extensions()
+                .get::<Session>()
+                .filter(|session| session.is_authenticated())
+                .cloned();
+            let Some(session) = session else {
+                return Ok(HttpError::unauthorized::<()>("You must be logged in")
+                    .unwrap_err()
+                    .into_response());
+            };
+
+            if let Some(guard) = &layer.guard {
+                if !guard(session).await {
+                    return Ok(HttpError::forbidden::<()>("You are not allowed to do this")
+                        .unwrap_err()
+                        .into_response());
+                }
+            }
+
+            inner.call(request).await
+        })
+    }
+}
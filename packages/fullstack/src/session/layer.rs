@@ -0,0 +1,498 @@
+use super::{MemorySessionStore, SessionRecord, SessionStore, CSRF_COOKIE, CSRF_HEADER, USER_KEY};
+use crate::FullstackContext;
+use axum::{
+    extract::{FromRequestParts, Request},
+    response::{IntoResponse, Response},
+};
+use cookie::{Cookie, CookieJar, SameSite};
+use dioxus_fullstack_core::HttpError;
+use futures_util::future::BoxFuture;
+use http::{
+    header::{COOKIE, SET_COOKIE},
+    request::Parts,
+    HeaderValue, Method,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+
+pub use cookie::Key;
+
+/// The session of the current request.
+///
+/// Sessions are created by the [`SessionLayer`]. Changes to the session are saved when the response
+/// is sent. A new session is only stored once a value is inserted into it.
+#[derive(Clone)]
+pub struct Session {
+    inner: Arc<Mutex<SessionState>>,
+}
+
+struct SessionState {
+    /// The id of the session, or `None` if it was not stored yet.
+    id: Option<String>,
+    record: SessionRecord,
+    changed: bool,
+    renew: bool,
+    destroyed: bool,
+}
+
+impl Session {
+    fn new(id: Option<String>, record: SessionRecord) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SessionState {
+                id,
+                record,
+                changed: false,
+                renew: false,
+                destroyed: false,
+            })),
+        }
+    }
+
+    /// The session of the request that is being handled, if the router has a [`SessionLayer`].
+    pub fn current() -> Option<Self> {
+        FullstackContext::current()?.extension::<Self>()
+    }
+
+    fn state(&self) -> MutexGuard<'_, SessionState> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Read a value from the session.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let state = self.state();
+        let value = state.record.data.get(key)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Insert a value into the session, replacing the value that was stored under `key`.
+    pub fn insert<T: Serialize>(&self, key: impl Into<String>, value: T) -> serde_json::Result<()> {
+        let value = serde_json::to_value(value)?;
+        let mut state = self.state();
+        state.record.data.insert(key.into(), value);
+        state.changed = true;
+        state.destroyed = false;
+        Ok(())
+    }
+
+    /// Remove a value from the session.
+    pub fn remove(&self, key: &str) {
+        let mut state = self.state();
+        if state.record.data.remove(key).is_some() {
+            state.changed = true;
+        }
+    }
+
+    /// Give the session a new id and CSRF token, keeping its values.
+    ///
+    /// Renew the session whenever the privileges of the user change, so an id that leaked before
+    /// can't be used to act as the user.
+    pub fn renew(&self) {
+        let mut state = self.state();
+        state.renew = true;
+        state.changed = true;
+    }
+
+    /// Remove the session from the store and the cookies of the client.
+    pub fn destroy(&self) {
+        let mut state = self.state();
+        state.record = SessionRecord {
+            csrf_token: random_token(),
+            ..Default::default()
+        };
+        state.destroyed = true;
+        // Values inserted after this start a new session
+        state.renew = true;
+    }
+
+    /// Store `user` as the logged in user and renew the session.
+    pub fn login<U: Serialize>(&self, user: &U) -> serde_json::Result<()> {
+        self.insert(USER_KEY, user)?;
+        self.renew();
+        Ok(())
+    }
+
+    /// Log the user out by destroying the session.
+    pub fn logout(&self) {
+        self.destroy();
+    }
+
+    /// The logged in user, if there is one.
+    pub fn user<U: DeserializeOwned>(&self) -> Option<U> {
+        self.get(USER_KEY)
+    }
+
+    /// Returns true if a user is logged in.
+    pub fn is_authenticated(&self) -> bool {
+        self.state().record.data.contains_key(USER_KEY)
+    }
+
+    /// The token that mutating requests must send in the `X-CSRF-Token` header.
+    pub fn csrf_token(&self) -> String {
+        self.state().record.csrf_token.clone()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for Session {
+    type Rejection = HttpError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<Self>().cloned().ok_or_else(|| {
+            HttpError::new(
+                http::StatusCode::INTERNAL_SERVER_ERROR,
+                "The router has no SessionLayer",
+            )
+        })
+    }
+}
+
+/// A layer that gives every request a [`Session`] and checks the CSRF token of mutating requests.
+///
+/// The session id is kept in a cookie that is signed with the key, so clients can't forge ids.
+/// With [`SessionLayer::encrypted`], the cookie is also encrypted.
+#[derive(Clone)]
+pub struct SessionLayer {
+    key: Key,
+    store: Arc<dyn SessionStore>,
+    cookie_name: &'static str,
+    ttl: Duration,
+    secure: bool,
+    encrypted: bool,
+}
+
+impl SessionLayer {
+    /// Create a layer that signs its cookies with `key` and keeps sessions in a
+    /// [`MemorySessionStore`] for one week.
+    ///
+    /// Use the same key for every run of the server, or all sessions end when it restarts.
+    pub fn new(key: Key) -> Self {
+        Self {
+            key,
+            store: Arc::new(MemorySessionStore::new()),
+            cookie_name: "dioxus-session",
+            ttl: Duration::from_secs(7 * 24 * 60 * 60),
+            secure: true,
+            encrypted: false,
+        }
+    }
+
+    /// Keep sessions in `store`.
+    pub fn store(self, store: impl SessionStore) -> Self {
+        Self {
+            store: Arc::new(store),
+            ..self
+        }
+    }
+
+    /// Set the name of the cookie that holds the session id.
+    pub fn cookie_name(self, cookie_name: &'static str) -> Self {
+        Self {
+            cookie_name,
+            ..self
+        }
+    }
+
+    /// Set how long a session lasts after it was last changed.
+    pub fn ttl(self, ttl: Duration) -> Self {
+        Self { ttl, ..self }
+    }
+
+    /// Set if the cookies are only sent over https. This is on by default, and can be turned off for
+    /// local development over http.
+    pub fn secure(self, secure: bool) -> Self {
+        Self { secure, ..self }
+    }
+
+    /// Encrypt the session cookie instead of only signing it.
+    pub fn encrypted(self, encrypted: bool) -> Self {
+        Self { encrypted, ..self }
+    }
+
+    /// Read the session id from the cookies of a request.
+    fn session_id(&self, parts: &Parts) -> Option<String> {
+        let mut jar = CookieJar::new();
+        for header in parts.headers.get_all(COOKIE) {
+            let Ok(header) = header.to_str() else {
+                continue;
+            };
+            for cookie in Cookie::split_parse_encoded(header).flatten() {
+                jar.add_original(cookie.into_owned());
+            }
+        }
+
+        let cookie = match self.encrypted {
+            true => jar.private(&self.key).get(self.cookie_name),
+            false => jar.signed(&self.key).get(self.cookie_name),
+        };
+        cookie.map(|cookie| cookie.value().to_string())
+    }
+
+    /// The cookies that store the session id and CSRF token on the client.
+    fn cookies(&self, id: Option<&str>, csrf_token: &str) -> Vec<Cookie<'static>> {
+        let mut session = Cookie::build((self.cookie_name, id.unwrap_or_default().to_string()))
+            .path("/")
+            .http_only(true)
+            .secure(self.secure)
+            .same_site(SameSite::Lax)
+            .build();
+        let mut csrf = Cookie::build((CSRF_COOKIE, csrf_token.to_string()))
+            .path("/")
+            .secure(self.secure)
+            .same_site(SameSite::Strict)
+            .build();
+
+        match id {
+            Some(_) => {
+                let max_age = cookie::time::Duration::seconds(self.ttl.as_secs() as i64);
+                session.set_max_age(max_age);
+                csrf.set_max_age(max_age);
+            }
+            None => {
+                session.make_removal();
+                csrf.make_removal();
+            }
+        }
+
+        // Sign or encrypt the session cookie
+        let mut jar = CookieJar::new();
+        match self.encrypted {
+            true => jar.private_mut(&self.key).add(session),
+            false => jar.signed_mut(&self.key).add(session),
+        }
+        jar.add(csrf);
+        jar.delta().cloned().collect()
+    }
+}
+
+impl<S> Layer<S> for SessionLayer {
+    type Service = SessionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SessionService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service created by [`SessionLayer`].
+#[derive(Clone)]
+pub struct SessionService<S> {
+    inner: S,
+    layer: SessionLayer,
+}
+
+impl<S> Service<Request> for SessionService<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Take the service that was driven to readiness and leave a clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+
+            let stored = match layer.session_id(&parts) {
+                Some(id) => match layer.store.load(&id).await {
+                    Ok(record) => record.map(|record| (id, record)),
+                    Err(err) => {
+                        tracing::error!("Failed to load session: {err:#}");
+                        None
+                    }
+                },
+                None => None,
+            };
+
+            let session = match stored {
+                Some((id, record)) => {
+                    if !is_safe(&parts.method) && !has_csrf_token(&parts, &record.csrf_token) {
+                        return Ok(HttpError::forbidden::<()>("Missing or invalid CSRF token")
+                            .unwrap_err()
+                            .into_response());
+                    }
+                    Session::new(Some(id), record)
+                }
+                None => Session::new(
+                    None,
+                    SessionRecord {
+                        csrf_token: random_token(),
+                        ..Default::default()
+                    },
+                ),
+            };
+
+            parts.extensions.insert(session.clone());
+            let mut response = inner.call(Request::from_parts(parts, body)).await?;
+
+            for cookie in save(&layer, &session).await {
+                if let Ok(value) = HeaderValue::from_str(&cookie.encoded().to_string()) {
+                    response.headers_mut().append(SET_COOKIE, value);
+                }
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+/// Save the changes to a session and return the cookies the client needs to update.
+async fn save(layer: &SessionLayer, session: &Session) -> Vec<Cookie<'static>> {
+    let (old_id, new_id, record) = {
+        let mut state = session.state();
+        let old_id = state.id.clone();
+
+        if state.destroyed {
+            state.id = None;
+            (old_id, None, None)
+        } else if !state.changed || (state.id.is_none() && state.record.data.is_empty()) {
+            return Vec::new();
+        } else {
+            if state.renew || state.id.is_none() {
+                state.id = Some(random_token());
+            }
+            if state.renew {
+                state.record.csrf_token = random_token();
+            }
+            state.changed = false;
+            state.renew = false;
+            (old_id, state.id.clone(), Some(state.record.clone()))
+        }
+    };
+
+    // Remove the old session if the id changed or the session was destroyed
+    if let Some(old_id) = old_id.filter(|old_id| Some(old_id) != new_id.as_ref()) {
+        if let Err(err) = layer.store.delete(&old_id).await {
+            tracing::error!("Failed to delete session: {err:#}");
+        }
+    }
+
+    match (new_id, record) {
+        (Some(id), Some(record)) => {
+            if let Err(err) = layer.store.save(&id, &record, layer.ttl).await {
+                tracing::error!("Failed to save session: {err:#}");
+                return Vec::new();
+            }
+            layer.cookies(Some(&id), &record.csrf_token)
+        }
+        _ => layer.cookies(None, ""),
+    }
+}
+
+/// Methods that must not change state, so they don't need a CSRF token.
+fn is_safe(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// Compare the CSRF header of a request with the token of its session in constant time.
+fn has_csrf_token(parts: &Parts, expected: &str) -> bool {
+    let Some(token) = parts.headers.get(CSRF_HEADER) else {
+        return false;
+    };
+    let token = token.as_bytes();
+    let expected = expected.as_bytes();
+    token.len() == expected.len()
+        && token
+            .iter()
+            .zip(expected)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// A random 256 bit token from the operating system's random number generator.
+fn random_token() -> String {
+    Key::generate().master()[..32]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::{body::Body, routing::post, Router};
+    use tower::ServiceExt;
+
+    fn router() -> Router {
+        Router::new()
+            .route(
+                "/login",
+                post(|session: Session| async move {
+                    session.login(&"alice").unwrap();
+                }),
+            )
+            .route(
+                "/whoami",
+                post(
+                    |session: Session| async move { session.user::<String>().unwrap_or_default() },
+                ),
+            )
+            .layer(SessionLayer::new(Key::generate()))
+    }
+
+    fn request(uri: &str, cookies: &[String], csrf: Option<&str>) -> Request {
+        let mut request = Request::builder().method(Method::POST).uri(uri);
+        if !cookies.is_empty() {
+            request = request.header(COOKIE, cookies.join("; "));
+        }
+        if let Some(csrf) = csrf {
+            request = request.header(CSRF_HEADER, csrf);
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn mutating_requests_need_the_csrf_token() {
+        let router = router();
+
+        let response = router
+            .clone()
+            .oneshot(request("/login", &[], None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let cookies: Vec<String> = response
+            .headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .map(|cookie| {
+                let cookie = Cookie::parse(cookie.to_str().unwrap().to_string()).unwrap();
+                format!("{}={}", cookie.name(), cookie.value())
+            })
+            .collect();
+        let csrf = super::super::csrf_token_from_cookies(&cookies.join("; ")).unwrap();
+
+        let response = router
+            .clone()
+            .oneshot(request("/whoami", &cookies, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+
+        let response = router
+            .oneshot(request("/whoami", &cookies, Some(&csrf)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body, "alice");
+    }
+}
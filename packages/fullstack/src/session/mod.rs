@@ -0,0 +1,134 @@
+//! Cookie sessions, CSRF protection and authentication guards for server functions.
+//!
+//! Add a [`SessionLayer`] to the router to give every request a [`Session`]. The session id is kept
+//! in a signed or encrypted cookie, and the data of the session in a [`SessionStore`]:
+//!
+//! ```rust, ignore
+//! dioxus::serve(|| async move {
+//!     let key = Key::from(&std::env::var("SESSION_KEY")?.into_bytes());
+//!     Ok(dioxus::server::router(app).layer(SessionLayer::new(key)))
+//! });
+//! ```
+//!
+//! Server functions read and change the session with the [`Session`] extractor:
+//!
+//! ```rust, ignore
+//! #[post("/api/login", session: Session)]
+//! async fn login(name: String, password: String) -> Result<()> {
+//!     let user = check_password(&name, &password).await?;
+//!     session.login(&user)?;
+//!     Ok(())
+//! }
+//!
+//! #[server(auth)]
+//! async fn secret() -> Result<String> {
+//!     Ok("Only for logged in users".to_string())
+//! }
+//!
+//! #[server(auth = is_admin)]
+//! async fn delete_everything() -> Result<()> {
+//!     // ...
+//! }
+//!
+//! async fn is_admin(session: Session) -> bool {
+//!     session.user::<User>().is_some_and(|user| user.admin)
+//! }
+//! ```
+//!
+//! `#[server(auth)]` rejects calls without a logged in user with `401 Unauthorized`.
+//! `#[server(auth = guard)]` also calls the guard with the session, and rejects the call with
+//! `403 Forbidden` if it returns false.
+//!
+//! Components read the logged in user with [`use_current_user`] and other session values with
+//! [`use_session`]. Both are read from the session while the page is rendered on the server and
+//! hydrated on the client.
+//!
+//! ## CSRF protection
+//!
+//! Every session has a CSRF token, which the layer also sets in the `dioxus-csrf` cookie that
+//! scripts can read. Requests with a session that are not `GET`, `HEAD` or `OPTIONS` must send the
+//! token in the `X-CSRF-Token` header, or they are rejected with `403 Forbidden`. The client of
+//! server functions sends the header automatically, so this only needs attention for requests made
+//! by other clients.
+
+#[cfg(feature = "server")]
+mod auth;
+#[cfg(feature = "server")]
+mod layer;
+#[cfg(feature = "server")]
+mod store;
+
+#[cfg(feature = "server")]
+pub use auth::*;
+#[cfg(feature = "server")]
+pub use layer::*;
+#[cfg(feature = "server")]
+pub use store::*;
+
+use dioxus_fullstack_core::use_server_cached;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// The cookie that holds the CSRF token of the session.
+pub const CSRF_COOKIE: &str = "dioxus-csrf";
+
+/// The header that mutating requests send the CSRF token in.
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+/// The session key that [`Session::login`] stores the user in.
+pub const USER_KEY: &str = "dioxus.user";
+
+/// The user that is logged in to the session of the current request, or `None` if no user is logged
+/// in.
+///
+/// The user is read from the session while the page is rendered on the server and hydrated on the
+/// client. Components that are first rendered on the client, after the page was hydrated, see `None`.
+#[track_caller]
+pub fn use_current_user<U>() -> Option<U>
+where
+    U: Serialize + DeserializeOwned + Clone + 'static,
+{
+    use_server_cached(|| {
+        #[cfg(feature = "server")]
+        {
+            Session::current().and_then(|session| session.user::<U>())
+        }
+
+        #[cfg(not(feature = "server"))]
+        {
+            None
+        }
+    })
+}
+
+/// A value from the session of the current request, or `None` if the session doesn't contain `key`.
+///
+/// The value is read from the session while the page is rendered on the server and hydrated on the
+/// client, so only read values the user may see.
+#[track_caller]
+pub fn use_session<T>(key: &'static str) -> Option<T>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    use_server_cached(move || {
+        #[cfg(feature = "server")]
+        {
+            Session::current().and_then(|session| session.get::<T>(key))
+        }
+
+        #[cfg(not(feature = "server"))]
+        {
+            _ = key;
+            None
+        }
+    })
+}
+
+/// The value of the CSRF cookie in a `Cookie` header.
+#[cfg_attr(all(target_arch = "wasm32", not(feature = "web")), allow(dead_code))]
+pub(crate) fn csrf_token_from_cookies(cookies: &str) -> Option<String> {
+    cookies
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == CSRF_COOKIE)
+        .map(|(_, value)| value.to_string())
+}
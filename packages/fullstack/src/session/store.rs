@@ -0,0 +1,92 @@
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// How many writes happen between two sweeps of expired sessions.
+const SWEEP_INTERVAL: usize = 1024;
+
+/// The data of a session as it is kept in a [`SessionStore`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// The values of the session.
+    pub data: HashMap<String, serde_json::Value>,
+
+    /// The token that mutating requests must send in the `X-CSRF-Token` header.
+    pub csrf_token: String,
+}
+
+/// A store that keeps the data of sessions.
+///
+/// See the [module documentation](crate::session) for how sessions are used.
+pub trait SessionStore: Send + Sync + 'static {
+    /// Look up a session that did not expire yet.
+    fn load<'a>(&'a self, id: &'a str) -> BoxFuture<'a, anyhow::Result<Option<SessionRecord>>>;
+
+    /// Insert or replace a session, which expires after `ttl`.
+    fn save<'a>(
+        &'a self,
+        id: &'a str,
+        record: &'a SessionRecord,
+        ttl: Duration,
+    ) -> BoxFuture<'a, anyhow::Result<()>>;
+
+    /// Remove a session.
+    fn delete<'a>(&'a self, id: &'a str) -> BoxFuture<'a, anyhow::Result<()>>;
+}
+
+/// A [`SessionStore`] that keeps sessions in the memory of the server.
+///
+/// Sessions are lost when the server restarts and are not shared between multiple servers.
+#[derive(Default)]
+pub struct MemorySessionStore {
+    sessions: Mutex<HashMap<String, (SessionRecord, Instant)>>,
+    writes: AtomicUsize,
+}
+
+impl MemorySessionStore {
+    /// Create a new, empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SessionStore for MemorySessionStore {
+    fn load<'a>(&'a self, id: &'a str) -> BoxFuture<'a, anyhow::Result<Option<SessionRecord>>> {
+        let now = Instant::now();
+        let record = self
+            .sessions
+            .lock()
+            .unwrap()
+            .get(id)
+            .filter(|(_, expires)| *expires > now)
+            .map(|(record, _)| record.clone());
+        Box::pin(async move { Ok(record) })
+    }
+
+    fn save<'a>(
+        &'a self,
+        id: &'a str,
+        record: &'a SessionRecord,
+        ttl: Duration,
+    ) -> BoxFuture<'a, anyhow::Result<()>> {
+        let now = Instant::now();
+        let mut sessions = self.sessions.lock().unwrap();
+        if self.writes.fetch_add(1, Ordering::Relaxed) % SWEEP_INTERVAL == 0 {
+            sessions.retain(|_, (_, expires)| *expires > now);
+        }
+        sessions.insert(id.to_string(), (record.clone(), now + ttl));
+        Box::pin(async { Ok(()) })
+    }
+
+    fn delete<'a>(&'a self, id: &'a str) -> BoxFuture<'a, anyhow::Result<()>> {
+        self.sessions.lock().unwrap().remove(id);
+        Box::pin(async { Ok(()) })
+    }
+}
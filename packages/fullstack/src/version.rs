@@ -0,0 +1,98 @@
+use dioxus_fullstack_core::ServerFnError;
+use http::StatusCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static RELOAD_ON_VERSION_MISMATCH: AtomicBool = AtomicBool::new(true);
+
+/// Choose whether the web client reloads the page when it calls a version of a server function
+/// that the server no longer serves. This is on by default.
+///
+/// The call still fails with [`ServerFnError::VersionMismatch`], so apps that turn the reload off
+/// can ask the user to reload instead.
+pub fn set_reload_on_version_mismatch(reload: bool) {
+    RELOAD_ON_VERSION_MISMATCH.store(reload, Ordering::Relaxed);
+}
+
+/// Turn the error payload of a failed call into a [`ServerFnError`].
+///
+/// Calls of a version the server doesn't serve are answered with `412 Precondition Failed` and the
+/// [`ServerFnError::VersionMismatch`] in the details. Those become that variant, and reload the page
+/// on the web so the client picks up the current build of the app.
+pub(crate) fn server_error(
+    message: String,
+    code: u16,
+    details: Option<serde_json::Value>,
+) -> ServerFnError {
+    if code == StatusCode::PRECONDITION_FAILED.as_u16() {
+        if let Some(Ok(err @ ServerFnError::VersionMismatch { .. })) = details
+            .as_ref()
+            .map(|details| serde_json::from_value::<ServerFnError>(details.clone()))
+        {
+            if RELOAD_ON_VERSION_MISMATCH.load(Ordering::Relaxed) {
+                reload();
+            }
+            return err;
+        }
+    }
+
+    ServerFnError::ServerError {
+        message,
+        code,
+        details,
+    }
+}
+
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+fn reload() {
+    if let Some(window) = web_sys::window() {
+        _ = window.location().reload();
+    }
+}
+
+#[cfg(not(all(feature = "web", target_arch = "wasm32")))]
+fn reload() {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn mismatch() -> ServerFnError {
+        ServerFnError::VersionMismatch {
+            requested: 3,
+            supported: vec![1, 2],
+        }
+    }
+
+    #[test]
+    fn decodes_version_mismatches() {
+        // The payload the server sends for calls of an unknown version
+        let details = serde_json::to_value(mismatch()).unwrap();
+        let err = server_error(mismatch().to_string(), 412, Some(details));
+        assert_eq!(err, mismatch());
+    }
+
+    #[test]
+    fn other_errors_stay_server_errors() {
+        let details = serde_json::to_value(mismatch()).unwrap();
+        let err = server_error("Conflict".to_string(), 409, Some(details.clone()));
+        assert_eq!(
+            err,
+            ServerFnError::ServerError {
+                message: "Conflict".to_string(),
+                code: 409,
+                details: Some(details),
+            }
+        );
+
+        let details = serde_json::json!({ "reason": "stale" });
+        let err = server_error("Stale".to_string(), 412, Some(details.clone()));
+        assert_eq!(
+            err,
+            ServerFnError::ServerError {
+                message: "Stale".to_string(),
+                code: 412,
+                details: Some(details),
+            }
+        );
+    }
+}
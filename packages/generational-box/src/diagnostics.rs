@@ -0,0 +1,153 @@
+//! Accounting for live generational boxes, used to find leaks.
+//!
+//! With the `debug_allocations` feature, every box is counted under the location it was created at while it is alive.
+//! Locations are only recorded in debug builds, so snapshots are always empty in release builds or without the feature.
+
+use crate::GenerationalLocation;
+use std::{collections::BTreeMap, fmt::Display, panic::Location};
+
+type Site = &'static Location<'static>;
+
+#[cfg(feature = "debug_allocations")]
+static LIVE_ALLOCATIONS: parking_lot::Mutex<BTreeMap<Site, usize>> =
+    parking_lot::const_mutex(BTreeMap::new());
+
+/// Count a new box under the location it was created at
+#[cfg_attr(not(feature = "debug_allocations"), allow(unused_variables))]
+pub(crate) fn track_allocation(location: &GenerationalLocation) {
+    #[cfg(feature = "debug_allocations")]
+    if let Some(site) = location.created_at() {
+        *LIVE_ALLOCATIONS.lock().entry(site).or_default() += 1;
+    }
+}
+
+/// Stop counting a box once its memory location is recycled
+#[cfg_attr(not(feature = "debug_allocations"), allow(unused_variables))]
+pub(crate) fn track_release(location: &GenerationalLocation) {
+    #[cfg(feature = "debug_allocations")]
+    if let Some(site) = location.created_at() {
+        let mut live = LIVE_ALLOCATIONS.lock();
+        if let Some(count) = live.get_mut(site) {
+            *count -= 1;
+            if *count == 0 {
+                live.remove(site);
+            }
+        }
+    }
+}
+
+/// The number of live generational boxes, grouped by the location they were created at.
+///
+/// Global snapshots are only recorded with the `debug_allocations` feature. Every allocation and release locks a
+/// process wide map while the feature is enabled, so it should only be turned on while looking for leaks.
+/// [`Owner::allocations`](crate::Owner::allocations) works without the feature.
+///
+/// Take a snapshot before and after an action that should free everything it creates, and compare them with
+/// [`AllocationSnapshot::diff`]:
+///
+/// ```rust
+/// use generational_box::{AllocationSnapshot, AnyStorage, UnsyncStorage};
+///
+/// let before = AllocationSnapshot::capture();
+/// let owner = UnsyncStorage::owner();
+/// owner.insert("some state");
+/// drop(owner);
+/// assert!(before.diff(&AllocationSnapshot::capture()).is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllocationSnapshot {
+    sites: BTreeMap<Site, usize>,
+}
+
+impl AllocationSnapshot {
+    /// Capture the live boxes of every storage on every thread. This is always empty without the `debug_allocations`
+    /// feature.
+    pub fn capture() -> Self {
+        #[cfg(feature = "debug_allocations")]
+        return Self {
+            sites: LIVE_ALLOCATIONS.lock().clone(),
+        };
+
+        #[cfg(not(feature = "debug_allocations"))]
+        Self::default()
+    }
+
+    pub(crate) fn from_locations(
+        locations: impl IntoIterator<Item = GenerationalLocation>,
+    ) -> Self {
+        let mut sites = BTreeMap::new();
+        for site in locations
+            .into_iter()
+            .filter_map(|location| location.created_at())
+        {
+            *sites.entry(site).or_default() += 1;
+        }
+        Self { sites }
+    }
+
+    /// Get the total number of live boxes
+    pub fn total(&self) -> usize {
+        self.sites.values().sum()
+    }
+
+    /// Get the number of live boxes created at a location
+    pub fn count_at(&self, location: &Location<'static>) -> usize {
+        self.sites.get(location).copied().unwrap_or_default()
+    }
+
+    /// Iterate over each location with live boxes and the number of boxes created there
+    pub fn sites(&self) -> impl Iterator<Item = (&'static Location<'static>, usize)> + '_ {
+        self.sites.iter().map(|(site, count)| (*site, *count))
+    }
+
+    /// Compare this snapshot to a later one
+    pub fn diff(&self, later: &AllocationSnapshot) -> AllocationDiff {
+        let mut changes = BTreeMap::new();
+        for site in self.sites.keys().chain(later.sites.keys()) {
+            let change = later.count_at(site) as isize - self.count_at(site) as isize;
+            if change != 0 {
+                changes.insert(*site, change);
+            }
+        }
+        AllocationDiff { changes }
+    }
+}
+
+/// The change in live boxes between two [`AllocationSnapshot`]s, grouped by the location they were created at.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllocationDiff {
+    changes: BTreeMap<Site, isize>,
+}
+
+impl AllocationDiff {
+    /// Returns true if the number of live boxes didn't change at any location
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Get the change in live boxes created at a location
+    pub fn change_at(&self, location: &Location<'static>) -> isize {
+        self.changes.get(location).copied().unwrap_or_default()
+    }
+
+    /// Iterate over each location where the number of live boxes changed
+    pub fn changes(&self) -> impl Iterator<Item = (&'static Location<'static>, isize)> + '_ {
+        self.changes.iter().map(|(site, change)| (*site, *change))
+    }
+
+    /// Iterate over each location where the number of live boxes grew. These are the likely leaks.
+    pub fn grown(&self) -> impl Iterator<Item = (&'static Location<'static>, usize)> + '_ {
+        self.changes()
+            .filter(|(_, change)| *change > 0)
+            .map(|(site, change)| (site, change as usize))
+    }
+}
+
+impl Display for AllocationDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (site, change) in self.changes() {
+            writeln!(f, "{site}: {change:+}")?;
+        }
+        Ok(())
+    }
+}
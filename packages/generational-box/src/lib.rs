@@ -10,11 +10,13 @@ use std::{
     sync::Arc,
 };
 
+pub use diagnostics::*;
 pub use error::*;
 pub use references::*;
 pub use sync::SyncStorage;
 pub use unsync::UnsyncStorage;
 
+mod diagnostics;
 mod entry;
 mod error;
 mod references;
@@ -252,6 +254,14 @@ pub trait AnyStorage: Default {
     /// Recycle a memory location. This will drop the memory location and return it to the runtime.
     fn recycle(location: GenerationalPointer<Self>);
 
+    /// Check if a pointer still points to a live value. This should only be used for diagnostics.
+    ///
+    /// The default implementation assumes every pointer is alive, so [`Owner::len`] counts boxes that were already
+    /// dropped manually.
+    fn is_alive(_pointer: GenerationalPointer<Self>) -> bool {
+        true
+    }
+
     /// Create a new owner. The owner will be responsible for dropping all of the generational boxes that it creates.
     fn owner() -> Owner<Self>
     where
@@ -432,6 +442,35 @@ impl<S: AnyStorage> Owner<S> {
         }
     }
 
+    /// Get the number of live boxes this owner is responsible for dropping. Boxes that were already dropped manually are
+    /// not counted.
+    ///
+    /// An owner that keeps growing while the app is idle is usually leaking state, for example by creating signals in
+    /// event handlers.
+    pub fn len(&self) -> usize {
+        self.live_pointers().count()
+    }
+
+    /// Returns true if this owner has no live boxes
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the live boxes of this owner grouped by the location they were created at. This is always empty in release
+    /// builds.
+    pub fn allocations(&self) -> AllocationSnapshot {
+        AllocationSnapshot::from_locations(
+            self.live_pointers()
+                .map(|pointer| pointer.location)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn live_pointers(&self) -> impl Iterator<Item = GenerationalPointer<S>> {
+        let owned = self.0.lock().owned.clone();
+        owned.into_iter().filter(|pointer| S::is_alive(*pointer))
+    }
+
     /// Create a new reference to an existing box. The reference will be dropped when the owner is dropped.
     ///
     /// This method may return an error if the other box is no longer valid or it is already borrowed mutably.
@@ -155,6 +155,7 @@ impl SyncStorage {
                     created_at: caller,
                 };
                 write.data = value;
+                crate::diagnostics::track_allocation(&location);
                 GenerationalPointer { storage, location }
             }
             None => {
@@ -168,6 +169,7 @@ impl SyncStorage {
                     #[cfg(any(debug_assertions, feature = "debug_borrows"))]
                     created_at: caller,
                 };
+                crate::diagnostics::track_allocation(&location);
 
                 GenerationalPointer { storage, location }
             }
@@ -229,6 +231,13 @@ impl AnyStorage for SyncStorage {
         self.data.data_ptr() as *const ()
     }
 
+    fn is_alive(pointer: GenerationalPointer<Self>) -> bool {
+        // A value that is borrowed mutably right now is still alive
+        pointer.storage.data.try_read().is_none_or(|entry| {
+            entry.valid(&pointer.location) && !matches!(entry.data, RwLockStorageEntryData::Empty)
+        })
+    }
+
     fn recycle(pointer: GenerationalPointer<Self>) {
         let mut borrow_mut = pointer.storage.data.write();
 
@@ -237,6 +246,7 @@ impl AnyStorage for SyncStorage {
             return;
         }
 
+        crate::diagnostics::track_release(&pointer.location);
         borrow_mut.increment_generation();
 
         // Then decrement the reference count or drop the value if it's the last reference
@@ -268,6 +278,7 @@ fn drop_ref(pointer: GenerationalPointer<SyncStorage>) {
         // Decrement the reference count
         if entry.drop_ref() {
             // If the reference count is now zero, drop the value
+            crate::diagnostics::track_release(&pointer.location);
             borrow_mut.data = RwLockStorageEntryData::Empty;
             sync_runtime().lock().push(pointer.storage);
         }
@@ -156,6 +156,7 @@ impl UnsyncStorage {
                     created_at: caller,
                 };
                 write.data = value;
+                crate::diagnostics::track_allocation(&location);
                 GenerationalPointer { storage, location }
             }
             None => {
@@ -169,6 +170,7 @@ impl UnsyncStorage {
                     #[cfg(any(debug_assertions, feature = "debug_borrows"))]
                     created_at: caller,
                 };
+                crate::diagnostics::track_allocation(&location);
 
                 GenerationalPointer { storage, location }
             }
@@ -224,6 +226,13 @@ impl AnyStorage for UnsyncStorage {
         self.data.as_ptr() as *const ()
     }
 
+    fn is_alive(pointer: GenerationalPointer<Self>) -> bool {
+        // A value that is borrowed mutably right now is still alive
+        pointer.storage.data.try_borrow().map_or(true, |entry| {
+            entry.valid(&pointer.location) && !matches!(entry.data, RefCellStorageEntryData::Empty)
+        })
+    }
+
     fn recycle(pointer: GenerationalPointer<Self>) {
         let mut borrow_mut = pointer.storage.data.borrow_mut();
 
@@ -232,6 +241,7 @@ impl AnyStorage for UnsyncStorage {
             return;
         }
 
+        crate::diagnostics::track_release(&pointer.location);
         borrow_mut.increment_generation();
         // Then decrement the reference count or drop the value if it's the last reference
         match &mut borrow_mut.data {
@@ -264,6 +274,7 @@ fn drop_ref(pointer: GenerationalPointer<UnsyncStorage>) {
         // Decrement the reference count
         if entry.drop_ref() {
             // If the reference count is now zero, drop the value
+            crate::diagnostics::track_release(&pointer.location);
             borrow_mut.data = RefCellStorageEntryData::Empty;
             UNSYNC_RUNTIME.with(|runtime| runtime.borrow_mut().push(pointer.storage));
         }
@@ -0,0 +1,60 @@
+// Creation locations are only recorded in debug builds
+#![cfg(debug_assertions)]
+
+use generational_box::{AnyStorage, Storage, SyncStorage, UnsyncStorage};
+
+// Global snapshots are only recorded with the debug_allocations feature
+#[cfg(feature = "debug_allocations")]
+#[test]
+fn snapshots_count_live_boxes_by_location() {
+    use generational_box::AllocationSnapshot;
+
+    fn snapshots_count_live_boxes_by_location_test<S: Storage<usize> + 'static>() {
+        let before = AllocationSnapshot::capture();
+        let owner = S::owner();
+        let boxes: Vec<_> = (0..3).map(|i| owner.insert(i)).collect();
+        let site = boxes[0].created_at().unwrap();
+
+        let during = AllocationSnapshot::capture();
+        assert_eq!(during.count_at(site), before.count_at(site) + 3);
+        assert_eq!(before.diff(&during).change_at(site), 3);
+        assert!(before.diff(&during).grown().any(|grown| grown == (site, 3)));
+
+        drop(owner);
+        let after = AllocationSnapshot::capture();
+        assert_eq!(before.diff(&after).change_at(site), 0);
+    }
+
+    snapshots_count_live_boxes_by_location_test::<UnsyncStorage>();
+    snapshots_count_live_boxes_by_location_test::<SyncStorage>();
+}
+
+#[test]
+fn owners_only_count_live_boxes() {
+    fn owners_only_count_live_boxes_test<S: Storage<String> + 'static>() {
+        let owner = S::owner();
+        assert!(owner.is_empty());
+
+        let first = owner.insert("first".to_string());
+        let second = owner.insert("second".to_string());
+        assert_eq!(owner.len(), 2);
+        assert_eq!(owner.allocations().count_at(first.created_at().unwrap()), 1);
+
+        first.manually_drop();
+        assert_eq!(owner.len(), 1);
+
+        // References to a reference counted box are owned by the owner that created them
+        let other_owner = S::owner();
+        let rc = other_owner.insert_rc("shared".to_string());
+        owner.insert_reference(rc).unwrap();
+        assert_eq!(owner.len(), 2);
+        drop(other_owner);
+        assert_eq!(owner.len(), 2);
+
+        second.manually_drop();
+        assert_eq!(owner.len(), 1);
+    }
+
+    owners_only_count_live_boxes_test::<UnsyncStorage>();
+    owners_only_count_live_boxes_test::<SyncStorage>();
+}
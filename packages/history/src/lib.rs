@@ -253,6 +253,31 @@ pub trait History {
     #[allow(unused_variables)]
     fn updater(&self, callback: Arc<dyn Fn() + Send + Sync>) {}
 
+    /// Run `update` inside of a platform view transition.
+    ///
+    /// Platforms that support the [View Transitions API](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API)
+    /// should capture the current state of the page, call `update` to commit the new route and then
+    /// animate between the two states. The default implementation calls `update` immediately.
+    fn start_view_transition(&self, update: Box<dyn FnOnce()>) {
+        update()
+    }
+
+    /// Get the position of the current entry in the history stack.
+    ///
+    /// The index should stay the same when the entry is [replaced](History::replace) and when
+    /// the user navigates back to the entry. The router uses it to remember state for each entry,
+    /// like scroll positions. Returns [`None`] if the [`History`] can't track entries.
+    fn current_index(&self) -> Option<usize> {
+        None
+    }
+
+    /// Whether the [`History`] already restores the scroll position of the window when navigating.
+    ///
+    /// If this returns [`false`], the router will restore the scroll position of the window itself.
+    fn handles_scroll_restoration(&self) -> bool {
+        false
+    }
+
     /// Whether the router should include the legacy prevent default attribute instead of the new
     /// prevent default method. This should only be used by liveview.
     fn include_prevent_default(&self) -> bool {
@@ -117,4 +117,8 @@ impl History for MemoryHistory {
         let mut write = self.state.borrow_mut();
         write.current = path;
     }
+
+    fn current_index(&self) -> Option<usize> {
+        Some(self.state.borrow().history.len())
+    }
 }
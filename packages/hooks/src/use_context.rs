@@ -1,4 +1,6 @@
+use crate::use_memo;
 use dioxus_core::{consume_context, provide_context, try_consume_context, use_hook};
+use dioxus_signals::Memo;
 
 /// Consume some context in the tree, providing a sharable handle to the value
 ///
@@ -35,6 +37,36 @@ pub fn use_context<T: 'static + Clone>() -> T {
     use_hook(|| consume_context::<T>())
 }
 
+/// Consume some context in the tree and select a part of it.
+///
+/// The selector reruns whenever a signal it reads changes, but the component only reruns when the selected value
+/// changes. This lets many components share a large context without rerendering every consumer on every change.
+/// ```rust
+/// # use dioxus::prelude::*;
+/// #[derive(Clone, Copy)]
+/// struct AppState {
+///     user_name: Signal<String>,
+///     unread: Signal<usize>,
+/// }
+///
+/// #[component]
+/// fn UserName() -> Element {
+///     // Changes to `unread` don't rerun this component
+///     let name = use_context_selector(|state: &AppState| state.user_name.cloned());
+///     rsx! { "{name}" }
+/// }
+/// ```
+#[doc = include_str!("../docs/rules_of_hooks.md")]
+#[doc = include_str!("../docs/moving_state_around.md")]
+#[track_caller]
+#[must_use]
+pub fn use_context_selector<T: 'static + Clone, R: PartialEq + 'static>(
+    mut selector: impl FnMut(&T) -> R + 'static,
+) -> Memo<R> {
+    let context = use_context::<T>();
+    use_memo(move || selector(&context))
+}
+
 /// Provide some context via the tree and return a reference to it
 ///
 /// Once the context has been provided, it is immutable. Mutations should be done via interior mutability.
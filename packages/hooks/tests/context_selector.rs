@@ -0,0 +1,76 @@
+//! Components that select part of a context only rerun when their part changes
+
+use dioxus::prelude::*;
+use dioxus_core::TestDom;
+use std::{
+    cell::{Cell, RefCell},
+    thread::LocalKey,
+};
+
+#[derive(Clone, Copy)]
+struct AppState {
+    name: Signal<String>,
+    unread: Signal<usize>,
+}
+
+thread_local! {
+    static STATE: RefCell<Option<AppState>> = const { RefCell::new(None) };
+    static NAME_RENDERS: Cell<usize> = const { Cell::new(0) };
+    static UNREAD_RENDERS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn state() -> AppState {
+    STATE.with(|state| state.borrow().unwrap())
+}
+
+fn renders(counter: &'static LocalKey<Cell<usize>>) -> usize {
+    counter.with(Cell::get)
+}
+
+fn app() -> Element {
+    let state = use_context_provider(|| AppState {
+        name: Signal::new("Alice".to_string()),
+        unread: Signal::new(0),
+    });
+    use_hook(|| STATE.with(|cell| *cell.borrow_mut() = Some(state)));
+
+    rsx! {
+        Name {}
+        Unread {}
+    }
+}
+
+#[component]
+fn Name() -> Element {
+    NAME_RENDERS.with(|renders| renders.set(renders.get() + 1));
+    let name = use_context_selector(|state: &AppState| state.name.cloned());
+    rsx! { "{name}" }
+}
+
+#[component]
+fn Unread() -> Element {
+    UNREAD_RENDERS.with(|renders| renders.set(renders.get() + 1));
+    let has_unread = use_context_selector(|state: &AppState| (state.unread)() > 0);
+    rsx! { "{has_unread}" }
+}
+
+#[test]
+fn consumers_only_rerun_when_their_selection_changes() {
+    let mut dom = TestDom::new(app);
+    dom.rebuild();
+    assert_eq!((renders(&NAME_RENDERS), renders(&UNREAD_RENDERS)), (1, 1));
+
+    // The selection of `Unread` changes from false to true
+    dom.dom().in_runtime(|| state().unread.set(1));
+    dom.run_until_idle();
+    assert_eq!((renders(&NAME_RENDERS), renders(&UNREAD_RENDERS)), (1, 2));
+
+    // The signal changes, but the selection is still true
+    dom.dom().in_runtime(|| state().unread.set(2));
+    dom.run_until_idle();
+    assert_eq!((renders(&NAME_RENDERS), renders(&UNREAD_RENDERS)), (1, 2));
+
+    dom.dom().in_runtime(|| state().name.set("Bob".to_string()));
+    dom.run_until_idle();
+    assert_eq!((renders(&NAME_RENDERS), renders(&UNREAD_RENDERS)), (2, 2));
+}
@@ -5,6 +5,9 @@ import { setAttributeInner } from "./set_attribute";
 
 export type NodeId = number;
 
+// An element that a portal moved out of its place in the tree, along with the placeholder that keeps its place
+type PortalElement = HTMLElement & { portalAnchor?: Node };
+
 export class BaseInterpreter {
   // non bubbling events listen at the element the listener was created at
   global: {
@@ -28,6 +31,9 @@ export class BaseInterpreter {
     [key: number]: Node[];
   };
 
+  // elements that portals moved to their target
+  portals: Set<PortalElement>;
+
   // sledgehammer is generating this...
   m: any;
 
@@ -41,6 +47,7 @@ export class BaseInterpreter {
     this.nodes = [root];
     this.stack = [root];
     this.templates = {};
+    this.portals = new Set();
 
     this.handler = handler;
 
@@ -117,6 +124,11 @@ export class BaseInterpreter {
       if (this.global[event_name] === undefined) {
         this.global[event_name] = { active: 1, callback: this.handler };
         this.root.addEventListener(event_name, this.handler);
+        for (const portal of this.portals) {
+          if (!this.root.contains(portal)) {
+            portal.addEventListener(event_name, this.handler);
+          }
+        }
       } else {
         this.global[event_name].active++;
       }
@@ -148,6 +160,12 @@ export class BaseInterpreter {
         event_name,
         this.global[event_name].callback
       );
+      for (const portal of this.portals) {
+        portal.removeEventListener(
+          event_name,
+          this.global[event_name].callback
+        );
+      }
       delete this.global[event_name];
     }
   }
@@ -174,6 +192,69 @@ export class BaseInterpreter {
     this.stack.push(node);
   }
 
+  // The node that keeps the place of a node in the tree. This is the node itself, unless a portal moved it
+  placeOf(node: Node): Node {
+    return (node as PortalElement).portalAnchor ?? node;
+  }
+
+  // Move the element a portal renders its children into to the target, leaving a placeholder in its place
+  mountPortal(id: NodeId, target: string) {
+    const node = this.nodes[id] as PortalElement;
+    const parent = document.getElementById(target);
+    if (!parent) {
+      return;
+    }
+
+    if (!this.portals.has(node)) {
+      // Portals the server rendered into their target already have an anchor from hydration
+      if (!node.portalAnchor) {
+        node.portalAnchor = this.replaceWithAnchor(node);
+      }
+      node.setAttribute("data-dioxus-id", id.toString());
+      this.portals.add(node);
+    }
+
+    // Bubbling events are delegated to the root, so portals outside of the root listen for them themselves
+    const outside = !this.root.contains(parent);
+    for (const event_name in this.global) {
+      if (outside) {
+        node.addEventListener(event_name, this.global[event_name].callback);
+      } else {
+        node.removeEventListener(event_name, this.global[event_name].callback);
+      }
+    }
+
+    if (node.parentNode !== parent) {
+      parent.appendChild(node);
+    }
+  }
+
+  replaceWithAnchor(node: Node): Node {
+    const anchor = document.createComment("portal");
+    if (node.parentNode) {
+      node.parentNode.replaceChild(anchor, node);
+    } else {
+      // The node was just created and is still on the stack
+      const index = this.stack.lastIndexOf(node);
+      if (index >= 0) {
+        this.stack[index] = anchor;
+      }
+    }
+    return anchor;
+  }
+
+  // Remove the portals that were rendered by a node that is removed from the tree
+  removePortalsIn(removed: Node) {
+    for (const portal of this.portals) {
+      if (portal === removed || removed.contains(portal.portalAnchor)) {
+        this.portals.delete(portal);
+        portal.portalAnchor.parentNode?.removeChild(portal.portalAnchor);
+        portal.remove();
+        this.removePortalsIn(portal);
+      }
+    }
+  }
+
   appendChildren(id: NodeId, many: number) {
     const root = this.nodes[id];
     const els = this.stack.splice(this.stack.length - many);
@@ -269,6 +350,20 @@ export class BaseInterpreter {
             continue;
           }
 
+          // Then try to hydrate the comment node as the anchor of a portal the server rendered into its target
+          const portalSplit = id.split("portal");
+
+          if (portalSplit.length > 1) {
+            const portal = this.nodes[ids[parseInt(portalSplit[1])]] as PortalElement;
+            if (portal) {
+              portal.portalAnchor = currentNode;
+            }
+            if (!continueToNextNode()) {
+              break;
+            }
+            continue;
+          }
+
           // Then try to hydrate the comment node as a marker for the next text node
           const textNodeSplit = id.split("node-id");
 
@@ -45,7 +45,7 @@ mod js {
     pub struct Interpreter;
 
     fn push_root(root: u32) {
-        "{this.pushRoot(this.nodes[$root$]);}"
+        "{this.pushRoot(this.placeOf(this.nodes[$root$]));}"
     }
     fn append_children(id: u32, many: u16) {
         "{this.appendChildren($id$, $many$);}"
@@ -54,16 +54,16 @@ mod js {
         "{this.stack.pop();}"
     }
     fn replace_with(id: u32, n: u16) {
-        "{const root = this.nodes[$id$]; let els = this.stack.splice(this.stack.length-$n$); if (root.listening) { this.removeAllNonBubblingListeners(root); } root.replaceWith(...els);}"
+        "{const root = this.nodes[$id$]; let els = this.stack.splice(this.stack.length-$n$); if (root.listening) { this.removeAllNonBubblingListeners(root); } this.placeOf(root).replaceWith(...els); this.removePortalsIn(root);}"
     }
     fn insert_after(id: u32, n: u16) {
-        "{let node = this.nodes[$id$];node.after(...this.stack.splice(this.stack.length-$n$));}"
+        "{let node = this.placeOf(this.nodes[$id$]);node.after(...this.stack.splice(this.stack.length-$n$));}"
     }
     fn insert_before(id: u32, n: u16) {
-        "{let node = this.nodes[$id$];node.before(...this.stack.splice(this.stack.length-$n$));}"
+        "{let node = this.placeOf(this.nodes[$id$]);node.before(...this.stack.splice(this.stack.length-$n$));}"
     }
     fn remove(id: u32) {
-        "{let node = this.nodes[$id$]; if (node !== undefined) { if (node.listening) { this.removeAllNonBubblingListeners(node); } node.remove(); }}"
+        "{let node = this.nodes[$id$]; if (node !== undefined) { if (node.listening) { this.removeAllNonBubblingListeners(node); } node.remove(); this.removePortalsIn(node); }}"
     }
     fn create_raw_text(text: &str) {
         "{this.stack.push(document.createTextNode($text$));}"
@@ -130,6 +130,9 @@ mod js {
     fn load_template(tmpl_id: u16, index: u16, id: u32) {
         "{let node = this.templates[$tmpl_id$][$index$].cloneNode(true); this.nodes[$id$] = node; this.stack.push(node);}"
     }
+    fn mount_portal(id: u32, target: &str) {
+        "{this.mountPortal($id$, $target$);}"
+    }
 
     #[cfg(feature = "binary-protocol")]
     fn append_children_to_top(many: u16) {
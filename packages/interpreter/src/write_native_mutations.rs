@@ -191,4 +191,8 @@ impl WriteMutations for MutationState {
     fn push_root(&mut self, id: dioxus_core::ElementId) {
         self.channel.push_root(id.0 as _);
     }
+
+    fn mount_portal(&mut self, id: dioxus_core::ElementId, target: &str) {
+        self.channel.mount_portal(id.0 as u32, target);
+    }
 }
@@ -343,6 +343,11 @@ impl History for LiveviewHistory {
         *updater_callback = callback;
     }
 
+    fn current_index(&self) -> Option<usize> {
+        let timeline = self.timeline.lock().expect("unpoisoned mutex");
+        Some(timeline.current_index)
+    }
+
     fn include_prevent_default(&self) -> bool {
         true
     }
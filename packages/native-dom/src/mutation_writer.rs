@@ -19,6 +19,8 @@ pub struct DioxusState {
     pub(crate) node_id_mapping: Vec<Option<NodeId>>,
     /// Count of each handler type
     pub(crate) event_handler_counts: [u32; 32],
+    /// Mapping from the elements portals moved to their target -> the placeholders that keep their place
+    pub(crate) portal_anchors: FxHashMap<NodeId, NodeId>,
 }
 
 impl DioxusState {
@@ -29,6 +31,7 @@ impl DioxusState {
             stack: vec![root_id],
             node_id_mapping: vec![Some(root_id)],
             event_handler_counts: [0; 32],
+            portal_anchors: FxHashMap::default(),
         }
     }
 
@@ -42,8 +45,16 @@ impl DioxusState {
         self.node_id_mapping.get(element_id.0).copied().flatten()
     }
 
+    /// The node that keeps the place of a node in the tree. This is the node itself, unless a portal moved it
+    pub(crate) fn place_of(&self, node_id: NodeId) -> NodeId {
+        self.portal_anchors
+            .get(&node_id)
+            .copied()
+            .unwrap_or(node_id)
+    }
+
     pub(crate) fn anchor_and_nodes(&mut self, id: ElementId, m: usize) -> (usize, Vec<usize>) {
-        let anchor_node_id = self.element_to_node_id(id);
+        let anchor_node_id = self.place_of(self.element_to_node_id(id));
         let new_nodes = self.m_stack_nodes(m);
         (anchor_node_id, new_nodes)
     }
@@ -95,6 +106,37 @@ impl MutationWriter<'_> {
         let top_of_stack_node_id = *self.state.stack.last().unwrap();
         self.docm.node_at_path(top_of_stack_node_id, path)
     }
+
+    /// Whether `node_id` is `ancestor_id` or one of its descendants
+    fn is_inside(&self, mut node_id: NodeId, ancestor_id: NodeId) -> bool {
+        loop {
+            if node_id == ancestor_id {
+                return true;
+            }
+            match self.docm.doc.get_node(node_id).and_then(|node| node.parent) {
+                Some(parent_id) => node_id = parent_id,
+                None => return false,
+            }
+        }
+    }
+
+    /// Remove the portals that were rendered by a node that is removed from the tree
+    fn remove_portals_in(&mut self, removed_id: NodeId) {
+        let removed_portals: Vec<(NodeId, NodeId)> = self
+            .state
+            .portal_anchors
+            .iter()
+            .map(|(&node_id, &anchor_id)| (node_id, anchor_id))
+            .filter(|&(_, anchor_id)| self.is_inside(anchor_id, removed_id))
+            .collect();
+
+        // The placeholders were removed with the node, but the portals live in their targets
+        for (node_id, _) in removed_portals {
+            self.state.portal_anchors.remove(&node_id);
+            self.docm.remove_node(node_id);
+            self.remove_portals_in(node_id);
+        }
+    }
 }
 
 impl WriteMutations for MutationWriter<'_> {
@@ -145,6 +187,14 @@ impl WriteMutations for MutationWriter<'_> {
         trace!("replace_node_with id:{} m:{}", id.0, m);
         let (anchor_node_id, new_node_ids) = self.state.anchor_and_nodes(id, m);
         self.docm.replace_node_with(anchor_node_id, &new_node_ids);
+        if !self.state.portal_anchors.is_empty() {
+            // If the node is a portal, its placeholder was replaced and the node itself needs to be removed
+            let node_id = self.state.element_to_node_id(id);
+            if self.state.portal_anchors.remove(&node_id).is_some() {
+                self.docm.remove_node(node_id);
+            }
+            self.remove_portals_in(node_id);
+        }
     }
 
     fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
@@ -161,14 +211,51 @@ impl WriteMutations for MutationWriter<'_> {
         trace!("remove_node id:{}", id.0);
         let node_id = self.state.element_to_node_id(id);
         self.docm.remove_node(node_id);
+        if !self.state.portal_anchors.is_empty() {
+            if let Some(anchor_id) = self.state.portal_anchors.remove(&node_id) {
+                self.docm.remove_node(anchor_id);
+            }
+            self.remove_portals_in(node_id);
+        }
     }
 
     fn push_root(&mut self, id: ElementId) {
         trace!("push_root id:{}", id.0);
-        let node_id = self.state.element_to_node_id(id);
+        let node_id = self.state.place_of(self.state.element_to_node_id(id));
         self.state.stack.push(node_id);
     }
 
+    fn mount_portal(&mut self, id: ElementId, target: &str) {
+        trace!("mount_portal id:{} target:{}", id.0, target);
+        let node_id = self.state.element_to_node_id(id);
+        let Some(parent_id) = self
+            .docm
+            .doc
+            .query_selector(&format!("#{target}"))
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        if !self.state.portal_anchors.contains_key(&node_id) {
+            // Leave a placeholder in the place of the node. If the node was just created, it is still on the stack
+            let anchor_id = self.docm.create_comment_node();
+            match self.state.stack.iter().rposition(|&n| n == node_id) {
+                Some(index) => self.state.stack[index] = anchor_id,
+                None => self.docm.insert_nodes_before(node_id, &[anchor_id]),
+            }
+            self.state.portal_anchors.insert(node_id, anchor_id);
+
+            // Events are found by the dioxus id of the nodes they bubble through
+            let value = AttributeValue::Text(id.0.to_string());
+            self.set_attribute("data-dioxus-id", None, &value, id);
+        }
+
+        self.docm.remove_node(node_id);
+        self.docm.append_children(parent_id, &[node_id]);
+    }
+
     fn set_node_text(&mut self, value: &str, id: ElementId) {
         trace!("set_node_text id:{} value:{}", id.0, value);
         let node_id = self.state.element_to_node_id(id);
@@ -24,7 +24,7 @@ enum Route {
     #[layout(Nav)]
     #[route("/")]
     Home,
-    #[route("/child")]
+    #[route("/child", ChildSplit, lazy)]
     ChildSplit,
 }
 
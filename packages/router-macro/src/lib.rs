@@ -92,11 +92,12 @@ mod segment;
 /// # fn IndexComponent() -> Element { VNode::empty() }
 /// ```
 ///
-/// # `#[route("path", component)]`
+/// # `#[route("path", component, lazy)]`
 ///
-/// The `#[route]` attribute is used to define a route. It takes up to 2 parameters:
+/// The `#[route]` attribute is used to define a route. It takes up to 3 parameters:
 /// - `path`: The path to the enum variant (relative to the parent nest)
 /// - (optional) `component`: The component to render when the route is matched. If not specified, the name of the variant is used
+/// - (optional) `lazy` or `lazy(fallback = Component)`: Split the component into a separate wasm module that is loaded when the route is rendered
 ///
 /// Routes are the most basic attribute. They allow you to define a route and the component to render when the route is matched. The component must take all dynamic parameters of the route and all parent nests.
 /// The next variant will be tied to the component. If you link to that variant, the component will be rendered.
@@ -115,6 +116,36 @@ mod segment;
 /// # fn Index() -> Element { VNode::empty() }
 /// ```
 ///
+/// ## Lazy routes
+///
+/// With the `wasm-split` feature of `dioxus` enabled, routes marked as `lazy` are compiled into a separate
+/// wasm module that is only downloaded once the route is rendered. While the module loads, the route suspends.
+/// With `lazy(fallback = Component)`, the route is wrapped in a suspense boundary that renders `Component`
+/// instead. If the app starts on a lazy route, for example while hydrating a page rendered on the server, the
+/// `Router` starts loading the module right away.
+///
+/// Routes that are not marked as `lazy` are split into their own module as well, but they have no fallback and
+/// their module is not preloaded.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     // The admin panel is only downloaded when the user visits /admin
+///     #[route("/admin", AdminPanel, lazy(fallback = Loading))]
+///     Admin {},
+/// }
+/// # #[component]
+/// # fn Index() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn AdminPanel() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Loading() -> Element { VNode::empty() }
+/// ```
+///
 /// # `#[redirect("path", function)]`
 ///
 /// The `#[redirect]` attribute is used to define a redirect. It takes 2 parameters:
@@ -239,10 +270,51 @@ mod segment;
 /// # #[component]
 /// # fn Home() -> Element { VNode::empty() }
 /// ```
+///
+/// # `#[slot]`
+///
+/// The `#[slot]` attribute marks a field of a route as a parallel route slot. It takes no parameters.
+///
+/// The field must implement `RouteSlot` (for example `Option<OtherRoute>` where `OtherRoute` is another routable enum). The slot is
+/// encoded in the query string with the name of the field as the key and rendered by the `Outlet` with the same `name`. Links inside of
+/// the slot only change the route in the slot.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[layout(Dashboard)]
+///         // This is at /?modal=%2Fsettings when the settings modal is open
+///         #[route("/")]
+///         Home {
+///             #[slot]
+///             modal: Option<Modal>,
+///         },
+/// }
+///
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Modal {
+///     #[route("/settings")]
+///     Settings {},
+/// }
+///
+/// #[component]
+/// fn Dashboard() -> Element {
+///     rsx! {
+///         Outlet::<Route> {}
+///         Outlet::<Route> { name: "modal" }
+///     }
+/// }
+/// # #[component]
+/// # fn Home(modal: Option<Modal>) -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Settings() -> Element { VNode::empty() }
+/// ```
 #[doc(alias = "route")]
 #[proc_macro_derive(
     Routable,
-    attributes(route, nest, end_nest, layout, end_layout, redirect, child)
+    attributes(route, nest, end_nest, layout, end_layout, redirect, child, slot)
 )]
 pub fn routable(input: TokenStream) -> TokenStream {
     let routes_enum = parse_macro_input!(input as syn::ItemEnum);
@@ -508,6 +580,8 @@ impl RouteEnum {
         let name = &self.name;
 
         let error_name = format_ident!("{}MatchError", self.name);
+        // Routes with slots parse them from the query before it is decoded
+        let raw_query = self.has_slots().then(|| quote! { let raw_query = query; });
         let tokens = tree.roots.iter().map(|&id| {
             let route = tree.get(id).unwrap();
             route.to_tokens(&self.nests, &tree, self.name.clone(), error_name.clone())
@@ -529,6 +603,7 @@ impl RouteEnum {
                     let route = s;
                     let (route, hash) = route.split_once('#').unwrap_or((route, ""));
                     let (route, query) = route.split_once('?').unwrap_or((route, ""));
+                    #raw_query
                     // Remove any trailing slashes. We parse /route/ and /route in the same way
                     // Note: we don't use trim because it includes more code
                     let route = route.strip_suffix('/').unwrap_or(route);
@@ -564,6 +639,13 @@ impl RouteEnum {
         }
     }
 
+    fn has_slots(&self) -> bool {
+        self.endpoints.iter().any(|endpoint| match endpoint {
+            RouteEndpoint::Route(route) => !route.slots.is_empty(),
+            RouteEndpoint::Redirect(_) => false,
+        })
+    }
+
     fn error_name(&self) -> Ident {
         Ident::new(&(self.name.to_string() + "MatchError"), Span::call_site())
     }
@@ -669,15 +751,46 @@ impl RouteEnum {
         let site_map = &self.site_map;
 
         let mut matches = Vec::new();
+        let mut slot_matches = Vec::new();
+        let mut lazy_loaders = Vec::new();
+        let mut preload_matches = Vec::new();
 
         // Collect all routes matches
         for route in &self.endpoints {
             if let RouteEndpoint::Route(route) = route {
                 matches.push(route.routable_match(&self.layouts, &self.nests, name));
+                slot_matches.extend(route.slot_matches());
+                lazy_loaders.extend(route.lazy_loader(name));
+                preload_matches.extend(route.preload_match(name));
             }
         }
 
+        let render_slot = (!slot_matches.is_empty()).then(|| {
+            quote! {
+                fn render_slot(&self, name: &str) -> dioxus_core::Element {
+                    match (name, self) {
+                        #(#slot_matches)*
+                        _ => VNode::empty()
+                    }
+                }
+            }
+        });
+
+        let preload = (!preload_matches.is_empty()).then(|| {
+            quote! {
+                #[allow(unreachable_patterns)]
+                fn preload(&self) {
+                    match self.clone() {
+                        #(#preload_matches)*
+                        _ => {}
+                    }
+                }
+            }
+        });
+
         quote! {
+            #(#lazy_loaders)*
+
             impl dioxus_router::routable::Routable for #name where Self: Clone {
                 const SITE_MAP: &'static [dioxus_router::routable::SiteMapSegment] = &[
                     #(#site_map,)*
@@ -690,6 +803,10 @@ impl RouteEnum {
                         _ => VNode::empty()
                     }
                 }
+
+                #render_slot
+
+                #preload
             }
         }
     }
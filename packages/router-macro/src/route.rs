@@ -19,25 +19,62 @@ use crate::segment::create_error_type;
 use crate::segment::parse_route_segments;
 use crate::segment::RouteSegment;
 
+mod kw {
+    syn::custom_keyword!(lazy);
+    syn::custom_keyword!(fallback);
+}
+
 struct RouteArgs {
     route: LitStr,
     comp_name: Option<Path>,
+    lazy: Option<LazyArgs>,
 }
 
 impl Parse for RouteArgs {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         let route = input.parse::<LitStr>()?;
 
+        let mut comp_name = None;
+        let mut lazy = None;
+        while input.parse::<syn::Token![,]>().is_ok() {
+            if input.peek(kw::lazy) {
+                lazy = Some(input.parse()?);
+            } else if let Ok(path) = input.parse() {
+                comp_name = Some(path);
+            }
+        }
+
         Ok(RouteArgs {
             route,
-            comp_name: {
-                let _ = input.parse::<syn::Token![,]>();
-                input.parse().ok()
-            },
+            comp_name,
+            lazy,
         })
     }
 }
 
+/// The `lazy` or `lazy(fallback = Component)` option of a route
+#[derive(Debug)]
+pub(crate) struct LazyArgs {
+    fallback: Option<Path>,
+}
+
+impl Parse for LazyArgs {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        input.parse::<kw::lazy>()?;
+
+        let mut fallback = None;
+        if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            content.parse::<kw::fallback>()?;
+            content.parse::<syn::Token![=]>()?;
+            fallback = Some(content.parse()?);
+        }
+
+        Ok(LazyArgs { fallback })
+    }
+}
+
 struct ChildArgs {
     route: LitStr,
 }
@@ -60,6 +97,8 @@ pub(crate) struct Route {
     pub hash: Option<HashFragment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
+    pub slots: Vec<(Ident, Type)>,
+    pub lazy: Option<LazyArgs>,
     fields: Vec<(Ident, Type)>,
 }
 
@@ -75,6 +114,7 @@ impl Route {
             .find(|attr| attr.path().is_ident("route"));
         let route;
         let ty;
+        let mut lazy = None;
         let route_name = variant.ident.clone();
         match route_attr {
             Some(attr) => {
@@ -84,6 +124,7 @@ impl Route {
                     component: comp_name,
                 };
                 route = args.route.value();
+                lazy = args.lazy;
             }
             None => {
                 if let Some(route_attr) = variant
@@ -154,6 +195,31 @@ impl Route {
             )?
         };
 
+        // Fields marked with #[slot] hold parallel routes that are encoded in the query string
+        let slots: Vec<(Ident, Type)> = match &variant.fields {
+            syn::Fields::Named(fields) => fields
+                .named
+                .iter()
+                .filter(|f| f.attrs.iter().any(|attr| attr.path().is_ident("slot")))
+                .map(|f| (f.ident.clone().unwrap(), f.ty.clone()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        if !slots.is_empty() {
+            if let RouteType::Child(_) = &ty {
+                return Err(syn::Error::new_spanned(
+                    variant.clone(),
+                    "Routable variants with a #[child(..)] attribute cannot have #[slot] fields",
+                ));
+            }
+            if let Some(QuerySegment::Single(_)) = &query {
+                return Err(syn::Error::new_spanned(
+                    variant.clone(),
+                    "Routable variants with #[slot] fields cannot use a spread query segment (`?:..query`). Use named query segments (`?:query&:other_query`) instead",
+                ));
+            }
+        }
+
         Ok(Self {
             ty,
             route_name,
@@ -163,6 +229,8 @@ impl Route {
             hash,
             nests,
             layouts,
+            slots,
+            lazy,
             fields,
         })
     }
@@ -199,11 +267,13 @@ impl Route {
             RouteType::Leaf { .. } => {
                 let write_nests = self.nests.iter().map(|id| nests[id.0].write());
                 let write_segments = self.segments.iter().map(|s| s.write_segment());
+                let write_slots = self.write_slots();
                 quote! {
                     Self::#name { #(#dynamic_segments,)* } => {
                         #(#write_nests)*
                         #(#write_segments)*
                         #write_query
+                        #write_slots
                         #write_hash
                     }
                 }
@@ -271,103 +341,285 @@ impl Route {
                 let dynamic_segments = self.dynamic_segments();
                 let dynamic_segments_from_route = self.dynamic_segments();
 
-
                 let component = quote_spanned! { name.span() =>
                     #component
                 };
 
-                /*
-                The implementation of this is pretty gnarly/gross.
-
-                We achieve the bundle splitting by wrapping the incoming function in a new component
-                that suspends based on an internal lazy loader. This lets us use suspense features
-                without breaking the rules of hooks. The router derive is quite complex so this shoves
-                the complexity towards the "leaf" of the codegen rather to its core. In the future though,
-                we should think about restructuring the router macro completely since its codegen
-                makes up nearly 30-40% of the binary size in the dioxus docsite.
-                */
-                use sha2::Digest;
-                let dynamic_segments_receiver = self.dynamic_segments();
-                let dynamic_segments_from_route_ = self.dynamic_segments();
-                let dynamic_segments_from_route__ = self.dynamic_segments();
-                    let unique_identifier = base16::encode_lower(
-                    &sha2::Sha256::digest(format!("{name} {span:?}", span = name.span()))[..16],
-                );
-                let module_name = format_ident!("module{}{unique_identifier}", name).to_string();
-                let comp_name = format_ident!("route{}{unique_identifier}", name);
-
-                quote! {
-                    #[allow(unused)]
-                    (#last_index, Self::#name { #(#dynamic_segments,)* }) => {
-                        dioxus::config_macros::maybe_wasm_split! {
-                            if wasm_split {
-                                {
-                                    fn #comp_name(args: #router_name) -> Element {
-                                        match args {
-                                            #router_name::#name { #(#dynamic_segments_from_route_,)* } => {
-                                                rsx! {
-                                                    #component {
-                                                        #(#dynamic_segments_from_route__: #dynamic_segments_from_route__,)*
-                                                    }
-                                                }
+                if self.lazy.is_some() {
+                    // Lazy routes are rendered by the loader function generated in `lazy_loader`
+                    let lazy_fn = self.lazy_fn_name(router_name);
+                    quote! {
+                        #[allow(unused)]
+                        (#last_index, __route @ Self::#name { .. }) => {
+                            #lazy_fn(__route, false)
+                        }
+                    }
+                } else {
+                    let split_items = self.split_items(router_name, &component);
+                    let dynamic_segments_receiver = self.dynamic_segments();
+                    quote! {
+                        #[allow(unused)]
+                        (#last_index, Self::#name { #(#dynamic_segments,)* }) => {
+                            dioxus::config_macros::maybe_wasm_split! {
+                                if wasm_split {
+                                    {
+                                        #split_items
+
+                                        rsx! {
+                                            LoaderInner {
+                                                args: NoPartialEq(#router_name::#name { #(#dynamic_segments_receiver,)* } )
+                                            }
+                                        }
+                                    }
+                                } else {
+                                    {
+                                        rsx! {
+                                            #component {
+                                                #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
                                             }
-                                            _ => unreachable!()
                                         }
                                     }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
 
+        tokens
+    }
 
+    /*
+    The implementation of this is pretty gnarly/gross.
+
+    We achieve the bundle splitting by wrapping the incoming function in a new component
+    that suspends based on an internal lazy loader. This lets us use suspense features
+    without breaking the rules of hooks. The router derive is quite complex so this shoves
+    the complexity towards the "leaf" of the codegen rather to its core. In the future though,
+    we should think about restructuring the router macro completely since its codegen
+    makes up nearly 30-40% of the binary size in the dioxus docsite.
+    */
+    /// Generate the items that split the component of this route into a separate wasm module: the
+    /// `MODULE` lazy loader and the `LoaderInner` component that suspends until the module is loaded.
+    fn split_items(&self, router_name: &Ident, component: &TokenStream2) -> TokenStream2 {
+        use sha2::Digest;
 
-                                    #[component]
-                                    fn LoaderInner(args: NoPartialEq<#router_name>) -> Element {
-                                        static MODULE: wasm_split::LazyLoader<#router_name, Element> =
-                                            wasm_split::lazy_loader!(extern #module_name fn #comp_name(props: #router_name) -> Element);
+        let name = &self.route_name;
+        let dynamic_segments_from_route_ = self.dynamic_segments();
+        let dynamic_segments_from_route__ = self.dynamic_segments();
+        let unique_identifier = base16::encode_lower(
+            &sha2::Sha256::digest(format!("{name} {span:?}", span = name.span()))[..16],
+        );
+        let module_name = format_ident!("module{}{unique_identifier}", name).to_string();
+        let comp_name = format_ident!("route{}{unique_identifier}", name);
+
+        quote! {
+            fn #comp_name(args: #router_name) -> Element {
+                match args {
+                    #router_name::#name { #(#dynamic_segments_from_route_,)* } => {
+                        rsx! {
+                            #component {
+                                #(#dynamic_segments_from_route__: #dynamic_segments_from_route__,)*
+                            }
+                        }
+                    }
+                    _ => unreachable!()
+                }
+            }
 
-                                        use_resource(|| async move { MODULE.load().await }).suspend()?;
-                                        MODULE.call(args.0).unwrap()
-                                    }
+            static MODULE: wasm_split::LazyLoader<#router_name, Element> =
+                wasm_split::lazy_loader!(extern #module_name fn #comp_name(props: #router_name) -> Element);
 
-                                    struct NoPartialEq<T>(T);
+            #[component]
+            fn LoaderInner(args: NoPartialEq<#router_name>) -> Element {
+                use_resource(|| async move { MODULE.load().await }).suspend()?;
+                MODULE.call(args.0).unwrap()
+            }
 
-                                    impl<T: Clone> Clone for NoPartialEq<T> {
-                                        fn clone(&self) -> Self {
-                                            Self(self.0.clone())
-                                        }
-                                    }
+            struct NoPartialEq<T>(T);
 
-                                    impl<T: ::std::fmt::Display> ::std::fmt::Display for NoPartialEq<T> {
-                                        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-                                            self.0.fmt(f)
-                                        }
-                                    }
+            impl<T: Clone> Clone for NoPartialEq<T> {
+                fn clone(&self) -> Self {
+                    Self(self.0.clone())
+                }
+            }
 
-                                    impl<T> PartialEq for NoPartialEq<T> {
-                                        fn eq(&self, _other: &Self) -> bool {
-                                            false
-                                        }
-                                    }
+            impl<T: ::std::fmt::Display> ::std::fmt::Display for NoPartialEq<T> {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    self.0.fmt(f)
+                }
+            }
 
-                                    rsx! {
-                                        LoaderInner {
-                                            args: NoPartialEq(#router_name::#name { #(#dynamic_segments_receiver,)* } )
-                                        }
-                                    }
-                                }
-                            } else {
-                                {
-                                    rsx! {
-                                        #component {
-                                            #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
-                                        }
-                                    }
-                                }
+            impl<T> PartialEq for NoPartialEq<T> {
+                fn eq(&self, _other: &Self) -> bool {
+                    false
+                }
+            }
+        }
+    }
+
+    /// The loader function is a free function next to the enum, so it is named after both the enum
+    /// and the variant to keep routers in the same module apart.
+    fn lazy_fn_name(&self, router_name: &Ident) -> Ident {
+        format_ident!("__lazy_{}_{}", router_name, self.route_name)
+    }
+
+    /// Generate the function that renders or preloads a `lazy` route. The split module is only
+    /// referenced from this function so rendering and preloading share the same loader.
+    pub(crate) fn lazy_loader(&self, router_name: &Ident) -> Option<TokenStream2> {
+        let lazy = self.lazy.as_ref()?;
+        let RouteType::Leaf { component } = &self.ty else {
+            return None;
+        };
+
+        let name = &self.route_name;
+        let lazy_fn = self.lazy_fn_name(router_name);
+        let component = quote_spanned! { name.span() =>
+            #component
+        };
+        let split_items = self.split_items(router_name, &component);
+        let dynamic_segments = self.dynamic_segments();
+        let dynamic_segments_from_route = self.dynamic_segments();
+
+        let loader = quote! {
+            LoaderInner {
+                args: NoPartialEq(route)
+            }
+        };
+        let direct = quote! {
+            #component {
+                #(#dynamic_segments_from_route: #dynamic_segments_from_route,)*
+            }
+        };
+        // The server renders the fallback boundary as well so the html matches the client during hydration
+        let (loader, direct) = match &lazy.fallback {
+            Some(fallback) => (
+                quote! {
+                    dioxus_core::SuspenseBoundary {
+                        fallback: |_| rsx! { #fallback {} },
+                        #loader
+                    }
+                },
+                quote! {
+                    dioxus_core::SuspenseBoundary {
+                        fallback: |_| rsx! { #fallback {} },
+                        #direct
+                    }
+                },
+            ),
+            None => (loader, direct),
+        };
+
+        Some(quote! {
+            #[allow(non_snake_case, unreachable_patterns)]
+            fn #lazy_fn(route: #router_name, preload: bool) -> Element {
+                dioxus::config_macros::maybe_wasm_split! {
+                    if wasm_split {
+                        {
+                            #split_items
+
+                            if preload {
+                                dioxus_core::spawn(async {
+                                    MODULE.load().await;
+                                });
+                                return VNode::empty();
+                            }
+
+                            rsx! { #loader }
+                        }
+                    } else {
+                        {
+                            if preload {
+                                return VNode::empty();
+                            }
+
+                            match route {
+                                #router_name::#name { #(#dynamic_segments,)* } => rsx! { #direct },
+                                _ => unreachable!()
                             }
                         }
                     }
                 }
             }
+        })
+    }
+
+    /// Generate the match arm that starts loading the module of a `lazy` route.
+    pub(crate) fn preload_match(&self, router_name: &Ident) -> Option<TokenStream2> {
+        self.lazy.as_ref()?;
+        let name = &self.route_name;
+        let lazy_fn = self.lazy_fn_name(router_name);
+        Some(quote! {
+            __route @ Self::#name { .. } => {
+                let _ = #lazy_fn(__route, true);
+            }
+        })
+    }
+
+    fn write_slots(&self) -> Option<TokenStream2> {
+        if self.slots.is_empty() {
+            return None;
+        }
+
+        // Named query segments always write the `?`, so the slots continue the query string
+        let separator = match &self.query {
+            Some(_) => quote! { '&' },
+            None => quote! { '?' },
+        };
+        let write_slots = self.slots.iter().map(|(ident, _)| {
+            quote! {
+                if let Some(__slot) = dioxus_router::routable::RouteSlot::slot_route(#ident) {
+                    let __slot = __slot.to_string();
+                    write!(f, "{}{}={}", __separator, stringify!(#ident), dioxus_router::exports::percent_encoding::utf8_percent_encode(&__slot, dioxus_router::exports::SLOT_ASCII_SET))?;
+                    __separator = '&';
+                }
+            }
         });
 
-        tokens
+        Some(quote! {
+            {
+                let mut __separator = #separator;
+                #(#write_slots)*
+                let _ = __separator;
+            }
+        })
+    }
+
+    /// Render the route in each `#[slot]` field inside a child router that maps the slot route to
+    /// and from the full route.
+    pub(crate) fn slot_matches(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        let name = &self.route_name;
+        self.slots.iter().map(move |(ident, _)| {
+            let slot_name = ident.to_string();
+            quote! {
+                #[allow(unused)]
+                (#slot_name, Self::#name { #ident, .. }) => {
+                    match dioxus_router::routable::RouteSlot::slot_route(#ident) {
+                        Some(__slot) => rsx! {
+                            dioxus_router::components::child_router::ChildRouter {
+                                route: __slot.clone(),
+                                slot: #slot_name.to_string(),
+                                // Parse the full route and take the route out of this slot
+                                parse_route_from_root_route: |__route| match __route.parse::<Self>() {
+                                    Ok(Self::#name { #ident, .. }) => dioxus_router::routable::RouteSlot::slot_route(&#ident).cloned(),
+                                    _ => None,
+                                },
+                                // Put the slot route into the current full route
+                                format_route_as_root_route: |__slot| {
+                                    let mut __route = dioxus_router::router().current::<Self>();
+                                    if let Self::#name { #ident, .. } = &mut __route {
+                                        dioxus_router::routable::RouteSlot::set_slot_route(#ident, __slot);
+                                    }
+                                    __route.to_string()
+                                },
+                            }
+                        },
+                        None => VNode::empty(),
+                    }
+                }
+            }
+        })
     }
 
     fn dynamic_segments(&self) -> impl Iterator<Item = TokenStream2> + '_ {
@@ -401,6 +653,9 @@ impl Route {
                     from_route = true
                 }
             }
+            if self.slots.iter().any(|(slot, _)| slot == name) {
+                from_route = true
+            }
 
             if from_route {
                 quote! {#name}
@@ -447,9 +702,31 @@ impl Route {
     }
 
     pub(crate) fn parse_query(&self) -> TokenStream2 {
-        match &self.query {
+        let parse_query = match &self.query {
             Some(query) => query.parse(),
             None => quote! {},
+        };
+        // Slots contain full routes, so they are read from the query before it is decoded
+        let parse_slots = self.slots.iter().map(|(ident, ty)| {
+            quote! {
+                let #ident = {
+                    let __argument = raw_query.split('&').find_map(|__argument| {
+                        let (__name, __value) = __argument.split_once('=')?;
+                        (__name == stringify!(#ident)).then_some(__value)
+                    });
+                    let __argument = __argument.map(|__argument| {
+                        dioxus_router::exports::percent_encoding::percent_decode_str(__argument)
+                            .decode_utf8()
+                            .unwrap_or(__argument.into())
+                    });
+                    <#ty as dioxus_router::routable::RouteSlot>::from_slot_argument(__argument.as_deref())
+                };
+            }
+        });
+
+        quote! {
+            #parse_query
+            #(#parse_slots)*
         }
     }
 
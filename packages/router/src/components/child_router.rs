@@ -45,6 +45,8 @@ pub struct ChildRouterProps<R: Routable> {
     parse_route_from_root_route: fn(&str) -> Option<R>,
     /// Take a child route and return a parent route
     format_route_as_root_route: fn(R) -> String,
+    /// The name of the slot this router is rendered in, if the route comes from a `#[slot]` field
+    slot: Option<String>,
 }
 
 impl<R: Routable> PartialEq for ChildRouterProps<R> {
@@ -62,7 +64,7 @@ pub fn ChildRouter<R: Routable>(props: ChildRouterProps<R>) -> Element {
             format_route_as_root_route: props.format_route_as_root_route,
             parse_route_from_root_route: props.parse_route_from_root_route,
         });
-        provide_context(OutletContext::<R>::new());
+        provide_context(OutletContext::<R>::new_slot(props.slot.clone()));
     });
 
     rsx! { Outlet::<R> {} }
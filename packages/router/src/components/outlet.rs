@@ -1,5 +1,23 @@
 use crate::{outlet::OutletContext, *};
 use dioxus_core::Element;
+use dioxus_core_macro::Props;
+
+/// The props for [`Outlet`].
+#[derive(Props, Clone, PartialEq, Default)]
+pub struct OutletProps {
+    /// The name of the `#[slot]` field of the current route to render in this outlet.
+    ///
+    /// If this is not set, the outlet renders the next level of the current route.
+    #[props(into, default)]
+    pub name: Option<String>,
+
+    /// Animate between routes when the component rendered by this outlet changes.
+    ///
+    /// The outgoing route is kept mounted while the transition runs. See [`OutletTransition`] for
+    /// the available options.
+    #[props(into, default)]
+    pub transition: Option<OutletTransition>,
+}
 
 /// An outlet for the current content.
 ///
@@ -68,6 +86,63 @@ use dioxus_core::Element;
 /// # vdom.rebuild_in_place();
 /// # assert_eq!(dioxus_ssr::render(&vdom), "<h1>App</h1><p>Child</p>");
 /// ```
-pub fn Outlet<R: Routable + Clone>() -> Element {
-    OutletContext::<R>::render()
+///
+/// # Named outlets
+/// Routes can hold parallel routes in `#[slot]` fields that are encoded in the query string. An
+/// [`Outlet`] with a `name` renders the route in the slot with the same name. See [`RouteSlot`]
+/// for an example.
+///
+/// # Transitions
+/// Pass a [`OutletTransition`] to keep the outgoing route mounted while the new route animates in.
+/// The layout can observe the transition with [`use_outlet_transition`] and routes can read their own
+/// phase with [`use_transition_phase`].
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::{use_outlet_transition, OutletTransition};
+/// # use std::time::Duration;
+/// # #[derive(Clone, Routable, PartialEq)]
+/// # enum Route {
+/// #     #[layout(Wrapper)]
+/// #         #[route("/")]
+/// #         Index {},
+/// # }
+/// # #[component]
+/// # fn Index() -> Element { VNode::empty() }
+/// #[component]
+/// fn Wrapper() -> Element {
+///     let transition = use_outlet_transition::<Route>();
+///     rsx! {
+///         main {
+///             class: if transition.read().is_transitioning() { "animating" },
+///             Outlet::<Route> { transition: Duration::from_millis(250) }
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Calling the outlet directly
+/// [`Outlet`] now takes [`OutletProps`]. Code that called the component as a function with no
+/// arguments, like `Outlet::<Route>()`, must pass the default props instead:
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::components::OutletProps;
+/// # #[derive(Clone, Routable, PartialEq)]
+/// # enum Route {
+/// #     #[layout(Wrapper)]
+/// #         #[route("/")]
+/// #         Index {},
+/// # }
+/// # #[component]
+/// # fn Index() -> Element { VNode::empty() }
+/// #[component]
+/// fn Wrapper() -> Element {
+///     Outlet::<Route>(OutletProps::default())
+/// }
+/// ```
+///
+/// Using `Outlet::<Route> {}` inside of `rsx!` works the same as before.
+pub fn Outlet<R: Routable + Clone>(props: OutletProps) -> Element {
+    OutletContext::<R>::render(props.name.as_deref(), props.transition.as_ref())
 }
@@ -37,9 +37,15 @@ pub fn Router<R: Routable + Clone>(props: RouterProps<R>) -> Element {
     use crate::{outlet::OutletContext, RouterContext};
 
     use_hook(|| {
-        provide_router_context(RouterContext::new(props.config.call(())));
+        let cfg = props.config.call(());
+        #[cfg(feature = "html")]
+        provide_context(crate::scroll::ScrollManager::new(&cfg));
+        provide_router_context(RouterContext::new(cfg));
     });
 
+    #[cfg(feature = "html")]
+    crate::scroll::use_scroll_restoration();
+
     #[cfg(feature = "streaming")]
     dioxus_hooks::use_after_suspense_resolved(|| {
         dioxus_fullstack_core::commit_initial_chunk();
@@ -49,5 +55,10 @@ pub fn Router<R: Routable + Clone>(props: RouterProps<R>) -> Element {
         provide_context(OutletContext::<R>::new());
     });
 
+    // Start loading the initial route if it is lazy before the layouts around it render
+    use_hook(|| {
+        crate::router().current::<R>().preload();
+    });
+
     rsx! { Outlet::<R> {} }
 }
@@ -1,6 +1,12 @@
+use std::{cell::OnceCell, rc::Rc};
+
 use dioxus_core::{provide_context, try_consume_context, use_hook, Element, VNode};
 
-use crate::{routable::Routable, utils::use_router_internal::use_router_internal};
+use crate::{
+    routable::Routable,
+    transition::{ExitingRoute, OutletTransition, TransitionController},
+    utils::use_router_internal::use_router_internal,
+};
 
 /// A context that manages nested routing levels for outlet components.
 ///
@@ -13,6 +19,7 @@ use crate::{routable::Routable, utils::use_router_internal::use_router_internal}
 #[derive(Clone, Default)]
 pub struct OutletContext<R> {
     current_level: usize,
+    slot: Option<String>,
     _marker: std::marker::PhantomData<R>,
 }
 
@@ -21,14 +28,24 @@ impl<R> OutletContext<R> {
     pub fn new() -> Self {
         Self {
             current_level: 0,
+            slot: None,
             _marker: std::marker::PhantomData,
         }
     }
 
+    /// Creates a new outlet context starting at level 0 inside of the named slot
+    pub(crate) fn new_slot(slot: Option<String>) -> Self {
+        Self {
+            slot,
+            ..Self::new()
+        }
+    }
+
     /// Creates a new outlet context for the next nesting level
     pub fn next(&self) -> Self {
         Self {
             current_level: self.current_level + 1,
+            slot: self.slot.clone(),
             _marker: std::marker::PhantomData,
         }
     }
@@ -38,7 +55,15 @@ impl<R> OutletContext<R> {
         self.current_level
     }
 
-    pub(crate) fn render() -> Element
+    /// Returns the name of the slot this outlet renders in, or `None` for the main outlet tree.
+    ///
+    /// Every named [`Outlet`](crate::components::Outlet) starts a new outlet tree for the route
+    /// in its slot with its own nesting levels.
+    pub fn slot(&self) -> Option<&str> {
+        self.slot.as_deref()
+    }
+
+    pub(crate) fn render(name: Option<&str>, transition: Option<&OutletTransition>) -> Element
     where
         R: Routable + Clone,
     {
@@ -46,6 +71,8 @@ impl<R> OutletContext<R> {
         let outlet: OutletContext<R> = use_outlet_context();
         let current_level = outlet.level();
         provide_context(outlet.next());
+        // Only outlets with a transition need a controller
+        let controller = use_hook(|| Rc::new(OnceCell::<TransitionController<R>>::new()));
 
         if let Some(error) = router.render_error() {
             return if current_level == 0 {
@@ -55,7 +82,24 @@ impl<R> OutletContext<R> {
             };
         }
 
-        router.current::<R>().render(current_level)
+        // Routes that are transitioning out keep rendering the route they were created for
+        let route =
+            match try_consume_context::<ExitingRoute<R>>().and_then(|exiting| exiting.route()) {
+                Some(route) => route,
+                None => router.current::<R>(),
+            };
+
+        // Named outlets render the route in the slot of the current route instead of the next level
+        if let Some(name) = name {
+            return route.render_slot(name);
+        }
+
+        match transition {
+            Some(transition) => controller
+                .get_or_init(|| TransitionController::new(current_level))
+                .render(route, current_level, transition),
+            None => route.render(current_level),
+        }
     }
 }
 
@@ -0,0 +1,302 @@
+use std::{collections::HashMap, rc::Rc};
+
+use dioxus_core::{consume_context, spawn, use_hook, ScopeId};
+use dioxus_history::history;
+use dioxus_hooks::use_effect;
+use dioxus_html::{geometry::PixelsVector2D, MountedData, ScrollBehavior};
+use dioxus_signals::{CopyValue, ReadableExt, WritableExt};
+
+use crate::{router, RouterConfig};
+
+/// Something the router saves the scroll position of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum ScrollKey {
+    Window,
+    Container(&'static str),
+}
+
+/// The scroll positions saved for one entry in the history.
+struct SavedEntry {
+    route: String,
+    positions: HashMap<ScrollKey, PixelsVector2D>,
+}
+
+/// An element registered with the router, along with the scope that registered it.
+struct Registration {
+    scope: ScopeId,
+    element: Rc<MountedData>,
+}
+
+struct ScrollManagerInner {
+    restore_scroll: bool,
+    focus_landmark: Option<&'static str>,
+    /// The history entry and route that is currently rendered.
+    current: Option<(Option<usize>, String)>,
+    entries: HashMap<usize, SavedEntry>,
+    containers: HashMap<&'static str, Registration>,
+    landmarks: HashMap<&'static str, Registration>,
+    /// Set after a navigation until the focus landmark has been focused.
+    pending_focus: bool,
+}
+
+/// Saves the scroll positions of the window and registered scroll containers for each history entry,
+/// and restores them and moves the focus after navigation.
+#[derive(Clone, Copy)]
+pub(crate) struct ScrollManager {
+    inner: CopyValue<ScrollManagerInner>,
+}
+
+impl ScrollManager {
+    pub(crate) fn new<R>(cfg: &RouterConfig<R>) -> Self {
+        Self {
+            inner: CopyValue::new_in_scope(
+                ScrollManagerInner {
+                    restore_scroll: cfg.scroll_restoration,
+                    focus_landmark: cfg.focus_landmark,
+                    current: None,
+                    entries: HashMap::new(),
+                    containers: HashMap::new(),
+                    landmarks: HashMap::new(),
+                    pending_focus: false,
+                },
+                ScopeId::ROOT,
+            ),
+        }
+    }
+
+    /// Whether the router needs to restore the scroll position of the window itself.
+    fn restores_window(&self) -> bool {
+        self.inner.read().restore_scroll && !history().handles_scroll_restoration()
+    }
+
+    /// Save the scroll position of `key` for the entry that is currently rendered.
+    fn record(&self, key: ScrollKey, position: PixelsVector2D) {
+        let mut inner = self.inner.write_unchecked();
+        let Some((Some(index), route)) = inner.current.clone() else {
+            return;
+        };
+        let entry = inner.entries.entry(index).or_insert_with(|| SavedEntry {
+            route: route.clone(),
+            positions: HashMap::new(),
+        });
+        // The entry was replaced by a new page
+        if entry.route != route {
+            entry.route = route;
+            entry.positions.clear();
+        }
+        entry.positions.insert(key, position);
+    }
+
+    /// The saved scroll position of `key` in the entry that is currently rendered.
+    fn saved_position(&self, key: ScrollKey) -> Option<PixelsVector2D> {
+        let inner = self.inner.read();
+        let (Some(index), route) = inner.current.as_ref()? else {
+            return None;
+        };
+        let entry = inner
+            .entries
+            .get(index)
+            .filter(|entry| &entry.route == route)?;
+        entry.positions.get(&key).copied()
+    }
+
+    /// Update the rendered entry. If the entry changed, restore the scroll positions and move the
+    /// focus. This must be called after the new route is rendered.
+    fn update(&self, index: Option<usize>, route: String) {
+        let mut inner = self.inner.write_unchecked();
+        let previous = inner.current.replace((index, route.clone()));
+        let navigated = match previous {
+            // The first render is not a navigation
+            None => false,
+            // Replacing the current entry keeps the scroll position
+            Some((Some(previous), _)) if index == Some(previous) => false,
+            Some((_, previous_route)) => previous_route != route,
+        };
+        if !navigated {
+            return;
+        }
+
+        // Positions that were saved for a different route belong to an entry that was discarded
+        if let Some(index) = index {
+            if inner
+                .entries
+                .get(&index)
+                .is_some_and(|entry| entry.route != route)
+            {
+                inner.entries.remove(&index);
+            }
+        }
+        inner.pending_focus = inner.focus_landmark.is_some();
+        drop(inner);
+
+        self.restore();
+    }
+
+    /// Restore the scroll positions of the current entry and move the focus to the landmark.
+    fn restore(&self) {
+        let inner = self.inner.read();
+        let landmark = inner
+            .focus_landmark
+            .and_then(|name| inner.landmarks.get(name))
+            .map(|landmark| landmark.element.clone());
+        let containers: Vec<_> = inner
+            .containers
+            .iter()
+            .map(|(key, container)| (*key, container.element.clone()))
+            .collect();
+        drop(inner);
+
+        if landmark.is_some() {
+            self.inner.write_unchecked().pending_focus = false;
+        }
+        let window = self
+            .restores_window()
+            .then(|| self.saved_position(ScrollKey::Window).unwrap_or_default());
+        let containers: Vec<_> = containers
+            .into_iter()
+            .map(|(key, element)| {
+                let position = self
+                    .saved_position(ScrollKey::Container(key))
+                    .unwrap_or_default();
+                (element, position)
+            })
+            .collect();
+
+        spawn(async move {
+            // Focusing an element scrolls it into view, so focus the landmark before restoring the
+            // scroll positions
+            if let Some(landmark) = landmark {
+                _ = landmark.set_focus(true).await;
+            }
+            if let Some(position) = window {
+                scroll_window(position);
+            }
+            for (element, position) in containers {
+                _ = element.scroll(position, ScrollBehavior::Instant).await;
+            }
+        });
+    }
+
+    pub(crate) fn register_container(
+        &self,
+        key: &'static str,
+        scope: ScopeId,
+        element: Rc<MountedData>,
+    ) {
+        let mut inner = self.inner.write_unchecked();
+        if !inner.restore_scroll {
+            return;
+        }
+        inner.containers.insert(
+            key,
+            Registration {
+                scope,
+                element: element.clone(),
+            },
+        );
+        drop(inner);
+
+        // Containers that are mounted by the new route are restored as soon as they are mounted
+        if let Some(position) = self.saved_position(ScrollKey::Container(key)) {
+            spawn(async move {
+                _ = element.scroll(position, ScrollBehavior::Instant).await;
+            });
+        }
+    }
+
+    pub(crate) fn record_container(&self, key: &'static str, position: PixelsVector2D) {
+        self.record(ScrollKey::Container(key), position);
+    }
+
+    pub(crate) fn register_landmark(
+        &self,
+        name: &'static str,
+        scope: ScopeId,
+        element: Rc<MountedData>,
+    ) {
+        let mut inner = self.inner.write_unchecked();
+        inner.landmarks.insert(
+            name,
+            Registration {
+                scope,
+                element: element.clone(),
+            },
+        );
+
+        // If the landmark is mounted by the new route, focus it as soon as it is mounted
+        if inner.pending_focus && inner.focus_landmark == Some(name) {
+            inner.pending_focus = false;
+            spawn(async move {
+                _ = element.set_focus(true).await;
+            });
+        }
+    }
+
+    /// Remove the scroll container `key` if it is still registered by `scope`.
+    pub(crate) fn unregister_container(&self, key: &'static str, scope: ScopeId) {
+        unregister(&mut self.inner.write_unchecked().containers, key, scope);
+    }
+
+    /// Remove the focus landmark `name` if it is still registered by `scope`.
+    pub(crate) fn unregister_landmark(&self, name: &'static str, scope: ScopeId) {
+        unregister(&mut self.inner.write_unchecked().landmarks, name, scope);
+    }
+}
+
+/// Another component may have registered the same key since, so only the registration of `scope`
+/// is removed.
+fn unregister(registrations: &mut HashMap<&'static str, Registration>, key: &str, scope: ScopeId) {
+    if registrations
+        .get(key)
+        .is_some_and(|registration| registration.scope == scope)
+    {
+        registrations.remove(key);
+    }
+}
+
+/// Keep track of the history entry the router renders, and restore the scroll positions and focus
+/// when it changes. This must be called in the component that provides the router.
+pub(crate) fn use_scroll_restoration() {
+    let manager = use_hook(|| {
+        let manager = consume_context::<ScrollManager>();
+        if manager.restores_window() {
+            spawn(track_window_scroll(manager));
+        }
+        manager
+    });
+
+    // Read the route in an effect so the effect reruns after every navigation, once the new route is
+    // rendered, without rerendering the router
+    use_effect(move || {
+        let route = router().full_route_string();
+        manager.update(history().current_index(), route);
+    });
+}
+
+/// Save the scroll position of the window whenever it is scrolled. Scroll events are batched into
+/// one message per frame.
+async fn track_window_scroll(manager: ScrollManager) {
+    let mut eval = dioxus_document::eval(
+        r#"
+        let frame;
+        window.addEventListener(
+            "scroll",
+            () => {
+                cancelAnimationFrame(frame);
+                frame = requestAnimationFrame(() => dioxus.send([window.scrollX, window.scrollY]));
+            },
+            { passive: true }
+        );
+        "#,
+    );
+    while let Ok([x, y]) = eval.recv::<[f64; 2]>().await {
+        manager.record(ScrollKey::Window, PixelsVector2D::new(x, y));
+    }
+}
+
+fn scroll_window(position: PixelsVector2D) {
+    dioxus_document::eval(&format!(
+        "window.scrollTo({{ left: {}, top: {}, behavior: \"instant\" }});",
+        position.x, position.y
+    ));
+}
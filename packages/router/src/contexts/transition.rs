@@ -0,0 +1,496 @@
+use std::{sync::Arc, time::Duration};
+
+use dioxus_core::{
+    provide_context, schedule_update, spawn, try_consume_context, use_hook, Callback, DynamicNode,
+    Element,
+};
+use dioxus_core_macro::{rsx, Props};
+use dioxus_history::history;
+use dioxus_signals::{CopyValue, ReadSignal, ReadableExt, Signal, WritableExt};
+
+use crate::routable::Routable;
+
+/// Configuration for animated route transitions in an [`Outlet`](crate::components::Outlet).
+///
+/// When the component rendered by the outlet changes, the outgoing route is kept mounted next to the
+/// incoming route until the transition is over. While a transition is active, each route is wrapped
+/// in an element with a `data-outlet-transition` attribute set to `entering`, `exiting` or `idle`
+/// that you can target with css.
+///
+/// ```rust
+/// # use dioxus_router::OutletTransition;
+/// # use std::time::Duration;
+/// // Keep the outgoing route mounted for 300ms
+/// let slide = OutletTransition::new(Duration::from_millis(300));
+///
+/// // Keep the outgoing route mounted until its css animation finishes, but at most one second
+/// let fade = OutletTransition::new(Duration::from_secs(1)).until_animation_end();
+///
+/// // Let the browser animate between the old and new page
+/// let view = OutletTransition::default().view_transition();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutletTransition {
+    pub(crate) duration: Duration,
+    pub(crate) until_animation_end: bool,
+    pub(crate) view_transition: bool,
+}
+
+impl Default for OutletTransition {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(300))
+    }
+}
+
+impl From<Duration> for OutletTransition {
+    fn from(duration: Duration) -> Self {
+        Self::new(duration)
+    }
+}
+
+#[doc(hidden)]
+pub struct OutletTransitionDurationMarker;
+
+// The `transition` prop of an `Outlet` is optional, so it is an `Option`. This lets it take a duration directly
+impl dioxus_core::SuperFrom<Duration, OutletTransitionDurationMarker> for Option<OutletTransition> {
+    fn super_from(duration: Duration) -> Self {
+        Some(OutletTransition::new(duration))
+    }
+}
+
+impl OutletTransition {
+    /// Keep the outgoing route mounted for `duration` after the route changes.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            until_animation_end: false,
+            view_transition: false,
+        }
+    }
+
+    /// Unmount the outgoing route as soon as an `animationend` or `transitionend` event fires inside
+    /// of it. The duration is still used as an upper bound in case the event never fires.
+    ///
+    /// This requires the `html` feature. Without it, the outgoing route is always kept for the full duration.
+    pub fn until_animation_end(self) -> Self {
+        Self {
+            until_animation_end: true,
+            ..self
+        }
+    }
+
+    /// Use the [View Transitions API](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API)
+    /// instead of keeping the outgoing route mounted.
+    ///
+    /// The outlet keeps rendering the old route until the [`History`](dioxus_history::History) has captured
+    /// the current page and then swaps in the new route. Histories without view transition support
+    /// swap the route immediately.
+    pub fn view_transition(self) -> Self {
+        Self {
+            view_transition: true,
+            ..self
+        }
+    }
+}
+
+/// The phase of a route rendered inside of an [`Outlet`](crate::components::Outlet) with a transition.
+///
+/// Read it from a route component with [`use_transition_phase`](crate::hooks::use_transition_phase).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TransitionPhase {
+    /// No transition is active.
+    #[default]
+    Idle,
+    /// The route was just navigated to and the previous route is still exiting.
+    Entering,
+    /// The route was navigated away from and will be unmounted when the transition ends.
+    Exiting,
+}
+
+impl TransitionPhase {
+    /// The value of the `data-outlet-transition` attribute for this phase.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransitionPhase::Idle => "idle",
+            TransitionPhase::Entering => "entering",
+            TransitionPhase::Exiting => "exiting",
+        }
+    }
+}
+
+/// The state of an [`Outlet`](crate::components::Outlet) with a transition, as seen from the layout
+/// that renders it.
+///
+/// Read it with [`use_outlet_transition`](crate::hooks::use_outlet_transition).
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutletTransitionState<R> {
+    /// The route that is being navigated to, if a transition is active.
+    pub entering: Option<R>,
+    /// The route that is being navigated away from, if a transition is active.
+    pub exiting: Option<R>,
+}
+
+impl<R> Default for OutletTransitionState<R> {
+    fn default() -> Self {
+        Self {
+            entering: None,
+            exiting: None,
+        }
+    }
+}
+
+impl<R> OutletTransitionState<R> {
+    /// Check if the outlet is currently transitioning between two routes.
+    pub fn is_transitioning(&self) -> bool {
+        self.exiting.is_some()
+    }
+}
+
+/// Provided by [`use_outlet_transition`](crate::hooks::use_outlet_transition) so the outlet at `level`
+/// can publish its state to the layout.
+pub(crate) struct OutletTransitionHandle<R: 'static> {
+    pub(crate) level: usize,
+    pub(crate) state: Signal<OutletTransitionState<R>>,
+}
+
+impl<R> Clone for OutletTransitionHandle<R> {
+    fn clone(&self) -> Self {
+        Self {
+            level: self.level,
+            state: self.state,
+        }
+    }
+}
+
+/// Provided to the children of every route rendered by an outlet with a transition. Once the route is
+/// exiting, nested outlets keep rendering the route they were created for instead of the new route.
+#[derive(Clone)]
+pub(crate) struct ExitingRoute<R: 'static> {
+    route: CopyValue<Option<R>>,
+    /// The exiting route of the outlet this outlet is nested in
+    parent: Option<Box<ExitingRoute<R>>>,
+}
+
+impl<R: Clone> ExitingRoute<R> {
+    /// The route nested outlets should render, if this route or one of its parents is exiting.
+    pub(crate) fn route(&self) -> Option<R> {
+        self.route
+            .cloned()
+            .or_else(|| self.parent.as_ref()?.route())
+    }
+}
+
+/// Provided to the children of every route rendered by an outlet with a transition.
+#[derive(Clone, Copy)]
+pub(crate) struct TransitionPhaseContext(pub(crate) ReadSignal<TransitionPhase>);
+
+#[derive(Clone)]
+struct TransitionSlot<R> {
+    id: usize,
+    route: R,
+    /// The element the route rendered the last time it was the current route
+    element: Element,
+    component: Option<&'static str>,
+}
+
+struct TransitionControllerInner<R> {
+    current: Option<TransitionSlot<R>>,
+    exiting: Option<TransitionSlot<R>>,
+    next_id: usize,
+    view_transition_pending: bool,
+    view_transition_ready: bool,
+    until_animation_end: bool,
+    published: (Option<usize>, Option<usize>),
+}
+
+impl<R> TransitionControllerInner<R> {
+    /// Unmount the exiting route if it is still the route with `id`. Returns true if the outlet needs to rerender.
+    fn finish_exit(&mut self, id: usize) -> bool {
+        if self
+            .exiting
+            .as_ref()
+            .is_some_and(|exiting| exiting.id == id)
+        {
+            self.exiting = None;
+            return true;
+        }
+        false
+    }
+}
+
+/// The state an [`Outlet`](crate::components::Outlet) keeps to animate between routes.
+pub(crate) struct TransitionController<R: 'static> {
+    inner: CopyValue<TransitionControllerInner<R>>,
+    handle: Option<OutletTransitionHandle<R>>,
+    update: Arc<dyn Fn() + Send + Sync>,
+    on_end: Callback<usize>,
+}
+
+impl<R> Clone for TransitionController<R> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner,
+            handle: self.handle.clone(),
+            update: self.update.clone(),
+            on_end: self.on_end,
+        }
+    }
+}
+
+impl<R: Routable> TransitionController<R> {
+    /// Create the controller for the outlet at `level`. This must be called while the outlet is rendering.
+    pub(crate) fn new(level: usize) -> Self {
+        let handle = try_consume_context::<OutletTransitionHandle<R>>()
+            .filter(|handle| handle.level == level);
+
+        let inner = CopyValue::new(TransitionControllerInner {
+            current: None,
+            exiting: None,
+            next_id: 0,
+            view_transition_pending: false,
+            view_transition_ready: false,
+            until_animation_end: false,
+            published: (None, None),
+        });
+        let update = schedule_update();
+        let on_end = Callback::new({
+            let update = update.clone();
+            move |id: usize| {
+                let mut inner = inner.write_unchecked();
+                if inner.until_animation_end && inner.finish_exit(id) {
+                    drop(inner);
+                    update();
+                }
+            }
+        });
+
+        Self {
+            inner,
+            handle,
+            update,
+            on_end,
+        }
+    }
+
+    /// Render `route` at `level`, keeping the previous route around if the rendered component changed.
+    pub(crate) fn render(&self, route: R, level: usize, transition: &OutletTransition) -> Element {
+        let element = route.render(level);
+        let component = rendered_component(&element);
+
+        let mut inner = self.inner.write_unchecked();
+        inner.until_animation_end = transition.until_animation_end;
+        let changed = inner
+            .current
+            .as_ref()
+            .is_some_and(|current| current.component != component);
+
+        if !changed {
+            let id = inner
+                .current
+                .as_ref()
+                .map(|current| current.id)
+                .unwrap_or_default();
+            inner.current = Some(TransitionSlot {
+                id,
+                route,
+                element,
+                component,
+            });
+        } else if transition.view_transition && !inner.view_transition_ready {
+            // Keep the old route on screen until the platform has captured it
+            if !inner.view_transition_pending {
+                inner.view_transition_pending = true;
+                let controller = self.clone();
+                history().start_view_transition(Box::new(move || {
+                    let mut inner = controller.inner.write_unchecked();
+                    inner.view_transition_pending = false;
+                    inner.view_transition_ready = true;
+                    drop(inner);
+                    (controller.update)();
+                }));
+            }
+        } else if transition.view_transition {
+            inner.view_transition_ready = false;
+            inner.next_id += 1;
+            inner.current = Some(TransitionSlot {
+                id: inner.next_id,
+                route,
+                element,
+                component,
+            });
+        } else {
+            inner.next_id += 1;
+            let id = inner.next_id;
+            inner.exiting = inner.current.take();
+            inner.current = Some(TransitionSlot {
+                id,
+                route,
+                element,
+                component,
+            });
+
+            if let Some(exiting) = &inner.exiting {
+                let exiting = exiting.id;
+                let duration = transition.duration;
+                let controller = self.clone();
+                spawn(async move {
+                    dioxus_core::internal::sleep(duration).await;
+                    controller.finish_exit(exiting);
+                });
+            }
+        }
+
+        let current = inner.current.clone();
+        let exiting = inner.exiting.clone();
+        drop(inner);
+
+        self.publish(current.as_ref(), exiting.as_ref());
+
+        let current_phase = match exiting {
+            Some(_) => TransitionPhase::Entering,
+            None => TransitionPhase::Idle,
+        };
+        let slots = exiting
+            .map(|slot| (slot, TransitionPhase::Exiting))
+            .into_iter()
+            .chain(current.map(|slot| (slot, current_phase)));
+        let on_end = self.on_end;
+
+        rsx! {
+            for (slot, phase) in slots {
+                TransitionRoute::<R> {
+                    key: "{slot.id}",
+                    id: slot.id,
+                    route: slot.route,
+                    element: slot.element,
+                    phase,
+                    on_end,
+                }
+            }
+        }
+    }
+
+    /// Unmount the exiting route if it is still the route with `id`.
+    fn finish_exit(&self, id: usize) {
+        let finished = self.inner.write_unchecked().finish_exit(id);
+        if finished {
+            (self.update)();
+        }
+    }
+
+    /// Share the current state with the layout if it asked for it with `use_outlet_transition`.
+    fn publish(&self, current: Option<&TransitionSlot<R>>, exiting: Option<&TransitionSlot<R>>) {
+        let Some(handle) = &self.handle else {
+            return;
+        };
+
+        let published = (current.map(|slot| slot.id), exiting.map(|slot| slot.id));
+        let mut inner = self.inner.write_unchecked();
+        if inner.published == published {
+            return;
+        }
+        inner.published = published;
+        drop(inner);
+
+        let mut state = handle.state;
+        state.set(OutletTransitionState {
+            entering: exiting.and(current).map(|slot| slot.route.clone()),
+            exiting: exiting.map(|slot| slot.route.clone()),
+        });
+    }
+}
+
+/// Find the name of the component a route renders at a level. Routes that render the same component are
+/// updated in place instead of transitioning.
+fn rendered_component(element: &Element) -> Option<&'static str> {
+    element
+        .as_ref()
+        .ok()?
+        .dynamic_nodes
+        .iter()
+        .find_map(|node| match node {
+            DynamicNode::Component(component) => Some(component.name),
+            _ => None,
+        })
+}
+
+#[derive(Props, Clone)]
+#[cfg_attr(not(feature = "html"), allow(dead_code))]
+struct TransitionRouteProps<R: Routable> {
+    id: usize,
+    route: R,
+    /// The element the outlet already rendered for the route
+    element: Element,
+    /// Updated in place when the phase changes, so the route and the components that read the phase rerender
+    phase: ReadSignal<TransitionPhase>,
+    on_end: Callback<usize>,
+}
+
+impl<R: Routable> PartialEq for TransitionRouteProps<R> {
+    fn eq(&self, other: &Self) -> bool {
+        // The element is compared by pointer. It is the same as long as the outlet keeps an exiting route around
+        self.id == other.id
+            && self.element == other.element
+            && self.phase == other.phase
+            && self.on_end == other.on_end
+    }
+}
+
+/// Renders one of the routes of a transitioning outlet and provides its phase to the route.
+#[allow(non_snake_case)]
+fn TransitionRoute<R: Routable>(props: TransitionRouteProps<R>) -> Element {
+    let mut exiting = use_hook(|| {
+        provide_context(TransitionPhaseContext(props.phase));
+        let exiting = ExitingRoute {
+            route: CopyValue::new(None),
+            parent: try_consume_context::<ExitingRoute<R>>().map(Box::new),
+        };
+        provide_context(exiting.clone()).route
+    });
+
+    let phase = props.phase.cloned();
+    if phase == TransitionPhase::Exiting && exiting.peek().is_none() {
+        exiting.set(Some(props.route.clone()));
+    }
+
+    wrap_transition_route(&props, phase, props.element.clone())
+}
+
+/// Wrap the route in an element that exposes the phase to css and listens for the end of animations.
+#[cfg(feature = "html")]
+fn wrap_transition_route<R: Routable>(
+    props: &TransitionRouteProps<R>,
+    phase: TransitionPhase,
+    element: Element,
+) -> Element {
+    use dioxus_html as dioxus_elements;
+
+    let id = props.id;
+    let exiting = phase == TransitionPhase::Exiting;
+    let on_end = props.on_end;
+    rsx! {
+        div {
+            "data-outlet-transition": phase.as_str(),
+            onanimationend: move |_| {
+                if exiting {
+                    on_end.call(id)
+                }
+            },
+            ontransitionend: move |_| {
+                if exiting {
+                    on_end.call(id)
+                }
+            },
+            {element}
+        }
+    }
+}
+
+#[cfg(not(feature = "html"))]
+fn wrap_transition_route<R: Routable>(
+    _: &TransitionRouteProps<R>,
+    _: TransitionPhase,
+    element: Element,
+) -> Element {
+    element
+}
@@ -0,0 +1,85 @@
+use dioxus_core::{provide_context, try_consume_context, use_hook};
+use dioxus_signals::{ReadSignal, ReadableExt, Signal};
+
+use crate::{
+    transition::{OutletTransitionHandle, OutletTransitionState, TransitionPhaseContext},
+    use_outlet_context, Routable, TransitionPhase,
+};
+
+/// A hook that lets a layout observe the transition of the [`Outlet`](crate::components::Outlet) it renders.
+///
+/// The state only changes if the outlet has a [`transition`](crate::components::OutletProps::transition).
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::use_outlet_transition;
+/// # use std::time::Duration;
+/// #[derive(Clone, Routable, PartialEq)]
+/// enum Route {
+///     #[layout(Wrapper)]
+///         #[route("/")]
+///         Index {},
+///         #[route("/about")]
+///         About {},
+/// }
+///
+/// #[component]
+/// fn Wrapper() -> Element {
+///     let transition = use_outlet_transition::<Route>();
+///     // Slide forward when navigating to the about page and back otherwise
+///     let direction = match &transition.read().entering {
+///         Some(Route::About {}) => "forward",
+///         _ => "backward",
+///     };
+///
+///     rsx! {
+///         div {
+///             class: "slide-{direction}",
+///             Outlet::<Route> { transition: Duration::from_millis(300) }
+///         }
+///     }
+/// }
+/// # #[component]
+/// # fn Index() -> Element { VNode::empty() }
+/// # #[component]
+/// # fn About() -> Element { VNode::empty() }
+/// ```
+#[must_use]
+pub fn use_outlet_transition<R: Routable>() -> ReadSignal<OutletTransitionState<R>> {
+    let outlet = use_outlet_context::<R>();
+    use_hook(|| {
+        let state = Signal::new(OutletTransitionState::default());
+        provide_context(OutletTransitionHandle {
+            level: outlet.level(),
+            state,
+        });
+        state.into()
+    })
+}
+
+/// A hook that returns the [`TransitionPhase`] of the route the current component is rendered in.
+///
+/// Returns [`TransitionPhase::Idle`] if the closest [`Outlet`](crate::components::Outlet) doesn't have a transition.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::{use_transition_phase, TransitionPhase};
+/// #[component]
+/// fn Page() -> Element {
+///     let phase = use_transition_phase();
+///     rsx! {
+///         div {
+///             // Stop accepting input while the page is animating out
+///             inert: phase == TransitionPhase::Exiting,
+///             "Page"
+///         }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn use_transition_phase() -> TransitionPhase {
+    let phase = use_hook(try_consume_context::<TransitionPhaseContext>);
+    phase
+        .map(|TransitionPhaseContext(phase)| phase.cloned())
+        .unwrap_or_default()
+}
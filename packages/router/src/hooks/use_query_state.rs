@@ -0,0 +1,284 @@
+use std::time::Duration;
+
+use dioxus_core::{internal::sleep, spawn, use_hook, Subscribers, Task};
+use dioxus_hooks::{use_effect, use_memo};
+use dioxus_signals::{
+    BorrowError, CopyValue, Readable, ReadableExt, ReadableRef, Signal, UnsyncStorage, WritableExt,
+};
+use percent_encoding::{percent_decode_str, utf8_percent_encode};
+
+use crate::{
+    query_sets::QUERY_ARGUMENT_ASCII_SET, router, DisplayQueryArgument, FromQueryArgument,
+    NavigationTarget, ToQueryArgument,
+};
+
+/// A hook that binds a value to one query argument of the current url.
+///
+/// The value is parsed with [`FromQueryArgument`] and formatted with [`ToQueryArgument`], just like
+/// a query argument in a `#[route("/?:name")]` segment. If the argument is missing or fails to parse,
+/// the value is [`Default::default`]. Setting the value to the default removes the argument from the
+/// url. When the url changes, for example because the user navigated back, the value follows it.
+///
+/// By default, setting the value replaces the current entry in the history right away. Use
+/// [`QueryState::push`] to create a new entry instead, and [`QueryState::debounce`] to wait until the
+/// value stops changing before updating the url.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::use_query_state;
+/// # use std::time::Duration;
+/// #[derive(Clone, Routable, PartialEq)]
+/// enum Route {
+///     #[route("/?:search")]
+///     Search { search: String },
+/// }
+///
+/// #[component]
+/// fn Search(search: String) -> Element {
+///     // Keep `?search=` in sync with the input, but only update the url after typing stops
+///     let mut query = use_query_state::<String>("search").debounce(Duration::from_millis(300));
+///
+///     rsx! {
+///         input {
+///             value: "{query}",
+///             oninput: move |event| query.set(event.value()),
+///         }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn use_query_state<T>(name: &'static str) -> QueryState<T>
+where
+    T: FromQueryArgument + ToQueryArgument + Clone + PartialEq + 'static,
+{
+    let router = use_hook(router);
+    let argument = use_memo(move || query_argument(&router.full_route_string(), name));
+
+    let state = use_hook(|| QueryState {
+        name,
+        value: Signal::new(parse_query_argument(argument.peek().as_deref())),
+        pending: CopyValue::new(None),
+        push: false,
+        debounce: Duration::ZERO,
+    });
+
+    // Follow the url when it changes outside of this hook. A navigation wins over a debounced
+    // update that hasn't reached the url yet.
+    let mut seen = use_hook(|| CopyValue::new((*argument.peek()).clone()));
+    use_effect(move || {
+        let argument = argument.read();
+        if *seen.peek() == *argument {
+            return;
+        }
+        seen.set((*argument).clone());
+        if let Some(task) = state.pending.write_unchecked().take() {
+            task.cancel();
+        }
+        let parsed = parse_query_argument(argument.as_deref());
+        let mut value = state.value;
+        if *value.peek() != parsed {
+            value.set(parsed);
+        }
+    });
+
+    state
+}
+
+/// A value bound to a query argument, created with [`use_query_state`].
+///
+/// Read it like any other signal and update it with [`QueryState::set`] or [`QueryState::with_mut`].
+pub struct QueryState<T: 'static> {
+    name: &'static str,
+    value: Signal<T>,
+    pending: CopyValue<Option<Task>>,
+    push: bool,
+    debounce: Duration,
+}
+
+impl<T> QueryState<T>
+where
+    T: FromQueryArgument + ToQueryArgument + Clone + PartialEq + 'static,
+{
+    /// Push a new entry to the history when the value changes instead of replacing the current one.
+    pub fn push(self) -> Self {
+        Self { push: true, ..self }
+    }
+
+    /// Wait until the value hasn't changed for `debounce` before updating the url. The value itself
+    /// updates immediately. If the url changes in the meantime, for example because the user
+    /// navigated back, the pending update is dropped and the value follows the url.
+    pub fn debounce(self, debounce: Duration) -> Self {
+        Self { debounce, ..self }
+    }
+
+    /// Set the value and update the url.
+    pub fn set(&mut self, value: T) {
+        self.value.set(value);
+        self.schedule_update();
+    }
+
+    /// Modify the value in place and update the url.
+    pub fn with_mut(&mut self, f: impl FnOnce(&mut T)) {
+        f(&mut self.value.write());
+        self.schedule_update();
+    }
+
+    fn schedule_update(&self) {
+        if let Some(task) = self.pending.write_unchecked().take() {
+            task.cancel();
+        }
+
+        if self.debounce.is_zero() {
+            self.update_url();
+            return;
+        }
+
+        let myself = *self;
+        let task = spawn(async move {
+            sleep(myself.debounce).await;
+            myself.pending.write_unchecked().take();
+            myself.update_url();
+        });
+        *self.pending.write_unchecked() = Some(task);
+    }
+
+    fn update_url(&self) {
+        let router = router();
+        let route = router.full_route_string();
+        let value = self.value.peek();
+        let argument = (*value != T::default())
+            .then(|| DisplayQueryArgument::new(self.name, &*value).to_string())
+            .filter(|argument| !argument.is_empty());
+        drop(value);
+        // The argument is formatted as `name=value`, only the value is escaped
+        let value = argument.as_deref().map(|argument| {
+            argument
+                .split_once('=')
+                .map_or(argument, |(_, value)| value)
+        });
+        let new_route = set_query_argument(&route, self.name, value);
+
+        if new_route == route {
+            return;
+        }
+        let target = NavigationTarget::Internal(new_route);
+        if self.push {
+            router.push(target);
+        } else {
+            router.replace(target);
+        }
+    }
+}
+
+impl<T> Clone for QueryState<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for QueryState<T> {}
+
+impl<T> PartialEq for QueryState<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: 'static> Readable for QueryState<T> {
+    type Target = T;
+    type Storage = UnsyncStorage;
+
+    #[track_caller]
+    fn try_read_unchecked(&self) -> Result<ReadableRef<'static, Self>, BorrowError> {
+        self.value.try_read_unchecked()
+    }
+
+    #[track_caller]
+    fn try_peek_unchecked(&self) -> Result<ReadableRef<'static, Self>, BorrowError> {
+        self.value.try_peek_unchecked()
+    }
+
+    fn subscribers(&self) -> Subscribers {
+        self.value.subscribers()
+    }
+}
+
+dioxus_signals::read_impls!(QueryState<T>);
+
+fn parse_query_argument<T: FromQueryArgument>(argument: Option<&str>) -> T {
+    argument
+        .and_then(|argument| T::from_query_argument(argument).ok())
+        .unwrap_or_default()
+}
+
+/// Split a route into the path, query and hash fragment. The query and hash don't include the `?`
+/// and `#` separators.
+fn split_route(route: &str) -> (&str, Option<&str>, Option<&str>) {
+    let (route, hash) = match route.split_once('#') {
+        Some((route, hash)) => (route, Some(hash)),
+        None => (route, None),
+    };
+    let (path, query) = match route.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (route, None),
+    };
+    (path, query, hash)
+}
+
+/// Get the decoded value of the query argument `name` in `route`.
+fn query_argument(route: &str, name: &str) -> Option<String> {
+    let (_, query, _) = split_route(route);
+    query?.split('&').find_map(|argument| {
+        let (key, value) = argument.split_once('=').unwrap_or((argument, ""));
+        (key == name).then(|| {
+            percent_decode_str(value)
+                .decode_utf8()
+                .map(Into::into)
+                .unwrap_or_else(|_| value.to_string())
+        })
+    })
+}
+
+/// Set the query argument `name` in `route` to the unescaped `value`, or remove it if `value` is
+/// [`None`]. Other query arguments keep their order.
+fn set_query_argument(route: &str, name: &str, value: Option<&str>) -> String {
+    let (path, query, hash) = split_route(route);
+    let mut arguments: Vec<String> = query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|argument| !argument.is_empty())
+        .map(ToString::to_string)
+        .collect();
+
+    let encoded = value.map(|value| {
+        format!(
+            "{name}={}",
+            utf8_percent_encode(value, QUERY_ARGUMENT_ASCII_SET)
+        )
+    });
+    let position = arguments.iter().position(|argument| {
+        argument
+            .split_once('=')
+            .map_or(argument.as_str(), |(key, _)| key)
+            == name
+    });
+    match (position, encoded) {
+        (Some(position), Some(encoded)) => arguments[position] = encoded,
+        (Some(position), None) => {
+            arguments.remove(position);
+        }
+        (None, Some(encoded)) => arguments.push(encoded),
+        (None, None) => {}
+    }
+
+    let mut route = path.to_string();
+    if !arguments.is_empty() {
+        route.push('?');
+        route.push_str(&arguments.join("&"));
+    }
+    if let Some(hash) = hash {
+        route.push('#');
+        route.push_str(hash);
+    }
+    route
+}
@@ -0,0 +1,152 @@
+use dioxus_core::{current_scope_id, try_consume_context, use_drop, use_hook, ScopeId};
+use dioxus_html::{geometry::PixelsVector2D, MountedEvent, ScrollEvent};
+
+use crate::scroll::ScrollManager;
+
+/// A scroll container registered with [`use_scroll_container`].
+#[derive(Clone, Copy)]
+pub struct ScrollContainer {
+    manager: Option<ScrollManager>,
+    key: &'static str,
+    scope: ScopeId,
+}
+
+impl ScrollContainer {
+    /// Register the mounted element as the scroll container. Pass this to the `onmounted` handler
+    /// of the element that scrolls.
+    pub fn onmounted(&self, event: MountedEvent) {
+        if let Some(manager) = self.manager {
+            manager.register_container(self.key, self.scope, event.data());
+        }
+    }
+
+    /// Save the scroll position of the container. Pass this to the `onscroll` handler of the element
+    /// that scrolls.
+    pub fn onscroll(&self, event: ScrollEvent) {
+        if let Some(manager) = self.manager {
+            let position = PixelsVector2D::new(event.scroll_left(), event.scroll_top());
+            manager.record_container(self.key, position);
+        }
+    }
+}
+
+/// A hook that lets the router save and restore the scroll position of an element that scrolls
+/// independently of the window.
+///
+/// The router saves the scroll position of the container for every entry in the history. When you
+/// navigate back or forward to an entry, the container is scrolled to where it was. When you
+/// navigate to a new entry, the container is scrolled to the top. The `key` identifies the
+/// container across routes, so a sidebar that is rendered by multiple routes should use the same key
+/// everywhere.
+///
+/// Scroll restoration can be turned off with [`RouterConfig::scroll_restoration`](crate::RouterConfig::scroll_restoration).
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::use_scroll_container;
+/// #[component]
+/// fn Sidebar() -> Element {
+///     let container = use_scroll_container("sidebar");
+///     rsx! {
+///         nav {
+///             overflow_y: "scroll",
+///             onmounted: move |event| container.onmounted(event),
+///             onscroll: move |event| container.onscroll(event),
+///             for i in 0..100 {
+///                 p { "Item {i}" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn use_scroll_container(key: &'static str) -> ScrollContainer {
+    let container = use_hook(|| ScrollContainer {
+        manager: try_consume_context(),
+        key,
+        scope: current_scope_id(),
+    });
+    use_drop(move || {
+        if let Some(manager) = container.manager {
+            manager.unregister_container(container.key, container.scope);
+        }
+    });
+    container
+}
+
+/// A focus landmark registered with [`use_focus_landmark`].
+#[derive(Clone, Copy)]
+pub struct FocusLandmark {
+    manager: Option<ScrollManager>,
+    name: &'static str,
+    scope: ScopeId,
+}
+
+impl FocusLandmark {
+    /// Register the mounted element as the landmark. Pass this to the `onmounted` handler of the
+    /// element that should receive the focus.
+    pub fn onmounted(&self, event: MountedEvent) {
+        if let Some(manager) = self.manager {
+            manager.register_landmark(self.name, self.scope, event.data());
+        }
+    }
+}
+
+/// A hook that registers an element the router moves the focus to after navigation.
+///
+/// Screen readers and keyboard users keep their position on the page when the route changes. Moving
+/// the focus to the main content after navigation lets them continue from the start of the new page.
+/// The router focuses the landmark with the name set in
+/// [`RouterConfig::focus_landmark`](crate::RouterConfig::focus_landmark). No landmark is set by
+/// default, so the router only moves the focus once you opt in.
+///
+/// Most elements can only receive the focus if they have a `tabindex`. Use `tabindex: "-1"` to let
+/// the router focus the element without adding it to the tab order.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::use_focus_landmark;
+/// #[derive(Clone, Routable, PartialEq)]
+/// enum Route {
+///     #[layout(Wrapper)]
+///         #[route("/")]
+///         Index {},
+/// }
+///
+/// fn App() -> Element {
+///     rsx! {
+///         Router::<Route> {
+///             config: || RouterConfig::default().focus_landmark(Some("main")),
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Wrapper() -> Element {
+///     let landmark = use_focus_landmark("main");
+///     rsx! {
+///         nav { "Navigation" }
+///         main {
+///             tabindex: "-1",
+///             onmounted: move |event| landmark.onmounted(event),
+///             Outlet::<Route> {}
+///         }
+///     }
+/// }
+/// # #[component]
+/// # fn Index() -> Element { VNode::empty() }
+/// ```
+#[must_use]
+pub fn use_focus_landmark(name: &'static str) -> FocusLandmark {
+    let landmark = use_hook(|| FocusLandmark {
+        manager: try_consume_context(),
+        name,
+        scope: current_scope_id(),
+    });
+    use_drop(move || {
+        if let Some(manager) = landmark.manager {
+            manager.unregister_landmark(landmark.name, landmark.scope);
+        }
+    });
+    landmark
+}
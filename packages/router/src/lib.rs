@@ -43,9 +43,13 @@ mod contexts {
     pub(crate) mod outlet;
     pub use outlet::{use_outlet_context, OutletContext};
     pub(crate) mod router;
+    pub(crate) mod transition;
     pub use navigator::*;
     pub(crate) use router::*;
+    #[cfg(feature = "html")]
+    pub(crate) mod scroll;
     pub use router::{root_router, GenericRouterContext, ParseRouteError, RouterContext};
+    pub use transition::{OutletTransition, OutletTransitionState, TransitionPhase};
 }
 
 mod router_cfg;
@@ -61,13 +65,24 @@ pub mod hooks {
 
     mod use_navigator;
     pub use use_navigator::*;
+
+    mod use_outlet_transition;
+    pub use use_outlet_transition::*;
+
+    mod use_query_state;
+    pub use use_query_state::*;
+
+    #[cfg(feature = "html")]
+    mod use_scroll_container;
+    #[cfg(feature = "html")]
+    pub use use_scroll_container::*;
 }
 
 pub use hooks::router;
 
 #[cfg(feature = "html")]
 pub use crate::components::{GoBackButton, GoForwardButton, HistoryButtonProps, Link, LinkProps};
-pub use crate::components::{Outlet, Router, RouterProps};
+pub use crate::components::{Outlet, OutletProps, Router, RouterProps};
 pub use crate::contexts::*;
 pub use crate::hooks::*;
 pub use crate::navigation::*;
@@ -117,6 +132,20 @@ pub(crate) mod query_sets {
         .add(b'{')
         .add(b'}');
 
+    /// The ASCII set that must be escaped in route slots. Slots contain a whole route, so the
+    /// characters that separate query arguments must be escaped as well.
+    pub const SLOT_ASCII_SET: &AsciiSet = &PATH_ASCII_SET
+        .add(b'%')
+        .add(b'&')
+        .add(b'+')
+        .add(b'/')
+        .add(b'=');
+
+    /// The ASCII set that must be escaped in the value of a single query argument. The characters
+    /// that separate query arguments must be escaped as well.
+    pub const QUERY_ARGUMENT_ASCII_SET: &AsciiSet =
+        &QUERY_ASCII_SET.add(b'%').add(b'&').add(b'+').add(b'=');
+
     /// The ASCII set that must be escaped in hash fragments.
     pub const FRAGMENT_ASCII_SET: &AsciiSet = &percent_encoding::CONTROLS
         .add(b' ')
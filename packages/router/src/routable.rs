@@ -386,6 +386,99 @@ where
     }
 }
 
+/// A parallel route that is stored in a `#[slot]` field of a route and rendered in a named
+/// [`Outlet`](crate::components::Outlet).
+///
+/// Slots are encoded in the query string of the url with the name of the field as the key. For
+/// example, `Route::Dashboard { panel: Some(Panel::Details { id: 1 }) }` is displayed as
+/// `/dashboard?panel=%2Fdetails%2F1`.
+///
+/// **This trait is implemented for `Option<R>` where `R` is any [`Routable`] type.** An empty slot
+/// renders nothing in its outlet.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// #[rustfmt::skip]
+/// enum Route {
+///     #[layout(Dashboard)]
+///         #[route("/dashboard")]
+///         Home {
+///             #[slot]
+///             panel: Option<Panel>,
+///         },
+/// }
+///
+/// #[derive(Routable, Clone, PartialEq, Debug)]
+/// enum Panel {
+///     #[route("/details/:id")]
+///     Details { id: usize },
+/// }
+///
+/// #[component]
+/// fn Dashboard() -> Element {
+///     rsx! {
+///         main { Outlet::<Route> {} }
+///         // The panel route is rendered in the outlet with the same name as the slot field
+///         aside { Outlet::<Route> { name: "panel" } }
+///     }
+/// }
+///
+/// # #[component]
+/// # fn Home(panel: Option<Panel>) -> Element { VNode::empty() }
+/// # #[component]
+/// # fn Details(id: usize) -> Element { VNode::empty() }
+/// let route = Route::Home { panel: Some(Panel::Details { id: 1 }) };
+/// assert_eq!(route.to_string(), "/dashboard?panel=%2Fdetails%2F1");
+/// assert_eq!("/dashboard?panel=%2Fdetails%2F1".parse::<Route>().unwrap(), route);
+/// ```
+#[rustversion::attr(
+    since(1.78.0),
+    diagnostic::on_unimplemented(
+        message = "`RouteSlot` is not implemented for `{Self}`",
+        label = "slot",
+        note = "RouteSlot is implemented for `Option<R>` where `R` implements `Routable`."
+    )
+)]
+pub trait RouteSlot {
+    /// The route that is rendered in the slot.
+    type Route: Routable;
+
+    /// Create the slot from the url decoded query argument with the name of the slot, or `None` if the
+    /// argument is missing.
+    fn from_slot_argument(argument: Option<&str>) -> Self;
+
+    /// Get the route that is currently in the slot.
+    fn slot_route(&self) -> Option<&Self::Route>;
+
+    /// Replace the route in the slot.
+    fn set_slot_route(&mut self, route: Self::Route);
+}
+
+impl<R: Routable> RouteSlot for Option<R> {
+    type Route = R;
+
+    fn from_slot_argument(argument: Option<&str>) -> Self {
+        let argument = argument?;
+        match R::from_str(argument) {
+            Ok(route) => Some(route),
+            Err(err) => {
+                tracing::error!("Failed to parse route slot: {}", err);
+                None
+            }
+        }
+    }
+
+    fn slot_route(&self) -> Option<&Self::Route> {
+        self.as_ref()
+    }
+
+    fn set_slot_route(&mut self, route: Self::Route) {
+        *self = Some(route);
+    }
+}
+
 /// Something that can be created from a single route segment. This must be implemented for any type that is used as a route segment like `#[route("/:route_segment")]`.
 ///
 ///
@@ -734,6 +827,22 @@ pub trait Routable: FromStr<Err: Display> + Display + Clone + 'static {
     /// Render the route at the given level
     fn render(&self, level: usize) -> Element;
 
+    /// Render the route stored in the `#[slot]` field called `name`.
+    ///
+    /// This is called by an [`Outlet`](crate::components::Outlet) with a `name`. Routes without a
+    /// matching slot render nothing.
+    #[allow(unused_variables)]
+    fn render_slot(&self, name: &str) -> Element {
+        dioxus_core::VNode::empty()
+    }
+
+    /// Start loading the code of the route if it was split into a separate module with
+    /// `#[route("/path", Component, lazy)]`.
+    ///
+    /// The [`Router`](crate::components::Router) calls this for the initial route so the module of a
+    /// lazy route loads while the rest of the app hydrates. Routes that aren't lazy don't do anything.
+    fn preload(&self) {}
+
     /// Checks if this route is a child of the given route.
     ///
     /// # Example
@@ -29,6 +29,10 @@ use std::sync::Arc;
 pub struct RouterConfig<R> {
     pub(crate) failure_external_navigation: fn() -> Element,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    #[cfg(feature = "html")]
+    pub(crate) scroll_restoration: bool,
+    #[cfg(feature = "html")]
+    pub(crate) focus_landmark: Option<&'static str>,
 }
 
 #[cfg(not(feature = "html"))]
@@ -47,6 +51,8 @@ impl<R> Default for RouterConfig<R> {
         Self {
             failure_external_navigation: crate::components::FailureExternalNavigation,
             on_update: None,
+            scroll_restoration: true,
+            focus_landmark: None,
         }
     }
 }
@@ -90,4 +96,34 @@ where
             ..self
         }
     }
+
+    /// Whether the router should save and restore scroll positions for each entry in the history.
+    ///
+    /// This restores the scroll position of the window and every container registered with
+    /// [`use_scroll_container`](crate::hooks::use_scroll_container). If the history already restores
+    /// the scroll position of the window, like [`WebHistory`](https://docs.rs/dioxus-web/latest/dioxus_web/struct.WebHistory.html)
+    /// does, the router leaves the window to the history.
+    ///
+    /// Defaults to [`true`].
+    #[cfg(feature = "html")]
+    pub fn scroll_restoration(self, scroll_restoration: bool) -> Self {
+        Self {
+            scroll_restoration,
+            ..self
+        }
+    }
+
+    /// The name of the landmark the router should focus after navigation.
+    ///
+    /// Landmarks are registered with [`use_focus_landmark`](crate::hooks::use_focus_landmark). If
+    /// this is [`None`], the router doesn't move the focus.
+    ///
+    /// Defaults to [`None`].
+    #[cfg(feature = "html")]
+    pub fn focus_landmark(self, focus_landmark: Option<&'static str>) -> Self {
+        Self {
+            focus_landmark,
+            ..self
+        }
+    }
 }
@@ -162,3 +162,54 @@ fn optional_query_segments_parse() {
         route_without_query_and_other
     );
 }
+
+#[test]
+fn slots_parse() {
+    #[derive(Debug, Clone, PartialEq, Routable)]
+    enum Route {
+        #[route("/?:page")]
+        Index {
+            page: usize,
+            #[slot]
+            panel: Option<Panel>,
+        },
+    }
+
+    #[derive(Debug, Clone, PartialEq, Routable)]
+    enum Panel {
+        #[route("/search?:query&:limit")]
+        Search { query: String, limit: usize },
+    }
+
+    #[component]
+    fn Index(page: usize, panel: Option<Panel>) -> Element {
+        unimplemented!()
+    }
+
+    #[component]
+    fn Search(query: String, limit: usize) -> Element {
+        unimplemented!()
+    }
+
+    let route = Route::Index {
+        page: 2,
+        panel: Some(Panel::Search {
+            query: "dioxus".to_string(),
+            limit: 10,
+        }),
+    };
+    assert_eq!(
+        route.to_string(),
+        "/?page=2&panel=%2Fsearch%3Fquery%3Ddioxus%26limit%3D10"
+    );
+    let parsed_route = route.to_string().parse::<Route>().unwrap();
+    assert_eq!(parsed_route, route);
+
+    let route_without_panel = Route::Index {
+        page: 2,
+        panel: None,
+    };
+    assert_eq!(route_without_panel.to_string(), "/?page=2");
+    let parsed_route_without_panel = "/?page=2".parse::<Route>().unwrap();
+    assert_eq!(parsed_route_without_panel, route_without_panel);
+}
@@ -0,0 +1,119 @@
+use dioxus::prelude::*;
+use dioxus_core::NoOpMutations;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::components::HistoryProvider;
+use std::rc::Rc;
+
+#[test]
+fn lazy_routes_render_their_component() {
+    let mut vdom = VirtualDom::new(app);
+    vdom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Home!</h1>");
+}
+
+// Lazy variants with the same name in two routers of one module must not collide
+#[test]
+fn lazy_routes_of_different_routers_in_one_module() {
+    let mut vdom = VirtualDom::new(|| rsx! { Router::<AdminRoute> {} });
+    vdom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Other</h1>");
+}
+
+#[test]
+fn lazy_routes_pass_their_segments_to_the_component() {
+    let mut vdom = VirtualDom::new(|| {
+        rsx! {
+            HistoryProvider {
+                history: |_| Rc::new(MemoryHistory::with_initial_path("/user/7")) as Rc<dyn History>,
+                Router::<UserRoute> {}
+            }
+        }
+    });
+    vdom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>User 7</h1>");
+}
+
+#[test]
+fn lazy_routes_can_be_preloaded_and_navigated_to() {
+    let mut vdom = VirtualDom::new(|| rsx! { Router::<NavigateRoute> {} });
+    vdom.rebuild_in_place();
+    assert_eq!(dioxus_ssr::render(&vdom), "Start");
+
+    // The effect of Start preloads the other route and navigates to it
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Other</h1>");
+}
+
+fn app() -> Element {
+    rsx! {
+        Router::<Route> {}
+    }
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/", Home, lazy(fallback = Loading))]
+    Index {},
+    #[route("/other", lazy)]
+    Other {},
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum AdminRoute {
+    #[route("/", Other, lazy)]
+    Index {},
+    #[route("/other", lazy)]
+    Other {},
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum UserRoute {
+    #[route("/user/:id", lazy)]
+    User { id: u32 },
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum NavigateRoute {
+    #[route("/", lazy)]
+    Start {},
+    #[route("/other", lazy)]
+    Other {},
+}
+
+#[component]
+fn User(id: u32) -> Element {
+    rsx! {
+        h1 { "User {id}" }
+    }
+}
+
+#[component]
+fn Start() -> Element {
+    use_effect(|| {
+        let other = NavigateRoute::Other {};
+        other.preload();
+        router().push(other);
+    });
+    rsx! { "Start" }
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! {
+        h1 { "Home!" }
+    }
+}
+
+#[component]
+fn Other() -> Element {
+    rsx! {
+        h1 { "Other" }
+    }
+}
+
+#[component]
+fn Loading() -> Element {
+    rsx! {
+        "Loading..."
+    }
+}
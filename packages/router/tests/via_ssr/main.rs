@@ -1,6 +1,11 @@
 mod child_outlet;
+mod lazy;
 mod link;
+mod named_outlet;
 mod navigation;
 mod outlet;
+mod query_state;
 mod redirect;
+mod scroll;
+mod transition;
 mod without_index;
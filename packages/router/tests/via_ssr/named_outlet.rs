@@ -0,0 +1,96 @@
+#![allow(unused)]
+
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_history::{History, MemoryHistory};
+use dioxus_router::{components::HistoryProvider, use_outlet_context};
+
+fn prepare(path: impl Into<String>) -> VirtualDom {
+    let mut vdom = VirtualDom::new_with_props(
+        App,
+        AppProps {
+            path: path.into().parse().unwrap(),
+        },
+    );
+    vdom.rebuild_in_place();
+    return vdom;
+
+    #[derive(Routable, Clone, PartialEq)]
+    #[rustfmt::skip]
+    enum Route {
+        #[layout(Dashboard)]
+            #[route("/")]
+            Home {
+                #[slot]
+                panel: Option<Panel>,
+            },
+    }
+
+    #[derive(Routable, Clone, PartialEq)]
+    #[rustfmt::skip]
+    enum Panel {
+        #[layout(PanelFrame)]
+            #[route("/details/:id")]
+            Details { id: usize },
+    }
+
+    #[component]
+    fn App(path: Route) -> Element {
+        rsx! {
+            HistoryProvider {
+                history:  move |_| Rc::new(MemoryHistory::with_initial_path(path.clone())) as Rc<dyn History>,
+                Router::<Route> {}
+            }
+        }
+    }
+
+    #[component]
+    fn Dashboard() -> Element {
+        rsx! {
+            main { Outlet::<Route> {} }
+            aside { Outlet::<Route> { name: "panel" } }
+        }
+    }
+
+    #[component]
+    fn Home(panel: Option<Panel>) -> Element {
+        rsx! { h1 { "Home" } }
+    }
+
+    #[component]
+    fn PanelFrame() -> Element {
+        let slot = use_outlet_context::<Panel>()
+            .slot()
+            .unwrap_or_default()
+            .to_string();
+        rsx! {
+            h2 { "Panel in {slot}" }
+            Outlet::<Panel> {}
+        }
+    }
+
+    #[component]
+    fn Details(id: usize) -> Element {
+        rsx! { p { "Details {id}" } }
+    }
+}
+
+#[test]
+fn empty_slot() {
+    let vdom = prepare("/");
+    let html = dioxus_ssr::render(&vdom);
+
+    assert_eq!(html, "<main><h1>Home</h1></main><aside></aside>");
+}
+
+#[test]
+fn filled_slot() {
+    let vdom = prepare("/?panel=%2Fdetails%2F3");
+    let html = dioxus_ssr::render(&vdom);
+
+    assert_eq!(
+        html,
+        "<main><h1>Home</h1></main><aside><h2>Panel in panel</h2><p>Details 3</p></aside>"
+    );
+}
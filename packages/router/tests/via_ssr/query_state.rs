@@ -0,0 +1,126 @@
+use dioxus::prelude::*;
+use dioxus_core::{current_scope_id, NoOpMutations};
+use dioxus_router::QueryState;
+use std::{cell::RefCell, time::Duration};
+
+#[test]
+fn writing_query_state_updates_the_url() {
+    let mut vdom = VirtualDom::new(app);
+    rebuild(&mut vdom);
+    assert_eq!(dioxus_ssr::render(&vdom), "<p></p><p>/?search=</p>");
+
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<p>dioxus router</p><p>/?search=dioxus%20router</p>"
+    );
+}
+
+fn app() -> Element {
+    rsx! {
+        Router::<Route> {}
+    }
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/?:search")]
+    Search { search: String },
+}
+
+#[component]
+fn Search(search: String) -> Element {
+    let mut query = use_query_state::<String>("search");
+    let route = use_route::<Route>();
+
+    // Queue an effect to update the query after rebuild_in_place
+    use_effect(move || {
+        query.set("dioxus router".to_string());
+    });
+
+    rsx! {
+        p { "{query}" }
+        p { "{route}" }
+    }
+}
+
+thread_local! {
+    /// The scope and query state of the `Field` component
+    static FIELD: RefCell<Option<(ScopeId, QueryState<String>)>> = const { RefCell::new(None) };
+}
+
+#[test]
+fn query_state_escapes_argument_separators() {
+    let mut vdom = VirtualDom::new(|| rsx! { Router::<FieldRoute> {} });
+    rebuild(&mut vdom);
+
+    let (scope, mut query) = field();
+    vdom.in_runtime(|| vdom.in_scope(scope, || query.set("a&b=c%+d".to_string())));
+    wait_for_debounce(&mut vdom);
+
+    assert_eq!(current_route(&vdom, scope), "/?search=a%26b%3Dc%25%2Bd");
+    vdom.in_runtime(|| assert_eq!(*query.peek(), "a&b=c%+d"));
+}
+
+#[test]
+fn navigation_drops_pending_debounced_update() {
+    let mut vdom = VirtualDom::new(|| rsx! { Router::<FieldRoute> {} });
+    rebuild(&mut vdom);
+
+    let (scope, mut query) = field();
+    vdom.in_runtime(|| vdom.in_scope(scope, || router().push("/?search=first")));
+    vdom.render_immediate(&mut NoOpMutations);
+    vdom.process_events();
+    vdom.in_runtime(|| assert_eq!(*query.peek(), "first"));
+
+    // Navigate back before the debounced update reaches the url
+    vdom.in_runtime(|| vdom.in_scope(scope, || query.set("second".to_string())));
+    vdom.in_runtime(|| vdom.in_scope(scope, || router().go_back()));
+    vdom.render_immediate(&mut NoOpMutations);
+    vdom.process_events();
+    vdom.in_runtime(|| assert_eq!(*query.peek(), ""));
+
+    wait_for_debounce(&mut vdom);
+    assert_eq!(current_route(&vdom, scope), "/?search=");
+    vdom.in_runtime(|| assert_eq!(*query.peek(), ""));
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum FieldRoute {
+    #[route("/?:search")]
+    Field { search: String },
+}
+
+#[component]
+fn Field(search: String) -> Element {
+    let query = use_query_state::<String>("search").debounce(Duration::from_millis(20));
+    use_hook(|| FIELD.with(|field| *field.borrow_mut() = Some((current_scope_id(), query))));
+
+    rsx! {
+        p { "{query}" }
+    }
+}
+
+fn field() -> (ScopeId, QueryState<String>) {
+    FIELD.with(|field| field.borrow().unwrap())
+}
+
+/// Build the dom and rerender the router. It replaces the initial url with the formatted route, which
+/// marks it dirty, so effects only run after the second pass
+fn rebuild(vdom: &mut VirtualDom) {
+    vdom.rebuild_in_place();
+    vdom.render_immediate(&mut NoOpMutations);
+}
+
+/// Let the debounced update of `Field` run
+fn wait_for_debounce(vdom: &mut VirtualDom) {
+    // Poll the debounce task once to start its timer
+    vdom.render_immediate(&mut NoOpMutations);
+    std::thread::sleep(Duration::from_millis(100));
+    vdom.process_events();
+    vdom.render_immediate(&mut NoOpMutations);
+}
+
+fn current_route(vdom: &VirtualDom, scope: ScopeId) -> String {
+    vdom.in_runtime(|| vdom.in_scope(scope, || router().full_route_string()))
+}
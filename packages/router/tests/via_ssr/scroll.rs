@@ -0,0 +1,209 @@
+use dioxus::html::{
+    geometry::PixelsVector2D, HasScrollData, MountedData, MountedResult, RenderedElementBacking,
+    ScrollBehavior, ScrollData,
+};
+use dioxus::prelude::*;
+use dioxus_core::{current_scope_id, NoOpMutations};
+use dioxus_router::{
+    use_focus_landmark, use_scroll_container, RouterConfig, RouterContext, ScrollContainer,
+};
+use std::{any::Any, cell::RefCell, future::Future, pin::Pin, rc::Rc};
+
+thread_local! {
+    /// The scroll and focus calls the router made on the elements
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    /// The scope of the layout, used to navigate from the test
+    static LAYOUT: RefCell<Option<ScopeId>> = const { RefCell::new(None) };
+    /// The scroll container of the route that is currently rendered
+    static CONTAINER: RefCell<Option<ScrollContainer>> = const { RefCell::new(None) };
+}
+
+#[test]
+fn scroll_position_is_restored_on_back_and_forward() {
+    let mut vdom = VirtualDom::new(app);
+    vdom.rebuild_in_place();
+    vdom.process_events();
+    take_log();
+
+    scroll_container(&vdom, 300.0);
+    navigate(&mut vdom, |router| _ = router.push(Route::Other {}));
+    // A new entry starts at the top
+    assert_eq!(last_scroll("/other"), Some(0.0));
+
+    scroll_container(&vdom, 120.0);
+    navigate(&mut vdom, |router| router.go_back());
+    assert_eq!(last_scroll("/"), Some(300.0));
+
+    navigate(&mut vdom, |router| router.go_forward());
+    assert_eq!(last_scroll("/other"), Some(120.0));
+}
+
+#[test]
+fn landmark_is_focused_after_navigation() {
+    let mut vdom = VirtualDom::new(|| {
+        rsx! {
+            Router::<Route> { config: || RouterConfig::default().focus_landmark(Some("main")) }
+        }
+    });
+    vdom.rebuild_in_place();
+    vdom.process_events();
+    // The first render is not a navigation
+    assert!(!take_log().contains(&"focus main".to_string()));
+
+    navigate(&mut vdom, |router| _ = router.push(Route::Other {}));
+    assert!(take_log().contains(&"focus main".to_string()));
+}
+
+#[test]
+fn focus_is_not_moved_by_default() {
+    let mut vdom = VirtualDom::new(app);
+    vdom.rebuild_in_place();
+    vdom.process_events();
+
+    navigate(&mut vdom, |router| _ = router.push(Route::Other {}));
+    assert!(!take_log().contains(&"focus main".to_string()));
+}
+
+fn app() -> Element {
+    rsx! {
+        Router::<Route> {}
+    }
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(Layout)]
+        #[route("/")]
+        Home {},
+        #[route("/other")]
+        Other {},
+}
+
+#[component]
+fn Layout() -> Element {
+    let landmark = use_focus_landmark("main");
+    use_hook(|| {
+        LAYOUT.with(|layout| *layout.borrow_mut() = Some(current_scope_id()));
+        landmark.onmounted(mounted("main"));
+    });
+
+    rsx! {
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! {
+        List { name: "/" }
+    }
+}
+
+#[component]
+fn Other() -> Element {
+    rsx! {
+        List { name: "/other" }
+    }
+}
+
+#[component]
+fn List(name: &'static str) -> Element {
+    let container = use_scroll_container("list");
+    use_hook(|| {
+        CONTAINER.with(|current| *current.borrow_mut() = Some(container));
+        container.onmounted(mounted(name));
+    });
+
+    rsx! { "{name}" }
+}
+
+/// Run a navigation from inside the router and let the router restore the scroll positions
+fn navigate(vdom: &mut VirtualDom, navigate: impl FnOnce(RouterContext)) {
+    let layout = LAYOUT.with(|layout| layout.borrow().unwrap());
+    vdom.in_runtime(|| vdom.in_scope(layout, || navigate(router())));
+    vdom.render_immediate(&mut NoOpMutations);
+    vdom.process_events();
+}
+
+/// Scroll the container of the current route to `top`
+fn scroll_container(vdom: &VirtualDom, top: f64) {
+    let container = CONTAINER.with(|container| container.borrow().unwrap());
+    vdom.in_runtime(|| {
+        container.onscroll(Event::new(Rc::new(ScrollData::new(Scrolled(top))), false))
+    });
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|log| std::mem::take(&mut *log.borrow_mut()))
+}
+
+/// The last position the router scrolled the container of `name` to
+fn last_scroll(name: &str) -> Option<f64> {
+    let prefix = format!("scroll {name} to ");
+    take_log()
+        .iter()
+        .rev()
+        .find_map(|entry| entry.strip_prefix(&prefix)?.parse().ok())
+}
+
+fn mounted(name: &'static str) -> MountedEvent {
+    Event::new(Rc::new(MountedData::new(FakeElement(name))), false)
+}
+
+/// An element that logs the calls the router makes instead of rendering
+struct FakeElement(&'static str);
+
+impl RenderedElementBacking for FakeElement {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scroll(
+        &self,
+        coordinates: PixelsVector2D,
+        _behavior: ScrollBehavior,
+    ) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        let entry = format!("scroll {} to {}", self.0, coordinates.y);
+        LOG.with(|log| log.borrow_mut().push(entry));
+        Box::pin(async { Ok(()) })
+    }
+
+    fn set_focus(&self, _focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        let entry = format!("focus {}", self.0);
+        LOG.with(|log| log.borrow_mut().push(entry));
+        Box::pin(async { Ok(()) })
+    }
+}
+
+struct Scrolled(f64);
+
+impl HasScrollData for Scrolled {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scroll_top(&self) -> f64 {
+        self.0
+    }
+
+    fn scroll_left(&self) -> f64 {
+        0.0
+    }
+
+    fn scroll_width(&self) -> i32 {
+        0
+    }
+
+    fn scroll_height(&self) -> i32 {
+        0
+    }
+
+    fn client_width(&self) -> i32 {
+        0
+    }
+
+    fn client_height(&self) -> i32 {
+        0
+    }
+}
@@ -0,0 +1,87 @@
+use dioxus::prelude::*;
+use dioxus_core::{NoOpMutations, TestDom};
+use std::time::Duration;
+
+#[test]
+fn outlet_keeps_exiting_route_mounted() {
+    let mut vdom = VirtualDom::new(app);
+    vdom.rebuild_in_place();
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        r#"<div data-outlet-transition="idle">Home!</div>"#
+    );
+
+    // The first pass renders the new route. The phase of the old route is a signal, so it rerenders
+    // as exiting in the next pass
+    vdom.render_immediate(&mut NoOpMutations);
+    vdom.render_immediate(&mut NoOpMutations);
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        r#"<div data-outlet-transition="exiting">Home!</div><div data-outlet-transition="entering">Other</div>"#
+    );
+}
+
+#[test]
+fn exiting_route_is_removed_after_the_transition() {
+    let mut dom = TestDom::new(app);
+    dom.rebuild();
+    assert_eq!(
+        dioxus_ssr::render(dom.dom()),
+        r#"<div data-outlet-transition="exiting">Home!</div><div data-outlet-transition="entering">Other</div>"#
+    );
+
+    dom.advance(Duration::from_secs(59));
+    assert_eq!(
+        dioxus_ssr::render(dom.dom()),
+        r#"<div data-outlet-transition="exiting">Home!</div><div data-outlet-transition="entering">Other</div>"#
+    );
+
+    // The entering route becomes idle once the exiting route is unmounted
+    dom.advance(Duration::from_secs(1));
+    assert_eq!(
+        dioxus_ssr::render(dom.dom()),
+        r#"<div data-outlet-transition="idle">Other</div>"#
+    );
+}
+
+fn app() -> Element {
+    rsx! {
+        Router::<Route> {}
+    }
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+#[rustfmt::skip]
+enum Route {
+    #[layout(NavBar)]
+        #[route("/")]
+        Home {},
+        #[route("/other")]
+        Other {},
+}
+
+#[component]
+fn NavBar() -> Element {
+    // Queue an effect to navigate to the other route after rebuild_in_place
+    use_effect(|| {
+        router().push(Route::Other {});
+    });
+
+    rsx! {
+        Outlet::<Route> { transition: Duration::from_secs(60) }
+    }
+}
+
+#[component]
+fn Home() -> Element {
+    rsx! {
+        "Home!"
+    }
+}
+
+#[component]
+fn Other() -> Element {
+    rsx! {
+        "Other"
+    }
+}
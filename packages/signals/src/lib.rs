@@ -28,7 +28,8 @@ pub use global::*;
 mod impls;
 
 pub use generational_box::{
-    AnyStorage, BorrowError, BorrowMutError, Owner, Storage, SyncStorage, UnsyncStorage,
+    AllocationDiff, AllocationSnapshot, AnyStorage, BorrowError, BorrowMutError, Owner, Storage,
+    SyncStorage, UnsyncStorage,
 };
 
 mod read;
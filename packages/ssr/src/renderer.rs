@@ -2,7 +2,8 @@ use super::cache::Segment;
 use crate::cache::StringCache;
 
 use dioxus_core::{
-    Attribute, AttributeValue, DynamicNode, Element, ScopeId, Template, VNode, VirtualDom,
+    Attribute, AttributeValue, DynamicNode, Element, IslandProps, ScopeId, Template, VNode,
+    VirtualDom, ISLAND_TAG, PORTAL_ATTRIBUTE,
 };
 use rustc_hash::FxHashMap;
 use std::fmt::Write;
@@ -26,6 +27,22 @@ pub struct Renderer {
 
     /// The current dynamic node id for hydration
     dynamic_node_id: usize,
+
+    /// The portals rendered in the current pass. They are moved into their targets once the whole page is rendered
+    portals: Vec<RenderedPortal>,
+
+    /// If we are inside of [`Renderer::render_scope`]. Nested calls from the component callback render into the outer page
+    rendering_scope: bool,
+}
+
+/// A portal that was rendered, but not yet moved into its target
+struct RenderedPortal {
+    /// The id of the element the portal renders into
+    target: String,
+    /// The comment that marks the place of the portal in the page
+    anchor: String,
+    /// The html of the portal
+    html: String,
 }
 
 impl Renderer {
@@ -104,12 +121,67 @@ impl Renderer {
         scope: ScopeId,
     ) -> std::fmt::Result {
         let node = dom.get_scope(scope).unwrap().root_node();
-        self.render_template(buf, dom, node, true)?;
+        if self.rendering_scope {
+            return self.render_template(buf, dom, node, true);
+        }
 
-        Ok(())
+        // Portals may render into elements that were already written, so the page is rendered to a string first
+        self.rendering_scope = true;
+        let mut page = String::new();
+        let result = self.render_template(&mut page, dom, node, true);
+        self.rendering_scope = false;
+        let portals = std::mem::take(&mut self.portals);
+        result?;
+
+        place_portals(&mut page, portals, self.pre_render);
+        buf.write_str(&page)
     }
 
     fn render_template<W: Write + ?Sized>(
+        &mut self,
+        buf: &mut W,
+        dom: &VirtualDom,
+        template: &VNode,
+        parent_escaped: bool,
+    ) -> std::fmt::Result {
+        match portal_target(template) {
+            Some(target) => self.render_portal(buf, dom, template, target, parent_escaped),
+            None => self.render_template_contents(buf, dom, template, parent_escaped),
+        }
+    }
+
+    /// Render a portal to the side and leave an anchor in its place. The portal is moved into its target in
+    /// [`place_portals`]. When prerendering, the anchor keeps the hydration id of the portal element so the client
+    /// knows where the portal belongs in the tree.
+    fn render_portal<W: Write + ?Sized>(
+        &mut self,
+        buf: &mut W,
+        dom: &VirtualDom,
+        template: &VNode,
+        target: &str,
+        parent_escaped: bool,
+    ) -> std::fmt::Result {
+        let anchor = match self.pre_render {
+            true => format!("<!--portal{}-->", self.dynamic_node_id),
+            false => format!("<!--portal-{}-->", self.portals.len()),
+        };
+        write!(buf, "{anchor}")?;
+
+        // Reserve the place of the portal before rendering it so portals are placed in the order they appear in the page
+        let index = self.portals.len();
+        self.portals.push(RenderedPortal {
+            target: target.to_string(),
+            anchor,
+            html: String::new(),
+        });
+        let mut html = String::new();
+        self.render_template_contents(&mut html, dom, template, parent_escaped)?;
+        self.portals[index].html = html;
+
+        Ok(())
+    }
+
+    fn render_template_contents<W: Write + ?Sized>(
         &mut self,
         mut buf: &mut W,
         dom: &VirtualDom,
@@ -148,6 +220,8 @@ impl Renderer {
                     for attr in attrs {
                         if attr.name == "dangerous_inner_html" {
                             inner_html = Some(attr);
+                        } else if attr.name == PORTAL_ATTRIBUTE && attr.namespace.is_none() {
+                            // The portal attribute only marks the element that is moved into the target
                         } else if attr.namespace == Some("style") {
                             accumulated_dynamic_styles.push(attr);
                         } else if BOOL_ATTRS.contains(&attr.name) {
@@ -172,7 +246,10 @@ impl Renderer {
                     let escaped = escape_text.should_escape(parent_escaped);
                     match &template.dynamic_nodes[*index] {
                         DynamicNode::Component(node) => {
-                            if let Some(render_components) = self.render_components.clone() {
+                            if let Some(island) = node.island().filter(|_| !self.pre_render) {
+                                let scope = node.mounted_scope(*index, template, dom).unwrap();
+                                self.render_island(buf, dom, island, scope.root_node(), escaped)?;
+                            } else if let Some(render_components) = self.render_components.clone() {
                                 let scope_id =
                                     node.mounted_scope_id(*index, template, dom).unwrap();
 
@@ -288,6 +365,117 @@ impl Renderer {
 
         Ok(())
     }
+
+    /// Render an island in a static page. Each island is hydrated as a separate virtual dom, so the contents are always
+    /// rendered with hydration ids that start from zero.
+    fn render_island<W: Write + ?Sized>(
+        &mut self,
+        buf: &mut W,
+        dom: &VirtualDom,
+        island: &IslandProps,
+        contents: &VNode,
+        escaped: bool,
+    ) -> std::fmt::Result {
+        write!(
+            buf,
+            "<{ISLAND_TAG} data-island=\"{}\" data-island-hydrate=\"{}\" data-island-props=\"{}\" style=\"display:contents\">",
+            askama_escape::escape(island.name(), askama_escape::Html),
+            island.hydrate().as_str(),
+            askama_escape::escape(island.props(), askama_escape::Html),
+        )?;
+
+        let outer_dynamic_node_id = std::mem::take(&mut self.dynamic_node_id);
+        self.pre_render = true;
+        let result = self.render_template(buf, dom, contents, escaped);
+        self.pre_render = false;
+        self.dynamic_node_id = outer_dynamic_node_id;
+        result?;
+
+        write!(buf, "</{ISLAND_TAG}>")
+    }
+}
+
+/// The target of a node rendered by [`dioxus_core::Portal`]. The portal attribute is always on the root element.
+fn portal_target(node: &VNode) -> Option<&str> {
+    let (path, attrs) = node
+        .template
+        .attr_paths
+        .iter()
+        .zip(node.dynamic_attrs.iter())
+        .next()?;
+    if path.len() != 1 {
+        return None;
+    }
+    attrs.iter().find_map(|attr| match &attr.value {
+        AttributeValue::Text(target)
+            if attr.name == PORTAL_ATTRIBUTE && attr.namespace.is_none() =>
+        {
+            Some(target.as_str())
+        }
+        _ => None,
+    })
+}
+
+/// Move the rendered portals from their anchors to the end of their target. Portals whose target is not part of the
+/// page are rendered in place instead. The anchors are kept when prerendering so hydration can find them.
+fn place_portals(page: &mut String, portals: Vec<RenderedPortal>, pre_render: bool) {
+    // A portal inside of another portal comes after it, so its anchor is in the page by the time it is placed
+    for portal in portals {
+        let Some(anchor) = page.find(&portal.anchor) else {
+            continue;
+        };
+        let anchor = anchor..anchor + portal.anchor.len();
+        let Some(mut end) = target_end(page, &portal.target) else {
+            page.replace_range(anchor, &portal.html);
+            continue;
+        };
+        if !pre_render {
+            if end > anchor.start {
+                end -= anchor.len();
+            }
+            page.replace_range(anchor, "");
+        }
+        page.insert_str(end, &portal.html);
+    }
+}
+
+/// The position of the closing tag of the element with the id `target` in `page`
+fn target_end(page: &str, target: &str) -> Option<usize> {
+    let id = format!(
+        " id=\"{}\"",
+        askama_escape::escape(target, askama_escape::Html)
+    );
+    let attribute = page.find(&id)?;
+    let start = page[..attribute].rfind('<')?;
+    let tag_end = start + 1 + page[start + 1..].find(|c: char| c.is_whitespace() || c == '>')?;
+    let tag = &page[start + 1..tag_end];
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+
+    // Skip over any elements with the same tag inside of the target
+    let mut depth = 0;
+    let mut position = page[attribute..].find('>')? + attribute + 1;
+    loop {
+        let next_close = page[position..].find(&close)? + position;
+        let next_open = page[position..next_close]
+            .match_indices(&open)
+            .map(|(index, _)| index + position)
+            .find(|index| {
+                page[index + open.len()..]
+                    .starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            });
+        match next_open {
+            Some(index) => {
+                depth += 1;
+                position = index + open.len();
+            }
+            None if depth == 0 => return Some(next_close),
+            None => {
+                depth -= 1;
+                position = next_close + close.len();
+            }
+        }
+    }
 }
 
 #[test]
@@ -0,0 +1,56 @@
+use dioxus::prelude::*;
+use dioxus_core::{render_island, IslandHydration};
+
+#[derive(Props, Clone, PartialEq)]
+struct CounterProps {
+    start: i32,
+}
+
+#[allow(non_snake_case)]
+fn CounterBody(props: CounterProps) -> Element {
+    rsx! {
+        button { onclick: |_| {}, "{props.start}" }
+    }
+}
+
+#[allow(non_snake_case)]
+fn Counter(props: CounterProps) -> Element {
+    let serialized = format!("{{\"start\":{}}}", props.start);
+    render_island(
+        "Counter",
+        IslandHydration::Visible,
+        serialized,
+        CounterBody,
+        props,
+    )
+}
+
+fn app() -> Element {
+    let title = "Static";
+    rsx! {
+        h1 { "{title}" }
+        Counter { start: 5 }
+    }
+}
+
+#[test]
+fn static_pages_mark_islands() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(
+        dioxus_ssr::render(&dom),
+        r#"<h1>Static</h1><dx-island data-island="Counter" data-island-hydrate="visible" data-island-props="{&#34;start&#34;:5}" style="display:contents"><button data-node-hydration="0,click:1"><!--node-id1-->5<!--#--></button></dx-island>"#
+    );
+}
+
+#[test]
+fn hydrated_pages_render_islands_in_place() {
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    assert_eq!(
+        dioxus_ssr::pre_render(&dom),
+        r#"<h1 data-node-hydration="0"><!--node-id1-->Static<!--#--></h1><button data-node-hydration="2,click:1"><!--node-id3-->5<!--#--></button>"#
+    );
+}
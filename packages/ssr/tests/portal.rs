@@ -0,0 +1,83 @@
+use dioxus::prelude::*;
+
+#[test]
+fn portal_renders_into_a_later_target() {
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            Portal { target: "modals", p { "hello" } }
+            div { id: "modals" }
+        }),
+        r#"<div id="modals"><div><p>hello</p></div></div>"#
+    );
+}
+
+#[test]
+fn portal_renders_into_an_earlier_target() {
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div { id: "modals",
+                div { "first" }
+            }
+            div {
+                Portal { target: "modals", p { "hello" } }
+            }
+        }),
+        r#"<div id="modals"><div>first</div><div><p>hello</p></div></div><div></div>"#
+    );
+}
+
+#[test]
+fn portals_keep_their_order_in_the_target() {
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            Portal { target: "modals", "first" }
+            Portal { target: "modals", "second" }
+            div { id: "modals" }
+        }),
+        r#"<div id="modals"><div>first</div><div>second</div></div>"#
+    );
+}
+
+#[test]
+fn nested_portals() {
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            Portal { target: "modals",
+                Portal { target: "tooltips", "tooltip" }
+                "modal"
+            }
+            div { id: "modals" }
+            div { id: "tooltips" }
+        }),
+        r#"<div id="modals"><div>modal</div></div><div id="tooltips"><div>tooltip</div></div>"#
+    );
+}
+
+#[test]
+fn portal_without_target_renders_in_place() {
+    assert_eq!(
+        dioxus_ssr::render_element(rsx! {
+            div { Portal { target: "missing", p { "hello" } } }
+        }),
+        r#"<div><div><p>hello</p></div></div>"#
+    );
+}
+
+#[test]
+fn prerendered_portal_keeps_its_anchor() {
+    fn app() -> Element {
+        rsx! {
+            Portal { target: "modals", p { "hello" } }
+            div { id: "modals" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    // The anchor has the hydration id of the element the portal renders
+    assert_eq!(
+        dioxus_ssr::pre_render(&dom),
+        r#"<!--portal0--><div id="modals" data-node-hydration="2"><div data-node-hydration="0"><p data-node-hydration="1">hello</p></div></div>"#
+    );
+}
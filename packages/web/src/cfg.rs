@@ -1,4 +1,5 @@
 use std::rc::Rc;
+use std::time::Duration;
 
 use dioxus_core::LaunchConfig;
 use wasm_bindgen::JsCast as _;
@@ -17,6 +18,9 @@ pub struct Config {
     #[allow(dead_code)]
     pub(crate) panic_hook: bool,
     pub(crate) root: ConfigRoot,
+    pub(crate) time_slice: Option<Duration>,
+    /// Whether this dom hydrates a single island instead of the whole page
+    pub(crate) island: bool,
     #[cfg(feature = "document")]
     pub(crate) history: Option<Rc<dyn dioxus_history::History>>,
 }
@@ -74,6 +78,16 @@ impl Config {
         self
     }
 
+    /// Render in slices of at most this duration, letting the browser handle input between slices.
+    ///
+    /// By default, every update is rendered at once. With time slicing enabled, updates caused by user input are rendered
+    /// before any transitions started with [`start_transition`](dioxus_core::start_transition), and long renders are
+    /// split up so the page stays responsive. Any finished part of a render is shown right away.
+    pub fn time_slice(mut self, budget: Duration) -> Self {
+        self.time_slice = Some(budget);
+        self
+    }
+
     /// Set the history provider for the application.
     ///
     /// `dioxus-web` provides two history providers:
@@ -96,6 +110,8 @@ impl Default for Config {
         Self {
             hydrate: false,
             root: ConfigRoot::RootName("main".to_string()),
+            time_slice: None,
+            island: false,
             #[cfg(feature = "document")]
             history: None,
             panic_hook: true,
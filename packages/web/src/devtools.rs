@@ -191,6 +191,40 @@ fn make_ws(tx: UnboundedSender<HotReloadMsg>, poll_interval: i32, reload: bool)
         let ws: &JsValue = ws.as_ref();
         dioxus_interpreter_js::minimal_bindings::monkeyPatchConsole(ws.clone());
     }
+
+    #[cfg(feature = "profiler")]
+    SOCKET.with(|socket| *socket.borrow_mut() = Some(ws));
+}
+
+#[cfg(feature = "profiler")]
+thread_local! {
+    // The current connection to the devserver, used to stream render profiles
+    static SOCKET: std::cell::RefCell<Option<WebSocket>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Send the renders recorded since the last call to the devserver. Renders are dropped if the devserver isn't connected.
+#[cfg(feature = "profiler")]
+pub(crate) fn send_render_profile(runtime: &dioxus_core::Runtime) {
+    let profile = runtime.take_render_profile();
+    if profile.renders.is_empty() {
+        return;
+    }
+
+    SOCKET.with(|socket| {
+        let socket = socket.borrow();
+        let Some(socket) = socket
+            .as_ref()
+            .filter(|ws| ws.ready_state() == WebSocket::OPEN)
+        else {
+            return;
+        };
+        let msg = dioxus_devtools::ClientMsg::RenderProfile {
+            events: profile.chrome_trace_events().collect(),
+        };
+        if let Ok(text) = serde_json::to_string(&msg) {
+            _ = socket.send_with_str(&text);
+        }
+    });
 }
 
 /// Represents what color the toast should have.
@@ -48,6 +48,11 @@ pub struct WebsysDom {
 
     #[cfg(feature = "hydrate")]
     pub(crate) suspense_hydration_ids: crate::hydration::SuspenseHydrationIds,
+
+    // Portals that were created while mutations were skipped. They are mounted once the nodes they
+    // move are hydrated
+    #[cfg(feature = "hydrate")]
+    pub(crate) queued_portals: Vec<(ElementId, String)>,
 }
 
 impl WebsysDom {
@@ -129,6 +134,8 @@ impl WebsysDom {
             skip_mutations: false,
             #[cfg(feature = "hydrate")]
             suspense_hydration_ids: Default::default(),
+            #[cfg(feature = "hydrate")]
+            queued_portals: Default::default(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use std::{cell::RefCell, rc::Rc};
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
 use web_sys::{window, Event, History, ScrollRestoration, Window};
 
@@ -38,7 +39,7 @@ impl WebHistory {
         let current_route_str = current_route.to_string();
         let prefix_str = myself.prefix.as_deref().unwrap_or("");
         let current_url = format!("{prefix_str}{current_route_str}");
-        let state = myself.create_state();
+        let state = myself.create_state(initial_index(&myself.history));
         let _ = replace_state_with_url(&myself.history, &state, Some(&current_url));
 
         myself
@@ -81,9 +82,9 @@ impl WebHistory {
         }
     }
 
-    fn create_state(&self) -> [f64; 2] {
+    fn create_state(&self, index: usize) -> [f64; 3] {
         let scroll = self.scroll_pos();
-        [scroll.x, scroll.y]
+        [scroll.x, scroll.y, index as f64]
     }
 
     fn handle_nav(&self) {
@@ -145,15 +146,23 @@ impl dioxus_history::History for WebHistory {
         // update the scroll position before pushing the new state
         update_scroll(&w, &h);
 
-        if push_state_and_url(&self.history, &self.create_state(), self.full_path(&state)).is_ok() {
+        let index = get_current_index(&self.history).map_or(0, |index| index + 1);
+        if push_state_and_url(
+            &self.history,
+            &self.create_state(index),
+            self.full_path(&state),
+        )
+        .is_ok()
+        {
             self.handle_nav();
         }
     }
 
     fn replace(&self, state: String) {
+        let index = get_current_index(&self.history).unwrap_or_default();
         if replace_state_with_url(
             &self.history,
-            &self.create_state(),
+            &self.create_state(index),
             Some(&self.full_path(&state)),
         )
         .is_ok()
@@ -186,6 +195,18 @@ impl dioxus_history::History for WebHistory {
             )
             .unwrap();
     }
+
+    fn start_view_transition(&self, update: Box<dyn FnOnce()>) {
+        start_view_transition(&self.window, update)
+    }
+
+    fn current_index(&self) -> Option<usize> {
+        get_current_index(&self.history)
+    }
+
+    fn handles_scroll_restoration(&self) -> bool {
+        self.do_scroll_restoration
+    }
 }
 
 /// A [`dioxus_history::History`] provider that integrates with a browser via the [History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API)
@@ -215,7 +236,7 @@ impl HashHistory {
         let current_route_str = current_route.to_string();
         let pathname_str = &myself.pathname;
         let current_url = format!("{pathname_str}#{current_route_str}");
-        let state = myself.create_state();
+        let state = myself.create_state(initial_index(&myself.history));
         let _ = replace_state_with_url(&myself.history, &state, Some(&current_url));
 
         myself
@@ -248,9 +269,9 @@ impl HashHistory {
         }
     }
 
-    fn create_state(&self) -> [f64; 2] {
+    fn create_state(&self, index: usize) -> [f64; 3] {
         let scroll = self.scroll_pos();
-        [scroll.x, scroll.y]
+        [scroll.x, scroll.y, index as f64]
     }
 
     fn full_path(&self, state: &String) -> String {
@@ -301,15 +322,23 @@ impl dioxus_history::History for HashHistory {
         // update the scroll position before pushing the new state
         update_scroll(&w, &h);
 
-        if push_state_and_url(&self.history, &self.create_state(), self.full_path(&state)).is_ok() {
+        let index = get_current_index(&self.history).map_or(0, |index| index + 1);
+        if push_state_and_url(
+            &self.history,
+            &self.create_state(index),
+            self.full_path(&state),
+        )
+        .is_ok()
+        {
             self.handle_nav();
         }
     }
 
     fn replace(&self, state: String) {
+        let index = get_current_index(&self.history).unwrap_or_default();
         if replace_state_with_url(
             &self.history,
-            &self.create_state(),
+            &self.create_state(index),
             Some(&self.full_path(&state)),
         )
         .is_ok()
@@ -342,6 +371,18 @@ impl dioxus_history::History for HashHistory {
             )
             .unwrap();
     }
+
+    fn start_view_transition(&self, update: Box<dyn FnOnce()>) {
+        start_view_transition(&self.window, update)
+    }
+
+    fn current_index(&self) -> Option<usize> {
+        get_current_index(&self.history)
+    }
+
+    fn handles_scroll_restoration(&self) -> bool {
+        self.do_scroll_restoration
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -370,26 +411,29 @@ impl ScrollPosition {
     }
 }
 
+/// The history state is stored as `[scroll_x, scroll_y, index]`
+fn state_to_js(value: &[f64; 3]) -> js_sys::Array {
+    let state = js_sys::Array::new();
+    for value in value {
+        state.push(&JsValue::from(*value));
+    }
+    state
+}
+
 pub(crate) fn replace_state_with_url(
     history: &History,
-    value: &[f64; 2],
+    value: &[f64; 3],
     url: Option<&str>,
 ) -> Result<(), JsValue> {
-    let position = js_sys::Array::new();
-    position.push(&JsValue::from(value[0]));
-    position.push(&JsValue::from(value[1]));
-    history.replace_state_with_url(&position, "", url)
+    history.replace_state_with_url(&state_to_js(value), "", url)
 }
 
 pub(crate) fn push_state_and_url(
     history: &History,
-    value: &[f64; 2],
+    value: &[f64; 3],
     url: String,
 ) -> Result<(), JsValue> {
-    let position = js_sys::Array::new();
-    position.push(&JsValue::from(value[0]));
-    position.push(&JsValue::from(value[1]));
-    history.push_state_with_url(&position, "", Some(&url))
+    history.push_state_with_url(&state_to_js(value), "", Some(&url))
 }
 
 pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
@@ -402,7 +446,60 @@ pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
     })
 }
 
+/// Get the position of the current entry in the session history from the history state.
+pub(crate) fn get_current_index(history: &History) -> Option<usize> {
+    use wasm_bindgen::JsCast;
+    history.state().ok().and_then(|state| {
+        let state = state.dyn_into::<js_sys::Array>().ok()?;
+        let index = state.get(2).as_f64()?;
+        Some(index as usize)
+    })
+}
+
+/// The index of the entry the page was loaded into. If the page was reloaded, the browser keeps the
+/// state of the entry so we can reuse the index from before.
+fn initial_index(history: &History) -> usize {
+    get_current_index(history)
+        .unwrap_or_else(|| history.length().unwrap_or(1).saturating_sub(1) as usize)
+}
+
+/// Run `update` inside of `document.startViewTransition` if the browser supports it. Otherwise `update`
+/// is called immediately.
+fn start_view_transition(window: &Window, update: Box<dyn FnOnce()>) {
+    let start = window.document().and_then(|document| {
+        let start =
+            js_sys::Reflect::get(&document, &JsValue::from_str("startViewTransition")).ok()?;
+        Some((document, start.dyn_into::<js_sys::Function>().ok()?))
+    });
+    let Some((document, start)) = start else {
+        return update();
+    };
+
+    let update = Rc::new(RefCell::new(Some(update)));
+    let callback = {
+        let update = update.clone();
+        let window = window.clone();
+        Closure::once_into_js(move || {
+            if let Some(update) = update.borrow_mut().take() {
+                update();
+            }
+            // The renderer applies the new route asynchronously. Wait for the next frame before
+            // letting the browser capture the new state of the page
+            js_sys::Promise::new(&mut |resolve, _| {
+                let _ = window.request_animation_frame(&resolve);
+            })
+        })
+    };
+
+    if start.call1(&document, &callback).is_err() {
+        if let Some(update) = update.borrow_mut().take() {
+            update();
+        }
+    }
+}
+
 fn update_scroll(window: &Window, history: &History) {
     let scroll = ScrollPosition::of_window(window);
-    let _ = replace_state_with_url(history, &[scroll.x, scroll.y], None);
+    let index = get_current_index(history).unwrap_or_default();
+    let _ = replace_state_with_url(history, &[scroll.x, scroll.y, index as f64], None);
 }
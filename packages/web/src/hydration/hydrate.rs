@@ -200,6 +200,14 @@ impl WebsysDom {
 
         self.interpreter.base().hydrate(ids, under);
 
+        // Now that the nodes are hydrated, move the portals to their targets
+        if !self.queued_portals.is_empty() {
+            for (id, target) in std::mem::take(&mut self.queued_portals) {
+                self.interpreter.mount_portal(id.0 as u32, &target);
+            }
+            self.interpreter.flush();
+        }
+
         #[cfg(feature = "mounted")]
         for id in to_mount {
             self.send_mount_event(id);
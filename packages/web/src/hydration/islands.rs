@@ -0,0 +1,152 @@
+//! Hydrate the islands on a static page. Each island is hydrated as its own [`VirtualDom`](dioxus_core::VirtualDom)
+//! once its hydration strategy allows it.
+
+use crate::Config;
+use dioxus_core::{IslandHydration, ISLAND_TAG};
+use std::{cell::OnceCell, rc::Rc};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry};
+
+pub use dioxus_fullstack_core::{island, IslandDefinition};
+
+/// The events that hydrate an island with the [`IslandHydration::Interaction`] strategy
+const INTERACTION_EVENTS: [&str; 3] = ["pointerdown", "focusin", "keydown"];
+
+/// Hydrate every island on the page that was rendered by one of the given islands.
+///
+/// The page must be rendered on the server with [`dioxus_ssr::render`](https://docs.rs/dioxus-ssr) so each island is
+/// wrapped in a boundary with its props. Islands that are not in the list are left static.
+///
+/// ```rust, ignore
+/// dioxus::web::hydrate_islands([island(Counter), island(SearchBox)]);
+/// ```
+pub fn hydrate_islands(islands: impl IntoIterator<Item = IslandDefinition>) {
+    let islands: Vec<_> = islands.into_iter().collect();
+    let document = web_sys::window().unwrap().document().unwrap();
+    let Ok(elements) = document.query_selector_all(ISLAND_TAG) else {
+        return;
+    };
+
+    for index in 0..elements.length() {
+        let Some(element) = elements
+            .item(index)
+            .and_then(|node| node.dyn_into::<Element>().ok())
+        else {
+            continue;
+        };
+        let name = element.get_attribute("data-island").unwrap_or_default();
+        let Some(definition) = islands.iter().find(|island| island.name() == name).copied() else {
+            tracing::warn!(
+                "The island {name} was not passed to `hydrate_islands`. It will stay static."
+            );
+            continue;
+        };
+        let hydrate = element
+            .get_attribute("data-island-hydrate")
+            .and_then(|strategy| IslandHydration::parse(&strategy))
+            .unwrap_or_default();
+
+        match hydrate {
+            IslandHydration::Load => mount(definition, element),
+            IslandHydration::Idle => on_idle(move || mount(definition, element)),
+            IslandHydration::Visible => {
+                on_visible(&element.clone(), move || mount(definition, element))
+            }
+            IslandHydration::Interaction => {
+                on_interaction(&element.clone(), move || mount(definition, element))
+            }
+        }
+    }
+}
+
+/// Create the virtual dom for an island and hydrate it in place
+fn mount(definition: IslandDefinition, element: Element) {
+    let props = element
+        .get_attribute("data-island-props")
+        .unwrap_or_default();
+    let Some(dom) = definition.mount(&props) else {
+        tracing::error!("Failed to hydrate the island {}", definition.name());
+        return;
+    };
+
+    let mut config = Config::new().hydrate(true).rootelement(element);
+    config.island = true;
+    crate::launch::launch_virtual_dom(dom, config);
+}
+
+/// Run the callback once the browser is idle, or on the next task if the browser can't tell us
+fn on_idle(callback: impl FnOnce() + 'static) {
+    let window = web_sys::window().unwrap();
+    let callback = Closure::once_into_js(callback);
+    let supports_idle =
+        js_sys::Reflect::has(&window, &"requestIdleCallback".into()).unwrap_or_default();
+
+    if supports_idle {
+        _ = window.request_idle_callback(callback.unchecked_ref());
+    } else {
+        _ = window.set_timeout_with_callback(callback.unchecked_ref());
+    }
+}
+
+/// Run the callback the first time the island scrolls into view
+fn on_visible(element: &Element, callback: impl FnOnce() + 'static) {
+    // The boundary is `display: contents`, so it has no box of its own to intersect with. Watch its children instead.
+    let mut children = Vec::new();
+    let mut child = element.first_element_child();
+    while let Some(current) = child {
+        child = current.next_element_sibling();
+        children.push(current);
+    }
+
+    // An island without any elements can never scroll into view
+    if children.is_empty() {
+        return on_idle(callback);
+    }
+
+    let mut callback = Some(callback);
+    let observe = Closure::<dyn FnMut(js_sys::Array, IntersectionObserver)>::new(
+        move |entries: js_sys::Array, observer: IntersectionObserver| {
+            let visible = entries.iter().any(|entry| {
+                entry
+                    .unchecked_into::<IntersectionObserverEntry>()
+                    .is_intersecting()
+            });
+            if visible {
+                observer.disconnect();
+                if let Some(callback) = callback.take() {
+                    callback();
+                }
+            }
+        },
+    );
+    let Ok(observer) = IntersectionObserver::new(observe.into_js_value().unchecked_ref()) else {
+        return;
+    };
+    for child in &children {
+        observer.observe(child);
+    }
+}
+
+/// Run the callback the first time the user interacts with the island
+fn on_interaction(element: &Element, callback: impl FnOnce() + 'static) {
+    let mut callback = Some(callback);
+    let listener = Rc::new(OnceCell::<js_sys::Function>::new());
+    let target = element.clone();
+    let handle = listener.clone();
+    let on_event = Closure::<dyn FnMut()>::new(move || {
+        if let Some(listener) = handle.get() {
+            for event in INTERACTION_EVENTS {
+                _ = target.remove_event_listener_with_callback(event, listener);
+            }
+        }
+        if let Some(callback) = callback.take() {
+            callback();
+        }
+    });
+
+    let function: js_sys::Function = on_event.into_js_value().unchecked_into();
+    for event in INTERACTION_EVENTS {
+        _ = element.add_event_listener_with_callback(event, &function);
+    }
+    _ = listener.set(function);
+}
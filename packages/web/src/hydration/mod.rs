@@ -5,6 +5,12 @@ mod hydrate;
 #[allow(unused)]
 pub use hydrate::*;
 
+#[cfg(feature = "hydrate")]
+mod islands;
+
+#[cfg(feature = "hydrate")]
+pub use islands::*;
+
 /// The message sent from the server to the client to hydrate a suspense boundary
 #[derive(Debug)]
 pub(crate) struct SuspenseMessage {
@@ -51,8 +51,12 @@ pub use hydration::*;
 /// wasm_bindgen_futures::spawn_local(app_fut);
 /// ```
 pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
+    // Islands share the page with each other, so they don't connect to the devserver on their own
     #[cfg(all(feature = "devtools", debug_assertions))]
-    let mut hotreload_rx = devtools::init(&web_config);
+    let mut hotreload_rx = match web_config.island {
+        true => futures_channel::mpsc::unbounded().1,
+        false => devtools::init(&web_config),
+    };
 
     #[cfg(feature = "document")]
     if let Some(history) = web_config.history.clone() {
@@ -64,9 +68,16 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
 
     let runtime = virtual_dom.runtime();
 
+    // Stream the renders to the devserver so `dx serve` can write them out as a chrome trace
+    #[cfg(all(feature = "profiler", debug_assertions))]
+    runtime.start_profiling();
+
     // If the hydrate feature is enabled, launch the client with hydration enabled
     let should_hydrate = web_config.hydrate || cfg!(feature = "hydrate");
 
+    let time_slice = web_config.time_slice;
+    #[allow(unused)]
+    let island = web_config.island;
     let mut websys_dom = WebsysDom::new(web_config, runtime);
 
     let mut hydration_receiver: Option<futures_channel::mpsc::UnboundedReceiver<SuspenseMessage>> =
@@ -77,7 +88,7 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
         // Let's wait for a moment to see if we get a hotreload message before we start hydrating.
         // That way, the hydration will use the same functions that the server used to serialize the data.
         #[cfg(all(feature = "devtools", debug_assertions))]
-        loop {
+        while !island {
             let mut timeout = gloo_timers::future::TimeoutFuture::new(100).fuse();
             futures_util::select! {
                 msg = hotreload_rx.next() => {
@@ -116,20 +127,25 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
                 fn get_initial_hydration_debug_types() -> Option<Vec<String>>;
                 fn get_initial_hydration_debug_locations() -> Option<Vec<String>>;
             }
-            let hydration_data = get_initial_hydration_data().to_vec();
-
-            // If we are running in debug mode, also get the debug types and locations
-            #[cfg(debug_assertions)]
-            let debug_types = get_initial_hydration_debug_types();
-            #[cfg(not(debug_assertions))]
-            let debug_types = None;
-            #[cfg(debug_assertions)]
-            let debug_locations = get_initial_hydration_debug_locations();
-            #[cfg(not(debug_assertions))]
-            let debug_locations = None;
-
-            let server_data =
-                HydrationContext::from_serialized(&hydration_data, debug_types, debug_locations);
+
+            // Islands carry their props in the html instead of the page's hydration data
+            let server_data = if island {
+                HydrationContext::default()
+            } else {
+                let hydration_data = get_initial_hydration_data().to_vec();
+
+                // If we are running in debug mode, also get the debug types and locations
+                #[cfg(debug_assertions)]
+                let debug_types = get_initial_hydration_debug_types();
+                #[cfg(not(debug_assertions))]
+                let debug_types = None;
+                #[cfg(debug_assertions)]
+                let debug_locations = get_initial_hydration_debug_locations();
+                #[cfg(not(debug_assertions))]
+                let debug_locations = None;
+
+                HydrationContext::from_serialized(&hydration_data, debug_types, debug_locations)
+            };
             // If the server serialized an error into the root suspense boundary, throw it into the root scope
             if let Some(error) = server_data.error_entry().get().ok().flatten() {
                 virtual_dom.in_runtime(|| virtual_dom.runtime().throw_error(ScopeId::APP, error));
@@ -244,23 +260,43 @@ pub async fn run(mut virtual_dom: VirtualDom, web_config: Config) -> ! {
             websys_dom.rehydrate_streaming(hydration_data, &mut virtual_dom);
         }
 
-        // Todo: This is currently disabled because it has a negative impact on response times for events but it could be re-enabled for tasks
-        // Jank free rendering
+        // Jank free rendering is opt in with `Config::time_slice`
         //
-        // 1. wait for the browser to give us "idle" time
-        // 2. During idle time, diff the dom
-        // 3. Stop diffing if the deadline is exceeded
-        // 4. Wait for the animation frame to patch the dom
-
-        // wait for the mainthread to schedule us in
-        // let deadline = work_loop.wait_for_idle_time().await;
+        // 1. Diff the dom, most urgent updates first
+        // 2. Stop diffing if the deadline is exceeded
+        // 3. Patch the dom with the finished work
+        // 4. Give the browser a chance to handle input before diffing the rest
+        let done = match time_slice {
+            Some(budget) => {
+                let start = js_sys::Date::now();
+                let budget = budget.as_secs_f64() * 1000.0;
+                virtual_dom
+                    .render_with_deadline(&mut websys_dom, || js_sys::Date::now() - start >= budget)
+            }
+            None => {
+                virtual_dom.render_immediate(&mut websys_dom);
+                true
+            }
+        };
 
-        // run the virtualdom work phase until the frame deadline is reached
-        virtual_dom.render_immediate(&mut websys_dom);
+        websys_dom.flush_edits();
 
-        // wait for the animation frame to fire so we can apply our changes
-        // work_loop.wait_for_raf().await;
+        #[cfg(all(feature = "profiler", debug_assertions))]
+        devtools::send_render_profile(&virtual_dom.runtime());
 
-        websys_dom.flush_edits();
+        // If the deadline was reached, let the browser handle input before rendering the rest
+        if !done {
+            yield_to_browser().await;
+        }
     }
 }
+
+/// Wait for the browser to run a macrotask, which gives it a chance to handle any pending input events
+async fn yield_to_browser() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        _ = web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback(&resolve);
+    });
+    _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
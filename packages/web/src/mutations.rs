@@ -259,4 +259,13 @@ impl WriteMutations for WebsysDom {
         }
         self.interpreter.push_root(id.0 as u32)
     }
+
+    fn mount_portal(&mut self, id: ElementId, target: &str) {
+        #[cfg(feature = "hydrate")]
+        if self.skip_mutations {
+            self.queued_portals.push((id, target.to_string()));
+            return;
+        }
+        self.interpreter.mount_portal(id.0 as u32, target)
+    }
 }